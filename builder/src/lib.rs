@@ -1,9 +1,11 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::{borrow::Cow, collections::HashMap, mem};
 
 pub use exgui_core::builder::*;
 use exgui_core::{
-    AlignHor, AlignVer, Circle, Clip, Comp, EventName, Fill, Group, Listener, Model, Node, Padding, Path, PathCommand,
-    Prim, Real, RealValue, Rect, Rounding, Shape, Stroke, Text, Transform,
+    AlignHor, AlignVer, BlendMode, Circle, Clip, Color, Comp, Effect, Ellipse, EventName, Fill, FillRule, Group, Line,
+    Listener, Model, Node, Padding, Path, PathCommand, PathParseError, Polygon, Polyline, Prim, Real, RealValue, Rect,
+    Rounding, Shadow, Shape, SizeConstraints, SpanStyle, Stroke, Text, TextDecoration, TextOverflow, TextPath,
+    TextSpan, TextWrap, Transform,
 };
 
 pub struct PrimBuilder<M: Model> {
@@ -44,6 +46,31 @@ impl<M: Model> CircleBuilder<M> {
         self
     }
 
+    pub fn constraints(mut self, constraints: SizeConstraints) -> Self {
+        self.shape.constraints = constraints;
+        self
+    }
+
+    pub fn min_width(mut self, width: impl Into<RealValue>) -> Self {
+        self.shape.constraints.min_width = Some(width.into());
+        self
+    }
+
+    pub fn max_width(mut self, width: impl Into<RealValue>) -> Self {
+        self.shape.constraints.max_width = Some(width.into());
+        self
+    }
+
+    pub fn min_height(mut self, height: impl Into<RealValue>) -> Self {
+        self.shape.constraints.min_height = Some(height.into());
+        self
+    }
+
+    pub fn max_height(mut self, height: impl Into<RealValue>) -> Self {
+        self.shape.constraints.max_height = Some(height.into());
+        self
+    }
+
     pub fn padding(mut self, padding: impl Into<Padding>) -> Self {
         self.shape.padding = padding.into();
         self
@@ -133,6 +160,11 @@ impl<M: Model> Primitive<M> for CircleBuilder<M> {
         self
     }
 
+    fn effect(mut self, effect: impl Into<Effect>) -> Self {
+        self.shape.effects.push(effect.into());
+        self
+    }
+
     fn remove_stroke(mut self) -> Self {
         self.shape.stroke = None;
         self
@@ -150,6 +182,11 @@ impl<M: Model> Primitive<M> for CircleBuilder<M> {
         self.shape.clip = Clip::new_scissor(x.into(), y.into(), width.into(), height.into());
         self
     }
+
+    fn clip_to(mut self, clip: Clip) -> Self {
+        self.shape.clip = clip;
+        self
+    }
 }
 
 impl<M: Model> EventHandler<M> for CircleBuilder<M> {
@@ -191,6 +228,37 @@ impl<M: Model> RectBuilder<M> {
         self
     }
 
+    pub fn constraints(mut self, constraints: SizeConstraints) -> Self {
+        self.shape.constraints = constraints;
+        self
+    }
+
+    pub fn min_width(mut self, width: impl Into<RealValue>) -> Self {
+        self.shape.constraints.min_width = Some(width.into());
+        self
+    }
+
+    pub fn max_width(mut self, width: impl Into<RealValue>) -> Self {
+        self.shape.constraints.max_width = Some(width.into());
+        self
+    }
+
+    pub fn min_height(mut self, height: impl Into<RealValue>) -> Self {
+        self.shape.constraints.min_height = Some(height.into());
+        self
+    }
+
+    pub fn max_height(mut self, height: impl Into<RealValue>) -> Self {
+        self.shape.constraints.max_height = Some(height.into());
+        self
+    }
+
+    /// Ratio of the width to the height, see `SizeConstraints::aspect_ratio`.
+    pub fn aspect_ratio(mut self, ratio: Real) -> Self {
+        self.shape.constraints.aspect_ratio = Some(ratio);
+        self
+    }
+
     pub fn rounding(mut self, rounding: impl Into<Rounding>) -> Self {
         self.shape.rounding = Some(rounding.into());
         self
@@ -333,6 +401,11 @@ impl<M: Model> Primitive<M> for RectBuilder<M> {
         self
     }
 
+    fn effect(mut self, effect: impl Into<Effect>) -> Self {
+        self.shape.effects.push(effect.into());
+        self
+    }
+
     fn remove_stroke(mut self) -> Self {
         self.shape.stroke = None;
         self
@@ -350,6 +423,11 @@ impl<M: Model> Primitive<M> for RectBuilder<M> {
         self.shape.clip = Clip::new_scissor(x.into(), y.into(), width.into(), height.into());
         self
     }
+
+    fn clip_to(mut self, clip: Clip) -> Self {
+        self.shape.clip = clip;
+        self
+    }
 }
 
 impl<M: Model> EventHandler<M> for RectBuilder<M> {
@@ -372,6 +450,11 @@ pub fn text<M: Model>(content: impl Into<String>) -> TextBuilder<M> {
     }
 }
 
+/// Start the text made of the styled spans.
+pub fn rich_text<M: Model>() -> TextBuilder<M> {
+    text("")
+}
+
 pub struct TextBuilder<M: Model> {
     shape: Text,
     prim: PrimBuilder<M>,
@@ -398,6 +481,64 @@ impl<M: Model> TextBuilder<M> {
         self.shape.align = align.into();
         self
     }
+
+    pub fn max_width(mut self, width: impl Into<RealValue>) -> Self {
+        self.shape.layout.max_width = Some(width.into());
+        self
+    }
+
+    pub fn wrap(mut self, wrap: TextWrap) -> Self {
+        self.shape.layout.wrap = wrap;
+        self
+    }
+
+    /// Set the line height as the multiplier of the font line height.
+    pub fn line_height(mut self, line_height: impl Into<Real>) -> Self {
+        self.shape.layout.line_height = line_height.into();
+        self
+    }
+
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.shape.layout.max_lines = Some(max_lines);
+        self
+    }
+
+    /// Set how the lines wider than the maximum width are displayed.
+    pub fn overflow(mut self, overflow: TextOverflow) -> Self {
+        self.shape.layout.overflow = overflow;
+        self
+    }
+
+    pub fn letter_spacing(mut self, spacing: impl Into<Real>) -> Self {
+        self.shape.letter_spacing = spacing.into();
+        self
+    }
+
+    pub fn decoration(mut self, decoration: TextDecoration) -> Self {
+        self.shape.decoration = self.shape.decoration.union(decoration);
+        self
+    }
+
+    /// Lay out the lines along the path, see `TextPath`.
+    pub fn path(mut self, path: impl Into<TextPath>) -> Self {
+        self.shape.path = Some(path.into());
+        self
+    }
+
+    /// Append the span with the default style, see `styled_span`.
+    pub fn span(self, content: impl Into<String>) -> Self {
+        self.styled_span(content, normal())
+    }
+
+    /// Append the styled span. The plain content set before the first span becomes the span with the default style.
+    pub fn styled_span(mut self, content: impl Into<String>, style: impl Into<SpanStyle>) -> Self {
+        if self.shape.spans.is_empty() && !self.shape.content.is_empty() {
+            let plain = mem::take(&mut self.shape.content);
+            self.shape.push_span(TextSpan::new(plain, normal()));
+        }
+        self.shape.push_span(TextSpan::new(content, style.into()));
+        self
+    }
 }
 
 impl<M: Model> Builder<M> for TextBuilder<M> {
@@ -449,6 +590,11 @@ impl<M: Model> Primitive<M> for TextBuilder<M> {
         self
     }
 
+    fn effect(mut self, effect: impl Into<Effect>) -> Self {
+        self.shape.effects.push(effect.into());
+        self
+    }
+
     fn remove_stroke(mut self) -> Self {
         self.shape.stroke = None;
         self
@@ -466,6 +612,11 @@ impl<M: Model> Primitive<M> for TextBuilder<M> {
         self.shape.clip = Clip::new_scissor(x.into(), y.into(), width.into(), height.into());
         self
     }
+
+    fn clip_to(mut self, clip: Clip) -> Self {
+        self.shape.clip = clip;
+        self
+    }
 }
 
 impl<M: Model> EventHandler<M> for TextBuilder<M> {
@@ -488,12 +639,22 @@ pub fn path<M: Model>(cmd: impl Into<Vec<PathCommand>>) -> PathBuilder<M> {
     }
 }
 
+/// Build the path from the SVG path data string, such as `"M10 10 h 20 v 20 z"`.
+pub fn path_data<M: Model>(data: impl AsRef<str>) -> Result<PathBuilder<M>, PathParseError> {
+    PathCommand::parse(data.as_ref()).map(path)
+}
+
 pub struct PathBuilder<M: Model> {
     shape: Path,
     prim: PrimBuilder<M>,
 }
 
-impl<M: Model> PathBuilder<M> {}
+impl<M: Model> PathBuilder<M> {
+    pub fn fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.shape.fill_rule = Some(fill_rule);
+        self
+    }
+}
 
 impl<M: Model> Builder<M> for PathBuilder<M> {
     fn build(self) -> Node<M> {
@@ -544,6 +705,11 @@ impl<M: Model> Primitive<M> for PathBuilder<M> {
         self
     }
 
+    fn effect(mut self, effect: impl Into<Effect>) -> Self {
+        self.shape.effects.push(effect.into());
+        self
+    }
+
     fn remove_stroke(mut self) -> Self {
         self.shape.stroke = None;
         self
@@ -561,6 +727,11 @@ impl<M: Model> Primitive<M> for PathBuilder<M> {
         self.shape.clip = Clip::new_scissor(x.into(), y.into(), width.into(), height.into());
         self
     }
+
+    fn clip_to(mut self, clip: Clip) -> Self {
+        self.shape.clip = clip;
+        self
+    }
 }
 
 impl<M: Model> EventHandler<M> for PathBuilder<M> {
@@ -573,6 +744,469 @@ impl<M: Model> EventHandler<M> for PathBuilder<M> {
     }
 }
 
+pub fn ellipse<M: Model>() -> EllipseBuilder<M> {
+    EllipseBuilder {
+        shape: Default::default(),
+        prim: Default::default(),
+    }
+}
+
+pub struct EllipseBuilder<M: Model> {
+    shape: Ellipse,
+    prim: PrimBuilder<M>,
+}
+
+impl<M: Model> EllipseBuilder<M> {
+    pub fn center(mut self, x: impl Into<RealValue>, y: impl Into<RealValue>) -> Self {
+        self.shape.cx = x.into();
+        self.shape.cy = y.into();
+        self
+    }
+
+    pub fn radii(mut self, rx: impl Into<RealValue>, ry: impl Into<RealValue>) -> Self {
+        self.shape.rx = rx.into();
+        self.shape.ry = ry.into();
+        self
+    }
+}
+
+impl<M: Model> Builder<M> for EllipseBuilder<M> {
+    fn build(self) -> Node<M> {
+        Node::Prim(Prim::new(
+            Cow::Borrowed(Ellipse::NAME),
+            Shape::Ellipse(self.shape),
+            self.prim.children,
+            self.prim.listeners,
+        ))
+    }
+}
+
+impl<M: Model> Entity for EllipseBuilder<M> {
+    fn id(mut self, id: impl Into<String>) -> Self {
+        self.shape.id = Some(id.into());
+        self
+    }
+
+    fn transform(mut self, transform: impl Into<Transform>) -> Self {
+        self.shape.transform = transform.into();
+        self
+    }
+}
+
+impl<M: Model> Primitive<M> for EllipseBuilder<M> {
+    fn child(mut self, child: impl Builder<M>) -> Self {
+        self.prim.children.push(child.build());
+        self
+    }
+
+    fn children(mut self, children: impl IntoIterator<Item = Node<M>>) -> Self {
+        self.prim.children.extend(children);
+        self
+    }
+
+    fn transparency(mut self, transparency: impl Into<Real>) -> Self {
+        self.shape.transparency = transparency.into();
+        self
+    }
+
+    fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.shape.stroke = Some(stroke.into());
+        self
+    }
+
+    fn fill(mut self, fill: impl Into<Fill>) -> Self {
+        self.shape.fill = Some(fill.into());
+        self
+    }
+
+    fn effect(mut self, effect: impl Into<Effect>) -> Self {
+        self.shape.effects.push(effect.into());
+        self
+    }
+
+    fn remove_stroke(mut self) -> Self {
+        self.shape.stroke = None;
+        self
+    }
+
+    fn remove_fill(mut self) -> Self {
+        self.shape.fill = None;
+        self
+    }
+
+    fn clip(
+        mut self, x: impl Into<RealValue>, y: impl Into<RealValue>, width: impl Into<RealValue>,
+        height: impl Into<RealValue>,
+    ) -> Self {
+        self.shape.clip = Clip::new_scissor(x.into(), y.into(), width.into(), height.into());
+        self
+    }
+
+    fn clip_to(mut self, clip: Clip) -> Self {
+        self.shape.clip = clip;
+        self
+    }
+}
+
+impl<M: Model> EventHandler<M> for EllipseBuilder<M> {
+    fn add_listener(&mut self, listener: Listener<M>) {
+        self.prim
+            .listeners
+            .entry(listener.event_name())
+            .or_default()
+            .push(listener);
+    }
+}
+
+pub fn line<M: Model>() -> LineBuilder<M> {
+    LineBuilder {
+        shape: Default::default(),
+        prim: Default::default(),
+    }
+}
+
+pub struct LineBuilder<M: Model> {
+    shape: Line,
+    prim: PrimBuilder<M>,
+}
+
+impl<M: Model> LineBuilder<M> {
+    pub fn start(mut self, x: impl Into<RealValue>, y: impl Into<RealValue>) -> Self {
+        self.shape.x1 = x.into();
+        self.shape.y1 = y.into();
+        self
+    }
+
+    pub fn end(mut self, x: impl Into<RealValue>, y: impl Into<RealValue>) -> Self {
+        self.shape.x2 = x.into();
+        self.shape.y2 = y.into();
+        self
+    }
+}
+
+impl<M: Model> Builder<M> for LineBuilder<M> {
+    fn build(self) -> Node<M> {
+        Node::Prim(Prim::new(
+            Cow::Borrowed(Line::NAME),
+            Shape::Line(self.shape),
+            self.prim.children,
+            self.prim.listeners,
+        ))
+    }
+}
+
+impl<M: Model> Entity for LineBuilder<M> {
+    fn id(mut self, id: impl Into<String>) -> Self {
+        self.shape.id = Some(id.into());
+        self
+    }
+
+    fn transform(mut self, transform: impl Into<Transform>) -> Self {
+        self.shape.transform = transform.into();
+        self
+    }
+}
+
+impl<M: Model> Primitive<M> for LineBuilder<M> {
+    fn child(mut self, child: impl Builder<M>) -> Self {
+        self.prim.children.push(child.build());
+        self
+    }
+
+    fn children(mut self, children: impl IntoIterator<Item = Node<M>>) -> Self {
+        self.prim.children.extend(children);
+        self
+    }
+
+    fn transparency(mut self, transparency: impl Into<Real>) -> Self {
+        self.shape.transparency = transparency.into();
+        self
+    }
+
+    fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.shape.stroke = Some(stroke.into());
+        self
+    }
+
+    /// The line has no area to fill, so the fill is ignored.
+    fn fill(self, _fill: impl Into<Fill>) -> Self {
+        self
+    }
+
+    fn effect(mut self, effect: impl Into<Effect>) -> Self {
+        self.shape.effects.push(effect.into());
+        self
+    }
+
+    fn remove_stroke(mut self) -> Self {
+        self.shape.stroke = None;
+        self
+    }
+
+    fn remove_fill(self) -> Self {
+        self
+    }
+
+    fn clip(
+        mut self, x: impl Into<RealValue>, y: impl Into<RealValue>, width: impl Into<RealValue>,
+        height: impl Into<RealValue>,
+    ) -> Self {
+        self.shape.clip = Clip::new_scissor(x.into(), y.into(), width.into(), height.into());
+        self
+    }
+
+    fn clip_to(mut self, clip: Clip) -> Self {
+        self.shape.clip = clip;
+        self
+    }
+}
+
+impl<M: Model> EventHandler<M> for LineBuilder<M> {
+    fn add_listener(&mut self, listener: Listener<M>) {
+        self.prim
+            .listeners
+            .entry(listener.event_name())
+            .or_default()
+            .push(listener);
+    }
+}
+
+/// Build the open polyline through the points, such as `polyline(vec![(0, 0), (20, 0), (10, 15)])`.
+pub fn polyline<M: Model, X: Into<RealValue>, Y: Into<RealValue>>(
+    points: impl IntoIterator<Item = (X, Y)>,
+) -> PolylineBuilder<M> {
+    PolylineBuilder {
+        shape: Polyline {
+            points: points.into_iter().map(|(x, y)| (x.into(), y.into())).collect(),
+            ..Default::default()
+        },
+        prim: Default::default(),
+    }
+}
+
+pub struct PolylineBuilder<M: Model> {
+    shape: Polyline,
+    prim: PrimBuilder<M>,
+}
+
+impl<M: Model> PolylineBuilder<M> {
+    pub fn point(mut self, x: impl Into<RealValue>, y: impl Into<RealValue>) -> Self {
+        self.shape.points.push((x.into(), y.into()));
+        self
+    }
+
+    pub fn fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.shape.fill_rule = Some(fill_rule);
+        self
+    }
+}
+
+impl<M: Model> Builder<M> for PolylineBuilder<M> {
+    fn build(self) -> Node<M> {
+        Node::Prim(Prim::new(
+            Cow::Borrowed(Polyline::NAME),
+            Shape::Polyline(self.shape),
+            self.prim.children,
+            self.prim.listeners,
+        ))
+    }
+}
+
+impl<M: Model> Entity for PolylineBuilder<M> {
+    fn id(mut self, id: impl Into<String>) -> Self {
+        self.shape.id = Some(id.into());
+        self
+    }
+
+    fn transform(mut self, transform: impl Into<Transform>) -> Self {
+        self.shape.transform = transform.into();
+        self
+    }
+}
+
+impl<M: Model> Primitive<M> for PolylineBuilder<M> {
+    fn child(mut self, child: impl Builder<M>) -> Self {
+        self.prim.children.push(child.build());
+        self
+    }
+
+    fn children(mut self, children: impl IntoIterator<Item = Node<M>>) -> Self {
+        self.prim.children.extend(children);
+        self
+    }
+
+    fn transparency(mut self, transparency: impl Into<Real>) -> Self {
+        self.shape.transparency = transparency.into();
+        self
+    }
+
+    fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.shape.stroke = Some(stroke.into());
+        self
+    }
+
+    fn fill(mut self, fill: impl Into<Fill>) -> Self {
+        self.shape.fill = Some(fill.into());
+        self
+    }
+
+    fn effect(mut self, effect: impl Into<Effect>) -> Self {
+        self.shape.effects.push(effect.into());
+        self
+    }
+
+    fn remove_stroke(mut self) -> Self {
+        self.shape.stroke = None;
+        self
+    }
+
+    fn remove_fill(mut self) -> Self {
+        self.shape.fill = None;
+        self
+    }
+
+    fn clip(
+        mut self, x: impl Into<RealValue>, y: impl Into<RealValue>, width: impl Into<RealValue>,
+        height: impl Into<RealValue>,
+    ) -> Self {
+        self.shape.clip = Clip::new_scissor(x.into(), y.into(), width.into(), height.into());
+        self
+    }
+
+    fn clip_to(mut self, clip: Clip) -> Self {
+        self.shape.clip = clip;
+        self
+    }
+}
+
+impl<M: Model> EventHandler<M> for PolylineBuilder<M> {
+    fn add_listener(&mut self, listener: Listener<M>) {
+        self.prim
+            .listeners
+            .entry(listener.event_name())
+            .or_default()
+            .push(listener);
+    }
+}
+
+/// Build the closed polygon through the points, such as `polygon(vec![(0, 0), (20, 0), (10, 15)])`.
+pub fn polygon<M: Model, X: Into<RealValue>, Y: Into<RealValue>>(
+    points: impl IntoIterator<Item = (X, Y)>,
+) -> PolygonBuilder<M> {
+    PolygonBuilder {
+        shape: Polygon {
+            points: points.into_iter().map(|(x, y)| (x.into(), y.into())).collect(),
+            ..Default::default()
+        },
+        prim: Default::default(),
+    }
+}
+
+pub struct PolygonBuilder<M: Model> {
+    shape: Polygon,
+    prim: PrimBuilder<M>,
+}
+
+impl<M: Model> PolygonBuilder<M> {
+    pub fn point(mut self, x: impl Into<RealValue>, y: impl Into<RealValue>) -> Self {
+        self.shape.points.push((x.into(), y.into()));
+        self
+    }
+
+    pub fn fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.shape.fill_rule = Some(fill_rule);
+        self
+    }
+}
+
+impl<M: Model> Builder<M> for PolygonBuilder<M> {
+    fn build(self) -> Node<M> {
+        Node::Prim(Prim::new(
+            Cow::Borrowed(Polygon::NAME),
+            Shape::Polygon(self.shape),
+            self.prim.children,
+            self.prim.listeners,
+        ))
+    }
+}
+
+impl<M: Model> Entity for PolygonBuilder<M> {
+    fn id(mut self, id: impl Into<String>) -> Self {
+        self.shape.id = Some(id.into());
+        self
+    }
+
+    fn transform(mut self, transform: impl Into<Transform>) -> Self {
+        self.shape.transform = transform.into();
+        self
+    }
+}
+
+impl<M: Model> Primitive<M> for PolygonBuilder<M> {
+    fn child(mut self, child: impl Builder<M>) -> Self {
+        self.prim.children.push(child.build());
+        self
+    }
+
+    fn children(mut self, children: impl IntoIterator<Item = Node<M>>) -> Self {
+        self.prim.children.extend(children);
+        self
+    }
+
+    fn transparency(mut self, transparency: impl Into<Real>) -> Self {
+        self.shape.transparency = transparency.into();
+        self
+    }
+
+    fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.shape.stroke = Some(stroke.into());
+        self
+    }
+
+    fn fill(mut self, fill: impl Into<Fill>) -> Self {
+        self.shape.fill = Some(fill.into());
+        self
+    }
+
+    fn effect(mut self, effect: impl Into<Effect>) -> Self {
+        self.shape.effects.push(effect.into());
+        self
+    }
+
+    fn remove_stroke(mut self) -> Self {
+        self.shape.stroke = None;
+        self
+    }
+
+    fn remove_fill(mut self) -> Self {
+        self.shape.fill = None;
+        self
+    }
+
+    fn clip(
+        mut self, x: impl Into<RealValue>, y: impl Into<RealValue>, width: impl Into<RealValue>,
+        height: impl Into<RealValue>,
+    ) -> Self {
+        self.shape.clip = Clip::new_scissor(x.into(), y.into(), width.into(), height.into());
+        self
+    }
+
+    fn clip_to(mut self, clip: Clip) -> Self {
+        self.shape.clip = clip;
+        self
+    }
+}
+
+impl<M: Model> EventHandler<M> for PolygonBuilder<M> {
+    fn add_listener(&mut self, listener: Listener<M>) {
+        self.prim
+            .listeners
+            .entry(listener.event_name())
+            .or_default()
+            .push(listener);
+    }
+}
+
 pub fn group<M: Model>() -> GroupBuilder<M> {
     GroupBuilder {
         shape: Default::default(),
@@ -585,7 +1219,17 @@ pub struct GroupBuilder<M: Model> {
     prim: PrimBuilder<M>,
 }
 
-impl<M: Model> GroupBuilder<M> {}
+impl<M: Model> GroupBuilder<M> {
+    pub fn layer(mut self) -> Self {
+        self.shape.layer = true;
+        self
+    }
+
+    pub fn blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.shape.blend_mode = blend_mode;
+        self
+    }
+}
 
 impl<M: Model> Builder<M> for GroupBuilder<M> {
     fn build(self) -> Node<M> {
@@ -636,6 +1280,11 @@ impl<M: Model> Primitive<M> for GroupBuilder<M> {
         self
     }
 
+    fn effect(mut self, effect: impl Into<Effect>) -> Self {
+        self.shape.effects.push(effect.into());
+        self
+    }
+
     fn remove_stroke(mut self) -> Self {
         self.shape.stroke = None;
         self
@@ -653,6 +1302,11 @@ impl<M: Model> Primitive<M> for GroupBuilder<M> {
         self.shape.clip = Clip::new_scissor(x.into(), y.into(), width.into(), height.into());
         self
     }
+
+    fn clip_to(mut self, clip: Clip) -> Self {
+        self.shape.clip = clip;
+        self
+    }
 }
 
 impl<M: Model> EventHandler<M> for GroupBuilder<M> {
@@ -691,6 +1345,54 @@ impl Entity for CompBuilder {
     }
 }
 
+/// Style of the span that inherits everything from the text.
+pub fn normal() -> SpanStyle {
+    SpanStyle::default()
+}
+
+pub fn bold() -> SpanStyle {
+    SpanStyle::default().bold()
+}
+
+pub fn italic() -> SpanStyle {
+    SpanStyle::default().italic()
+}
+
+pub fn underline() -> SpanStyle {
+    SpanStyle::default().underline()
+}
+
+pub fn strikethrough() -> SpanStyle {
+    SpanStyle::default().strikethrough()
+}
+
+pub fn overline() -> SpanStyle {
+    SpanStyle::default().overline()
+}
+
+pub fn shadow(offset_x: impl Into<Real>, offset_y: impl Into<Real>, blur: impl Into<Real>, color: Color) -> Shadow {
+    Shadow::new(offset_x.into(), offset_y.into(), blur.into(), color)
+}
+
+pub fn blur(radius: impl Into<Real>) -> Effect {
+    Effect::blur(radius.into())
+}
+
+pub fn rounded_clip(
+    x: impl Into<RealValue>, y: impl Into<RealValue>, width: impl Into<RealValue>, height: impl Into<RealValue>,
+    rounding: impl Into<Rounding>,
+) -> Clip {
+    Clip::new_rounded_rect(x.into(), y.into(), width.into(), height.into(), rounding)
+}
+
+pub fn circle_clip(cx: impl Into<RealValue>, cy: impl Into<RealValue>, r: impl Into<RealValue>) -> Clip {
+    Clip::new_circle(cx.into(), cy.into(), r.into())
+}
+
+pub fn path_clip(cmd: impl Into<Vec<PathCommand>>) -> Clip {
+    Clip::new_path(cmd)
+}
+
 // TODO: use RealValue's
 pub fn translate(x: impl Into<Real>, y: impl Into<Real>) -> Transform {
     Transform::new().with_translation(x.into(), y.into())
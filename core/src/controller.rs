@@ -32,4 +32,12 @@ impl InputEvent {
     pub fn char(ch: char) -> Self {
         Self::Char(ch)
     }
+
+    pub fn mouse_pos(&self) -> Option<MousePos> {
+        match self {
+            Self::MouseDown(press) => Some(press.pos),
+            Self::MouseScroll(scroll) => Some(scroll.pos),
+            _ => None,
+        }
+    }
 }
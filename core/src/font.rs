@@ -0,0 +1,372 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+#[cfg(feature = "ttf")]
+pub use self::ttf::TtfMeasurer;
+
+#[cfg(feature = "ttf")]
+pub mod ttf;
+
+/// Family of the face bundled with the crate. The new registries have its regular face, it is replaced
+/// by the face registered with the same family, weight and style.
+#[cfg(feature = "default-font")]
+pub const DEFAULT_FONT_FAMILY: &str = "Roboto";
+
+/// Name of the bundled face, it differs from the name of the registered face of the same family, weight
+/// and style, so the face names don't refer to the bundled face and renderers load the replacing face.
+#[cfg(feature = "default-font")]
+const DEFAULT_FONT_NAME: &str = "Roboto-Regular-Bundled";
+
+#[cfg(feature = "default-font")]
+const DEFAULT_FONT_DATA: &[u8] = include_bytes!("../resources/Roboto-Regular.ttf");
+
+/// Weight of the font, the same as CSS `font-weight` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: FontWeight = FontWeight(100);
+    pub const EXTRA_LIGHT: FontWeight = FontWeight(200);
+    pub const LIGHT: FontWeight = FontWeight(300);
+    pub const NORMAL: FontWeight = FontWeight(400);
+    pub const MEDIUM: FontWeight = FontWeight(500);
+    pub const SEMI_BOLD: FontWeight = FontWeight(600);
+    pub const BOLD: FontWeight = FontWeight(700);
+    pub const EXTRA_BOLD: FontWeight = FontWeight(800);
+    pub const BLACK: FontWeight = FontWeight(900);
+
+    /// Conventional name of the weight used in the font names, such as `Bold` in `Roboto-Bold`.
+    pub fn name(self) -> &'static str {
+        match self.0 {
+            0..=149 => "Thin",
+            150..=249 => "ExtraLight",
+            250..=349 => "Light",
+            350..=449 => "Regular",
+            450..=549 => "Medium",
+            550..=649 => "SemiBold",
+            650..=749 => "Bold",
+            750..=849 => "ExtraBold",
+            _ => "Black",
+        }
+    }
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        FontWeight::NORMAL
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FontStyle {
+    #[default]
+    Normal,
+    Italic,
+}
+
+/// Font face registered in the `FontRegistry`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontFace {
+    /// Unique name of the face in the `Family-BoldItalic` form, renderers load the face by this name.
+    pub name: String,
+    pub family: String,
+    pub weight: FontWeight,
+    pub style: FontStyle,
+    pub data: Arc<Vec<u8>>,
+}
+
+impl FontFace {
+    pub fn new(family: impl Into<String>, weight: FontWeight, style: FontStyle, data: impl Into<Vec<u8>>) -> Self {
+        let family = family.into();
+        let variant = match (weight, style) {
+            (FontWeight::NORMAL, FontStyle::Italic) => "Italic".to_string(),
+            (weight, FontStyle::Italic) => format!("{}Italic", weight.name()),
+            (weight, FontStyle::Normal) => weight.name().to_string(),
+        };
+        Self {
+            name: format!("{}-{}", family, variant),
+            family,
+            weight,
+            style,
+            data: Arc::new(data.into()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum FontError {
+    Io(io::Error, PathBuf),
+    /// The face with the same family, weight and style is already registered.
+    AlreadyRegistered(String),
+    /// Neither the face nor the family with the name is registered.
+    NotFound(String),
+    /// The font name is empty and there is no default family.
+    NoDefaultFont,
+    /// The face data can't be parsed as a font.
+    InvalidData(String),
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FontError::Io(err, path) => write!(f, "Failed to read font '{}': {}", path.display(), err),
+            FontError::AlreadyRegistered(name) => write!(f, "Font '{}' is already registered", name),
+            FontError::NotFound(name) => write!(f, "Font '{}' not found", name),
+            FontError::NoDefaultFont => write!(f, "No default font is registered"),
+            FontError::InvalidData(name) => write!(f, "Font '{}' has invalid data", name),
+        }
+    }
+}
+
+impl Error for FontError {}
+
+/// Renderer independent set of the font faces grouped by families.
+///
+/// Faces are only added to the registry, or replace the bundled face under the new name, so renderers
+/// can load the new faces incrementally.
+/// Fallback families are used for the glyphs missing in the font, such as emoji or CJK.
+#[derive(Debug, Clone)]
+pub struct FontRegistry {
+    faces: Vec<FontFace>,
+    default_family: Option<String>,
+    fallbacks: Vec<String>,
+    family_fallbacks: HashMap<String, Vec<String>>,
+}
+
+/// The registry has the bundled face with the `default-font` feature, it is the default family
+/// until another one is set.
+impl Default for FontRegistry {
+    fn default() -> Self {
+        let registry = Self::empty();
+        #[cfg(feature = "default-font")]
+        let registry = Self {
+            faces: vec![FontFace {
+                name: DEFAULT_FONT_NAME.to_string(),
+                ..FontFace::new(DEFAULT_FONT_FAMILY, FontWeight::NORMAL, FontStyle::Normal, DEFAULT_FONT_DATA)
+            }],
+            ..registry
+        };
+        registry
+    }
+}
+
+impl FontRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The registry without the bundled face.
+    pub fn empty() -> Self {
+        Self {
+            faces: Vec::new(),
+            default_family: None,
+            fallbacks: Vec::new(),
+            family_fallbacks: HashMap::new(),
+        }
+    }
+
+    pub fn load_file(
+        &mut self, family: impl Into<String>, weight: FontWeight, style: FontStyle, path: impl AsRef<Path>,
+    ) -> Result<&FontFace, FontError> {
+        let path = path.as_ref();
+        let data = fs::read(path).map_err(|err| FontError::Io(err, path.to_path_buf()))?;
+        self.load_bytes(family, weight, style, data)
+    }
+
+    pub fn load_bytes(
+        &mut self, family: impl Into<String>, weight: FontWeight, style: FontStyle, data: impl Into<Vec<u8>>,
+    ) -> Result<&FontFace, FontError> {
+        let face = FontFace::new(family, weight, style, data);
+        if self.face(&face.name).is_some() {
+            return Err(FontError::AlreadyRegistered(face.name));
+        }
+        #[cfg(feature = "default-font")]
+        {
+            // The registered face replaces the bundled one in its place, so the default family is kept
+            let bundled = self.faces.iter().position(|other| {
+                other.name == DEFAULT_FONT_NAME
+                    && (&other.family, other.weight, other.style) == (&face.family, face.weight, face.style)
+            });
+            if let Some(idx) = bundled {
+                self.faces[idx] = face;
+                return Ok(&self.faces[idx]);
+            }
+        }
+        self.faces.push(face);
+        Ok(&self.faces[self.faces.len() - 1])
+    }
+
+    pub fn faces(&self) -> &[FontFace] {
+        &self.faces
+    }
+
+    pub fn face(&self, name: &str) -> Option<&FontFace> {
+        self.faces.iter().find(|face| face.name == name)
+    }
+
+    /// Registered families in the order of registration.
+    pub fn families(&self) -> Vec<&str> {
+        let mut families: Vec<&str> = Vec::new();
+        for face in &self.faces {
+            if !families.contains(&face.family.as_str()) {
+                families.push(&face.family);
+            }
+        }
+        families
+    }
+
+    /// Set the family used for the texts with the empty font name.
+    pub fn set_default_family(&mut self, family: impl Into<String>) {
+        self.default_family = Some(family.into());
+    }
+
+    /// The default family, or the family of the first registered face if it is not set. It is the bundled
+    /// family in the registry made with the `default-font` feature.
+    pub fn default_family(&self) -> Option<&str> {
+        self.default_family
+            .as_deref()
+            .or_else(|| self.faces.first().map(|face| face.family.as_str()))
+    }
+
+    /// Append the family to the fallback chain of all families.
+    pub fn add_fallback(&mut self, family: impl Into<String>) {
+        self.fallbacks.push(family.into());
+    }
+
+    /// Set the fallback families of the family, they are used before the common fallbacks.
+    pub fn set_family_fallbacks(&mut self, family: impl Into<String>, fallbacks: Vec<String>) {
+        self.family_fallbacks.insert(family.into(), fallbacks);
+    }
+
+    /// Find the face by its name, or the face of the family nearest to the weight and style.
+    /// The empty name refers to the default family.
+    pub fn resolve(&self, name: &str, weight: FontWeight, style: FontStyle) -> Result<&FontFace, FontError> {
+        let family = if name.is_empty() {
+            self.default_family().ok_or(FontError::NoDefaultFont)?
+        } else if let Some(face) = self.face(name) {
+            return Ok(face);
+        } else {
+            name
+        };
+
+        // Heavier faces are preferred for the bold weights and lighter ones otherwise, as in CSS
+        let prefer_heavier = weight > FontWeight::MEDIUM;
+        self.faces
+            .iter()
+            .filter(|face| face.family == family)
+            .min_by_key(|face| {
+                let distance = (i32::from(face.weight.0) - i32::from(weight.0)).abs();
+                let wrong_side = if prefer_heavier { face.weight < weight } else { face.weight > weight };
+                (face.style != style, distance, wrong_side)
+            })
+            .ok_or_else(|| FontError::NotFound(family.to_string()))
+    }
+
+    /// The resolved face followed by the faces of its fallback families. Fallback families
+    /// that are not registered are skipped.
+    pub fn fallback_chain(
+        &self, name: &str, weight: FontWeight, style: FontStyle,
+    ) -> Result<Vec<&FontFace>, FontError> {
+        let face = self.resolve(name, weight, style)?;
+        let mut chain = vec![face];
+        let family_fallbacks = self.family_fallbacks.get(&face.family).into_iter().flatten();
+        for family in family_fallbacks.chain(&self.fallbacks) {
+            if let Ok(fallback) = self.resolve(family, weight, style) {
+                if chain.iter().all(|face| face.name != fallback.name) {
+                    chain.push(fallback);
+                }
+            }
+        }
+        Ok(chain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> FontRegistry {
+        let mut registry = FontRegistry::empty();
+        for &(family, weight, style) in &[
+            ("Roboto", FontWeight::NORMAL, FontStyle::Normal),
+            ("Roboto", FontWeight::BOLD, FontStyle::Normal),
+            ("Roboto", FontWeight::NORMAL, FontStyle::Italic),
+            ("Emoji", FontWeight::NORMAL, FontStyle::Normal),
+            ("Cjk", FontWeight::NORMAL, FontStyle::Normal),
+        ] {
+            registry.load_bytes(family, weight, style, vec![]).unwrap();
+        }
+        registry
+    }
+
+    #[test]
+    fn resolve_faces() {
+        let registry = registry();
+        assert_eq!(registry.families(), vec!["Roboto", "Emoji", "Cjk"]);
+        assert_eq!(registry.default_family(), Some("Roboto"));
+
+        let resolve = |name, weight, style| registry.resolve(name, weight, style).map(|face| face.name.as_str());
+        assert_eq!(resolve("", FontWeight::NORMAL, FontStyle::Normal).unwrap(), "Roboto-Regular");
+        assert_eq!(resolve("Roboto", FontWeight::BLACK, FontStyle::Normal).unwrap(), "Roboto-Bold");
+        assert_eq!(resolve("Roboto", FontWeight::LIGHT, FontStyle::Normal).unwrap(), "Roboto-Regular");
+        assert_eq!(resolve("Roboto", FontWeight::BOLD, FontStyle::Italic).unwrap(), "Roboto-Italic");
+        assert_eq!(resolve("Roboto-Bold", FontWeight::NORMAL, FontStyle::Italic).unwrap(), "Roboto-Bold");
+        assert!(matches!(
+            resolve("Sans", FontWeight::NORMAL, FontStyle::Normal),
+            Err(FontError::NotFound(_))
+        ));
+        assert!(matches!(
+            FontRegistry::empty().resolve("", FontWeight::NORMAL, FontStyle::Normal),
+            Err(FontError::NoDefaultFont)
+        ));
+    }
+
+    #[test]
+    fn fallback_chains() {
+        let mut registry = registry();
+        registry.add_fallback("Emoji");
+        registry.add_fallback("Missing");
+        registry.set_family_fallbacks("Roboto", vec!["Cjk".to_string()]);
+
+        let chain: Vec<_> = registry
+            .fallback_chain("Roboto", FontWeight::BOLD, FontStyle::Normal)
+            .unwrap()
+            .into_iter()
+            .map(|face| face.name.as_str())
+            .collect();
+        assert_eq!(chain, vec!["Roboto-Bold", "Cjk-Regular", "Emoji-Regular"]);
+
+        assert!(matches!(
+            registry.load_bytes("Roboto", FontWeight::BOLD, FontStyle::Normal, vec![]),
+            Err(FontError::AlreadyRegistered(_))
+        ));
+    }
+
+    #[cfg(feature = "default-font")]
+    #[test]
+    fn default_face() {
+        let mut registry = FontRegistry::new();
+        assert_eq!(registry.default_family(), Some(DEFAULT_FONT_FAMILY));
+        let face = registry.resolve("", FontWeight::BOLD, FontStyle::Italic).unwrap();
+        assert_eq!((face.name.as_str(), face.data.is_empty()), (DEFAULT_FONT_NAME, false));
+        assert!(registry.face("Roboto-Regular").is_none());
+
+        registry.load_bytes("Emoji", FontWeight::NORMAL, FontStyle::Normal, vec![]).unwrap();
+        assert_eq!(registry.default_family(), Some(DEFAULT_FONT_FAMILY));
+
+        // The registered face of the family replaces the bundled one
+        let face = registry
+            .load_bytes(DEFAULT_FONT_FAMILY, FontWeight::NORMAL, FontStyle::Normal, vec![1])
+            .unwrap();
+        assert_eq!(face.name, "Roboto-Regular");
+        assert_eq!(registry.faces().len(), 2);
+        assert_eq!(registry.default_family(), Some(DEFAULT_FONT_FAMILY));
+        let face = registry.resolve("", FontWeight::NORMAL, FontStyle::Normal).unwrap();
+        assert_eq!(*face.data, vec![1]);
+    }
+}
@@ -0,0 +1,211 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use rustybuzz::{Direction, Face, UnicodeBuffer};
+use unicode_bidi::BidiInfo;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{FontError, FontFace, Real, ShapedText, TextMeasurer, TextMetrics};
+
+/// Text measurer backed by the pure Rust TrueType and OpenType parser and shaper. Ligatures,
+/// combining marks and kerning are applied by the shaping of the runs with the same face and direction.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TtfMeasurer;
+
+/// Part of the content shaped at once.
+struct ShapeRun {
+    /// Byte range of the run in the content.
+    range: Range<usize>,
+    face: usize,
+    rtl: bool,
+}
+
+impl TtfMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn parse(face: &FontFace) -> Result<Face<'_>, FontError> {
+        Face::from_slice(&face.data, 0).ok_or_else(|| FontError::InvalidData(face.name.clone()))
+    }
+
+    /// Split the content into the runs of the grapheme clusters with the same face and direction.
+    /// Each cluster uses the first face that has the glyphs of all its chars.
+    fn runs(faces: &[Face], content: &str) -> Vec<ShapeRun> {
+        let bidi = BidiInfo::new(content, None);
+        let mut runs: Vec<ShapeRun> = Vec::new();
+        for (start, grapheme) in content.grapheme_indices(true) {
+            let face = faces
+                .iter()
+                .position(|face| grapheme.chars().all(|ch| face.glyph_index(ch).is_some()))
+                .unwrap_or(0);
+            let rtl = bidi.levels[start].is_rtl();
+            let end = start + grapheme.len();
+            match runs.last_mut() {
+                Some(run) if run.face == face && run.rtl == rtl => run.range.end = end,
+                _ => runs.push(ShapeRun {
+                    range: start..end,
+                    face,
+                    rtl,
+                }),
+            }
+        }
+        runs
+    }
+}
+
+impl TextMeasurer for TtfMeasurer {
+    fn shape(&self, faces: &[&FontFace], font_size: Real, content: &str) -> Result<ShapedText, FontError> {
+        let parsed = faces.iter().map(|face| Self::parse(face)).collect::<Result<Vec<_>, _>>()?;
+        let primary = match parsed.first() {
+            Some(primary) => primary,
+            None => return Ok(ShapedText::default()),
+        };
+
+        let scale = |face: &Face| font_size / face.units_per_em() as Real;
+        let primary_scale = scale(primary);
+        let ascender = Real::from(primary.ascender()) * primary_scale;
+        let descender = Real::from(primary.descender()) * primary_scale;
+        let line_gap = Real::from(primary.line_gap()) * primary_scale;
+        let metrics = TextMetrics {
+            ascender,
+            descender,
+            line_height: ascender - descender + line_gap,
+        };
+
+        let offsets: Vec<usize> = content.char_indices().map(|(offset, _)| offset).collect();
+        let char_idx = |offset: usize| offsets.binary_search(&offset).unwrap_or_else(|idx| idx);
+        let grapheme_starts: Vec<usize> = content.grapheme_indices(true).map(|(offset, _)| offset).collect();
+
+        let mut advances = vec![0.0; offsets.len()];
+        for run in Self::runs(&parsed, content) {
+            let face = &parsed[run.face];
+            let mut buffer = UnicodeBuffer::new();
+            buffer.push_str(&content[run.range.clone()]);
+            buffer.set_direction(if run.rtl {
+                Direction::RightToLeft
+            } else {
+                Direction::LeftToRight
+            });
+            buffer.guess_segment_properties();
+            let glyphs = rustybuzz::shape(face, &[], buffer);
+
+            // Glyphs of the ligatures and the marks share the cluster of their first char
+            let mut clusters = BTreeMap::new();
+            for (info, pos) in glyphs.glyph_infos().iter().zip(glyphs.glyph_positions()) {
+                *clusters.entry(run.range.start + info.cluster as usize).or_insert(0) += pos.x_advance;
+            }
+            let starts: Vec<usize> = clusters.keys().copied().chain(Some(run.range.end)).collect();
+            for (cluster, &advance) in clusters.values().enumerate() {
+                let (start, end) = (starts[cluster], starts[cluster + 1]);
+                let advance = advance as Real * scale(face);
+
+                // The advance of the cluster with several graphemes is shared by them for the caret positions
+                let graphemes: Vec<usize> = grapheme_starts
+                    .iter()
+                    .copied()
+                    .filter(|offset| start <= *offset && *offset < end)
+                    .collect();
+                if graphemes.is_empty() {
+                    advances[char_idx(start)] += advance;
+                }
+                for &offset in &graphemes {
+                    advances[char_idx(offset)] += advance / graphemes.len() as Real;
+                }
+            }
+        }
+        Ok(ShapedText { metrics, advances })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FontRegistry, FontStyle, FontWeight, RealValue, Text, TextOverflow};
+
+    fn registry() -> FontRegistry {
+        let mut registry = FontRegistry::empty();
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/Roboto-Regular.ttf");
+        registry
+            .load_file("Roboto", FontWeight::NORMAL, FontStyle::Normal, path)
+            .unwrap();
+        registry
+    }
+
+    #[test]
+    fn measure_chars() {
+        let registry = registry();
+        let faces = registry
+            .fallback_chain("Roboto", FontWeight::NORMAL, FontStyle::Normal)
+            .unwrap();
+        let shaped = TtfMeasurer.shape(&faces, 20.0, "mi m").unwrap();
+
+        assert!(shaped.metrics.ascender > 0.0);
+        assert!(shaped.metrics.descender < 0.0);
+        assert!(shaped.metrics.line_height >= shaped.metrics.ascender - shaped.metrics.descender);
+        assert_eq!(shaped.advances.len(), 4);
+        assert!(shaped.advances[0] > shaped.advances[1]);
+        assert_eq!(shaped.advances[0], shaped.advances[3]);
+
+        let double = TtfMeasurer.shape(&faces, 40.0, "m").unwrap();
+        assert!((double.advances[0] - shaped.advances[0] * 2.0).abs() < 1e-3);
+
+        let invalid = FontFace::new("Broken", FontWeight::NORMAL, FontStyle::Normal, vec![0; 4]);
+        assert!(matches!(
+            TtfMeasurer.shape(&[&invalid], 20.0, "m"),
+            Err(FontError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn shape_clusters() {
+        let registry = registry();
+        let faces = registry
+            .fallback_chain("Roboto", FontWeight::NORMAL, FontStyle::Normal)
+            .unwrap();
+
+        // The combining mark is a part of the base char cluster
+        let combined = TtfMeasurer.shape(&faces, 20.0, "e\u{301}x").unwrap();
+        let plain = TtfMeasurer.shape(&faces, 20.0, "ex").unwrap();
+        assert_eq!(combined.advances.len(), 3);
+        assert_eq!(combined.advances[1], 0.0);
+        assert!(combined.advances[0] > 0.0);
+        assert!((combined.advances[2] - plain.advances[1]).abs() < 1e-3);
+
+        // Right-to-left chars are measured in their own run
+        let mixed = TtfMeasurer.shape(&faces, 20.0, "a\u{5d0}\u{5d1}b").unwrap();
+        assert_eq!(mixed.advances.len(), 4);
+        assert!(mixed.advances.iter().all(|advance| *advance > 0.0));
+    }
+
+    #[test]
+    fn layout_and_hit_test() {
+        let registry = registry();
+        let mut text = Text {
+            content: "Hello world".to_string(),
+            font_size: RealValue::px(20.0),
+            ..Default::default()
+        };
+        let faces = registry.fallback_chain("", FontWeight::NORMAL, FontStyle::Normal).unwrap();
+        let hello = TtfMeasurer.shape(&faces, 20.0, "Hello ").unwrap();
+        let hello_width: Real = hello.advances.iter().sum();
+        text.layout.max_width = Some(RealValue::px(hello_width + 1.0));
+
+        text.layout_with(&TtfMeasurer, &registry).unwrap();
+        assert_eq!(text.lines.len(), 2);
+        assert_eq!(text.lines[0].range, 0..6);
+        assert_eq!(text.lines[1].range, 6..11);
+        assert!(text.lines[1].top() >= text.lines[0].bottom() - 1e-3);
+
+        let second_line_y = text.lines[1].baseline;
+        assert_eq!(text.char_index_at(-1.0, 1.0), 0);
+        assert_eq!(text.char_index_at(text.glyph_positions[2].x + 1.0, 1.0), 2);
+        assert_eq!(text.char_index_at(text.glyph_positions[7].max_x() - 1.0, second_line_y), 8);
+
+        text.layout.max_lines = Some(1);
+        text.layout.overflow = TextOverflow::Ellipsis(Default::default());
+        text.layout_with(&TtfMeasurer, &registry).unwrap();
+        assert_eq!(text.lines.len(), 1);
+        assert!(text.lines[0].elided.is_some());
+        assert!(text.lines[0].width <= hello_width + 1.0);
+    }
+}
@@ -1,6 +1,7 @@
-pub use self::{controller::*, listener::*, model::*, node::*, render::*};
+pub use self::{controller::*, font::*, listener::*, model::*, node::*, render::*};
 
 pub mod controller;
+pub mod font;
 pub mod listener;
 pub mod model;
 pub mod node;
@@ -1,10 +1,11 @@
-pub use self::{clip::*, comp::*, converter::*, prim::*, shape::*, transform::*, value::*};
+pub use self::{clip::*, comp::*, converter::*, interpolate::*, prim::*, shape::*, transform::*, value::*};
 use crate::{Model, SystemMessage};
 
 pub mod builder;
 pub mod clip;
 pub mod comp;
 pub mod converter;
+pub mod interpolate;
 pub mod prim;
 pub mod shape;
 pub mod transform;
@@ -167,10 +168,10 @@ impl<M: Model> Node<M> {
         }
     }
 
-    pub fn send_system_msg(&mut self, msg: SystemMessage, outputs: &mut Vec<M::Message>) {
+    pub fn send_system_msg(&mut self, msg: SystemMessage, clipped: bool, outputs: &mut Vec<M::Message>) {
         match self {
-            Node::Prim(prim) => prim.send_system_msg(msg, outputs),
-            Node::Comp(comp) => comp.send_system_msg(msg),
+            Node::Prim(prim) => prim.send_system_msg(msg, clipped, outputs),
+            Node::Comp(comp) => comp.send_clipped_system_msg(msg, clipped),
         }
     }
 
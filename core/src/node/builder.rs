@@ -1,5 +1,6 @@
 use crate::{
-    Fill, KeyboardEvent, Listener, Model, MouseDown, MouseScroll, Node, On, Real, RealValue, Stroke, Transform,
+    Clip, Effect, Fill, KeyboardEvent, Listener, Model, MouseDown, MouseScroll, Node, On, Real, RealValue, Stroke,
+    Transform,
 };
 
 pub trait Builder<M: Model> {
@@ -23,12 +24,15 @@ pub trait Primitive<M: Model> {
     fn transparency(self, transparency: impl Into<Real>) -> Self;
     fn stroke(self, stroke: impl Into<Stroke>) -> Self;
     fn fill(self, fill: impl Into<Fill>) -> Self;
+    fn effect(self, effect: impl Into<Effect>) -> Self;
     fn remove_stroke(self) -> Self;
     fn remove_fill(self) -> Self;
     fn clip(
         self, x: impl Into<RealValue>, y: impl Into<RealValue>, width: impl Into<RealValue>,
         height: impl Into<RealValue>,
     ) -> Self;
+    /// Clip by any region, such as the rounded rectangle, the circle or the path.
+    fn clip_to(self, clip: Clip) -> Self;
 }
 
 pub trait EventHandler<M: Model>: Sized {
@@ -1,4 +1,7 @@
-use crate::{RealValue, Transform};
+use crate::{
+    node::shape::path::flatten::HIT_TOLERANCE, PathBuilder, PathCommand, PathSegment, Real, RealValue, Rounding,
+    Transform, TransformMatrix,
+};
 
 /// A scissor defines a region on the screen in which drawing operations are allowed.
 /// Pixels drawn outside of this region are clipped.
@@ -11,10 +14,42 @@ pub struct Scissor {
     pub transform: Transform,
 }
 
-/// Define how to clip specified region.
+/// Rectangle region with the rounded corners, such as the content of the rounded card.
 #[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoundedScissor {
+    pub x: RealValue,
+    pub y: RealValue,
+    pub width: RealValue,
+    pub height: RealValue,
+    pub rounding: Rounding,
+    pub transform: Transform,
+}
+
+/// Circle region, such as the rounded avatar.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CircleClip {
+    pub cx: RealValue,
+    pub cy: RealValue,
+    pub r: RealValue,
+    pub transform: Transform,
+}
+
+/// Region inside the path outline by the non-zero fill rule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathClip {
+    pub cmd: Vec<PathCommand>,
+    pub transform: Transform,
+}
+
+/// Define how to clip specified region. The clip of the group is intersected with the clips
+/// of its children and the clips of the nested groups.
+#[derive(Clone, Debug, Default, PartialEq)]
 pub enum Clip {
     Scissor(Scissor),
+    RoundedRect(RoundedScissor),
+    Circle(CircleClip),
+    Path(PathClip),
+    #[default]
     None,
 }
 
@@ -29,12 +64,37 @@ impl Clip {
         })
     }
 
+    pub fn new_rounded_rect(
+        x: RealValue, y: RealValue, width: RealValue, height: RealValue, rounding: impl Into<Rounding>,
+    ) -> Self {
+        Clip::RoundedRect(RoundedScissor {
+            x,
+            y,
+            width,
+            height,
+            rounding: rounding.into(),
+            transform: Transform::default(),
+        })
+    }
+
+    pub fn new_circle(cx: RealValue, cy: RealValue, r: RealValue) -> Self {
+        Clip::Circle(CircleClip {
+            cx,
+            cy,
+            r,
+            transform: Transform::default(),
+        })
+    }
+
+    pub fn new_path(cmd: impl Into<Vec<PathCommand>>) -> Self {
+        Clip::Path(PathClip {
+            cmd: cmd.into(),
+            transform: Transform::default(),
+        })
+    }
+
     pub fn is_none(&self) -> bool {
-        if let Clip::None = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Clip::None)
     }
 
     pub fn or(self, other: Self) -> Self {
@@ -48,28 +108,207 @@ impl Clip {
     pub fn scissor(&self) -> Option<&Scissor> {
         match self {
             Clip::Scissor(scissor) => Some(scissor),
-            Clip::None => None,
+            _ => None,
         }
     }
 
     pub fn scissor_mut(&mut self) -> Option<&mut Scissor> {
         match self {
             Clip::Scissor(scissor) => Some(scissor),
-            Clip::None => None,
+            _ => None,
         }
     }
 
     pub fn transform(&self) -> Option<&Transform> {
-        self.scissor().map(|scissor| &scissor.transform)
+        match self {
+            Clip::Scissor(Scissor { transform, .. })
+            | Clip::RoundedRect(RoundedScissor { transform, .. })
+            | Clip::Circle(CircleClip { transform, .. })
+            | Clip::Path(PathClip { transform, .. }) => Some(transform),
+            Clip::None => None,
+        }
     }
 
     pub fn transform_mut(&mut self) -> Option<&mut Transform> {
-        self.scissor_mut().map(|scissor| &mut scissor.transform)
+        match self {
+            Clip::Scissor(Scissor { transform, .. })
+            | Clip::RoundedRect(RoundedScissor { transform, .. })
+            | Clip::Circle(CircleClip { transform, .. })
+            | Clip::Path(PathClip { transform, .. }) => Some(transform),
+            Clip::None => None,
+        }
+    }
+
+    /// Outline of the clip region as the path commands in the clip coordinates.
+    pub fn outline(&self) -> Vec<PathCommand> {
+        match self {
+            Clip::Scissor(scissor) => PathBuilder::new()
+                .rect(scissor.x.val(), scissor.y.val(), scissor.width.val(), scissor.height.val())
+                .build(),
+            Clip::RoundedRect(rounded) => {
+                let rounding = rounded.rounding;
+                let radii = [
+                    rounding.top_left.val(),
+                    rounding.top_right.val(),
+                    rounding.bottom_right.val(),
+                    rounding.bottom_left.val(),
+                ];
+                PathBuilder::new()
+                    .rounded_rect(rounded.x.val(), rounded.y.val(), rounded.width.val(), rounded.height.val(), radii)
+                    .build()
+            }
+            Clip::Circle(circle) => {
+                let r = circle.r.val();
+                PathBuilder::new().ellipse(circle.cx.val(), circle.cy.val(), r, r).build()
+            }
+            Clip::Path(path) => path.cmd.clone(),
+            Clip::None => Vec::new(),
+        }
+    }
+
+    /// Bounds of the clip region as `[x, y, width, height]` in the clip coordinates.
+    pub fn bounds(&self) -> Option<[Real; 4]> {
+        match self {
+            Clip::Scissor(Scissor { x, y, width, height, .. })
+            | Clip::RoundedRect(RoundedScissor { x, y, width, height, .. }) => {
+                Some([x.val(), y.val(), width.val(), height.val()])
+            }
+            Clip::Circle(circle) => {
+                let r = circle.r.val();
+                Some([circle.cx.val() - r, circle.cy.val() - r, 2.0 * r, 2.0 * r])
+            }
+            Clip::Path(_) => {
+                let lines = self.outline_lines();
+                let mut points = lines.iter().filter_map(PathSegment::to);
+                let [x, y] = points.next()?;
+                let [min_x, min_y, max_x, max_y] = points.fold([x, y, x, y], |[min_x, min_y, max_x, max_y], [x, y]| {
+                    [min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)]
+                });
+                Some([min_x, min_y, max_x - min_x, max_y - min_y])
+            }
+            Clip::None => None,
+        }
+    }
+
+    /// Axis aligned bounds of the clip region as `[x, y, width, height]` in the global coordinates.
+    pub fn global_bounds(&self) -> Option<[Real; 4]> {
+        let [x, y, width, height] = self.bounds()?;
+        let matrix = self.global_matrix();
+        let corners = [
+            matrix * (x, y),
+            matrix * (x + width, y),
+            matrix * (x + width, y + height),
+            matrix * (x, y + height),
+        ];
+        let (x, y) = corners[0];
+        let [min_x, min_y, max_x, max_y] = corners[1..]
+            .iter()
+            .fold([x, y, x, y], |[min_x, min_y, max_x, max_y], &(x, y)| {
+                [min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)]
+            });
+        Some([min_x, min_y, max_x - min_x, max_y - min_y])
+    }
+
+    /// Whether the point in the global coordinates is inside the clip region, any point is inside the `None` clip.
+    pub fn contains(&self, x: Real, y: Real) -> bool {
+        let matrix = self.global_matrix();
+        let (x, y) = if !matrix.is_identity() {
+            matrix.inverse() * (x, y)
+        } else {
+            (x, y)
+        };
+        match self {
+            Clip::Scissor(scissor) => {
+                let (left, top) = (scissor.x.val(), scissor.y.val());
+                x >= left && x <= left + scissor.width.val() && y >= top && y <= top + scissor.height.val()
+            }
+            Clip::Circle(circle) => (x - circle.cx.val()).hypot(y - circle.cy.val()) <= circle.r.val(),
+            Clip::RoundedRect(_) | Clip::Path(_) => {
+                PathSegment::winding_number(&self.outline_lines(), [x, y]) != 0
+            }
+            Clip::None => true,
+        }
+    }
+
+    /// Whether the point in the global coordinates is inside all of the clips.
+    pub fn contains_all<'a>(clips: impl IntoIterator<Item = &'a Clip>, x: Real, y: Real) -> bool {
+        clips.into_iter().all(|clip| clip.contains(x, y))
+    }
+
+    fn global_matrix(&self) -> TransformMatrix {
+        self.transform()
+            .map(|transform| transform.global_matrix().unwrap_or_else(|| transform.matrix()))
+            .unwrap_or_else(TransformMatrix::identity)
+    }
+
+    /// Flattened outline, the path with the invalid commands has no area.
+    fn outline_lines(&self) -> Vec<PathSegment> {
+        PathSegment::resolve(&self.outline())
+            .map(|segments| PathSegment::flatten(&segments, HIT_TOLERANCE))
+            .unwrap_or_default()
     }
 }
 
-impl Default for Clip {
-    fn default() -> Self {
-        Clip::None
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clip_contains() {
+        let scissor = Clip::new_scissor(
+            RealValue::px(10.0),
+            RealValue::px(10.0),
+            RealValue::px(20.0),
+            RealValue::px(10.0),
+        );
+        assert!(scissor.contains(30.0, 15.0));
+        assert!(!scissor.contains(31.0, 15.0));
+
+        let rounded = Clip::new_rounded_rect(
+            RealValue::px(0.0),
+            RealValue::px(0.0),
+            RealValue::px(40.0),
+            RealValue::px(40.0),
+            RealValue::px(10.0),
+        );
+        assert!(rounded.contains(20.0, 1.0));
+        assert!(rounded.contains(4.0, 4.0));
+        assert!(!rounded.contains(1.0, 1.0));
+        assert!(!rounded.contains(41.0, 20.0));
+
+        let circle = Clip::new_circle(RealValue::px(20.0), RealValue::px(20.0), RealValue::px(10.0));
+        assert!(circle.contains(27.0, 27.0));
+        assert!(!circle.contains(28.0, 28.0));
+
+        // Open triangle is closed implicitly
+        let triangle = Clip::new_path(
+            PathBuilder::new()
+                .move_to(0.0, 0.0)
+                .line_to(20.0, 0.0)
+                .line_to(0.0, 20.0)
+                .build(),
+        );
+        assert!(triangle.contains(5.0, 5.0));
+        assert!(!triangle.contains(15.0, 15.0));
+        assert_eq!(triangle.bounds(), Some([0.0, 0.0, 20.0, 20.0]));
+
+        assert!(Clip::None.contains(-100.0, 100.0));
+        assert!(Clip::contains_all(&[scissor, circle], 25.0, 15.0));
+        assert!(!Clip::contains_all(&[rounded, triangle], 25.0, 15.0));
+    }
+
+    #[test]
+    fn transformed_clip() {
+        let mut clip = Clip::new_circle(RealValue::px(0.0), RealValue::px(0.0), RealValue::px(10.0));
+        clip.transform_mut()
+            .unwrap()
+            .translate(100.0, 50.0)
+            .scale(2.0, 2.0);
+        clip.transform_mut()
+            .unwrap()
+            .calculate_global(TransformMatrix::identity());
+        assert!(clip.contains(115.0, 50.0));
+        assert!(!clip.contains(10.0, 0.0));
+        assert_eq!(clip.global_bounds(), Some([80.0, 30.0, 40.0, 40.0]));
     }
 }
@@ -33,7 +33,7 @@ pub trait CompApi: AsAny {
     fn set_transform(&mut self, transform: Transform);
     fn as_composite_shape(&self) -> Option<&dyn CompositeShape>;
     fn as_composite_shape_mut(&mut self) -> Option<&mut dyn CompositeShape>;
-    fn send_system_msg(&mut self, msg: SystemMessage);
+    fn send_system_msg(&mut self, msg: SystemMessage, clipped: bool);
     fn update_view(&mut self) -> UpdateView;
     fn need_recalc(&self) -> bool;
     fn need_redraw(&self) -> bool;
@@ -137,7 +137,13 @@ impl Comp {
     }
 
     pub fn send_system_msg(&mut self, msg: SystemMessage) {
-        self.inner.send_system_msg(msg);
+        self.inner.send_system_msg(msg, false);
+    }
+
+    /// Send the message to the component nested in the tree, the mouse events `clipped` away
+    /// by the clips of the ancestor groups don't hit its view.
+    pub fn send_clipped_system_msg(&mut self, msg: SystemMessage, clipped: bool) {
+        self.inner.send_system_msg(msg, clipped);
     }
 
     pub fn update_view(&mut self) -> UpdateView {
@@ -229,14 +235,14 @@ impl<M: Model> CompApi for CompInner<M> {
         self.view.as_mut().map(|node| node as &mut dyn CompositeShape)
     }
 
-    fn send_system_msg(&mut self, msg: SystemMessage) {
+    fn send_system_msg(&mut self, msg: SystemMessage, clipped: bool) {
         let mut outputs = vec![];
         if let Some(msg) = self.model.system_update(msg) {
             outputs.push(msg);
         }
 
         if let Some(view) = self.view.as_mut() {
-            view.send_system_msg(msg, &mut outputs);
+            view.send_system_msg(msg, clipped, &mut outputs);
         }
 
         for msg in outputs {
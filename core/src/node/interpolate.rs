@@ -0,0 +1,90 @@
+use crate::{Real, RealValue, ValueType};
+
+/// Value that can be tweened between two states, such as by an animation.
+pub trait Interpolate: Sized {
+    /// Value between this one at `t = 0` and the other one at `t = 1`. The parts that can't be tweened
+    /// switch from this value to the other one in the middle.
+    fn interpolate(&self, other: &Self, t: Real) -> Self;
+}
+
+/// Switch from one value to the other one in the middle of the interpolation.
+pub fn step<T: Clone>(from: &T, to: &T, t: Real) -> T {
+    if t < 0.5 {
+        from.clone()
+    } else {
+        to.clone()
+    }
+}
+
+impl Interpolate for Real {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for [Real; 2] {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        [self[0].interpolate(&other[0], t), self[1].interpolate(&other[1], t)]
+    }
+}
+
+impl<A: Interpolate, B: Interpolate> Interpolate for (A, B) {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        (self.0.interpolate(&other.0, t), self.1.interpolate(&other.1, t))
+    }
+}
+
+/// Both values are interpolated if they are present, otherwise the value appears or disappears in the middle.
+impl<T: Interpolate + Clone> Interpolate for Option<T> {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        match (self, other) {
+            (Some(from), Some(to)) => Some(from.interpolate(to, t)),
+            _ => step(self, other, t),
+        }
+    }
+}
+
+/// The lengths are interpolated unit by unit, so the values in the different units are interpolated
+/// through the calc expressions. The auto values switch in the middle.
+impl Interpolate for RealValue {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        match (self.1, other.1) {
+            (ValueType::Auto, _) | (_, ValueType::Auto) => step(self, other, t),
+            _ => *self * (1.0 - t) + *other * t,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolate_values() {
+        assert_eq!((2.0 as Real).interpolate(&4.0, 0.25), 2.5);
+        assert_eq!(
+            (0.0 as Real, [0.0, 10.0]).interpolate(&(1.0, [10.0, 0.0]), 0.5),
+            (0.5, [5.0, 5.0])
+        );
+        assert_eq!(Some(1.0 as Real).interpolate(&Some(3.0), 0.5), Some(2.0));
+        assert_eq!(Some(1.0 as Real).interpolate(&None, 0.4), Some(1.0));
+        assert_eq!(Some(1.0 as Real).interpolate(&None, 0.5), None);
+
+        assert_eq!(
+            RealValue::px(10.0).interpolate(&RealValue::px(20.0), 0.5),
+            RealValue::px(15.0)
+        );
+        assert_eq!(
+            RealValue::pct(10.0).interpolate(&RealValue::pct(20.0), 0.5),
+            RealValue::pct(15.0)
+        );
+        assert_eq!(
+            RealValue::px(10.0).interpolate(&RealValue::pct(20.0), 0.5),
+            RealValue::px(5.0) + RealValue::pct(10.0)
+        );
+        assert_eq!(
+            RealValue::auto().interpolate(&RealValue::pct(20.0), 0.6),
+            RealValue::pct(20.0)
+        );
+    }
+}
@@ -2,7 +2,7 @@ use std::{borrow::Cow, collections::HashMap, marker::PhantomData};
 
 use crate::{
     CompositeShape, CompositeShapeIter, CompositeShapeIterMut, EventName, InputEvent, Listener, Model, Node, On, Shape,
-    SystemMessage, Transform, UpdateView,
+    SystemMessage, TextSpan, Transform, UpdateView,
 };
 
 pub struct Prim<M: Model> {
@@ -34,10 +34,27 @@ impl<M: Model> Prim<M> {
         self.shape.set_id(id);
     }
 
+    /// Replace the content of the text by the plain content, the spans of the text are removed.
     pub fn set_text(&mut self, content: impl Into<String>) -> bool {
         match self.shape {
             Shape::Text(ref mut text) => {
                 text.content = content.into();
+                text.spans.clear();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Replace the content of the text by the concatenation of the spans.
+    pub fn set_spans(&mut self, spans: impl IntoIterator<Item = TextSpan>) -> bool {
+        match self.shape {
+            Shape::Text(ref mut text) => {
+                text.content.clear();
+                text.spans.clear();
+                for span in spans {
+                    text.push_span(span);
+                }
                 true
             }
             _ => false,
@@ -52,11 +69,13 @@ impl<M: Model> Prim<M> {
         self.shape.transform_mut()
     }
 
-    pub fn send_system_msg(&mut self, msg: SystemMessage, outputs: &mut Vec<M::Message>) {
+    /// Send the message to the prim and its children. The mouse events `clipped` away by the clips
+    /// of the ancestor groups don't hit the prim.
+    pub fn send_system_msg(&mut self, msg: SystemMessage, clipped: bool, outputs: &mut Vec<M::Message>) {
         match msg {
             SystemMessage::Input(input) => match input {
                 InputEvent::MouseDown(press) => {
-                    if self.intersect(press.pos.x, press.pos.y) {
+                    if !clipped && self.intersect(press.pos.x, press.pos.y) {
                         if let Some(listeners) = self.listeners.get(&EventName::ON_MOUSE_DOWN) {
                             for listener in listeners {
                                 let msg = match listener {
@@ -83,7 +102,7 @@ impl<M: Model> Prim<M> {
                     }
                 }
                 InputEvent::MouseScroll(scroll) => {
-                    if self.intersect(scroll.pos.x, scroll.pos.y) {
+                    if !clipped && self.intersect(scroll.pos.x, scroll.pos.y) {
                         if let Some(listeners) = self.listeners.get(&EventName::ON_MOUSE_SCROLL) {
                             for listener in listeners {
                                 let msg = match listener {
@@ -156,8 +175,15 @@ impl<M: Model> Prim<M> {
             }
         }
 
+        let clipped = clipped
+            || match msg {
+                SystemMessage::Input(input) => input
+                    .mouse_pos()
+                    .is_some_and(|pos| !self.shape.children_clip().contains(pos.x, pos.y)),
+                _ => false,
+            };
         for child in self.children.iter_mut() {
-            child.send_system_msg(msg, outputs);
+            child.send_system_msg(msg, clipped, outputs);
         }
     }
 
@@ -197,3 +223,78 @@ impl<M: Model> CompositeShape for Prim<M> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChangeView, Clip, Group, MouseButton, MousePos, RealValue, Rect, Shaped, SpanStyle, Text};
+
+    struct Clicks;
+
+    impl Model for Clicks {
+        type Message = &'static str;
+        type Properties = ();
+
+        fn create(_props: Self::Properties) -> Self {
+            Clicks
+        }
+
+        fn update(&mut self, _msg: Self::Message) -> ChangeView {
+            ChangeView::None
+        }
+
+        fn build_view(&self) -> Node<Self> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn clipped_mouse_down() {
+        let button = Rect {
+            width: RealValue::px(20.0),
+            height: RealValue::px(20.0),
+            ..Default::default()
+        };
+        let mut listeners = HashMap::new();
+        listeners.insert(EventName::ON_MOUSE_DOWN, vec![Listener::OnMouseDown(|_| "down")]);
+        listeners.insert(EventName::ON_BLUR, vec![Listener::OnBlur(|_| "blur")]);
+        let scroll = Group {
+            clip: Clip::new_circle(RealValue::px(10.0), RealValue::px(10.0), RealValue::px(10.0)),
+            ..Default::default()
+        };
+        let button = Node::Prim(Prim::new(Rect::NAME.into(), Shape::Rect(button), vec![], listeners));
+        let mut prim = Prim::<Clicks>::new(Group::NAME.into(), Shape::Group(scroll), vec![button], HashMap::new());
+
+        let mut press = |x, y| {
+            let mut outputs = Vec::new();
+            let event = InputEvent::mouse_down(MousePos { x, y }, MouseButton::Left);
+            prim.send_system_msg(SystemMessage::Input(event), false, &mut outputs);
+            outputs
+        };
+        assert_eq!(press(10.0, 10.0), vec!["down"]);
+        // The corner of the button is clipped away by the circle
+        assert_eq!(press(1.0, 1.0), vec!["blur"]);
+        assert_eq!(press(30.0, 10.0), vec!["blur"]);
+    }
+
+    #[test]
+    fn set_text_content() {
+        let text = Text {
+            content: "Bold".to_string(),
+            ..Default::default()
+        };
+        let mut prim = Prim::<Clicks>::new(Text::NAME.into(), Shape::Text(text), vec![], HashMap::new());
+        let text = |prim: &Prim<Clicks>| prim.shape.text().cloned().unwrap();
+
+        assert!(prim.set_spans(vec![
+            TextSpan::new("Bold", SpanStyle::default().bold()),
+            TextSpan::new(" normal", SpanStyle::default()),
+        ]));
+        assert_eq!(text(&prim).content, "Bold normal");
+        assert_eq!(text(&prim).spans.len(), 2);
+
+        assert!(prim.set_text("Plain"));
+        assert_eq!(text(&prim).content, "Plain");
+        assert!(text(&prim).spans.is_empty());
+    }
+}
@@ -1,14 +1,23 @@
 pub use self::{
-    circle::*, fill::*, group::*, padding::*, paint::*, path::*, rect::*, rounding::*, stroke::*, text::*, translate::*,
+    circle::*, color::*, constraints::*, effect::*, ellipse::*, fill::*, group::*, line::*, padding::*, paint::*,
+    path::*, pattern::*, polygon::*, polyline::*, rect::*, rounding::*, stroke::*, text::*, translate::*,
 };
-use crate::{Real, Transform};
+use crate::{Clip, Real, Transform};
 
 pub mod circle;
+pub mod color;
+pub mod constraints;
+pub mod effect;
+pub mod ellipse;
 pub mod fill;
 pub mod group;
+pub mod line;
 pub mod padding;
 pub mod paint;
 pub mod path;
+pub mod pattern;
+pub mod polygon;
+pub mod polyline;
 pub mod rect;
 pub mod rounding;
 pub mod stroke;
@@ -19,6 +28,10 @@ pub mod translate;
 pub enum Shape {
     Rect(Rect),
     Circle(Circle),
+    Ellipse(Ellipse),
+    Line(Line),
+    Polyline(Polyline),
+    Polygon(Polygon),
     Path(Path),
     Group(Group),
     Text(Text),
@@ -31,6 +44,18 @@ pub trait Shaped {
     fn circle(&self) -> Option<&Circle>;
     fn circle_mut(&mut self) -> Option<&mut Circle>;
 
+    fn ellipse(&self) -> Option<&Ellipse>;
+    fn ellipse_mut(&mut self) -> Option<&mut Ellipse>;
+
+    fn line(&self) -> Option<&Line>;
+    fn line_mut(&mut self) -> Option<&mut Line>;
+
+    fn polyline(&self) -> Option<&Polyline>;
+    fn polyline_mut(&mut self) -> Option<&mut Polyline>;
+
+    fn polygon(&self) -> Option<&Polygon>;
+    fn polygon_mut(&mut self) -> Option<&mut Polygon>;
+
     fn path(&self) -> Option<&Path>;
     fn path_mut(&mut self) -> Option<&mut Path>;
 
@@ -49,6 +74,10 @@ impl Shape {
         match self {
             Shape::Rect(rect) => rect.id(),
             Shape::Circle(circle) => circle.id(),
+            Shape::Ellipse(ellipse) => ellipse.id(),
+            Shape::Line(line) => line.id(),
+            Shape::Polyline(polyline) => polyline.id(),
+            Shape::Polygon(polygon) => polygon.id(),
             Shape::Path(path) => path.id(),
             Shape::Group(group) => group.id(),
             Shape::Text(text) => text.id(),
@@ -60,6 +89,10 @@ impl Shape {
         match self {
             Shape::Rect(rect) => rect.id = id,
             Shape::Circle(circle) => circle.id = id,
+            Shape::Ellipse(ellipse) => ellipse.id = id,
+            Shape::Line(line) => line.id = id,
+            Shape::Polyline(polyline) => polyline.id = id,
+            Shape::Polygon(polygon) => polygon.id = id,
             Shape::Path(path) => path.id = id,
             Shape::Group(group) => group.id = id,
             Shape::Text(text) => text.id = id,
@@ -70,6 +103,10 @@ impl Shape {
         match self {
             Shape::Rect(rect) => &rect.transform,
             Shape::Circle(circle) => &circle.transform,
+            Shape::Ellipse(ellipse) => &ellipse.transform,
+            Shape::Line(line) => &line.transform,
+            Shape::Polyline(polyline) => &polyline.transform,
+            Shape::Polygon(polygon) => &polygon.transform,
             Shape::Path(path) => &path.transform,
             Shape::Group(group) => &group.transform,
             Shape::Text(text) => &text.transform,
@@ -80,12 +117,52 @@ impl Shape {
         match self {
             Shape::Rect(rect) => &mut rect.transform,
             Shape::Circle(circle) => &mut circle.transform,
+            Shape::Ellipse(ellipse) => &mut ellipse.transform,
+            Shape::Line(line) => &mut line.transform,
+            Shape::Polyline(polyline) => &mut polyline.transform,
+            Shape::Polygon(polygon) => &mut polygon.transform,
             Shape::Path(path) => &mut path.transform,
             Shape::Group(group) => &mut group.transform,
             Shape::Text(text) => &mut text.transform,
         }
     }
 
+    pub fn effects(&self) -> &[Effect] {
+        match self {
+            Shape::Rect(rect) => &rect.effects,
+            Shape::Circle(circle) => &circle.effects,
+            Shape::Ellipse(ellipse) => &ellipse.effects,
+            Shape::Line(line) => &line.effects,
+            Shape::Polyline(polyline) => &polyline.effects,
+            Shape::Polygon(polygon) => &polygon.effects,
+            Shape::Path(path) => &path.effects,
+            Shape::Group(group) => &group.effects,
+            Shape::Text(text) => &text.effects,
+        }
+    }
+
+    pub fn clip(&self) -> &Clip {
+        match self {
+            Shape::Rect(rect) => &rect.clip,
+            Shape::Circle(circle) => &circle.clip,
+            Shape::Ellipse(ellipse) => &ellipse.clip,
+            Shape::Line(line) => &line.clip,
+            Shape::Polyline(polyline) => &polyline.clip,
+            Shape::Polygon(polygon) => &polygon.clip,
+            Shape::Path(path) => &path.clip,
+            Shape::Group(group) => &group.clip,
+            Shape::Text(text) => &text.clip,
+        }
+    }
+
+    /// Clip of the children of the shape, it is intersected with the clips of the descendants.
+    pub fn children_clip(&self) -> &Clip {
+        match self {
+            Shape::Group(group) => &group.clip,
+            _ => &Clip::None,
+        }
+    }
+
     #[inline]
     pub fn as_ref(&self) -> ShapeRef {
         ShapeRef(self)
@@ -130,6 +207,70 @@ impl Shaped for Shape {
         }
     }
 
+    #[inline]
+    fn ellipse(&self) -> Option<&Ellipse> {
+        match self {
+            Shape::Ellipse(ellipse) => Some(ellipse),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn ellipse_mut(&mut self) -> Option<&mut Ellipse> {
+        match self {
+            Shape::Ellipse(ellipse) => Some(ellipse),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn line(&self) -> Option<&Line> {
+        match self {
+            Shape::Line(line) => Some(line),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn line_mut(&mut self) -> Option<&mut Line> {
+        match self {
+            Shape::Line(line) => Some(line),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn polyline(&self) -> Option<&Polyline> {
+        match self {
+            Shape::Polyline(polyline) => Some(polyline),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn polyline_mut(&mut self) -> Option<&mut Polyline> {
+        match self {
+            Shape::Polyline(polyline) => Some(polyline),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn polygon(&self) -> Option<&Polygon> {
+        match self {
+            Shape::Polygon(polygon) => Some(polygon),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn polygon_mut(&mut self) -> Option<&mut Polygon> {
+        match self {
+            Shape::Polygon(polygon) => Some(polygon),
+            _ => None,
+        }
+    }
+
     #[inline]
     fn path(&self) -> Option<&Path> {
         match self {
@@ -190,6 +331,26 @@ impl<'a> ShapeRef<'a> {
         self.0.circle()
     }
 
+    #[inline]
+    pub fn ellipse(&self) -> Option<&Ellipse> {
+        self.0.ellipse()
+    }
+
+    #[inline]
+    pub fn line(&self) -> Option<&Line> {
+        self.0.line()
+    }
+
+    #[inline]
+    pub fn polyline(&self) -> Option<&Polyline> {
+        self.0.polyline()
+    }
+
+    #[inline]
+    pub fn polygon(&self) -> Option<&Polygon> {
+        self.0.polygon()
+    }
+
     #[inline]
     pub fn path(&self) -> Option<&Path> {
         self.0.path()
@@ -217,6 +378,26 @@ impl<'a> ShapeRefMut<'a> {
         self.0.circle_mut()
     }
 
+    #[inline]
+    pub fn ellipse(&mut self) -> Option<&mut Ellipse> {
+        self.0.ellipse_mut()
+    }
+
+    #[inline]
+    pub fn line(&mut self) -> Option<&mut Line> {
+        self.0.line_mut()
+    }
+
+    #[inline]
+    pub fn polyline(&mut self) -> Option<&mut Polyline> {
+        self.0.polyline_mut()
+    }
+
+    #[inline]
+    pub fn polygon(&mut self) -> Option<&mut Polygon> {
+        self.0.polygon_mut()
+    }
+
     #[inline]
     pub fn path(&mut self) -> Option<&mut Path> {
         self.0.path_mut()
@@ -245,6 +426,30 @@ impl From<Circle> for Shape {
     }
 }
 
+impl From<Ellipse> for Shape {
+    fn from(ellipse: Ellipse) -> Self {
+        Shape::Ellipse(ellipse)
+    }
+}
+
+impl From<Line> for Shape {
+    fn from(line: Line) -> Self {
+        Shape::Line(line)
+    }
+}
+
+impl From<Polyline> for Shape {
+    fn from(polyline: Polyline) -> Self {
+        Shape::Polyline(polyline)
+    }
+}
+
+impl From<Polygon> for Shape {
+    fn from(polygon: Polygon) -> Self {
+        Shape::Polygon(polygon)
+    }
+}
+
 impl From<Path> for Shape {
     fn from(path: Path) -> Self {
         Shape::Path(path)
@@ -288,12 +493,22 @@ pub trait CompositeShape {
 
     fn need_redraw(&self) -> Option<bool>;
 
+    /// Hit test of the shape by the point in the global coordinates, the points clipped away by the shape
+    /// own clip don't hit it. The clips of the ancestor groups are checked by the event dispatching.
     fn intersect(&self, x: Real, y: Real) -> bool {
         if let Some(shape) = self.shape() {
+            if !shape.clip().contains(x, y) {
+                return false;
+            }
             match shape {
                 Shape::Rect(rect) => rect.intersect(x, y),
                 Shape::Circle(circle) => circle.intersect(x, y),
+                Shape::Ellipse(ellipse) => ellipse.intersect(x, y),
+                Shape::Line(line) => line.intersect(x, y),
+                Shape::Polyline(polyline) => polyline.intersect(x, y),
+                Shape::Polygon(polygon) => polygon.intersect(x, y),
                 Shape::Path(path) => path.intersect(x, y),
+                Shape::Text(text) => text.intersect(x, y),
                 _ => false,
             }
         } else {
@@ -1,4 +1,7 @@
-use crate::node::{Clip, Fill, Padding, Real, RealValue, Stroke, Transform, TransformMatrix};
+use crate::node::{
+    Clip, Effect, Fill, Padding, PathBuilder, PathCommand, Real, RealValue, SizeConstraints, Stroke, Transform,
+    TransformMatrix,
+};
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Circle {
@@ -6,10 +9,15 @@ pub struct Circle {
     pub cx: RealValue,
     pub cy: RealValue,
     pub r: RealValue,
+    /// Limits of the circle diameter, the aspect ratio is not used.
+    pub constraints: SizeConstraints,
+    /// Radius limited by the constraints, it is set by the layout.
+    pub limited_r: Option<Real>,
     pub padding: Padding,
     pub transparency: Real,
     pub stroke: Option<Stroke>,
     pub fill: Option<Fill>,
+    pub effects: Vec<Effect>,
     pub clip: Clip,
     pub transform: Transform,
 }
@@ -28,6 +36,27 @@ impl Circle {
         self.transform.calculate_global(parent_global)
     }
 
+    /// Apply the size constraints to the diameter, the auto radius is set by the content radius if it is known.
+    pub fn constrain_radius(&mut self, content: Option<Real>) {
+        self.limited_r = self.constraints.apply_radius(&self.r, content);
+    }
+
+    /// Radius of the circle limited by the constraints, or the declared one if it is not limited yet.
+    pub fn layout_r(&self) -> Real {
+        self.limited_r.unwrap_or_else(|| self.r.val())
+    }
+
+    /// Outline of the circle as the path commands, such as for the dashed stroke.
+    pub fn outline(&self) -> Vec<PathCommand> {
+        self.offset_outline(0.0)
+    }
+
+    /// Outline moved outward by the offset, or inward by the negative one.
+    pub fn offset_outline(&self, offset: Real) -> Vec<PathCommand> {
+        let r = (self.layout_r() + offset).max(0.0);
+        PathBuilder::new().ellipse(self.cx.val(), self.cy.val(), r, r).build()
+    }
+
     #[inline]
     pub fn intersect(&self, x: Real, y: Real) -> bool {
         let matrix = self
@@ -39,6 +68,7 @@ impl Circle {
         } else {
             (x, y)
         };
-        ((x - self.cx.val()).powi(2) + (y - self.cy.val()).powi(2)).sqrt() <= self.r.val()
+        let extent = self.stroke.as_ref().map_or(0.0, Stroke::outer_extent);
+        ((x - self.cx.val()).powi(2) + (y - self.cy.val()).powi(2)).sqrt() <= self.layout_r() + extent
     }
 }
@@ -0,0 +1,489 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use self::named::NAMED_COLORS;
+use crate::Interpolate;
+
+mod named;
+
+/// Color with the sRGB channels and the straight alpha in the range from 0 to 1.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+    Yellow,
+    White,
+    #[default]
+    Black,
+    RGB(f32, f32, f32),
+    RGBA(f32, f32, f32, f32),
+}
+
+impl Color {
+    pub fn from_rgb8(r: u8, g: u8, b: u8) -> Color {
+        Color::RGB(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0)
+    }
+
+    pub fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color::from_rgb8(r, g, b).with_alpha(a as f32 / 255.0)
+    }
+
+    /// Color of the CSS color name, such as `cornflowerblue` or `transparent`, the name is case insensitive.
+    /// The names of the basic colors give their own variants, note that the CSS `green` is darker than
+    /// `Color::Green`, which is the CSS `lime`.
+    pub fn named(name: &str) -> Option<Color> {
+        let name = name.to_ascii_lowercase();
+        match name.as_str() {
+            "red" => return Some(Color::Red),
+            "lime" => return Some(Color::Green),
+            "blue" => return Some(Color::Blue),
+            "yellow" => return Some(Color::Yellow),
+            "white" => return Some(Color::White),
+            "black" => return Some(Color::Black),
+            "transparent" => return Some(Color::RGBA(0.0, 0.0, 0.0, 0.0)),
+            _ => (),
+        }
+        NAMED_COLORS
+            .binary_search_by(|(probe, _)| probe.cmp(&name.as_str()))
+            .ok()
+            .map(|idx| {
+                let [r, g, b] = NAMED_COLORS[idx].1;
+                Color::from_rgb8(r, g, b)
+            })
+    }
+
+    /// Parse the CSS color: the hex `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa` colors, the `rgb()`, `rgba()`,
+    /// `hsl()` and `hsla()` functions with the comma or the space separated arguments, and the named colors.
+    pub fn parse(value: &str) -> Result<Color, ColorParseError> {
+        let value = value.trim();
+        let error = |kind| ColorParseError {
+            kind,
+            value: value.to_string(),
+        };
+        if let Some(hex) = value.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(|| error(ColorParseErrorKind::InvalidHex));
+        }
+        if let Some((name, args)) = value.strip_suffix(')').and_then(|value| value.split_once('(')) {
+            let color = match name.trim().to_ascii_lowercase().as_str() {
+                "rgb" | "rgba" => parse_rgb(args),
+                "hsl" | "hsla" => parse_hsl(args),
+                _ => None,
+            };
+            return color.ok_or_else(|| error(ColorParseErrorKind::InvalidFunction));
+        }
+        Color::named(value).ok_or_else(|| error(ColorParseErrorKind::UnknownName))
+    }
+
+    pub fn with_alpha(self, alpha: f32) -> Color {
+        let [r, g, b, _] = self.as_arr();
+        Color::RGBA(r, g, b, alpha)
+    }
+
+    pub fn as_arr(&self) -> [f32; 4] {
+        match *self {
+            Color::Red => [1.0, 0.0, 0.0, 1.0],
+            Color::Green => [0.0, 1.0, 0.0, 1.0],
+            Color::Blue => [0.0, 0.0, 1.0, 1.0],
+            Color::Yellow => [1.0, 1.0, 0.0, 1.0],
+            Color::White => [1.0, 1.0, 1.0, 1.0],
+            Color::Black => [0.0, 0.0, 0.0, 1.0],
+            Color::RGB(r, g, b) => [r, g, b, 1.0],
+            Color::RGBA(r, g, b, a) => [r, g, b, a],
+        }
+    }
+
+    pub fn alpha(&self) -> f32 {
+        self.as_arr()[3]
+    }
+
+    pub fn to_rgba8(&self) -> [u8; 4] {
+        self.as_arr().map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    /// Color of the hue in degrees, the saturation and the lightness in the range from 0 to 1.
+    pub fn hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let [r, g, b] = hue_rgb(hue, chroma);
+        let min = lightness - chroma / 2.0;
+        Color::RGB(r + min, g + min, b + min)
+    }
+
+    pub fn hsla(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Color {
+        Color::hsl(hue, saturation, lightness).with_alpha(alpha)
+    }
+
+    /// Hue in degrees, the saturation and the lightness of the color.
+    pub fn to_hsl(&self) -> [f32; 3] {
+        let [r, g, b, _] = self.as_arr();
+        let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+        let lightness = (max + min) / 2.0;
+        let saturation = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+        [hue([r, g, b]), saturation, lightness]
+    }
+
+    /// Color of the hue in degrees, the saturation and the value in the range from 0 to 1.
+    pub fn hsv(hue: f32, saturation: f32, value: f32) -> Color {
+        let value = value.clamp(0.0, 1.0);
+        let chroma = value * saturation.clamp(0.0, 1.0);
+        let [r, g, b] = hue_rgb(hue, chroma);
+        let min = value - chroma;
+        Color::RGB(r + min, g + min, b + min)
+    }
+
+    /// Hue in degrees, the saturation and the value of the color.
+    pub fn to_hsv(&self) -> [f32; 3] {
+        let [r, g, b, _] = self.as_arr();
+        let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+        let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+        [hue([r, g, b]), saturation, max]
+    }
+
+    /// Color of the perceptual OKLab lightness and the `a` and `b` opponent axes. The channels
+    /// out of the sRGB gamut are clamped.
+    pub fn oklab(lightness: f32, a: f32, b: f32) -> Color {
+        let l = (lightness + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+        let m = (lightness - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+        let s = (lightness - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+        Color::from_linear([
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+            1.0,
+        ])
+    }
+
+    /// OKLab lightness and the `a` and `b` opponent axes of the color.
+    pub fn to_oklab(&self) -> [f32; 3] {
+        let [r, g, b, _] = self.to_linear();
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+        [
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        ]
+    }
+
+    /// Color of the linear-light channels and the alpha.
+    pub fn from_linear([r, g, b, a]: [f32; 4]) -> Color {
+        let encode = |channel: f32| {
+            let channel = channel.clamp(0.0, 1.0);
+            if channel <= 0.003_130_8 {
+                12.92 * channel
+            } else {
+                1.055 * channel.powf(1.0 / 2.4) - 0.055
+            }
+        };
+        Color::RGBA(encode(r), encode(g), encode(b), a)
+    }
+
+    /// Linear-light channels and the alpha of the color, the light intensity is proportional to them.
+    pub fn to_linear(&self) -> [f32; 4] {
+        let [r, g, b, a] = self.as_arr();
+        let decode = |channel: f32| {
+            if channel <= 0.040_45 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        [decode(r), decode(g), decode(b), a]
+    }
+
+    /// Increase the HSL lightness by the amount in the range from 0 to 1, as the Sass `lighten`.
+    pub fn lighten(self, amount: f32) -> Color {
+        let [hue, saturation, lightness] = self.to_hsl();
+        Color::hsla(hue, saturation, lightness + amount, self.alpha())
+    }
+
+    /// Decrease the HSL lightness by the amount in the range from 0 to 1, as the Sass `darken`.
+    pub fn darken(self, amount: f32) -> Color {
+        self.lighten(-amount)
+    }
+
+    /// Mix the colors in the linear light, the ratio 0 is this color and the ratio 1 is the other one.
+    pub fn mix(self, other: Color, ratio: f32) -> Color {
+        let (from, to) = (self.to_linear(), other.to_linear());
+        let mut mixed = [0.0; 4];
+        for (idx, channel) in mixed.iter_mut().enumerate() {
+            *channel = from[idx] + (to[idx] - from[idx]) * ratio;
+        }
+        Color::from_linear(mixed)
+    }
+
+    /// Relative luminance of the color as defined by WCAG, the alpha is ignored.
+    pub fn relative_luminance(&self) -> f32 {
+        let [r, g, b, _] = self.to_linear();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// WCAG contrast ratio of the colors from 1 to 21, the text needs at least 4.5 to be readable.
+    pub fn contrast_ratio(&self, other: &Color) -> f32 {
+        let (first, second) = (self.relative_luminance(), other.relative_luminance());
+        (first.max(second) + 0.05) / (first.min(second) + 0.05)
+    }
+}
+
+impl From<(Color, f32)> for Color {
+    fn from((color, alpha): (Color, f32)) -> Self {
+        color.with_alpha(alpha)
+    }
+}
+
+/// The sRGB channels premultiplied by the alpha are interpolated as in the CSS transitions, so the fading
+/// color keeps its hue.
+impl Interpolate for Color {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        let ([r1, g1, b1, a1], [r2, g2, b2, a2]) = (self.as_arr(), other.as_arr());
+        let alpha = a1.interpolate(&a2, t);
+        if alpha <= 0.0 {
+            return Color::RGBA(0.0, 0.0, 0.0, 0.0);
+        }
+        let channel = |from: f32, to: f32| (from * a1).interpolate(&(to * a2), t) / alpha;
+        Color::RGBA(channel(r1, r2), channel(g1, g2), channel(b1, b2), alpha)
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Color::parse(value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorParseErrorKind {
+    InvalidHex,
+    /// Unknown color function or its invalid arguments.
+    InvalidFunction,
+    UnknownName,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorParseError {
+    pub kind: ColorParseErrorKind,
+    pub value: String,
+}
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ColorParseErrorKind::InvalidHex => write!(f, "Invalid hex color '{}'", self.value),
+            ColorParseErrorKind::InvalidFunction => write!(f, "Invalid color function '{}'", self.value),
+            ColorParseErrorKind::UnknownName => write!(f, "Unknown color name '{}'", self.value),
+        }
+    }
+}
+
+impl Error for ColorParseError {}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let digits: Vec<u8> = hex
+        .chars()
+        .map(|ch| ch.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<_>>()?;
+    let channels: Vec<u8> = match digits.len() {
+        3 | 4 => digits.iter().map(|digit| digit * 17).collect(),
+        6 | 8 => digits.chunks(2).map(|pair| pair[0] * 16 + pair[1]).collect(),
+        _ => return None,
+    };
+    Some(match channels.as_slice() {
+        [r, g, b] => Color::from_rgb8(*r, *g, *b),
+        [r, g, b, a] => Color::from_rgba8(*r, *g, *b, *a),
+        _ => return None,
+    })
+}
+
+/// Split the function arguments separated by the commas or the spaces, with the alpha after the slash.
+fn function_args(args: &str) -> (Vec<&str>, Option<&str>) {
+    let (channels, alpha) = match args.split_once('/') {
+        Some((channels, alpha)) => (channels, Some(alpha.trim())),
+        None => (args, None),
+    };
+    let mut channels: Vec<&str> = if channels.contains(',') {
+        channels.split(',').map(str::trim).collect()
+    } else {
+        channels.split_whitespace().collect()
+    };
+    let alpha = match alpha {
+        Some(alpha) => Some(alpha),
+        None if channels.len() == 4 => channels.pop(),
+        None => None,
+    };
+    (channels, alpha)
+}
+
+/// Number or percentage, the percentage is scaled to the range from 0 to `scale`.
+fn parse_number(value: &str, scale: f32) -> Option<f32> {
+    match value.strip_suffix('%') {
+        Some(pct) => pct.trim().parse::<f32>().ok().map(|pct| pct / 100.0 * scale),
+        None => value.parse::<f32>().ok(),
+    }
+}
+
+/// Color with the parsed alpha, the color without the alpha stays opaque.
+fn with_parsed_alpha(color: Color, alpha: Option<&str>) -> Option<Color> {
+    match alpha {
+        Some(alpha) => parse_number(alpha, 1.0).map(|alpha| color.with_alpha(alpha.clamp(0.0, 1.0))),
+        None => Some(color),
+    }
+}
+
+fn parse_rgb(args: &str) -> Option<Color> {
+    let (channels, alpha) = function_args(args);
+    let channels: Vec<f32> = channels
+        .iter()
+        .map(|channel| parse_number(channel, 255.0).map(|channel| (channel / 255.0).clamp(0.0, 1.0)))
+        .collect::<Option<_>>()?;
+    match channels.as_slice() {
+        [r, g, b] => with_parsed_alpha(Color::RGB(*r, *g, *b), alpha),
+        _ => None,
+    }
+}
+
+fn parse_hsl(args: &str) -> Option<Color> {
+    let (channels, alpha) = function_args(args);
+    match channels.as_slice() {
+        [hue, saturation, lightness] => with_parsed_alpha(
+            Color::hsl(
+                parse_hue(hue)?,
+                parse_number(saturation.trim_end_matches('%'), 1.0)? / 100.0,
+                parse_number(lightness.trim_end_matches('%'), 1.0)? / 100.0,
+            ),
+            alpha,
+        ),
+        _ => None,
+    }
+}
+
+/// Hue angle in degrees, it can have the `deg`, `grad`, `rad` or `turn` unit.
+fn parse_hue(hue: &str) -> Option<f32> {
+    let units = [("deg", 1.0), ("grad", 0.9), ("rad", 180.0 / std::f32::consts::PI), ("turn", 360.0)];
+    for (unit, scale) in units.iter() {
+        if let Some(value) = hue.strip_suffix(unit) {
+            return value.parse::<f32>().ok().map(|value| value * scale);
+        }
+    }
+    hue.parse().ok()
+}
+
+/// Hue of the sRGB channels in degrees.
+fn hue([r, g, b]: [f32; 3]) -> f32 {
+    let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+    let delta = max - min;
+    if delta == 0.0 {
+        return 0.0;
+    }
+    let hue = if max == r {
+        (g - b) / delta
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    (hue * 60.0).rem_euclid(360.0)
+}
+
+/// Channels of the hue with the chroma and the zero minimum.
+fn hue_rgb(hue: f32, chroma: f32) -> [f32; 3] {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    match sector as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Compare the colors up to the 8-bit channel precision.
+    fn assert_color_eq(color: Color, expected: Color) {
+        let (color, expected) = (color.as_arr(), expected.as_arr());
+        for idx in 0..4 {
+            assert!(
+                (color[idx] - expected[idx]).abs() <= 1.0 / 255.0,
+                "{:?} != {:?}",
+                color,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn parse_colors() {
+        assert_eq!(Color::parse("#ff8000"), Ok(Color::from_rgb8(255, 128, 0)));
+        assert_eq!(Color::parse("#F80"), Ok(Color::from_rgb8(255, 136, 0)));
+        assert_eq!(Color::parse("#ff800080"), Ok(Color::from_rgba8(255, 128, 0, 128)));
+        assert_eq!(Color::parse("#f808"), Ok(Color::from_rgba8(255, 136, 0, 136)));
+        assert_eq!(Color::parse("rgb(255, 128, 0)"), Ok(Color::RGB(1.0, 128.0 / 255.0, 0.0)));
+        assert_eq!(Color::parse("rgba(100%, 0%, 0%, 0.5)"), Ok(Color::RGBA(1.0, 0.0, 0.0, 0.5)));
+        assert_eq!(Color::parse("rgb(255 0 0 / 50%)"), Ok(Color::RGBA(1.0, 0.0, 0.0, 0.5)));
+        assert_color_eq("hsl(120, 100%, 25%)".parse().unwrap(), Color::from_rgb8(0, 128, 0));
+        assert_color_eq("hsla(0.5turn 100% 50% / 0.25)".parse().unwrap(), Color::RGBA(0.0, 1.0, 1.0, 0.25));
+        assert_eq!(Color::parse("CornflowerBlue"), Ok(Color::from_rgb8(100, 149, 237)));
+        assert_eq!(Color::parse("lime"), Ok(Color::Green));
+        assert_eq!(Color::parse("green"), Ok(Color::from_rgb8(0, 128, 0)));
+        assert_eq!(Color::parse("transparent").map(|color| color.alpha()), Ok(0.0));
+
+        assert_eq!(Color::parse("#12345").unwrap_err().kind, ColorParseErrorKind::InvalidHex);
+        assert_eq!(Color::parse("rgb(1, 2)").unwrap_err().kind, ColorParseErrorKind::InvalidFunction);
+        assert_eq!(Color::parse("blurple").unwrap_err().kind, ColorParseErrorKind::UnknownName);
+    }
+
+    #[test]
+    fn color_spaces() {
+        let color = Color::from_rgb8(51, 153, 204);
+        let [hue, saturation, lightness] = color.to_hsl();
+        assert!((hue - 200.0).abs() < 1e-3 && (saturation - 0.6).abs() < 1e-3 && (lightness - 0.5).abs() < 1e-3);
+        assert_color_eq(Color::hsl(hue, saturation, lightness), color);
+
+        let [hue, saturation, value] = color.to_hsv();
+        assert!((hue - 200.0).abs() < 1e-3 && (saturation - 0.75).abs() < 1e-3 && (value - 0.8).abs() < 1e-3);
+        assert_color_eq(Color::hsv(hue, saturation, value), color);
+
+        let [lightness, a, b] = Color::White.to_oklab();
+        assert!((lightness - 1.0).abs() < 1e-3 && a.abs() < 1e-3 && b.abs() < 1e-3);
+        let [lightness, a, b] = color.to_oklab();
+        assert_color_eq(Color::oklab(lightness, a, b), color);
+
+        assert_color_eq(Color::from_linear(color.to_linear()), color);
+        assert_eq!(Color::from_rgb8(128, 0, 255).to_rgba8(), [128, 0, 255, 255]);
+    }
+
+    #[test]
+    fn palette_helpers() {
+        assert_color_eq(Color::from_rgb8(0, 128, 0).lighten(0.25), Color::from_rgb8(0, 255, 0));
+        assert_color_eq(Color::White.darken(0.5), Color::from_rgb8(128, 128, 128));
+
+        // The linear light mix of black and white is brighter than the sRGB average
+        let [gray, ..] = Color::Black.mix(Color::White, 0.5).as_arr();
+        assert!((gray - 0.735).abs() < 1e-3);
+        assert_color_eq(Color::Red.mix(Color::Blue, 0.0), Color::Red);
+
+        assert!((Color::Black.contrast_ratio(&Color::White) - 21.0).abs() < 1e-3);
+        assert!((Color::White.contrast_ratio(&Color::White) - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn interpolate_colors() {
+        assert_color_eq(Color::Black.interpolate(&Color::White, 0.5), Color::from_rgb8(128, 128, 128));
+        // The transparent color doesn't darken the fading one
+        let transparent = Color::RGBA(0.0, 0.0, 0.0, 0.0);
+        assert_color_eq(Color::Red.interpolate(&transparent, 0.5), Color::RGBA(1.0, 0.0, 0.0, 0.5));
+        assert_eq!(transparent.interpolate(&transparent, 0.5).alpha(), 0.0);
+    }
+}
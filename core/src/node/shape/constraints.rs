@@ -0,0 +1,146 @@
+use crate::{Real, RealValue, UnitContext, ValueType};
+
+/// Limits of the shape size, they are applied when the size is resolved by the percentages or by the content.
+/// The minimum wins over the maximum, as in CSS.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct SizeConstraints {
+    pub min_width: Option<RealValue>,
+    pub max_width: Option<RealValue>,
+    pub min_height: Option<RealValue>,
+    pub max_height: Option<RealValue>,
+    /// Ratio of the width to the height. The auto dimension is set by the other one, the shape with both
+    /// auto dimensions is enlarged from its content to the ratio. The circle does not use it.
+    pub aspect_ratio: Option<Real>,
+}
+
+/// Size of the shape limited by the constraints, the declared size is kept as is. The dimension is `None`
+/// while it is unknown, such as the auto one before the content is laid out.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct LimitedSize {
+    pub width: Option<Real>,
+    pub height: Option<Real>,
+}
+
+impl SizeConstraints {
+    /// Resolve the relative limits by the parent size and the unit sizes.
+    pub fn resolve(&mut self, width: Real, height: Real, units: &UnitContext) {
+        for limit in self.min_width.iter_mut().chain(&mut self.max_width) {
+            limit.resolve(width, units);
+        }
+        for limit in self.min_height.iter_mut().chain(&mut self.max_height) {
+            limit.resolve(height, units);
+        }
+    }
+
+    pub fn clamp_width(&self, width: Real) -> Real {
+        clamp(width, self.min_width, self.max_width)
+    }
+
+    pub fn clamp_height(&self, height: Real) -> Real {
+        clamp(height, self.min_height, self.max_height)
+    }
+
+    /// Apply the aspect ratio and the limits to the resolved size. The auto dimensions are set by the content size
+    /// `(width, height)`, they are unknown until the content size is known unless the ratio sets them.
+    pub fn apply(&self, width: &RealValue, height: &RealValue, content: Option<(Real, Real)>) -> LimitedSize {
+        let (auto_width, auto_height) = (width.1 == ValueType::Auto, height.1 == ValueType::Auto);
+        let mut new_width = match auto_width {
+            true => content.map(|(width, _)| width),
+            false => Some(self.clamp_width(width.val())),
+        };
+        let mut new_height = match auto_height {
+            true => content.map(|(_, height)| height),
+            false => Some(self.clamp_height(height.val())),
+        };
+
+        if let Some(ratio) = self.aspect_ratio.filter(|ratio| *ratio > 0.0) {
+            match (new_width, new_height) {
+                (_, Some(height)) if auto_width && !auto_height => new_width = Some(height * ratio),
+                (Some(width), _) if !auto_width && auto_height => new_height = Some(width / ratio),
+                (Some(width), Some(height)) if auto_width && auto_height => {
+                    if width < height * ratio {
+                        new_width = Some(height * ratio);
+                    } else {
+                        new_height = Some(width / ratio);
+                    }
+                },
+                _ => (),
+            }
+        }
+
+        LimitedSize {
+            width: new_width.map(|width| self.clamp_width(width)),
+            height: new_height.map(|height| self.clamp_height(height)),
+        }
+    }
+
+    /// Apply the limits to the diameter of the circle, the auto radius is set by the content radius.
+    /// The diameter is limited by the width limits and then by the height limits.
+    pub fn apply_radius(&self, radius: &RealValue, content: Option<Real>) -> Option<Real> {
+        let new_radius = match radius.1 {
+            ValueType::Auto => content,
+            _ => Some(radius.val()),
+        };
+        new_radius.map(|radius| self.clamp_height(self.clamp_width(2.0 * radius)) / 2.0)
+    }
+}
+
+fn clamp(value: Real, min: Option<RealValue>, max: Option<RealValue>) -> Real {
+    let value = max.map_or(value, |max| value.min(max.val()));
+    min.map_or(value, |min| value.max(min.val()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_size() {
+        let mut constraints = SizeConstraints {
+            min_width: Some(RealValue::px(50.0)),
+            max_width: Some(RealValue::pct(50.0)),
+            max_height: Some(RealValue::vh(10.0)),
+            ..Default::default()
+        };
+        let units = UnitContext::default().with_viewport(800.0, 600.0);
+        constraints.resolve(400.0, 300.0, &units);
+
+        let (mut width, height) = (RealValue::pct(80.0), RealValue::px(100.0));
+        width.resolve(400.0, &units);
+        let limited = constraints.apply(&width, &height, None);
+        assert_eq!((limited.width, limited.height), (Some(200.0), Some(60.0)));
+        // The declared size is kept, so it is limited anew by the changed limit
+        assert_eq!(height, RealValue::px(100.0));
+
+        constraints.max_height = Some(RealValue::px(80.0));
+        assert_eq!(constraints.apply(&width, &height, None).height, Some(80.0));
+
+        let (width, height) = (RealValue::auto(), RealValue::auto());
+        assert_eq!(constraints.apply(&width, &height, None), LimitedSize::default());
+        let limited = constraints.apply(&width, &height, Some((10.0, 80.0)));
+        assert_eq!((limited.width, limited.height), (Some(50.0), Some(80.0)));
+
+        assert_eq!(constraints.apply_radius(&RealValue::auto(), Some(10.0)), Some(25.0));
+    }
+
+    #[test]
+    fn keep_aspect_ratio() {
+        let mut constraints = SizeConstraints {
+            aspect_ratio: Some(2.0),
+            ..Default::default()
+        };
+        let limited = constraints.apply(&RealValue::px(100.0), &RealValue::auto(), None);
+        assert_eq!(limited.height, Some(50.0));
+
+        let (width, height) = (RealValue::auto(), RealValue::auto());
+        let limited = constraints.apply(&width, &height, Some((30.0, 20.0)));
+        assert_eq!((limited.width, limited.height), (Some(40.0), Some(20.0)));
+        let limited = constraints.apply(&width, &height, Some((60.0, 20.0)));
+        assert_eq!((limited.width, limited.height), (Some(60.0), Some(30.0)));
+
+        // The limits win over the ratio
+        constraints.max_height = Some(RealValue::px(20.0));
+        let limited = constraints.apply(&RealValue::px(100.0), &RealValue::auto(), Some((0.0, 0.0)));
+        assert_eq!((limited.width, limited.height), (Some(100.0), Some(20.0)));
+    }
+}
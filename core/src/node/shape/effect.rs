@@ -0,0 +1,187 @@
+use crate::{Color, ColorInterpolation, ColorStop, Gradient, Real};
+
+/// Shadow of the shape, as the CSS `box-shadow`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Shadow {
+    pub offset_x: Real,
+    pub offset_y: Real,
+    /// Blur radius, the standard deviation of the Gaussian blur is a half of it.
+    pub blur: Real,
+    /// Distance the shadow is expanded by before the blur, it is shrunk by the negative one.
+    pub spread: Real,
+    pub color: Color,
+}
+
+impl Shadow {
+    pub fn new(offset_x: Real, offset_y: Real, blur: Real, color: impl Into<Color>) -> Self {
+        Self {
+            offset_x,
+            offset_y,
+            blur: blur.max(0.0),
+            spread: 0.0,
+            color: color.into(),
+        }
+    }
+
+    pub fn spread(mut self, spread: Real) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Turn the shadow into the inner one.
+    pub fn inner(self) -> Effect {
+        Effect::InnerShadow(self)
+    }
+
+    /// Box and corner radius of the shadow cast by the rounded box, that is offset and expanded by the spread.
+    pub fn cast_box(&self, [x, y, width, height]: [Real; 4], radius: Real) -> ([Real; 4], Real) {
+        let spread = self.spread.max(-0.5 * width.min(height));
+        let rect = [
+            x + self.offset_x - spread,
+            y + self.offset_y - spread,
+            width + 2.0 * spread,
+            height + 2.0 * spread,
+        ];
+        (rect, (radius + spread).max(0.0))
+    }
+
+    /// Width of the box gradient transition centered at the box edge, it spans the blur radius to both sides.
+    fn feather(&self) -> Real {
+        2.0 * self.blur.max(0.5)
+    }
+
+    /// Box gradient of the shadow outside the rounded box. It should fill the box returned
+    /// by the `outer_bounds`, the shape drawn over the shadow hides its opaque part.
+    pub fn outer_gradient(&self, bounds: [Real; 4], radius: Real) -> Gradient {
+        let ([x, y, width, height], radius) = self.cast_box(bounds, radius);
+        Gradient::Box {
+            position: (x, y),
+            size: (width, height),
+            radius,
+            feather: self.feather(),
+            stops: vec![ColorStop::new(0.0, self.color), ColorStop::new(1.0, self.color.with_alpha(0.0))],
+            interpolation: ColorInterpolation::Srgb,
+        }
+    }
+
+    /// Box to fill with the gradient of the outer shadow, it covers the blurred edges of the shadow.
+    pub fn outer_bounds(&self, bounds: [Real; 4], radius: Real) -> [Real; 4] {
+        let ([x, y, width, height], _) = self.cast_box(bounds, radius);
+        let blur = 0.5 * self.feather();
+        [x - blur, y - blur, width + 2.0 * blur, height + 2.0 * blur]
+    }
+
+    /// Box gradient of the shadow inside the rounded box, it should fill the box itself over its fill.
+    pub fn inner_gradient(&self, bounds: [Real; 4], radius: Real) -> Gradient {
+        let inset = Self {
+            spread: -self.spread,
+            ..*self
+        };
+        let ([x, y, width, height], radius) = inset.cast_box(bounds, radius);
+        Gradient::Box {
+            position: (x, y),
+            size: (width, height),
+            radius,
+            feather: self.feather(),
+            stops: vec![ColorStop::new(0.0, self.color.with_alpha(0.0)), ColorStop::new(1.0, self.color)],
+            interpolation: ColorInterpolation::Srgb,
+        }
+    }
+}
+
+/// Visual effect of the shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Effect {
+    /// Shadow outside the shape, it is drawn under the shape.
+    Shadow(Shadow),
+    /// Shadow inside the shape, it is drawn over the shape fill.
+    InnerShadow(Shadow),
+    /// Gaussian blur of the shape own content by the blur radius.
+    Blur(Real),
+}
+
+impl Effect {
+    pub fn shadow(offset_x: Real, offset_y: Real, blur: Real, color: impl Into<Color>) -> Self {
+        Effect::Shadow(Shadow::new(offset_x, offset_y, blur, color))
+    }
+
+    pub fn inner_shadow(offset_x: Real, offset_y: Real, blur: Real, color: impl Into<Color>) -> Self {
+        Effect::InnerShadow(Shadow::new(offset_x, offset_y, blur, color))
+    }
+
+    pub fn blur(radius: Real) -> Self {
+        Effect::Blur(radius.max(0.0))
+    }
+
+    /// Standard deviation of the Gaussian blur with the blur radius, as in SVG `stdDeviation`.
+    pub fn std_deviation(radius: Real) -> Real {
+        0.5 * radius
+    }
+
+    /// Distances the effects draw beyond the shape bounds at the left, top, right and bottom,
+    /// such as for the offscreen layers of the shapes.
+    pub fn outset(effects: &[Effect]) -> [Real; 4] {
+        effects.iter().fold([0.0; 4], |[left, top, right, bottom], effect| {
+            let (dx, dy, extent) = match *effect {
+                Effect::Shadow(shadow) => (shadow.offset_x, shadow.offset_y, shadow.blur + shadow.spread),
+                Effect::Blur(radius) => (0.0, 0.0, radius),
+                Effect::InnerShadow(_) => (0.0, 0.0, 0.0),
+            };
+            [
+                left.max(extent - dx),
+                top.max(extent - dy),
+                right.max(extent + dx),
+                bottom.max(extent + dy),
+            ]
+        })
+    }
+}
+
+impl From<Shadow> for Effect {
+    fn from(shadow: Shadow) -> Self {
+        Effect::Shadow(shadow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shadow_boxes() {
+        let shadow = Shadow::new(2.0, 4.0, 6.0, Color::Black).spread(1.0);
+        let bounds = [10.0, 10.0, 20.0, 10.0];
+        assert_eq!(shadow.cast_box(bounds, 3.0), ([11.0, 13.0, 22.0, 12.0], 4.0));
+        assert_eq!(shadow.outer_bounds(bounds, 3.0), [5.0, 7.0, 34.0, 24.0]);
+
+        // The negative spread does not shrink the box below zero size
+        let shrunk = shadow.spread(-10.0);
+        assert_eq!(shrunk.cast_box(bounds, 3.0), ([17.0, 19.0, 10.0, 0.0], 0.0));
+
+        match shadow.inner_gradient(bounds, 3.0) {
+            Gradient::Box {
+                position,
+                size,
+                stops,
+                ..
+            } => {
+                assert_eq!((position, size), ((13.0, 15.0), (18.0, 8.0)));
+                assert_eq!(stops[0].color.as_arr()[3], 0.0);
+                assert_eq!(stops[1].color, Color::Black);
+            }
+            gradient => panic!("Unexpected gradient {:?}", gradient),
+        }
+    }
+
+    #[test]
+    fn effects_outset() {
+        let effects = [
+            Effect::shadow(0.0, 4.0, 8.0, Color::Black),
+            Effect::inner_shadow(10.0, 10.0, 20.0, Color::Black),
+            Effect::blur(2.0),
+        ];
+        assert_eq!(Effect::outset(&effects), [8.0, 4.0, 8.0, 12.0]);
+        assert_eq!(Effect::outset(&[]), [0.0; 4]);
+        assert_eq!(Effect::std_deviation(8.0), 4.0);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::node::{
+    Clip, Effect, Fill, Path, PathBuilder, PathCommand, Real, RealValue, Stroke, Transform, TransformMatrix,
+    UnitContext,
+};
+
+/// Ellipse with the separate horizontal and vertical radii.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Ellipse {
+    pub id: Option<String>,
+    pub cx: RealValue,
+    pub cy: RealValue,
+    /// Horizontal radius, the percentage is relative to the parent width.
+    pub rx: RealValue,
+    /// Vertical radius, the percentage is relative to the parent height.
+    pub ry: RealValue,
+    pub transparency: Real,
+    pub stroke: Option<Stroke>,
+    pub fill: Option<Fill>,
+    pub effects: Vec<Effect>,
+    pub clip: Clip,
+    pub transform: Transform,
+}
+
+impl Ellipse {
+    pub const NAME: &'static str = "ellipse";
+
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn recalculate_transform(&mut self, parent_global: TransformMatrix) -> TransformMatrix {
+        if let Some(transform) = self.clip.transform_mut() {
+            transform.calculate_global(parent_global);
+        }
+        self.transform.calculate_global(parent_global)
+    }
+
+    /// Resolve the relative center and radii by the parent bounding box `[x, y, width, height]` and the unit sizes.
+    pub fn set_by_bounds(&mut self, [x, y, width, height]: [Real; 4], units: &UnitContext) {
+        self.cx.resolve_pos(x, width, units);
+        self.cy.resolve_pos(y, height, units);
+        self.rx.resolve(width, units);
+        self.ry.resolve(height, units);
+    }
+
+    /// Bounding box `[x, y, width, height]` of the ellipse without the stroke.
+    pub fn bounds(&self) -> [Real; 4] {
+        let (rx, ry) = (self.rx.val(), self.ry.val());
+        [self.cx.val() - rx, self.cy.val() - ry, 2.0 * rx, 2.0 * ry]
+    }
+
+    pub fn outline(&self) -> Vec<PathCommand> {
+        PathBuilder::new()
+            .ellipse(self.cx.val(), self.cy.val(), self.rx.val(), self.ry.val())
+            .build()
+    }
+
+    /// Path of the ellipse outline with the ellipse style, the renderers draw the ellipse by it.
+    pub fn to_path(&self) -> Path {
+        Path {
+            id: self.id.clone(),
+            cmd: self.outline(),
+            transparency: self.transparency,
+            stroke: self.stroke.clone(),
+            fill: self.fill.clone(),
+            effects: self.effects.clone(),
+            clip: self.clip.clone(),
+            transform: self.transform,
+            ..Default::default()
+        }
+    }
+
+    /// Hit test by the point in the global coordinates, it hits the ellipse as the path.
+    pub fn intersect(&self, x: Real, y: Real) -> bool {
+        self.to_path().intersect(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, StrokeAlignment};
+
+    #[test]
+    fn ellipse_geometry() {
+        let mut ellipse = Ellipse {
+            cx: RealValue::pct(50.0),
+            cy: RealValue::pct(50.0),
+            rx: RealValue::pct(50.0),
+            ry: 10.into(),
+            fill: Some(Color::Red.into()),
+            ..Default::default()
+        };
+        ellipse.set_by_bounds([0.0, 0.0, 100.0, 40.0], &UnitContext::default());
+        assert_eq!(ellipse.bounds(), [0.0, 10.0, 100.0, 20.0]);
+
+        assert!(ellipse.intersect(95.0, 20.0));
+        assert!(!ellipse.intersect(50.0, 5.0));
+        assert!(!ellipse.intersect(95.0, 28.0));
+
+        let ellipse = Ellipse {
+            stroke: Some(Stroke::default().width(6.0).alignment(StrokeAlignment::Outside)),
+            ..ellipse
+        };
+        assert!(ellipse.intersect(50.0, 5.0));
+    }
+}
@@ -1,9 +1,32 @@
-use super::{Color, Gradient, Paint};
-use crate::node::ConvertTo;
+use super::{Color, Gradient, Paint, Pattern};
+use crate::node::{step, ConvertTo, Interpolate, Real};
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+/// Rule that determines which points are inside of the path with the overlapping or nested sub-paths,
+/// as the SVG `fill-rule`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FillRule {
+    /// The point is inside if the outline winds around it at all, so the hole should go in the opposite
+    /// direction to its outer outline.
+    #[default]
+    NonZero,
+    /// The point is inside if it is enclosed by an odd number of the outlines regardless of their directions.
+    EvenOdd,
+}
+
+impl FillRule {
+    /// Whether the point with the winding number of the outline around it is inside.
+    pub fn contains(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Fill {
     pub paint: Paint,
+    pub fill_rule: FillRule,
 }
 
 impl Fill {
@@ -14,11 +37,32 @@ impl Fill {
     pub fn gradient<T: Into<Gradient>>(gradient: T) -> Self {
         Self::from(gradient.into())
     }
+
+    pub fn pattern<T: Into<Pattern>>(pattern: T) -> Self {
+        Self::from(pattern.into())
+    }
+
+    pub fn fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+}
+
+impl Interpolate for Fill {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        Self {
+            paint: self.paint.interpolate(&other.paint, t),
+            fill_rule: step(&self.fill_rule, &other.fill_rule, t),
+        }
+    }
 }
 
 impl From<Color> for Fill {
     fn from(color: Color) -> Self {
-        Self { paint: color.into() }
+        Self {
+            paint: color.into(),
+            ..Default::default()
+        }
     }
 }
 
@@ -32,6 +76,7 @@ impl From<(Color, f32)> for Fill {
     fn from((color, alpha): (Color, f32)) -> Self {
         Self {
             paint: color.with_alpha(alpha).into(),
+            ..Default::default()
         }
     }
 }
@@ -44,7 +89,10 @@ impl ConvertTo<Option<Fill>> for (Color, f32) {
 
 impl From<Gradient> for Fill {
     fn from(gradient: Gradient) -> Self {
-        Self { paint: gradient.into() }
+        Self {
+            paint: gradient.into(),
+            ..Default::default()
+        }
     }
 }
 
@@ -53,3 +101,18 @@ impl ConvertTo<Option<Fill>> for Gradient {
         Some(self.into())
     }
 }
+
+impl From<Pattern> for Fill {
+    fn from(pattern: Pattern) -> Self {
+        Self {
+            paint: pattern.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl ConvertTo<Option<Fill>> for Pattern {
+    fn convert(self) -> Option<Fill> {
+        Some(self.into())
+    }
+}
@@ -1,4 +1,73 @@
-use crate::node::{Clip, Fill, Real, Stroke, Transform, TransformMatrix};
+use crate::node::{Clip, Effect, Fill, Real, Stroke, Transform, TransformMatrix};
+
+/// Blending of the group layer with the content under it, as the CSS `mix-blend-mode`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+impl BlendMode {
+    pub const ALL: [BlendMode; 16] = [
+        BlendMode::Normal,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+        BlendMode::Overlay,
+        BlendMode::Darken,
+        BlendMode::Lighten,
+        BlendMode::ColorDodge,
+        BlendMode::ColorBurn,
+        BlendMode::HardLight,
+        BlendMode::SoftLight,
+        BlendMode::Difference,
+        BlendMode::Exclusion,
+        BlendMode::Hue,
+        BlendMode::Saturation,
+        BlendMode::Color,
+        BlendMode::Luminosity,
+    ];
+
+    /// The CSS keyword of the mode.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BlendMode::Normal => "normal",
+            BlendMode::Multiply => "multiply",
+            BlendMode::Screen => "screen",
+            BlendMode::Overlay => "overlay",
+            BlendMode::Darken => "darken",
+            BlendMode::Lighten => "lighten",
+            BlendMode::ColorDodge => "color-dodge",
+            BlendMode::ColorBurn => "color-burn",
+            BlendMode::HardLight => "hard-light",
+            BlendMode::SoftLight => "soft-light",
+            BlendMode::Difference => "difference",
+            BlendMode::Exclusion => "exclusion",
+            BlendMode::Hue => "hue",
+            BlendMode::Saturation => "saturation",
+            BlendMode::Color => "color",
+            BlendMode::Luminosity => "luminosity",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|mode| mode.name() == name)
+    }
+}
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Group {
@@ -6,6 +75,13 @@ pub struct Group {
     pub transparency: Option<Real>,
     pub stroke: Option<Stroke>,
     pub fill: Option<Fill>,
+    /// Effects of the children without their own effects, as the group stroke and fill.
+    pub effects: Vec<Effect>,
+    /// Render the subtree to the offscreen layer and composite it at once, so the overlapping
+    /// children fade as one unit. Renderers without the layers apply the transparency to each child.
+    pub layer: bool,
+    /// Blending of the layer, the group with the blend mode other than normal is always a layer.
+    pub blend_mode: BlendMode,
     pub clip: Clip,
     pub transform: Transform,
 }
@@ -25,6 +101,30 @@ impl Group {
     }
 
     pub fn empty_overrides(&self) -> bool {
-        self.stroke.is_none() && self.fill.is_none() && self.transform.is_not_exist()
+        self.stroke.is_none() && self.fill.is_none() && self.effects.is_empty() && self.transform.is_not_exist()
+    }
+
+    pub fn is_layer(&self) -> bool {
+        self.layer || self.blend_mode != BlendMode::Normal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blend_mode_names() {
+        for mode in BlendMode::ALL.iter() {
+            assert_eq!(BlendMode::from_name(mode.name()), Some(*mode));
+        }
+        assert_eq!(BlendMode::from_name("plus-lighter"), None);
+
+        let group = Group {
+            blend_mode: BlendMode::Multiply,
+            ..Default::default()
+        };
+        assert!(group.is_layer());
+        assert!(!Group::default().is_layer());
     }
 }
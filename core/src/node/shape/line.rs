@@ -0,0 +1,96 @@
+use crate::node::{
+    Clip, Effect, Path, PathBuilder, PathCommand, Real, RealValue, Stroke, Transform, TransformMatrix, UnitContext,
+};
+
+/// Straight line between two points. The line has no area, so it is drawn by the stroke only.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Line {
+    pub id: Option<String>,
+    pub x1: RealValue,
+    pub y1: RealValue,
+    pub x2: RealValue,
+    pub y2: RealValue,
+    pub transparency: Real,
+    pub stroke: Option<Stroke>,
+    pub effects: Vec<Effect>,
+    pub clip: Clip,
+    pub transform: Transform,
+}
+
+impl Line {
+    pub const NAME: &'static str = "line";
+
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn recalculate_transform(&mut self, parent_global: TransformMatrix) -> TransformMatrix {
+        if let Some(transform) = self.clip.transform_mut() {
+            transform.calculate_global(parent_global);
+        }
+        self.transform.calculate_global(parent_global)
+    }
+
+    /// Resolve the relative coordinates by the parent bounding box `[x, y, width, height]` and the unit sizes.
+    pub fn set_by_bounds(&mut self, [x, y, width, height]: [Real; 4], units: &UnitContext) {
+        self.x1.resolve_pos(x, width, units);
+        self.y1.resolve_pos(y, height, units);
+        self.x2.resolve_pos(x, width, units);
+        self.y2.resolve_pos(y, height, units);
+    }
+
+    /// Bounding box `[x, y, width, height]` of the line without the stroke.
+    pub fn bounds(&self) -> [Real; 4] {
+        let (x1, y1, x2, y2) = (self.x1.val(), self.y1.val(), self.x2.val(), self.y2.val());
+        [x1.min(x2), y1.min(y2), (x2 - x1).abs(), (y2 - y1).abs()]
+    }
+
+    pub fn outline(&self) -> Vec<PathCommand> {
+        PathBuilder::new()
+            .move_to(self.x1.val(), self.y1.val())
+            .line_to(self.x2.val(), self.y2.val())
+            .build()
+    }
+
+    /// Path of the line outline with the line style, the renderers draw the line by it.
+    pub fn to_path(&self) -> Path {
+        Path {
+            id: self.id.clone(),
+            cmd: self.outline(),
+            transparency: self.transparency,
+            stroke: self.stroke.clone(),
+            effects: self.effects.clone(),
+            clip: self.clip.clone(),
+            transform: self.transform,
+            ..Default::default()
+        }
+    }
+
+    /// Hit test by the point in the global coordinates, the point hits the line stroke.
+    pub fn intersect(&self, x: Real, y: Real) -> bool {
+        self.stroke.is_some() && self.to_path().intersect(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_geometry() {
+        let mut line = Line {
+            x1: RealValue::pct(50.0),
+            y1: 20.0.into(),
+            x2: 10.0.into(),
+            y2: RealValue::pct(100.0),
+            stroke: Some(Stroke::default().width(4.0)),
+            ..Default::default()
+        };
+        line.set_by_bounds([10.0, 0.0, 100.0, 60.0], &UnitContext::default());
+        assert_eq!(line.bounds(), [10.0, 20.0, 50.0, 40.0]);
+
+        assert!(line.intersect(35.0, 41.0));
+        assert!(!line.intersect(35.0, 30.0));
+        assert!(!Line { stroke: None, ..line }.intersect(35.0, 41.0));
+    }
+}
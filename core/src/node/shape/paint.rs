@@ -1,9 +1,30 @@
-use crate::Real;
+use crate::{step, Color, Interpolate, Pattern, Real, RealValue, UnitContext};
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Paint {
     Color(Color),
     Gradient(Gradient),
+    Pattern(Pattern),
+}
+
+impl Paint {
+    /// Resolve the relative coordinates of the gradient or the pattern by the bounding box of the painted shape
+    /// and the unit sizes.
+    pub fn set_by_bounds(&mut self, bounds: [Real; 4], units: &UnitContext) {
+        match self {
+            Paint::Color(_) => (),
+            Paint::Gradient(gradient) => gradient.set_by_bounds(bounds, units),
+            Paint::Pattern(pattern) => pattern.set_by_bounds(bounds, units),
+        }
+    }
+
+    /// Copy of the paint with the relative coordinates resolved by the bounding box of the painted shape
+    /// and the unit sizes.
+    pub fn with_bounds(&self, bounds: [Real; 4], units: &UnitContext) -> Paint {
+        let mut paint = self.clone();
+        paint.set_by_bounds(bounds, units);
+        paint
+    }
 }
 
 impl Default for Paint {
@@ -12,90 +33,562 @@ impl Default for Paint {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum Color {
-    Red,
-    Green,
-    Blue,
-    Yellow,
-    White,
-    Black,
-    RGB(f32, f32, f32),
-    RGBA(f32, f32, f32, f32),
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Paint::Color(color)
+    }
 }
 
-impl Color {
-    pub fn with_alpha(self, alpha: f32) -> Color {
-        let [r, g, b, _] = self.as_arr();
-        Color::RGBA(r, g, b, alpha)
+impl From<(Color, f32)> for Paint {
+    fn from((color, alpha): (Color, f32)) -> Self {
+        Paint::Color(color.with_alpha(alpha))
     }
+}
 
-    pub fn as_arr(&self) -> [f32; 4] {
-        match *self {
-            Color::Red => [1.0, 0.0, 0.0, 1.0],
-            Color::Green => [0.0, 1.0, 0.0, 1.0],
-            Color::Blue => [0.0, 0.0, 1.0, 1.0],
-            Color::Yellow => [1.0, 1.0, 0.0, 1.0],
-            Color::White => [1.0, 1.0, 1.0, 1.0],
-            Color::Black => [0.0, 0.0, 0.0, 1.0],
-            Color::RGB(r, g, b) => [r, g, b, 1.0],
-            Color::RGBA(r, g, b, a) => [r, g, b, a],
-        }
-    }
+/// Color space in which the gradient colors are interpolated.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum ColorInterpolation {
+    /// Interpolate the sRGB channels as is, as the browsers do by default.
+    #[default]
+    Srgb,
+    /// Interpolate the linear light intensities, it avoids the dark band in the middle of the gradient.
+    LinearLight,
 }
 
-impl Default for Color {
-    fn default() -> Self {
-        Color::Black
-    }
+/// How the gradient is painted outside of the range of its stops, as the SVG `spreadMethod`.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum SpreadMode {
+    /// Extend the colors of the first and the last stops.
+    #[default]
+    Pad,
+    Repeat,
+    /// Repeat the gradient mirrored every other time.
+    Reflect,
 }
 
-impl From<(Color, f32)> for Color {
-    fn from((color, alpha): (Color, f32)) -> Self {
-        color.with_alpha(alpha)
+/// Color of the gradient at the offset, the offset 0 is the gradient start and 1 is its end.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ColorStop {
+    pub offset: Real,
+    pub color: Color,
+}
+
+impl ColorStop {
+    pub fn new(offset: Real, color: impl Into<Color>) -> Self {
+        Self {
+            offset,
+            color: color.into(),
+        }
     }
 }
 
-impl From<Color> for Paint {
-    fn from(color: Color) -> Self {
-        Paint::Color(color)
+impl Interpolate for ColorStop {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        ColorStop::new(
+            self.offset.interpolate(&other.offset, t),
+            self.color.interpolate(&other.color, t),
+        )
     }
 }
 
-impl From<(Color, f32)> for Paint {
-    fn from((color, alpha): (Color, f32)) -> Self {
-        Paint::Color(color.with_alpha(alpha))
+impl<C: Into<Color>> From<(Real, C)> for ColorStop {
+    fn from((offset, color): (Real, C)) -> Self {
+        ColorStop::new(offset, color)
     }
 }
 
-/// Gradient paint used to fill or stroke paths with gradient.
-#[derive(Debug, Copy, Clone, PartialEq)]
+/// Number of the steps that approximate the linear light interpolation between two stops in the sRGB renderers.
+const LINEAR_LIGHT_STEPS: usize = 8;
+
+/// Gradient paint used to fill or stroke paths with gradient. The percentage coordinates are relative
+/// to the bounding box of the painted shape, the percentage radius is relative to the bounding box
+/// diagonal divided by the square root of 2, as in SVG.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Gradient {
     Linear {
-        start: (Real, Real),
-        end: (Real, Real),
-        start_color: Color,
-        end_color: Color,
+        start: (RealValue, RealValue),
+        end: (RealValue, RealValue),
+        stops: Vec<ColorStop>,
+        spread: SpreadMode,
+        interpolation: ColorInterpolation,
     },
+    /// Feathered rounded box, it has no spread since the box edge is the only transition.
     Box {
         position: (Real, Real),
         size: (Real, Real),
         radius: Real,
         feather: Real,
-        start_color: Color,
-        end_color: Color,
+        stops: Vec<ColorStop>,
+        interpolation: ColorInterpolation,
     },
     Radial {
-        center: (Real, Real),
-        inner_radius: Real,
-        outer_radius: Real,
-        start_color: Color,
-        end_color: Color,
+        center: (RealValue, RealValue),
+        inner_radius: RealValue,
+        outer_radius: RealValue,
+        stops: Vec<ColorStop>,
+        spread: SpreadMode,
+        interpolation: ColorInterpolation,
+    },
+    /// Sweep around the center, as the CSS `conic-gradient`. The offset 0 is at the start angle in radians
+    /// from the x axis and the offset 1 is a full turn clockwise on the screen.
+    Conic {
+        center: (RealValue, RealValue),
+        start_angle: Real,
+        stops: Vec<ColorStop>,
+        spread: SpreadMode,
+        interpolation: ColorInterpolation,
     },
 }
 
+impl Gradient {
+    pub fn linear(
+        start: (impl Into<RealValue>, impl Into<RealValue>), end: (impl Into<RealValue>, impl Into<RealValue>),
+    ) -> Self {
+        Gradient::Linear {
+            start: (start.0.into(), start.1.into()),
+            end: (end.0.into(), end.1.into()),
+            stops: Vec::new(),
+            spread: SpreadMode::default(),
+            interpolation: ColorInterpolation::default(),
+        }
+    }
+
+    pub fn radial(
+        center: (impl Into<RealValue>, impl Into<RealValue>), inner_radius: impl Into<RealValue>,
+        outer_radius: impl Into<RealValue>,
+    ) -> Self {
+        Gradient::Radial {
+            center: (center.0.into(), center.1.into()),
+            inner_radius: inner_radius.into(),
+            outer_radius: outer_radius.into(),
+            stops: Vec::new(),
+            spread: SpreadMode::default(),
+            interpolation: ColorInterpolation::default(),
+        }
+    }
+
+    pub fn conic(center: (impl Into<RealValue>, impl Into<RealValue>), start_angle: Real) -> Self {
+        Gradient::Conic {
+            center: (center.0.into(), center.1.into()),
+            start_angle,
+            stops: Vec::new(),
+            spread: SpreadMode::default(),
+            interpolation: ColorInterpolation::default(),
+        }
+    }
+
+    pub fn stop(mut self, offset: Real, color: impl Into<Color>) -> Self {
+        self.stops_mut().push(ColorStop::new(offset, color));
+        self
+    }
+
+    pub fn with_stops<S: Into<ColorStop>>(mut self, stops: impl IntoIterator<Item = S>) -> Self {
+        self.stops_mut().extend(stops.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the spread mode, the box gradient has no spread.
+    pub fn spread(mut self, mode: SpreadMode) -> Self {
+        match &mut self {
+            Gradient::Linear { spread, .. } | Gradient::Radial { spread, .. } | Gradient::Conic { spread, .. } => {
+                *spread = mode
+            }
+            Gradient::Box { .. } => (),
+        }
+        self
+    }
+
+    pub fn interpolation(mut self, mode: ColorInterpolation) -> Self {
+        match &mut self {
+            Gradient::Linear { interpolation, .. }
+            | Gradient::Box { interpolation, .. }
+            | Gradient::Radial { interpolation, .. }
+            | Gradient::Conic { interpolation, .. } => *interpolation = mode,
+        }
+        self
+    }
+
+    pub fn stops(&self) -> &[ColorStop] {
+        match self {
+            Gradient::Linear { stops, .. }
+            | Gradient::Box { stops, .. }
+            | Gradient::Radial { stops, .. }
+            | Gradient::Conic { stops, .. } => stops,
+        }
+    }
+
+    pub fn stops_mut(&mut self) -> &mut Vec<ColorStop> {
+        match self {
+            Gradient::Linear { stops, .. }
+            | Gradient::Box { stops, .. }
+            | Gradient::Radial { stops, .. }
+            | Gradient::Conic { stops, .. } => stops,
+        }
+    }
+
+    pub fn spread_mode(&self) -> SpreadMode {
+        match *self {
+            Gradient::Linear { spread, .. } | Gradient::Radial { spread, .. } | Gradient::Conic { spread, .. } => {
+                spread
+            }
+            Gradient::Box { .. } => SpreadMode::Pad,
+        }
+    }
+
+    pub fn interpolation_mode(&self) -> ColorInterpolation {
+        match *self {
+            Gradient::Linear { interpolation, .. }
+            | Gradient::Box { interpolation, .. }
+            | Gradient::Radial { interpolation, .. }
+            | Gradient::Conic { interpolation, .. } => interpolation,
+        }
+    }
+
+    /// Stops with the offsets clamped to the range from 0 to 1 and sorted as in CSS: the offset smaller
+    /// than the previous one is moved to the previous one. The single stop paints the solid color.
+    pub fn normalized_stops(&self) -> Vec<ColorStop> {
+        let mut stops: Vec<ColorStop> = Vec::with_capacity(self.stops().len());
+        for stop in self.stops() {
+            let min = stops.last().map_or(0.0, |last| last.offset);
+            stops.push(ColorStop::new(stop.offset.clamp(min, 1.0), stop.color));
+        }
+        if let [stop] = stops[..] {
+            stops = vec![ColorStop::new(0.0, stop.color), ColorStop::new(1.0, stop.color)];
+        }
+        stops
+    }
+
+    /// Normalized stops to render the gradient with the sRGB interpolation. The linear light gradient
+    /// is approximated by the intermediate stops.
+    pub fn color_stops(&self) -> Vec<ColorStop> {
+        let stops = self.normalized_stops();
+        match self.interpolation_mode() {
+            ColorInterpolation::Srgb => stops,
+            ColorInterpolation::LinearLight => {
+                let mut steps: Vec<ColorStop> = stops
+                    .windows(2)
+                    .flat_map(|pair| {
+                        (0..LINEAR_LIGHT_STEPS).map(move |step| {
+                            let t = step as Real / LINEAR_LIGHT_STEPS as Real;
+                            let offset = pair[0].offset + (pair[1].offset - pair[0].offset) * t;
+                            ColorStop::new(offset, pair[0].color.mix(pair[1].color, t))
+                        })
+                    })
+                    .collect();
+                steps.extend(stops.last().copied());
+                steps
+            }
+        }
+    }
+
+    /// Color of the gradient at the offset with the spread applied, the gradient without stops is transparent.
+    pub fn color_at(&self, offset: Real) -> Color {
+        let offset = match self.spread_mode() {
+            SpreadMode::Pad => offset.clamp(0.0, 1.0),
+            SpreadMode::Repeat => offset.rem_euclid(1.0),
+            SpreadMode::Reflect => 1.0 - (offset.rem_euclid(2.0) - 1.0).abs(),
+        };
+        let stops = self.normalized_stops();
+        let next = stops.iter().position(|stop| stop.offset > offset);
+        match next {
+            None => stops.last().map_or(Color::RGBA(0.0, 0.0, 0.0, 0.0), |stop| stop.color),
+            Some(0) => stops[0].color,
+            Some(idx) => {
+                let (from, to) = (stops[idx - 1], stops[idx]);
+                let t = (offset - from.offset) / (to.offset - from.offset);
+                match self.interpolation_mode() {
+                    ColorInterpolation::Srgb => {
+                        let (from, to) = (from.color.as_arr(), to.color.as_arr());
+                        let channel = |idx: usize| from[idx] + (to[idx] - from[idx]) * t;
+                        Color::RGBA(channel(0), channel(1), channel(2), channel(3))
+                    }
+                    ColorInterpolation::LinearLight => from.color.mix(to.color, t),
+                }
+            }
+        }
+    }
+
+    /// Average color of the gradient stops, the renderers fill the shape with it when they are not able
+    /// to draw the gradient.
+    pub fn average_color(&self) -> Color {
+        const SAMPLES: usize = 16;
+        let mut sum = [0.0; 4];
+        for sample in 0..SAMPLES {
+            let color = self.color_at((sample as Real + 0.5) / SAMPLES as Real).as_arr();
+            for (sum, channel) in sum.iter_mut().zip(color.iter()) {
+                *sum += channel / SAMPLES as Real;
+            }
+        }
+        let [r, g, b, a] = sum;
+        Color::RGBA(r, g, b, a)
+    }
+
+    /// Resolve the relative coordinates by the bounding box `[x, y, width, height]` of the painted shape
+    /// and the unit sizes.
+    pub fn set_by_bounds(&mut self, [x, y, width, height]: [Real; 4], units: &UnitContext) {
+        let set_point = |(point_x, point_y): &mut (RealValue, RealValue)| {
+            point_x.resolve_pos(x, width, units);
+            point_y.resolve_pos(y, height, units);
+        };
+        let diagonal = ((width * width + height * height) / 2.0).sqrt();
+        match self {
+            Gradient::Linear { start, end, .. } => {
+                set_point(start);
+                set_point(end);
+            }
+            Gradient::Radial {
+                center,
+                inner_radius,
+                outer_radius,
+                ..
+            } => {
+                set_point(center);
+                inner_radius.resolve(diagonal, units);
+                outer_radius.resolve(diagonal, units);
+            }
+            Gradient::Conic { center, .. } => set_point(center),
+            Gradient::Box { .. } => (),
+        }
+    }
+}
+
+/// The gradients of the same kind are interpolated, the other ones switch in the middle. The stops are interpolated
+/// pairwise if their numbers are equal, otherwise both gradients are sampled at the offsets of all stops.
+impl Interpolate for Gradient {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        let mut gradient = match (self, other) {
+            (
+                Gradient::Linear { start, end, .. },
+                Gradient::Linear {
+                    start: other_start,
+                    end: other_end,
+                    ..
+                },
+            ) => Gradient::linear(start.interpolate(other_start, t), end.interpolate(other_end, t)),
+            (
+                Gradient::Box {
+                    position,
+                    size,
+                    radius,
+                    feather,
+                    ..
+                },
+                Gradient::Box {
+                    position: other_position,
+                    size: other_size,
+                    radius: other_radius,
+                    feather: other_feather,
+                    ..
+                },
+            ) => Gradient::Box {
+                position: position.interpolate(other_position, t),
+                size: size.interpolate(other_size, t),
+                radius: radius.interpolate(other_radius, t),
+                feather: feather.interpolate(other_feather, t),
+                stops: Vec::new(),
+                interpolation: ColorInterpolation::default(),
+            },
+            (
+                Gradient::Radial {
+                    center,
+                    inner_radius,
+                    outer_radius,
+                    ..
+                },
+                Gradient::Radial {
+                    center: other_center,
+                    inner_radius: other_inner_radius,
+                    outer_radius: other_outer_radius,
+                    ..
+                },
+            ) => Gradient::radial(
+                center.interpolate(other_center, t),
+                inner_radius.interpolate(other_inner_radius, t),
+                outer_radius.interpolate(other_outer_radius, t),
+            ),
+            (
+                Gradient::Conic {
+                    center, start_angle, ..
+                },
+                Gradient::Conic {
+                    center: other_center,
+                    start_angle: other_start_angle,
+                    ..
+                },
+            ) => Gradient::conic(
+                center.interpolate(other_center, t),
+                start_angle.interpolate(other_start_angle, t),
+            ),
+            _ => return step(self, other, t),
+        };
+
+        let (stops, other_stops) = (self.normalized_stops(), other.normalized_stops());
+        *gradient.stops_mut() = if stops.len() == other_stops.len() {
+            stops
+                .iter()
+                .zip(&other_stops)
+                .map(|(from, to)| from.interpolate(to, t))
+                .collect()
+        } else {
+            let mut offsets: Vec<_> = stops.iter().chain(&other_stops).map(|stop| stop.offset).collect();
+            offsets.sort_by(Real::total_cmp);
+            offsets.dedup();
+            offsets
+                .into_iter()
+                .map(|offset| ColorStop::new(offset, self.color_at(offset).interpolate(&other.color_at(offset), t)))
+                .collect()
+        };
+        gradient
+            .spread(step(&self.spread_mode(), &other.spread_mode(), t))
+            .interpolation(step(&self.interpolation_mode(), &other.interpolation_mode(), t))
+    }
+}
+
+impl Gradient {
+    /// Gradient of the same geometry and stop offsets with the solid color, to tween between the color and
+    /// the gradient.
+    fn solid(&self, color: Color) -> Gradient {
+        let mut solid = self.clone();
+        let offsets: Vec<_> = self.normalized_stops().iter().map(|stop| stop.offset).collect();
+        *solid.stops_mut() = if offsets.is_empty() {
+            vec![ColorStop::new(0.0, color), ColorStop::new(1.0, color)]
+        } else {
+            offsets.into_iter().map(|offset| ColorStop::new(offset, color)).collect()
+        };
+        solid
+    }
+}
+
+/// The color and the gradient are interpolated as the gradient, the patterns with the same image are interpolated.
+/// The other paints switch in the middle.
+impl Interpolate for Paint {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        match (self, other) {
+            (Paint::Color(from), Paint::Color(to)) => Paint::Color(from.interpolate(to, t)),
+            (Paint::Gradient(from), Paint::Gradient(to)) => Paint::Gradient(from.interpolate(to, t)),
+            (Paint::Color(from), Paint::Gradient(to)) => Paint::Gradient(to.solid(*from).interpolate(to, t)),
+            (Paint::Gradient(from), Paint::Color(to)) => Paint::Gradient(from.interpolate(&from.solid(*to), t)),
+            (Paint::Pattern(from), Paint::Pattern(to)) => Paint::Pattern(from.interpolate(to, t)),
+            _ => step(self, other, t),
+        }
+    }
+}
+
 impl From<Gradient> for Paint {
     fn from(gradient: Gradient) -> Self {
         Paint::Gradient(gradient)
     }
 }
+
+impl From<Pattern> for Paint {
+    fn from(pattern: Pattern) -> Self {
+        Paint::Pattern(pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_stops() {
+        let gradient = Gradient::linear((0, 0), (10, 0))
+            .stop(0.0, Color::Black)
+            .stop(0.5, Color::White)
+            .stop(0.25, Color::Red);
+        let offsets: Vec<_> = gradient.normalized_stops().iter().map(|stop| stop.offset).collect();
+        assert_eq!(offsets, vec![0.0, 0.5, 0.5]);
+        assert_eq!(gradient.color_stops(), gradient.normalized_stops());
+        assert_eq!(gradient.color_at(0.25), Color::RGBA(0.5, 0.5, 0.5, 1.0));
+        assert_eq!(gradient.color_at(0.75), Color::Red);
+
+        let single = Gradient::radial((0, 0), 0, 10).stop(0.3, Color::Blue);
+        assert_eq!(single.normalized_stops(), vec![
+            ColorStop::new(0.0, Color::Blue),
+            ColorStop::new(1.0, Color::Blue)
+        ]);
+        assert_eq!(Gradient::conic((0, 0), 0.0).color_at(0.5).alpha(), 0.0);
+
+        let conic = Gradient::conic((0, 0), 0.0).stop(0.0, Color::Black).stop(1.0, Color::White);
+        let [gray, .., alpha] = conic.average_color().as_arr();
+        assert!((gray - 0.5).abs() < 1e-4 && alpha == 1.0);
+    }
+
+    #[test]
+    fn spread_modes() {
+        let gradient = Gradient::linear((0, 0), (10, 0)).with_stops(vec![(0.0, Color::Black), (1.0, Color::White)]);
+        assert_eq!(gradient.color_at(1.25), Color::White);
+        let repeat = gradient.clone().spread(SpreadMode::Repeat);
+        assert_eq!(repeat.color_at(1.25), Color::RGBA(0.25, 0.25, 0.25, 1.0));
+        let reflect = gradient.spread(SpreadMode::Reflect);
+        assert_eq!(reflect.color_at(1.25), Color::RGBA(0.75, 0.75, 0.75, 1.0));
+        assert_eq!(reflect.color_at(-0.25), Color::RGBA(0.25, 0.25, 0.25, 1.0));
+    }
+
+    #[test]
+    fn linear_light_stops() {
+        let gradient = Gradient::linear((0, 0), (10, 0))
+            .with_stops(vec![(0.0, Color::Black), (1.0, Color::White)])
+            .interpolation(ColorInterpolation::LinearLight);
+        let stops = gradient.color_stops();
+        assert_eq!(stops.len(), LINEAR_LIGHT_STEPS + 1);
+        assert_eq!(stops[0].color.as_arr(), Color::Black.as_arr());
+        let middle = stops[LINEAR_LIGHT_STEPS / 2];
+        assert_eq!(middle.offset, 0.5);
+        assert!(middle.color.as_arr()[0] > 0.7);
+        assert_eq!(gradient.color_at(0.5), middle.color);
+    }
+
+    #[test]
+    fn bounding_box_coordinates() {
+        let mut paint = Paint::from(
+            Gradient::linear((RealValue::pct(0.0), RealValue::pct(50.0)), (RealValue::pct(100.0), 5))
+                .stop(0.0, Color::Black),
+        );
+        paint.set_by_bounds([10.0, 20.0, 40.0, 30.0], &UnitContext::default());
+        match paint {
+            Paint::Gradient(Gradient::Linear { start, end, .. }) => {
+                assert_eq!((start.0.val(), start.1.val()), (10.0, 35.0));
+                assert_eq!((end.0.val(), end.1.val()), (50.0, 5.0));
+            }
+            paint => panic!("Unexpected paint {:?}", paint),
+        }
+
+        let radial = Gradient::radial((RealValue::pct(50.0), RealValue::pct(50.0)), 0, RealValue::pct(50.0));
+        match Paint::from(radial).with_bounds([0.0, 0.0, 30.0, 40.0], &UnitContext::default()) {
+            Paint::Gradient(Gradient::Radial { outer_radius, .. }) => {
+                assert!((outer_radius.val() - 0.5 * (1250.0 as Real).sqrt()).abs() < 1e-4)
+            }
+            paint => panic!("Unexpected paint {:?}", paint),
+        }
+    }
+
+    #[test]
+    fn interpolate_paints() {
+        let from = Gradient::linear((0, 0), (10, 0)).stop(0.0, Color::Black).stop(1.0, Color::White);
+        let to = Gradient::linear((0, 0), (20, 10))
+            .stop(0.0, Color::White)
+            .stop(0.5, Color::Red)
+            .stop(1.0, Color::Black);
+        match from.interpolate(&to, 0.5) {
+            gradient @ Gradient::Linear { end, .. } => {
+                assert_eq!((end.0.val(), end.1.val()), (15.0, 5.0));
+                let offsets: Vec<_> = gradient.stops().iter().map(|stop| stop.offset).collect();
+                assert_eq!(offsets, vec![0.0, 0.5, 1.0]);
+                assert_eq!(gradient.color_at(0.5), Color::RGBA(0.75, 0.25, 0.25, 1.0));
+            }
+            gradient => panic!("Unexpected gradient {:?}", gradient),
+        }
+
+        // The color turns into the gradient
+        let color = Paint::Color(Color::Red);
+        match color.interpolate(&Paint::Gradient(from.clone()), 0.5) {
+            Paint::Gradient(gradient) => {
+                assert_eq!(gradient.color_at(0.0), Color::RGBA(0.5, 0.0, 0.0, 1.0));
+                assert_eq!(gradient.color_at(1.0), Color::RGBA(1.0, 0.5, 0.5, 1.0));
+            }
+            paint => panic!("Unexpected paint {:?}", paint),
+        }
+        assert_eq!(from.interpolate(&Gradient::conic((0, 0), 0.0), 0.6), Gradient::conic((0, 0), 0.0));
+    }
+}
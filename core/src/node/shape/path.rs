@@ -1,4 +1,16 @@
-use crate::node::{Clip, Fill, Real, Stroke, Transform, TransformMatrix};
+pub use self::{boolean::*, builder::*, morph::*, parser::*, segment::*};
+use crate::node::{Clip, Effect, Fill, FillRule, LineJoin, Real, Stroke, StrokeAlignment, Transform, TransformMatrix};
+use flatten::HIT_TOLERANCE;
+
+pub mod boolean;
+pub mod builder;
+pub mod dash;
+pub mod flatten;
+pub mod geometry;
+pub mod morph;
+pub mod outline;
+pub mod parser;
+pub mod segment;
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Path {
@@ -7,6 +19,9 @@ pub struct Path {
     pub transparency: Real,
     pub stroke: Option<Stroke>,
     pub fill: Option<Fill>,
+    /// Fill rule of the path, it overrides the rule of the fill.
+    pub fill_rule: Option<FillRule>,
+    pub effects: Vec<Effect>,
     pub clip: Clip,
     pub transform: Transform,
 }
@@ -25,11 +40,116 @@ impl Path {
         self.transform.calculate_global(parent_global)
     }
 
-    pub fn intersect(&self, _x: Real, _y: Real) -> bool {
-        false // TODO: need impl
+    /// Resolve the path commands into absolute drawing segments.
+    pub fn segments(&self) -> Result<Vec<PathSegment>, PathError> {
+        PathSegment::resolve(&self.cmd)
+    }
+
+    /// Exact bounds `[x, y, width, height]` of the path outline, `None` if the commands are invalid or empty.
+    pub fn bounds(&self) -> Option<[Real; 4]> {
+        PathSegment::tight_bounds(&self.segments().ok()?)
+    }
+
+    /// Total length of the path outline.
+    pub fn length(&self) -> Result<Real, PathError> {
+        Ok(PathSegment::length(&self.segments()?))
+    }
+
+    /// Path drawing the part of the outline between the distances along it, see `PathSegment::trim`.
+    pub fn trimmed(&self, start: Real, end: Real) -> Result<Path, PathError> {
+        let segments = PathSegment::trim(&self.segments()?, start, end);
+        Ok(Path {
+            cmd: PathSegment::to_commands(&segments),
+            ..self.clone()
+        })
+    }
+
+    /// Commands of the outline of the boolean operation between the areas of the paths filled by their fill rules,
+    /// see `PathSegment::boolean`. The paths are combined in their own coordinates without the transforms.
+    pub fn boolean(&self, op: BooleanOp, other: &Path) -> Result<Vec<PathCommand>, PathError> {
+        let outline = PathSegment::boolean(
+            op,
+            &self.segments()?,
+            self.resolve_fill_rule(self.fill.as_ref()),
+            &other.segments()?,
+            other.resolve_fill_rule(other.fill.as_ref()),
+        );
+        Ok(PathSegment::to_commands(&outline))
+    }
+
+    pub fn union(&self, other: &Path) -> Result<Vec<PathCommand>, PathError> {
+        self.boolean(BooleanOp::Union, other)
+    }
+
+    pub fn intersection(&self, other: &Path) -> Result<Vec<PathCommand>, PathError> {
+        self.boolean(BooleanOp::Intersection, other)
+    }
+
+    pub fn difference(&self, other: &Path) -> Result<Vec<PathCommand>, PathError> {
+        self.boolean(BooleanOp::Difference, other)
+    }
+
+    pub fn xor(&self, other: &Path) -> Result<Vec<PathCommand>, PathError> {
+        self.boolean(BooleanOp::Xor, other)
+    }
+
+    /// Commands of the outline of the path area grown or shrunk by the distance, see `PathSegment::offset`.
+    pub fn offset(&self, distance: Real, line_join: LineJoin) -> Result<Vec<PathCommand>, PathError> {
+        let fill_rule = self.resolve_fill_rule(self.fill.as_ref());
+        let outline = PathSegment::offset(&self.segments()?, distance, line_join, fill_rule);
+        Ok(PathSegment::to_commands(&outline))
+    }
+
+    /// Commands of the outline that fills the area of the stroke, see `PathSegment::stroke_outline`.
+    pub fn stroke_outline(&self, stroke: &Stroke) -> Result<Vec<PathCommand>, PathError> {
+        let fill_rule = self.resolve_fill_rule(self.fill.as_ref());
+        let outline = PathSegment::stroke_outline(&self.segments()?, stroke, fill_rule);
+        Ok(PathSegment::to_commands(&outline))
+    }
+
+    /// Fill rule of the path filled by the fill, which is the own fill or the inherited one.
+    pub fn resolve_fill_rule(&self, fill: Option<&Fill>) -> FillRule {
+        self.fill_rule
+            .or_else(|| fill.map(|fill| fill.fill_rule))
+            .unwrap_or_default()
+    }
+
+    /// Hit test by the point in the global coordinates. The point hits the area inside the path by its fill rule
+    /// or the stroke, the paths with the stroke and without the fill are hit by the stroke only. The aligned
+    /// strokes are hit by their outlines, as the renderers draw them.
+    pub fn intersect(&self, x: Real, y: Real) -> bool {
+        let matrix = self
+            .transform
+            .global_matrix()
+            .unwrap_or_else(|| self.transform.matrix());
+        let (x, y) = if !matrix.is_identity() {
+            matrix.inverse() * (x, y)
+        } else {
+            (x, y)
+        };
+        let segments = match self.segments() {
+            Ok(segments) => segments,
+            Err(_) => return false,
+        };
+        let lines = PathSegment::flatten(&segments, HIT_TOLERANCE);
+
+        let fill_rule = self.resolve_fill_rule(self.fill.as_ref());
+        let inside = fill_rule.contains(PathSegment::winding_number(&lines, [x, y]));
+        if inside && (self.fill.is_some() || self.stroke.is_none()) {
+            return true;
+        }
+        self.stroke.as_ref().is_some_and(|stroke| match stroke.alignment {
+            StrokeAlignment::Center => PathSegment::distance_to(&lines, [x, y]) <= stroke.width / 2.0,
+            _ => {
+                let outline = PathSegment::stroke_outline(&segments, stroke, fill_rule);
+                PathSegment::winding_number(&outline, [x, y]) != 0
+            },
+        })
     }
 }
 
+/// Path commands follow the SVG path data semantics. Bezier curves take their control points
+/// from the preceding `BezCtrl`, `BezCtrlRel` or `BezReflectCtrl` commands.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PathCommand {
     Move([Real; 2]),
@@ -43,9 +163,112 @@ pub enum PathCommand {
     Close,
     BezCtrl([Real; 2]),
     BezCtrlRel([Real; 2]),
+    /// Control point that is the reflection of the last control point of the previous curve
+    /// relative to the current point. If the previous command was not a curve, the current point
+    /// is used as the control point.
     BezReflectCtrl,
     QuadBezTo([Real; 2]),
     QuadBezToRel([Real; 2]),
     CubBezTo([Real; 2]),
     CubBezToRel([Real; 2]),
+    /// Quadratic curve with the reflected control point, the same as SVG `T` command.
+    SmoothQuadBezTo([Real; 2]),
+    SmoothQuadBezToRel([Real; 2]),
+    /// Cubic curve with the reflected first control point, the same as SVG `S` command.
+    SmoothCubBezTo {
+        ctrl: [Real; 2],
+        to: [Real; 2],
+    },
+    SmoothCubBezToRel {
+        ctrl: [Real; 2],
+        to: [Real; 2],
+    },
+    /// Elliptical arc, the same as SVG `A` command, but `x_axis_rotation` is set in radians.
+    Arc {
+        radius: [Real; 2],
+        x_axis_rotation: Real,
+        large_arc: bool,
+        sweep: bool,
+        to: [Real; 2],
+    },
+    ArcRel {
+        radius: [Real; 2],
+        x_axis_rotation: Real,
+        large_arc: bool,
+        sweep: bool,
+        to: [Real; 2],
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    fn donut(reversed_hole: bool) -> Path {
+        let outline = PathBuilder::new().rect(0.0, 0.0, 30.0, 30.0);
+        let outline = if reversed_hole {
+            outline
+                .move_to(10.0, 10.0)
+                .line_to(10.0, 20.0)
+                .line_to(20.0, 20.0)
+                .line_to(20.0, 10.0)
+                .close()
+        } else {
+            outline.rect(10.0, 10.0, 10.0, 10.0)
+        };
+        Path {
+            cmd: outline.build(),
+            fill: Some(Fill::color(Color::Black)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hit_by_fill_rule() {
+        let path = donut(false);
+        assert!(path.intersect(5.0, 5.0));
+        assert!(path.intersect(15.0, 15.0));
+        assert!(!donut(true).intersect(15.0, 15.0));
+
+        let even_odd = Path {
+            fill_rule: Some(FillRule::EvenOdd),
+            ..path
+        };
+        assert!(even_odd.intersect(5.0, 5.0));
+        assert!(!even_odd.intersect(15.0, 15.0));
+        assert_eq!(even_odd.resolve_fill_rule(Some(&Fill::default())), FillRule::EvenOdd);
+
+        let by_fill = Path {
+            fill: Some(Fill::color(Color::Black).fill_rule(FillRule::EvenOdd)),
+            ..donut(false)
+        };
+        assert!(!by_fill.intersect(15.0, 15.0));
+    }
+
+    #[test]
+    fn hit_by_stroke_alignment() {
+        let square = |alignment| Path {
+            cmd: PathBuilder::new().rect(0.0, 0.0, 10.0, 10.0).build(),
+            stroke: Some(Stroke::color(Color::Black).width(2.0).alignment(alignment)),
+            ..Default::default()
+        };
+        let center = square(StrokeAlignment::Center);
+        assert!(center.intersect(10.9, 5.0) && center.intersect(9.1, 5.0));
+        assert!(!center.intersect(11.5, 5.0) && !center.intersect(5.0, 5.0));
+        let inside = square(StrokeAlignment::Inside);
+        assert!(inside.intersect(8.5, 5.0) && !inside.intersect(10.5, 5.0));
+        let outside = square(StrokeAlignment::Outside);
+        assert!(outside.intersect(11.5, 5.0) && !outside.intersect(9.5, 5.0));
+        // The miter corner of the outline is drawn and hit
+        assert!(outside.intersect(11.5, 11.5));
+
+        let line = Path {
+            cmd: PathBuilder::new().move_to(0.0, 0.0).line_to(10.0, 0.0).build(),
+            stroke: Some(Stroke::color(Color::Black).width(2.0).alignment(StrokeAlignment::Inside)),
+            ..Default::default()
+        };
+        assert!(line.intersect(5.0, 0.9) && line.intersect(5.0, -0.9));
+        assert!(!line.intersect(5.0, 1.5));
+    }
 }
@@ -0,0 +1,372 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    dash::subpaths,
+    flatten::{cross, distance, sub},
+};
+use crate::{FillRule, PathSegment, Real};
+
+/// Tolerance of the curves flattening before the boolean operations.
+pub(crate) const BOOLEAN_TOLERANCE: Real = 0.1;
+
+/// Distance within which the points of the outlines are merged.
+const SNAP_DISTANCE: Real = 1e-3;
+
+/// Distance from the outline edge to the points probing the areas on its sides.
+const PROBE_DISTANCE: Real = 1e-2;
+
+/// Operation that combines the areas of two paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanOp {
+    Union,
+    Intersection,
+    /// The area of the first path without the area of the second one.
+    Difference,
+    /// The area covered by exactly one of the paths.
+    Xor,
+}
+
+impl BooleanOp {
+    /// Whether the point inside or outside of each path is inside of the result.
+    pub fn contains(self, first: bool, second: bool) -> bool {
+        match self {
+            BooleanOp::Union => first || second,
+            BooleanOp::Intersection => first && second,
+            BooleanOp::Difference => first && !second,
+            BooleanOp::Xor => first != second,
+        }
+    }
+}
+
+impl PathSegment {
+    /// Outline of the area combined from the areas of the paths filled by their fill rules, the open sub-paths
+    /// are closed implicitly. The curves are flattened, so the result consists of the closed polygons only.
+    /// The polygons around the area go in the one direction and the polygons around the holes go in the other
+    /// one, so the result is filled the same way by both fill rules.
+    pub fn boolean(
+        op: BooleanOp, first: &[PathSegment], first_rule: FillRule, second: &[PathSegment], second_rule: FillRule,
+    ) -> Vec<PathSegment> {
+        let first = PathSegment::flatten(first, BOOLEAN_TOLERANCE);
+        let second = PathSegment::flatten(second, BOOLEAN_TOLERANCE);
+
+        let mut vertices = Vertices::default();
+        let mut edges = Vec::new();
+        for (points, _) in subpaths(&first).into_iter().chain(subpaths(&second)) {
+            let ids: Vec<_> = points.into_iter().map(|point| vertices.intern(point)).collect();
+            for (idx, &from) in ids.iter().enumerate() {
+                let to = ids[(idx + 1) % ids.len()];
+                if from != to {
+                    edges.push([from, to]);
+                }
+            }
+        }
+
+        let inside = |point| {
+            op.contains(
+                first_rule.contains(PathSegment::winding_number(&first, point)),
+                second_rule.contains(PathSegment::winding_number(&second, point)),
+            )
+        };
+        let mut kept = Vec::new();
+        let mut seen = HashSet::new();
+        for [from, to] in split_edges(&mut vertices, &edges) {
+            if !seen.insert([from.min(to), from.max(to)]) {
+                continue;
+            }
+            let ([from_x, from_y], [to_x, to_y]) = (vertices.points[from], vertices.points[to]);
+            let length = distance([from_x, from_y], [to_x, to_y]);
+            let [normal_x, normal_y] = [(from_y - to_y) / length, (to_x - from_x) / length];
+            let [mid_x, mid_y] = [(from_x + to_x) / 2.0, (from_y + to_y) / 2.0];
+            let left = inside([mid_x + normal_x * PROBE_DISTANCE, mid_y + normal_y * PROBE_DISTANCE]);
+            let right = inside([mid_x - normal_x * PROBE_DISTANCE, mid_y - normal_y * PROBE_DISTANCE]);
+            match (left, right) {
+                (true, false) => kept.push([from, to]),
+                (false, true) => kept.push([to, from]),
+                _ => (),
+            }
+        }
+
+        let mut outline = Vec::new();
+        for contour in link_edges(vertices.points.len(), &kept) {
+            let points = remove_collinear(contour.into_iter().map(|id| vertices.points[id]).collect());
+            if points.len() >= 3 {
+                outline.push(PathSegment::MoveTo(points[0]));
+                outline.extend(points[1..].iter().map(|&point| PathSegment::LineTo(point)));
+                outline.push(PathSegment::Close);
+            }
+        }
+        outline
+    }
+}
+
+/// Points of the outlines, the points closer than the snap distance are the same vertex.
+#[derive(Default)]
+struct Vertices {
+    points: Vec<[Real; 2]>,
+    grid: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl Vertices {
+    fn intern(&mut self, point: [Real; 2]) -> usize {
+        let (cell_x, cell_y) = (
+            (point[0] / SNAP_DISTANCE).floor() as i64,
+            (point[1] / SNAP_DISTANCE).floor() as i64,
+        );
+        for x in cell_x - 1..=cell_x + 1 {
+            for y in cell_y - 1..=cell_y + 1 {
+                let nearby = self.grid.get(&(x, y)).into_iter().flatten();
+                if let Some(&id) = nearby
+                    .into_iter()
+                    .find(|&&id| distance(self.points[id], point) <= SNAP_DISTANCE)
+                {
+                    return id;
+                }
+            }
+        }
+        self.points.push(point);
+        let id = self.points.len() - 1;
+        self.grid.entry((cell_x, cell_y)).or_default().push(id);
+        id
+    }
+}
+
+/// Split the edges at the points where they cross or touch each other.
+fn split_edges(vertices: &mut Vertices, edges: &[[usize; 2]]) -> Vec<[usize; 2]> {
+    let extent = |[from, to]: [usize; 2], axis: usize| {
+        let (from, to) = (vertices.points[from][axis], vertices.points[to][axis]);
+        (from.min(to) - SNAP_DISTANCE, from.max(to) + SNAP_DISTANCE)
+    };
+    let mut order: Vec<_> = (0..edges.len()).collect();
+    order.sort_by(|&a, &b| extent(edges[a], 0).0.total_cmp(&extent(edges[b], 0).0));
+    let extents: Vec<_> = edges.iter().map(|&edge| [extent(edge, 0), extent(edge, 1)]).collect();
+
+    let mut splits = vec![Vec::new(); edges.len()];
+    for (pos, &first) in order.iter().enumerate() {
+        let [(_, first_max_x), (first_min_y, first_max_y)] = extents[first];
+        for &second in &order[pos + 1..] {
+            let [(second_min_x, _), (second_min_y, second_max_y)] = extents[second];
+            if second_min_x > first_max_x {
+                break;
+            }
+            if second_min_y > first_max_y || first_min_y > second_max_y {
+                continue;
+            }
+            for vertex in crossings(vertices, edges[first], edges[second]) {
+                for edge in [first, second] {
+                    if !edges[edge].contains(&vertex) {
+                        splits[edge].push(vertex);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut split = Vec::with_capacity(edges.len());
+    for (&[from, to], mut points) in edges.iter().zip(splits) {
+        let (start, direction) = (vertices.points[from], sub(vertices.points[to], vertices.points[from]));
+        let param = |id: usize| {
+            let [x, y] = sub(vertices.points[id], start);
+            x * direction[0] + y * direction[1]
+        };
+        points.sort_by(|&a, &b| param(a).total_cmp(&param(b)));
+        points.dedup();
+        let mut current = from;
+        for id in points.into_iter().chain(Some(to)) {
+            if id != current {
+                split.push([current, id]);
+                current = id;
+            }
+        }
+    }
+    split
+}
+
+/// Vertices where the edges cross or the end of one edge lies on the other edge.
+fn crossings(vertices: &mut Vertices, first: [usize; 2], second: [usize; 2]) -> Vec<usize> {
+    let [a, b] = first.map(|id| vertices.points[id]);
+    let [c, d] = second.map(|id| vertices.points[id]);
+    let mut crossings: Vec<_> = second
+        .iter()
+        .filter(|&&id| segment_distance(a, b, vertices.points[id]) <= SNAP_DISTANCE)
+        .chain(
+            first
+                .iter()
+                .filter(|&&id| segment_distance(c, d, vertices.points[id]) <= SNAP_DISTANCE),
+        )
+        .copied()
+        .collect();
+
+    let (r, s) = (sub(b, a), sub(d, c));
+    let denominator = cross(r, s);
+    if denominator.abs() > Real::EPSILON * distance(r, [0.0, 0.0]) * distance(s, [0.0, 0.0]) {
+        let offset = sub(c, a);
+        let (t, u) = (cross(offset, s) / denominator, cross(offset, r) / denominator);
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            let point = [a[0] + r[0] * t, a[1] + r[1] * t];
+            if [a, b, c, d].iter().all(|&end| distance(end, point) > SNAP_DISTANCE) {
+                crossings.push(vertices.intern(point));
+            }
+        }
+    }
+    crossings
+}
+
+/// Link the directed edges into the closed contours of the vertices.
+fn link_edges(vertex_count: usize, edges: &[[usize; 2]]) -> Vec<Vec<usize>> {
+    let mut outgoing = vec![Vec::new(); vertex_count];
+    for (idx, &[from, _]) in edges.iter().enumerate() {
+        outgoing[from].push(idx);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut contours = Vec::new();
+    for start in 0..edges.len() {
+        let mut edge = start;
+        let mut contour = Vec::new();
+        while !used[edge] {
+            used[edge] = true;
+            let [from, to] = edges[edge];
+            contour.push(from);
+            match outgoing[to].iter().find(|&&next| !used[next]) {
+                Some(&next) => edge = next,
+                None => break,
+            }
+        }
+        if !contour.is_empty() {
+            contours.push(contour);
+        }
+    }
+    contours
+}
+
+/// Remove the points of the closed contour that lie on the straight lines between their neighbours.
+fn remove_collinear(mut points: Vec<[Real; 2]>) -> Vec<[Real; 2]> {
+    let mut idx = 0;
+    while points.len() >= 3 && idx < points.len() {
+        let len = points.len();
+        let (prev, point, next) = (points[(idx + len - 1) % len], points[idx], points[(idx + 1) % len]);
+        let (incoming, outgoing) = (sub(point, prev), sub(next, point));
+        let straight = incoming[0] * outgoing[0] + incoming[1] * outgoing[1] >= 0.0;
+        if straight && cross(incoming, outgoing).abs() <= SNAP_DISTANCE * distance(prev, next) {
+            points.remove(idx);
+            idx = idx.saturating_sub(1);
+        } else {
+            idx += 1;
+        }
+    }
+    points
+}
+
+/// Distance from the point to the segment between two points.
+fn segment_distance(from: [Real; 2], to: [Real; 2], point: [Real; 2]) -> Real {
+    let [dx, dy] = sub(to, from);
+    let length_sq = dx * dx + dy * dy;
+    if length_sq == 0.0 {
+        return distance(from, point);
+    }
+    let [px, py] = sub(point, from);
+    let t = ((px * dx + py * dy) / length_sq).clamp(0.0, 1.0);
+    distance([from[0] + dx * t, from[1] + dy * t], point)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{PathBuilder, PathCommand};
+
+    /// Sum of the signed areas of the sub-paths.
+    pub(crate) fn area(segments: &[PathSegment]) -> Real {
+        subpaths(&PathSegment::flatten(segments, BOOLEAN_TOLERANCE))
+            .iter()
+            .map(|(points, _)| {
+                let shoelace: Real = (0..points.len())
+                    .map(|idx| cross(points[idx], points[(idx + 1) % points.len()]))
+                    .sum();
+                shoelace / 2.0
+            })
+            .sum()
+    }
+
+    fn segments(cmds: Vec<PathCommand>) -> Vec<PathSegment> {
+        PathSegment::resolve(&cmds).unwrap()
+    }
+
+    fn contains(segments: &[PathSegment], point: [Real; 2]) -> bool {
+        FillRule::NonZero.contains(PathSegment::winding_number(segments, point))
+    }
+
+    #[test]
+    fn overlapping_squares() {
+        let first = segments(PathBuilder::new().rect(0.0, 0.0, 10.0, 10.0).build());
+        let second = segments(PathBuilder::new().rect(5.0, 5.0, 10.0, 10.0).build());
+        let combine = |op| PathSegment::boolean(op, &first, FillRule::NonZero, &second, FillRule::NonZero);
+
+        let union = combine(BooleanOp::Union);
+        assert_eq!(union.len(), 9, "{:?}", union);
+        assert!((area(&union).abs() - 175.0).abs() < 0.01);
+        assert!(contains(&union, [7.0, 7.0]) && contains(&union, [12.0, 12.0]) && !contains(&union, [12.0, 2.0]));
+
+        let intersection = combine(BooleanOp::Intersection);
+        assert_eq!(intersection.len(), 5, "{:?}", intersection);
+        assert!((area(&intersection).abs() - 25.0).abs() < 0.01);
+
+        let difference = combine(BooleanOp::Difference);
+        assert!((area(&difference).abs() - 75.0).abs() < 0.01);
+        assert!(contains(&difference, [2.0, 2.0]) && !contains(&difference, [7.0, 7.0]));
+
+        let xor = combine(BooleanOp::Xor);
+        assert!((area(&xor).abs() - 150.0).abs() < 0.01);
+        assert!(contains(&xor, [12.0, 12.0]) && !contains(&xor, [7.0, 7.0]));
+    }
+
+    #[test]
+    fn holes_and_touching_edges() {
+        let outer = segments(PathBuilder::new().rect(0.0, 0.0, 10.0, 10.0).build());
+        let inner = segments(PathBuilder::new().rect(2.0, 2.0, 6.0, 6.0).build());
+        let hole = PathSegment::boolean(
+            BooleanOp::Difference,
+            &outer,
+            FillRule::NonZero,
+            &inner,
+            FillRule::NonZero,
+        );
+        assert!((area(&hole).abs() - 64.0).abs() < 0.01);
+        for rule in [FillRule::NonZero, FillRule::EvenOdd] {
+            assert!(rule.contains(PathSegment::winding_number(&hole, [1.0, 1.0])));
+            assert!(!rule.contains(PathSegment::winding_number(&hole, [5.0, 5.0])));
+        }
+
+        // The shared edge disappears from the union
+        let right = segments(PathBuilder::new().rect(10.0, 0.0, 10.0, 10.0).build());
+        let union = PathSegment::boolean(BooleanOp::Union, &outer, FillRule::NonZero, &right, FillRule::NonZero);
+        assert_eq!(union.len(), 5, "{:?}", union);
+        assert!((area(&union).abs() - 200.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn fill_rules_and_curves() {
+        let star = segments(vec![
+            PathCommand::Move([50.0, 0.0]),
+            PathCommand::Line([79.0, 90.0]),
+            PathCommand::Line([2.0, 35.0]),
+            PathCommand::Line([98.0, 35.0]),
+            PathCommand::Line([21.0, 90.0]),
+            PathCommand::Close,
+        ]);
+        let simplify = |rule| PathSegment::boolean(BooleanOp::Union, &star, rule, &[], FillRule::NonZero);
+        assert!(contains(&simplify(FillRule::NonZero), [50.0, 50.0]));
+        assert!(!contains(&simplify(FillRule::EvenOdd), [50.0, 50.0]));
+        assert!(contains(&simplify(FillRule::EvenOdd), [50.0, 20.0]));
+
+        let circle = segments(PathBuilder::new().ellipse(0.0, 0.0, 10.0, 10.0).build());
+        let square = segments(PathBuilder::new().rect(0.0, 0.0, 20.0, 20.0).build());
+        let quarter = PathSegment::boolean(
+            BooleanOp::Intersection,
+            &circle,
+            FillRule::NonZero,
+            &square,
+            FillRule::NonZero,
+        );
+        // The flattened circle is a bit smaller than the circle
+        assert!((area(&quarter).abs() - 25.0 * std::f32::consts::PI).abs() < 1.0);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{PathCommand, Real};
+
+/// Magic constant to approximate a quarter of the ellipse by the cubic curve.
+const KAPPA: Real = 0.552_284_8;
+
+/// Fluent builder of the path commands.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PathBuilder {
+    cmd: Vec<PathCommand>,
+}
+
+impl PathBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn move_to(mut self, x: Real, y: Real) -> Self {
+        self.cmd.push(PathCommand::Move([x, y]));
+        self
+    }
+
+    pub fn line_to(mut self, x: Real, y: Real) -> Self {
+        self.cmd.push(PathCommand::Line([x, y]));
+        self
+    }
+
+    pub fn quad_to(mut self, ctrl_x: Real, ctrl_y: Real, x: Real, y: Real) -> Self {
+        self.cmd.push(PathCommand::BezCtrl([ctrl_x, ctrl_y]));
+        self.cmd.push(PathCommand::QuadBezTo([x, y]));
+        self
+    }
+
+    pub fn cubic_to(mut self, ctrl1_x: Real, ctrl1_y: Real, ctrl2_x: Real, ctrl2_y: Real, x: Real, y: Real) -> Self {
+        self.cmd.push(PathCommand::BezCtrl([ctrl1_x, ctrl1_y]));
+        self.cmd.push(PathCommand::BezCtrl([ctrl2_x, ctrl2_y]));
+        self.cmd.push(PathCommand::CubBezTo([x, y]));
+        self
+    }
+
+    /// Add the elliptical arc, `x_axis_rotation` is set in radians.
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc_to(
+        mut self, radius_x: Real, radius_y: Real, x_axis_rotation: Real, large_arc: bool, sweep: bool, x: Real, y: Real,
+    ) -> Self {
+        self.cmd.push(PathCommand::Arc {
+            radius: [radius_x, radius_y],
+            x_axis_rotation,
+            large_arc,
+            sweep,
+            to: [x, y],
+        });
+        self
+    }
+
+    pub fn close(mut self) -> Self {
+        self.cmd.push(PathCommand::Close);
+        self
+    }
+
+    /// Add the closed rectangle sub-path.
+    pub fn rect(self, x: Real, y: Real, width: Real, height: Real) -> Self {
+        self.move_to(x, y)
+            .line_to(x + width, y)
+            .line_to(x + width, y + height)
+            .line_to(x, y + height)
+            .close()
+    }
+
+    /// Add the closed rectangle sub-path with the rounded corners. Radii are set clockwise from
+    /// the top left corner and limited by the half of the rectangle size.
+    pub fn rounded_rect(self, x: Real, y: Real, width: Real, height: Real, radii: [Real; 4]) -> Self {
+        let max_radius = width.abs().min(height.abs()) / 2.0;
+        let [top_left, top_right, bottom_right, bottom_left] = radii.map(|radius| radius.clamp(0.0, max_radius));
+        let (right, bottom) = (x + width, y + height);
+        let corner = |builder: Self, radius: Real, [cx, cy]: [Real; 2], [dx1, dy1]: [Real; 2], [dx2, dy2]: [Real; 2]| {
+            let k = radius * (1.0 - KAPPA);
+            builder.cubic_to(
+                cx + dx1 * k,
+                cy + dy1 * k,
+                cx + dx2 * k,
+                cy + dy2 * k,
+                cx + dx2 * radius,
+                cy + dy2 * radius,
+            )
+        };
+        let builder = self.move_to(x + top_left, y).line_to(right - top_right, y);
+        let builder =
+            corner(builder, top_right, [right, y], [-1.0, 0.0], [0.0, 1.0]).line_to(right, bottom - bottom_right);
+        let builder =
+            corner(builder, bottom_right, [right, bottom], [0.0, -1.0], [-1.0, 0.0]).line_to(x + bottom_left, bottom);
+        let builder = corner(builder, bottom_left, [x, bottom], [1.0, 0.0], [0.0, -1.0]).line_to(x, y + top_left);
+        corner(builder, top_left, [x, y], [0.0, 1.0], [1.0, 0.0]).close()
+    }
+
+    /// Add the closed ellipse sub-path.
+    pub fn ellipse(self, cx: Real, cy: Real, radius_x: Real, radius_y: Real) -> Self {
+        let (kx, ky) = (radius_x * KAPPA, radius_y * KAPPA);
+        self.move_to(cx + radius_x, cy)
+            .cubic_to(cx + radius_x, cy + ky, cx + kx, cy + radius_y, cx, cy + radius_y)
+            .cubic_to(cx - kx, cy + radius_y, cx - radius_x, cy + ky, cx - radius_x, cy)
+            .cubic_to(cx - radius_x, cy - ky, cx - kx, cy - radius_y, cx, cy - radius_y)
+            .cubic_to(cx + kx, cy - radius_y, cx + radius_x, cy - ky, cx + radius_x, cy)
+            .close()
+    }
+
+    pub fn commands(&self) -> &[PathCommand] {
+        &self.cmd
+    }
+
+    pub fn build(self) -> Vec<PathCommand> {
+        self.cmd
+    }
+}
+
+impl From<PathBuilder> for Vec<PathCommand> {
+    fn from(builder: PathBuilder) -> Self {
+        builder.build()
+    }
+}
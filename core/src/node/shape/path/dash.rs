@@ -0,0 +1,196 @@
+use super::flatten::{distance, lerp};
+use crate::{DashArray, PathSegment, Real};
+
+/// Tolerance of the curves flattening before dashing.
+const DASH_TOLERANCE: Real = 0.1;
+
+impl PathSegment {
+    /// Split the path into the dash segments for the renderers without the native dashes.
+    /// The pattern starts over at each subpath, as in SVG. The zero length dashes are kept
+    /// as the degenerate lines, so the round and square caps draw them as dots.
+    pub fn dash(segments: &[PathSegment], dash: &DashArray, offset: Real) -> Vec<PathSegment> {
+        if dash.is_solid() {
+            return segments.to_vec();
+        }
+        let pattern = dash.pattern();
+        let total: Real = pattern.iter().sum();
+        let offset = offset.rem_euclid(total);
+
+        let mut dashes = Vec::new();
+        for (points, closed) in subpaths(&PathSegment::flatten(segments, DASH_TOLERANCE)) {
+            let mut dasher = Dasher::new(&pattern, offset);
+            if dasher.on {
+                dashes.push(PathSegment::MoveTo(points[0]));
+            }
+            let closing = if closed { Some(points[0]) } else { None };
+            let mut from = points[0];
+            for &to in points[1..].iter().chain(closing.as_ref()) {
+                let length = distance(from, to);
+                let mut pos = 0.0;
+                while dasher.remaining <= length - pos {
+                    pos += dasher.remaining;
+                    let point = if length > 0.0 { lerp(from, to, pos / length) } else { to };
+                    dashes.push(if dasher.on {
+                        PathSegment::LineTo(point)
+                    } else {
+                        PathSegment::MoveTo(point)
+                    });
+                    dasher.next();
+                }
+                dasher.remaining -= length - pos;
+                if dasher.on {
+                    dashes.push(PathSegment::LineTo(to));
+                }
+                from = to;
+            }
+        }
+        dashes
+    }
+}
+
+/// Current position in the dash pattern.
+struct Dasher<'a> {
+    pattern: &'a [Real],
+    idx: usize,
+    remaining: Real,
+    on: bool,
+}
+
+impl<'a> Dasher<'a> {
+    fn new(pattern: &'a [Real], mut offset: Real) -> Self {
+        let mut dasher = Self {
+            pattern,
+            idx: 0,
+            remaining: pattern[0],
+            on: true,
+        };
+        while offset > 0.0 {
+            if offset >= dasher.remaining {
+                offset -= dasher.remaining;
+                dasher.next();
+            } else {
+                dasher.remaining -= offset;
+                offset = 0.0;
+            }
+        }
+        dasher
+    }
+
+    fn next(&mut self) {
+        self.idx = (self.idx + 1) % self.pattern.len();
+        self.remaining = self.pattern[self.idx];
+        self.on = !self.on;
+    }
+}
+
+/// Points of the flattened subpaths with the flag of the closed subpath.
+pub(crate) fn subpaths(lines: &[PathSegment]) -> Vec<(Vec<[Real; 2]>, bool)> {
+    let mut subpaths: Vec<(Vec<[Real; 2]>, bool)> = Vec::new();
+    let mut current = [0.0, 0.0];
+    for segment in lines {
+        match *segment {
+            PathSegment::MoveTo(to) => {
+                subpaths.push((vec![to], false));
+                current = to;
+            }
+            PathSegment::Close => {
+                if let Some((points, closed)) = subpaths.last_mut() {
+                    *closed = true;
+                    current = points[0];
+                }
+                subpaths.push((vec![current], false));
+            }
+            _ => {
+                if let Some(to) = segment.to() {
+                    match subpaths.last_mut() {
+                        Some((points, false)) => points.push(to),
+                        _ => subpaths.push((vec![current, to], false)),
+                    }
+                    current = to;
+                }
+            }
+        }
+    }
+    subpaths.retain(|(points, _)| points.len() > 1);
+    subpaths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dash_lines() {
+        let line = [PathSegment::MoveTo([0.0, 0.0]), PathSegment::LineTo([10.0, 0.0])];
+        let dash = DashArray::from([2.0, 1.0]);
+        let points = |segments: Vec<PathSegment>| -> Vec<(bool, Real)> {
+            segments
+                .iter()
+                .map(|segment| match *segment {
+                    PathSegment::MoveTo([x, _]) => (false, x),
+                    PathSegment::LineTo([x, _]) => (true, x),
+                    _ => unreachable!(),
+                })
+                .collect()
+        };
+        assert_eq!(points(PathSegment::dash(&line, &dash, 0.0)), vec![
+            (false, 0.0),
+            (true, 2.0),
+            (false, 3.0),
+            (true, 5.0),
+            (false, 6.0),
+            (true, 8.0),
+            (false, 9.0),
+            (true, 10.0),
+        ]);
+        assert_eq!(points(PathSegment::dash(&line, &dash, 4.0)), vec![
+            (false, 0.0),
+            (true, 1.0),
+            (false, 2.0),
+            (true, 4.0),
+            (false, 5.0),
+            (true, 7.0),
+            (false, 8.0),
+            (true, 10.0),
+        ]);
+
+        // Odd arrays are repeated, so the gap of 3 follows the dash of 3
+        let odd = PathSegment::dash(&line, &DashArray::from([3.0]), -1.0);
+        assert_eq!(points(odd), vec![(false, 1.0), (true, 4.0), (false, 7.0), (true, 10.0)]);
+        assert_eq!(PathSegment::dash(&line, &DashArray::from([0.0, 0.0]), 0.0), line.to_vec());
+    }
+
+    #[test]
+    fn dash_closed_curves() {
+        let square = [
+            PathSegment::MoveTo([0.0, 0.0]),
+            PathSegment::LineTo([4.0, 0.0]),
+            PathSegment::LineTo([4.0, 4.0]),
+            PathSegment::LineTo([0.0, 4.0]),
+            PathSegment::Close,
+        ];
+        let dashes = PathSegment::dash(&square, &DashArray::from([0.0, 4.0]), 0.0);
+        let dots: Vec<_> = dashes.chunks(2).map(|dot| dot[0]).collect();
+        assert_eq!(dots, vec![
+            PathSegment::MoveTo([0.0, 0.0]),
+            PathSegment::MoveTo([4.0, 0.0]),
+            PathSegment::MoveTo([4.0, 4.0]),
+            PathSegment::MoveTo([0.0, 4.0]),
+            PathSegment::MoveTo([0.0, 0.0]),
+        ]);
+
+        let curve = [
+            PathSegment::MoveTo([0.0, 0.0]),
+            PathSegment::CubicTo {
+                ctrl1: [0.0, 10.0],
+                ctrl2: [10.0, 10.0],
+                to: [10.0, 0.0],
+            },
+        ];
+        let lines = PathSegment::flatten(&curve, 0.1);
+        assert!(lines.len() > 3);
+        assert_eq!(lines.last(), Some(&PathSegment::LineTo([10.0, 0.0])));
+        let dashes = PathSegment::dash(&curve, &DashArray::from([1.0, 1.0]), 0.0);
+        assert!(dashes.iter().all(|segment| matches!(segment, PathSegment::MoveTo(_) | PathSegment::LineTo(_))));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{PathSegment, Real};
+
+/// Tolerance of the curves flattening for the hit testing of the outlines.
+pub(crate) const HIT_TOLERANCE: Real = 0.25;
+
+impl PathSegment {
+    /// Approximate the curves by the lines, so that the lines deviate from the curves no more
+    /// than by the tolerance. The result consists of `MoveTo`, `LineTo` and `Close` segments only.
+    pub fn flatten(segments: &[PathSegment], tolerance: Real) -> Vec<PathSegment> {
+        let tolerance = tolerance.max(Real::EPSILON);
+        let mut lines = Vec::with_capacity(segments.len());
+        let mut current = [0.0, 0.0];
+        let mut subpath_start = current;
+        for segment in segments {
+            match *segment {
+                PathSegment::MoveTo(to) => {
+                    lines.push(PathSegment::MoveTo(to));
+                    subpath_start = to;
+                }
+                PathSegment::LineTo(to) => lines.push(PathSegment::LineTo(to)),
+                PathSegment::QuadTo { ctrl, to } => {
+                    let deviation = distance(sub(sub(current, ctrl), sub(ctrl, to)), [0.0, 0.0]);
+                    let steps = subdivisions(0.25 * deviation / tolerance);
+                    lines.extend((1..steps).map(|step| {
+                        PathSegment::LineTo(quad_point(current, ctrl, to, step as Real / steps as Real))
+                    }));
+                    lines.push(PathSegment::LineTo(to));
+                }
+                PathSegment::CubicTo { ctrl1, ctrl2, to } => {
+                    let deviation = distance(sub(sub(current, ctrl1), sub(ctrl1, ctrl2)), [0.0, 0.0])
+                        .max(distance(sub(sub(ctrl1, ctrl2), sub(ctrl2, to)), [0.0, 0.0]));
+                    let steps = subdivisions(0.75 * deviation / tolerance);
+                    lines.extend((1..steps).map(|step| {
+                        PathSegment::LineTo(cubic_point(current, ctrl1, ctrl2, to, step as Real / steps as Real))
+                    }));
+                    lines.push(PathSegment::LineTo(to));
+                }
+                PathSegment::Close => {
+                    lines.push(PathSegment::Close);
+                    current = subpath_start;
+                    continue;
+                }
+            }
+            if let Some(to) = segment.to() {
+                current = to;
+            }
+        }
+        lines
+    }
+
+    /// Winding number of the flattened outline around the point, the open sub-paths are closed implicitly.
+    pub fn winding_number(lines: &[PathSegment], point: [Real; 2]) -> i32 {
+        let mut winding = 0;
+        let mut start = None;
+        let mut current = [0.0, 0.0];
+        let mut cross = |from: [Real; 2], to: [Real; 2]| {
+            let [x, y] = point;
+            let side = (to[0] - from[0]) * (y - from[1]) - (x - from[0]) * (to[1] - from[1]);
+            if from[1] <= y && to[1] > y && side > 0.0 {
+                winding += 1;
+            } else if from[1] > y && to[1] <= y && side < 0.0 {
+                winding -= 1;
+            }
+        };
+        for line in lines {
+            match *line {
+                PathSegment::MoveTo(to) => {
+                    if let Some(start) = start.replace(to) {
+                        cross(current, start);
+                    }
+                    current = to;
+                }
+                PathSegment::LineTo(to) => {
+                    start.get_or_insert(current);
+                    cross(current, to);
+                    current = to;
+                }
+                PathSegment::Close => {
+                    if let Some(start) = start.take() {
+                        cross(current, start);
+                        current = start;
+                    }
+                }
+                PathSegment::QuadTo { .. } | PathSegment::CubicTo { .. } => (),
+            }
+        }
+        if let Some(start) = start {
+            cross(current, start);
+        }
+        winding
+    }
+
+    /// Distance from the point to the nearest line of the flattened outline, the open sub-paths are not closed.
+    /// The outline without lines is infinitely far.
+    pub fn distance_to(lines: &[PathSegment], point: [Real; 2]) -> Real {
+        let mut nearest = Real::INFINITY;
+        let mut current = [0.0, 0.0];
+        let mut subpath_start = current;
+        for line in lines {
+            match *line {
+                PathSegment::MoveTo(to) => subpath_start = to,
+                PathSegment::LineTo(to) => nearest = nearest.min(line_distance(current, to, point)),
+                PathSegment::Close => nearest = nearest.min(line_distance(current, subpath_start, point)),
+                PathSegment::QuadTo { .. } | PathSegment::CubicTo { .. } => continue,
+            }
+            current = match *line {
+                PathSegment::Close => subpath_start,
+                _ => line.to().unwrap_or(current),
+            };
+        }
+        nearest
+    }
+}
+
+/// Distance from the point to the line between two points.
+fn line_distance(from: [Real; 2], to: [Real; 2], point: [Real; 2]) -> Real {
+    let [dx, dy] = sub(to, from);
+    let length_sq = dx * dx + dy * dy;
+    if length_sq == 0.0 {
+        return distance(from, point);
+    }
+    let [px, py] = sub(point, from);
+    let t = ((px * dx + py * dy) / length_sq).clamp(0.0, 1.0);
+    distance([from[0] + dx * t, from[1] + dy * t], point)
+}
+
+/// Number of the lines approximating the curve by the Wang's formula.
+fn subdivisions(squared: Real) -> usize {
+    (squared.sqrt().ceil() as usize).clamp(1, 1000)
+}
+
+pub(crate) fn sub([x1, y1]: [Real; 2], [x2, y2]: [Real; 2]) -> [Real; 2] {
+    [x1 - x2, y1 - y2]
+}
+
+pub(crate) fn cross([x1, y1]: [Real; 2], [x2, y2]: [Real; 2]) -> Real {
+    x1 * y2 - y1 * x2
+}
+
+pub(crate) fn distance([x1, y1]: [Real; 2], [x2, y2]: [Real; 2]) -> Real {
+    (x2 - x1).hypot(y2 - y1)
+}
+
+pub(crate) fn lerp([x1, y1]: [Real; 2], [x2, y2]: [Real; 2], t: Real) -> [Real; 2] {
+    [x1 + (x2 - x1) * t, y1 + (y2 - y1) * t]
+}
+
+fn quad_point(from: [Real; 2], ctrl: [Real; 2], to: [Real; 2], t: Real) -> [Real; 2] {
+    lerp(lerp(from, ctrl, t), lerp(ctrl, to, t), t)
+}
+
+fn cubic_point(from: [Real; 2], ctrl1: [Real; 2], ctrl2: [Real; 2], to: [Real; 2], t: Real) -> [Real; 2] {
+    let ctrl = lerp(ctrl1, ctrl2, t);
+    lerp(lerp(lerp(from, ctrl1, t), ctrl, t), lerp(ctrl, lerp(ctrl2, to, t), t), t)
+}
@@ -0,0 +1,667 @@
+use super::flatten::{distance, lerp, sub};
+use crate::{PathCommand, PathSegment, Real};
+
+/// Tolerance of the curves flattening for the length measurements.
+const LENGTH_TOLERANCE: Real = 0.01;
+
+impl PathSegment {
+    /// Total length of the outline, including the closing lines of the closed sub-paths.
+    pub fn length(segments: &[PathSegment]) -> Real {
+        steps(segments)
+            .iter()
+            .filter_map(Step::piece)
+            .map(|piece| piece.length())
+            .sum()
+    }
+
+    /// Point at the distance along the outline, the distance is clamped to the outline length.
+    /// `None` if there are no segments.
+    pub fn point_at(segments: &[PathSegment], distance: Real) -> Option<[Real; 2]> {
+        match locate(&steps(segments), distance)? {
+            Location::Point(point) => Some(point),
+            Location::Piece(piece, t) => Some(piece.point(t)),
+        }
+    }
+
+    /// Unit direction of the outline at the distance along it, the distance is clamped to the outline length.
+    /// `None` if the outline has no length.
+    pub fn tangent_at(segments: &[PathSegment], distance: Real) -> Option<[Real; 2]> {
+        match locate(&steps(segments), distance)? {
+            Location::Point(_) => None,
+            Location::Piece(piece, t) => Some(piece.tangent(t)),
+        }
+    }
+
+    /// Exact bounds `[x, y, width, height]` of the outline. Unlike `bounds`, they don't include
+    /// the curve control points that are off the curves.
+    pub fn tight_bounds(segments: &[PathSegment]) -> Option<[Real; 4]> {
+        let mut points = steps(segments).into_iter().flat_map(|step| match step {
+            Step::Move(point) => vec![point],
+            Step::Draw(piece) | Step::Close(piece) => piece.extreme_points(),
+        });
+        let [x, y] = points.next()?;
+        let [min_x, min_y, max_x, max_y] = points.fold([x, y, x, y], |[min_x, min_y, max_x, max_y], [x, y]| {
+            [min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)]
+        });
+        Some([min_x, min_y, max_x - min_x, max_y - min_y])
+    }
+
+    /// Part of the outline between the distances along it, such as for the stroke drawn on by an animation.
+    /// The curves are split keeping their shape, the sub-paths are closed if they are kept whole.
+    pub fn trim(segments: &[PathSegment], start: Real, end: Real) -> Vec<PathSegment> {
+        let start = start.max(0.0);
+        let mut trimmed = Vec::new();
+        let mut pos = 0.0;
+        let mut need_move = true;
+        let mut whole_subpath = true;
+        for step in steps(segments) {
+            let piece = match step {
+                Step::Move(_) => {
+                    need_move = true;
+                    whole_subpath = pos >= start;
+                    continue;
+                },
+                Step::Draw(piece) | Step::Close(piece) => piece,
+            };
+            let length = piece.length();
+            let (piece_start, piece_end) = (pos, pos + length);
+            pos = piece_end;
+            if piece_end < start || (length > 0.0 && piece_end <= start) {
+                continue;
+            }
+            if piece_start > end || (length > 0.0 && piece_start >= end) {
+                break;
+            }
+
+            let from = if start > piece_start {
+                piece.param_at(start - piece_start)
+            } else {
+                0.0
+            };
+            let to = if end < piece_end {
+                piece.param_at(end - piece_start)
+            } else {
+                1.0
+            };
+            let part = piece.part(from, to);
+            if need_move || from > 0.0 {
+                trimmed.push(PathSegment::MoveTo(part.from()));
+                need_move = false;
+            }
+            trimmed.push(match step {
+                Step::Close(_) if whole_subpath && to == 1.0 => PathSegment::Close,
+                _ => part.segment(),
+            });
+        }
+        trimmed
+    }
+
+    /// Path commands drawing the segments.
+    pub fn to_commands(segments: &[PathSegment]) -> Vec<PathCommand> {
+        let mut cmds = Vec::with_capacity(segments.len());
+        for segment in segments {
+            match *segment {
+                PathSegment::MoveTo(to) => cmds.push(PathCommand::Move(to)),
+                PathSegment::LineTo(to) => cmds.push(PathCommand::Line(to)),
+                PathSegment::QuadTo { ctrl, to } => {
+                    cmds.extend_from_slice(&[PathCommand::BezCtrl(ctrl), PathCommand::QuadBezTo(to)])
+                },
+                PathSegment::CubicTo { ctrl1, ctrl2, to } => cmds.extend_from_slice(&[
+                    PathCommand::BezCtrl(ctrl1),
+                    PathCommand::BezCtrl(ctrl2),
+                    PathCommand::CubBezTo(to),
+                ]),
+                PathSegment::Close => cmds.push(PathCommand::Close),
+            }
+        }
+        cmds
+    }
+}
+
+impl PathCommand {
+    /// Convert the relative commands into the absolute ones, the other commands are kept as is.
+    pub fn to_absolute(cmds: &[PathCommand]) -> Vec<PathCommand> {
+        use PathCommand::*;
+
+        let mut current = [0.0, 0.0];
+        let mut subpath_start = current;
+        let mut absolute = Vec::with_capacity(cmds.len());
+        for &cmd in cmds {
+            let [x, y] = current;
+            let rel = |[dx, dy]: [Real; 2]| [x + dx, y + dy];
+            let cmd = match cmd {
+                MoveRel(to) => Move(rel(to)),
+                LineRel(to) => Line(rel(to)),
+                LineAlonXRel(dx) => LineAlonX(x + dx),
+                LineAlonYRel(dy) => LineAlonY(y + dy),
+                BezCtrlRel(ctrl) => BezCtrl(rel(ctrl)),
+                QuadBezToRel(to) => QuadBezTo(rel(to)),
+                CubBezToRel(to) => CubBezTo(rel(to)),
+                SmoothQuadBezToRel(to) => SmoothQuadBezTo(rel(to)),
+                SmoothCubBezToRel { ctrl, to } => SmoothCubBezTo {
+                    ctrl: rel(ctrl),
+                    to: rel(to),
+                },
+                ArcRel {
+                    radius,
+                    x_axis_rotation,
+                    large_arc,
+                    sweep,
+                    to,
+                } => Arc {
+                    radius,
+                    x_axis_rotation,
+                    large_arc,
+                    sweep,
+                    to: rel(to),
+                },
+                cmd => cmd,
+            };
+            match cmd {
+                Move(to) => {
+                    current = to;
+                    subpath_start = to;
+                },
+                Line(to) | QuadBezTo(to) | CubBezTo(to) | SmoothQuadBezTo(to) | SmoothCubBezTo { to, .. } => {
+                    current = to
+                },
+                Arc { to, .. } => current = to,
+                LineAlonX(to_x) => current = [to_x, y],
+                LineAlonY(to_y) => current = [x, to_y],
+                Close => current = subpath_start,
+                _ => (),
+            }
+            absolute.push(cmd);
+        }
+        absolute
+    }
+}
+
+/// Step along the outline, the closing line of the sub-path is the separate step.
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    Move([Real; 2]),
+    Draw(Piece),
+    Close(Piece),
+}
+
+impl Step {
+    fn piece(&self) -> Option<Piece> {
+        match *self {
+            Step::Move(_) => None,
+            Step::Draw(piece) | Step::Close(piece) => Some(piece),
+        }
+    }
+}
+
+fn steps(segments: &[PathSegment]) -> Vec<Step> {
+    let mut steps = Vec::with_capacity(segments.len());
+    let mut current = [0.0, 0.0];
+    let mut subpath_start = current;
+    for segment in segments {
+        match *segment {
+            PathSegment::MoveTo(to) => {
+                steps.push(Step::Move(to));
+                subpath_start = to;
+            },
+            PathSegment::LineTo(to) => steps.push(Step::Draw(Piece::Line(current, to))),
+            PathSegment::QuadTo { ctrl, to } => steps.push(Step::Draw(Piece::Quad(current, ctrl, to))),
+            PathSegment::CubicTo { ctrl1, ctrl2, to } => {
+                steps.push(Step::Draw(Piece::Cubic(current, ctrl1, ctrl2, to)))
+            },
+            PathSegment::Close => {
+                steps.push(Step::Close(Piece::Line(current, subpath_start)));
+                current = subpath_start;
+                continue;
+            },
+        }
+        if let Some(to) = segment.to() {
+            current = to;
+        }
+    }
+    steps
+}
+
+enum Location {
+    /// The outline has no length, so the distance is at its start point.
+    Point([Real; 2]),
+    Piece(Piece, Real),
+}
+
+/// Piece of the outline at the distance along it and the curve parameter of the distance on the piece.
+fn locate(steps: &[Step], distance: Real) -> Option<Location> {
+    let pieces: Vec<_> = steps
+        .iter()
+        .filter_map(Step::piece)
+        .filter(|piece| piece.length() > 0.0)
+        .collect();
+    let mut pos = 0.0;
+    for (idx, piece) in pieces.iter().enumerate() {
+        let length = piece.length();
+        if distance <= pos + length || idx + 1 == pieces.len() {
+            return Some(Location::Piece(*piece, piece.param_at(distance - pos)));
+        }
+        pos += length;
+    }
+    steps.first().map(|step| match *step {
+        Step::Move(point) => Location::Point(point),
+        Step::Draw(piece) | Step::Close(piece) => Location::Point(piece.from()),
+    })
+}
+
+/// Line or curve from its start point.
+#[derive(Debug, Clone, Copy)]
+enum Piece {
+    Line([Real; 2], [Real; 2]),
+    Quad([Real; 2], [Real; 2], [Real; 2]),
+    Cubic([Real; 2], [Real; 2], [Real; 2], [Real; 2]),
+}
+
+impl Piece {
+    fn from(&self) -> [Real; 2] {
+        match *self {
+            Piece::Line(from, _) | Piece::Quad(from, ..) | Piece::Cubic(from, ..) => from,
+        }
+    }
+
+    fn segment(&self) -> PathSegment {
+        match *self {
+            Piece::Line(_, to) => PathSegment::LineTo(to),
+            Piece::Quad(_, ctrl, to) => PathSegment::QuadTo { ctrl, to },
+            Piece::Cubic(_, ctrl1, ctrl2, to) => PathSegment::CubicTo { ctrl1, ctrl2, to },
+        }
+    }
+
+    fn point(&self, t: Real) -> [Real; 2] {
+        match *self {
+            Piece::Line(from, to) => lerp(from, to, t),
+            _ => self.split(t).1.from(),
+        }
+    }
+
+    /// Unit tangent at the curve parameter. The derivative vanishes at the curve end that coincides
+    /// with its control point, then the direction to the other control point is taken.
+    fn tangent(&self, t: Real) -> [Real; 2] {
+        let [dx, dy] = match *self {
+            Piece::Line(from, to) => sub(to, from),
+            Piece::Quad(from, ctrl, to) => {
+                let derivative = lerp(sub(ctrl, from), sub(to, ctrl), t);
+                if derivative == [0.0, 0.0] {
+                    sub(to, from)
+                } else {
+                    derivative
+                }
+            },
+            Piece::Cubic(from, ctrl1, ctrl2, to) => {
+                let (d1, d2, d3) = (sub(ctrl1, from), sub(ctrl2, ctrl1), sub(to, ctrl2));
+                let derivative = lerp(lerp(d1, d2, t), lerp(d2, d3, t), t);
+                if distance(derivative, [0.0, 0.0]) > Real::EPSILON {
+                    derivative
+                } else if t < 0.5 {
+                    sub(if ctrl2 != from { ctrl2 } else { to }, from)
+                } else {
+                    sub(to, if ctrl1 != to { ctrl1 } else { from })
+                }
+            },
+        };
+        let length = dx.hypot(dy);
+        if length > 0.0 {
+            [dx / length, dy / length]
+        } else {
+            [1.0, 0.0]
+        }
+    }
+
+    /// Split the piece at the curve parameter.
+    fn split(&self, t: Real) -> (Piece, Piece) {
+        match *self {
+            Piece::Line(from, to) => {
+                let mid = lerp(from, to, t);
+                (Piece::Line(from, mid), Piece::Line(mid, to))
+            },
+            Piece::Quad(from, ctrl, to) => {
+                let (ctrl1, ctrl2) = (lerp(from, ctrl, t), lerp(ctrl, to, t));
+                let mid = lerp(ctrl1, ctrl2, t);
+                (Piece::Quad(from, ctrl1, mid), Piece::Quad(mid, ctrl2, to))
+            },
+            Piece::Cubic(from, ctrl1, ctrl2, to) => {
+                let (a, b, c) = (lerp(from, ctrl1, t), lerp(ctrl1, ctrl2, t), lerp(ctrl2, to, t));
+                let (d, e) = (lerp(a, b, t), lerp(b, c, t));
+                let mid = lerp(d, e, t);
+                (Piece::Cubic(from, a, d, mid), Piece::Cubic(mid, e, c, to))
+            },
+        }
+    }
+
+    /// Part of the piece between the curve parameters.
+    fn part(&self, from: Real, to: Real) -> Piece {
+        let head = if to < 1.0 { self.split(to).0 } else { *self };
+        if from > 0.0 && to > 0.0 {
+            head.split(from / to).1
+        } else {
+            head
+        }
+    }
+
+    /// Points along the curve at the even parameter steps, including its ends.
+    fn samples(&self) -> Vec<[Real; 2]> {
+        let deviation = match *self {
+            Piece::Line(..) => return vec![self.from(), self.point(1.0)],
+            Piece::Quad(from, ctrl, to) => 0.25 * distance(sub(ctrl, from), sub(to, ctrl)),
+            Piece::Cubic(from, ctrl1, ctrl2, to) => {
+                let (d1, d2, d3) = (sub(ctrl1, from), sub(ctrl2, ctrl1), sub(to, ctrl2));
+                0.75 * distance(d1, d2).max(distance(d2, d3))
+            },
+        };
+        let steps = ((deviation / LENGTH_TOLERANCE).sqrt().ceil() as usize).clamp(1, 1000);
+        (0..=steps)
+            .map(|step| self.point(step as Real / steps as Real))
+            .collect()
+    }
+
+    fn length(&self) -> Real {
+        match *self {
+            Piece::Line(from, to) => distance(from, to),
+            _ => self.samples().windows(2).map(|pair| distance(pair[0], pair[1])).sum(),
+        }
+    }
+
+    /// Curve parameter at the distance along the piece, clamped to the piece ends.
+    fn param_at(&self, target: Real) -> Real {
+        if target <= 0.0 {
+            return 0.0;
+        }
+        let samples = self.samples();
+        let steps = (samples.len() - 1) as Real;
+        let mut pos = 0.0;
+        for (idx, pair) in samples.windows(2).enumerate() {
+            let length = distance(pair[0], pair[1]);
+            if length > 0.0 && pos + length >= target {
+                return (idx as Real + (target - pos) / length) / steps;
+            }
+            pos += length;
+        }
+        1.0
+    }
+
+    /// End points of the piece and the points where the curve turns along an axis.
+    fn extreme_points(&self) -> Vec<[Real; 2]> {
+        let mut params = vec![0.0, 1.0];
+        for axis in 0..2 {
+            match *self {
+                Piece::Line(..) => (),
+                Piece::Quad(from, ctrl, to) => {
+                    let denominator = from[axis] - 2.0 * ctrl[axis] + to[axis];
+                    if denominator != 0.0 {
+                        params.push((from[axis] - ctrl[axis]) / denominator);
+                    }
+                },
+                Piece::Cubic(from, ctrl1, ctrl2, to) => {
+                    // Roots of the derivative a t^2 + b t + c
+                    let a = -from[axis] + 3.0 * ctrl1[axis] - 3.0 * ctrl2[axis] + to[axis];
+                    let b = 2.0 * (from[axis] - 2.0 * ctrl1[axis] + ctrl2[axis]);
+                    let c = ctrl1[axis] - from[axis];
+                    if a.abs() < Real::EPSILON {
+                        if b != 0.0 {
+                            params.push(-c / b);
+                        }
+                    } else {
+                        let discriminant = b * b - 4.0 * a * c;
+                        if discriminant >= 0.0 {
+                            let sqrt = discriminant.sqrt();
+                            params.push((-b + sqrt) / (2.0 * a));
+                            params.push((-b - sqrt) / (2.0 * a));
+                        }
+                    }
+                },
+            }
+        }
+        params
+            .into_iter()
+            .filter(|t| (0.0..=1.0).contains(t))
+            .map(|t| self.point(t))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PathCommand::*;
+
+    fn assert_near(actual: &[Real], expected: &[Real]) {
+        assert!(
+            actual
+                .iter()
+                .zip(expected)
+                .all(|(actual, expected)| (actual - expected).abs() < 0.05),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    fn segments(cmds: &[PathCommand]) -> Vec<PathSegment> {
+        PathSegment::resolve(cmds).unwrap()
+    }
+
+    #[test]
+    fn length_and_sampling() {
+        // Square with the closing line
+        let square = segments(&[
+            Move([0.0, 0.0]),
+            Line([10.0, 0.0]),
+            Line([10.0, 10.0]),
+            Line([0.0, 10.0]),
+            Close,
+        ]);
+        assert_eq!(PathSegment::length(&square), 40.0);
+        assert_near(&PathSegment::point_at(&square, 15.0).unwrap(), &[10.0, 5.0]);
+        assert_near(&PathSegment::point_at(&square, 35.0).unwrap(), &[0.0, 5.0]);
+        assert_near(&PathSegment::tangent_at(&square, 35.0).unwrap(), &[0.0, -1.0]);
+        assert_near(&PathSegment::point_at(&square, 100.0).unwrap(), &[0.0, 0.0]);
+        assert_near(&PathSegment::point_at(&square, -1.0).unwrap(), &[0.0, 0.0]);
+
+        // Half circle of the radius 10 by the arc
+        let arc = segments(&[
+            Move([0.0, 0.0]),
+            Arc {
+                radius: [10.0, 10.0],
+                x_axis_rotation: 0.0,
+                large_arc: false,
+                sweep: true,
+                to: [20.0, 0.0],
+            },
+        ]);
+        assert_near(&[PathSegment::length(&arc)], &[10.0 * std::f32::consts::PI]);
+        let quarter = 5.0 * std::f32::consts::PI;
+        assert_near(&PathSegment::point_at(&arc, quarter).unwrap(), &[10.0, -10.0]);
+        assert_near(&PathSegment::tangent_at(&arc, quarter).unwrap(), &[1.0, 0.0]);
+
+        // Quadratic curve with the straight control polygon is the line
+        let quad = segments(&[Move([0.0, 0.0]), BezCtrl([5.0, 0.0]), QuadBezTo([10.0, 0.0])]);
+        assert_near(&[PathSegment::length(&quad)], &[10.0]);
+        assert_near(&PathSegment::point_at(&quad, 2.5).unwrap(), &[2.5, 0.0]);
+
+        // Cubic curve with the control points at its ends is the line too
+        let cubic = segments(&[
+            Move([0.0, 0.0]),
+            BezCtrl([0.0, 0.0]),
+            BezCtrl([0.0, 10.0]),
+            CubBezTo([0.0, 10.0]),
+        ]);
+        assert_near(&[PathSegment::length(&cubic)], &[10.0]);
+        assert_near(&PathSegment::tangent_at(&cubic, 0.0).unwrap(), &[0.0, 1.0]);
+
+        let dot = segments(&[Move([3.0, 4.0])]);
+        assert_eq!(PathSegment::length(&dot), 0.0);
+        assert_eq!(PathSegment::point_at(&dot, 1.0), Some([3.0, 4.0]));
+        assert_eq!(PathSegment::tangent_at(&dot, 1.0), None);
+        assert_eq!(PathSegment::point_at(&[], 1.0), None);
+    }
+
+    #[test]
+    fn tight_bounds() {
+        let quad = segments(&[Move([0.0, 0.0]), BezCtrl([5.0, 10.0]), QuadBezTo([10.0, 0.0])]);
+        assert_eq!(PathSegment::bounds(&quad), Some([0.0, 0.0, 10.0, 10.0]));
+        assert_near(&PathSegment::tight_bounds(&quad).unwrap(), &[0.0, 0.0, 10.0, 5.0]);
+
+        let cubic = segments(&[
+            Move([0.0, 0.0]),
+            BezCtrl([0.0, -10.0]),
+            BezCtrl([10.0, 10.0]),
+            CubBezTo([10.0, 0.0]),
+        ]);
+        let [x, y, width, height] = PathSegment::tight_bounds(&cubic).unwrap();
+        assert_near(&[x, width], &[0.0, 10.0]);
+        assert!(
+            y > -10.0 && y < -2.0 && (height + 2.0 * y).abs() < 0.01,
+            "{} {}",
+            y,
+            height
+        );
+
+        let lines = segments(&[Move([5.0, 5.0]), LineRel([-10.0, 2.0]), MoveRel([0.0, 10.0])]);
+        assert_eq!(PathSegment::tight_bounds(&lines), Some([-5.0, 5.0, 10.0, 12.0]));
+        assert_eq!(PathSegment::tight_bounds(&[]), None);
+    }
+
+    #[test]
+    fn trim() {
+        let square = segments(&[
+            Move([0.0, 0.0]),
+            Line([10.0, 0.0]),
+            Line([10.0, 10.0]),
+            Line([0.0, 10.0]),
+            Close,
+        ]);
+        assert_eq!(PathSegment::trim(&square, 0.0, 40.0), square);
+        assert_eq!(
+            PathSegment::trim(&square, 5.0, 15.0),
+            vec![
+                PathSegment::MoveTo([5.0, 0.0]),
+                PathSegment::LineTo([10.0, 0.0]),
+                PathSegment::LineTo([10.0, 5.0]),
+            ]
+        );
+        // The closing line of the partial sub-path is the line
+        assert_eq!(
+            PathSegment::trim(&square, 10.0, 40.0),
+            vec![
+                PathSegment::MoveTo([10.0, 0.0]),
+                PathSegment::LineTo([10.0, 10.0]),
+                PathSegment::LineTo([0.0, 10.0]),
+                PathSegment::LineTo([0.0, 0.0]),
+            ]
+        );
+        assert_eq!(PathSegment::trim(&square, 20.0, 10.0), vec![]);
+
+        // The curve part keeps its shape
+        let quad = segments(&[Move([0.0, 0.0]), BezCtrl([5.0, 10.0]), QuadBezTo([10.0, 0.0])]);
+        let half = PathSegment::length(&quad) / 2.0;
+        let trimmed = PathSegment::trim(&quad, 0.0, half);
+        assert_eq!(trimmed.len(), 2);
+        match trimmed[1] {
+            PathSegment::QuadTo { ctrl, to } => {
+                assert_near(&ctrl, &[2.5, 5.0]);
+                assert_near(&to, &[5.0, 5.0]);
+            },
+            segment => panic!("Unexpected segment {:?}", segment),
+        }
+        assert_near(&[PathSegment::length(&trimmed)], &[half]);
+
+        // Sub-paths after the start are moved to
+        let two = segments(&[
+            Move([0.0, 0.0]),
+            LineRel([10.0, 0.0]),
+            Move([0.0, 5.0]),
+            LineRel([10.0, 0.0]),
+        ]);
+        assert_eq!(
+            PathSegment::trim(&two, 5.0, 15.0),
+            vec![
+                PathSegment::MoveTo([5.0, 0.0]),
+                PathSegment::LineTo([10.0, 0.0]),
+                PathSegment::MoveTo([0.0, 5.0]),
+                PathSegment::LineTo([5.0, 5.0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn to_commands() {
+        let cmds = [
+            Move([0.0, 0.0]),
+            Line([10.0, 0.0]),
+            BezCtrl([15.0, 5.0]),
+            QuadBezTo([10.0, 10.0]),
+            BezCtrl([5.0, 15.0]),
+            BezCtrl([0.0, 15.0]),
+            CubBezTo([0.0, 10.0]),
+            Close,
+        ];
+        let segments = segments(&cmds);
+        assert_eq!(PathSegment::to_commands(&segments), cmds.to_vec());
+    }
+
+    #[test]
+    fn to_absolute() {
+        let cmds = [
+            MoveRel([10.0, 10.0]),
+            LineRel([5.0, 0.0]),
+            LineAlonXRel(5.0),
+            LineAlonYRel(5.0),
+            LineAlonX(0.0),
+            Close,
+            BezCtrlRel([1.0, 1.0]),
+            QuadBezToRel([2.0, 0.0]),
+            SmoothQuadBezToRel([2.0, 0.0]),
+            BezCtrlRel([1.0, 0.0]),
+            BezReflectCtrl,
+            CubBezToRel([2.0, 2.0]),
+            SmoothCubBezToRel {
+                ctrl: [1.0, 1.0],
+                to: [2.0, 0.0],
+            },
+            ArcRel {
+                radius: [3.0, 3.0],
+                x_axis_rotation: 0.0,
+                large_arc: false,
+                sweep: true,
+                to: [6.0, 0.0],
+            },
+            LineAlonY(0.0),
+        ];
+        let absolute = PathCommand::to_absolute(&cmds);
+        assert_eq!(
+            absolute,
+            vec![
+                Move([10.0, 10.0]),
+                Line([15.0, 10.0]),
+                LineAlonX(20.0),
+                LineAlonY(15.0),
+                LineAlonX(0.0),
+                Close,
+                BezCtrl([11.0, 11.0]),
+                QuadBezTo([12.0, 10.0]),
+                SmoothQuadBezTo([14.0, 10.0]),
+                BezCtrl([15.0, 10.0]),
+                BezReflectCtrl,
+                CubBezTo([16.0, 12.0]),
+                SmoothCubBezTo {
+                    ctrl: [17.0, 13.0],
+                    to: [18.0, 12.0],
+                },
+                Arc {
+                    radius: [3.0, 3.0],
+                    x_axis_rotation: 0.0,
+                    large_arc: false,
+                    sweep: true,
+                    to: [24.0, 12.0],
+                },
+                LineAlonY(0.0),
+            ]
+        );
+        assert_eq!(PathSegment::resolve(&absolute), PathSegment::resolve(&cmds));
+    }
+}
@@ -0,0 +1,341 @@
+use super::flatten::{distance, lerp};
+use crate::{step, Interpolate, Path, PathCommand, PathError, PathSegment, Real};
+
+/// Morph between two outlines. Their commands are normalized once into the sub-paths of the same number
+/// of cubic curves, which are interpolated point by point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathMorph {
+    from: Vec<PathCommand>,
+    to: Vec<PathCommand>,
+}
+
+impl PathMorph {
+    /// Normalize the outlines. The missing sub-paths grow from the centers of their counterparts, the curves
+    /// are split to match the number of the curves of the other sub-path. The closed sub-paths are rotated
+    /// to start at the nearest points, and they stay closed if both of them are closed.
+    pub fn new(from: &[PathCommand], to: &[PathCommand]) -> Result<Self, PathError> {
+        let mut from = contours(&PathSegment::resolve(from)?);
+        let mut to = contours(&PathSegment::resolve(to)?);
+        for idx in from.len().min(to.len())..from.len().max(to.len()) {
+            if idx < from.len() {
+                to.push(from[idx].collapsed());
+            } else {
+                from.push(to[idx].collapsed());
+            }
+        }
+        for (from, to) in from.iter_mut().zip(to.iter_mut()) {
+            let count = from.curves.len().max(to.curves.len());
+            from.subdivide(count);
+            to.subdivide(count);
+            if from.closed && to.closed {
+                to.align_start(from);
+            }
+        }
+
+        let mut commands = (Vec::new(), Vec::new());
+        for (from, to) in from.iter().zip(&to) {
+            let closed = from.closed && to.closed;
+            from.commands(&mut commands.0, closed);
+            to.commands(&mut commands.1, closed);
+        }
+        Ok(Self {
+            from: commands.0,
+            to: commands.1,
+        })
+    }
+
+    /// Normalized commands of the start outline.
+    pub fn from(&self) -> &[PathCommand] {
+        &self.from
+    }
+
+    /// Normalized commands of the end outline.
+    pub fn to(&self) -> &[PathCommand] {
+        &self.to
+    }
+
+    /// Commands of the outline between the start one at `t = 0` and the end one at `t = 1`.
+    pub fn at(&self, t: Real) -> Vec<PathCommand> {
+        self.from
+            .iter()
+            .zip(&self.to)
+            .map(|(from, to)| match (*from, *to) {
+                (PathCommand::Move(from), PathCommand::Move(to)) => PathCommand::Move(from.interpolate(&to, t)),
+                (PathCommand::BezCtrl(from), PathCommand::BezCtrl(to)) => {
+                    PathCommand::BezCtrl(from.interpolate(&to, t))
+                },
+                (PathCommand::CubBezTo(from), PathCommand::CubBezTo(to)) => {
+                    PathCommand::CubBezTo(from.interpolate(&to, t))
+                },
+                (from, _) => from,
+            })
+            .collect()
+    }
+}
+
+/// The outlines are morphed, the outlines with invalid commands switch in the middle.
+impl Interpolate for Path {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        let cmd = PathMorph::new(&self.cmd, &other.cmd)
+            .map(|morph| morph.at(t))
+            .unwrap_or_else(|_| step(&self.cmd, &other.cmd, t));
+        Path {
+            id: step(&self.id, &other.id, t),
+            cmd,
+            transparency: self.transparency.interpolate(&other.transparency, t),
+            stroke: self.stroke.interpolate(&other.stroke, t),
+            fill: self.fill.interpolate(&other.fill, t),
+            fill_rule: step(&self.fill_rule, &other.fill_rule, t),
+            effects: step(&self.effects, &other.effects, t),
+            clip: step(&self.clip, &other.clip, t),
+            transform: self.transform.interpolate(&other.transform, t),
+        }
+    }
+}
+
+/// Sub-path of the cubic curves `[ctrl1, ctrl2, to]`.
+#[derive(Debug, Clone)]
+struct Contour {
+    start: [Real; 2],
+    curves: Vec<[[Real; 2]; 3]>,
+    closed: bool,
+}
+
+impl Contour {
+    fn new(start: [Real; 2]) -> Self {
+        Self {
+            start,
+            curves: Vec::new(),
+            closed: false,
+        }
+    }
+
+    fn end(&self) -> [Real; 2] {
+        self.curves.last().map_or(self.start, |curve| curve[2])
+    }
+
+    /// Contour of the same number of the curves shrunk into the center of this one.
+    fn collapsed(&self) -> Contour {
+        let points = self.curves.iter().flatten().chain(Some(&self.start));
+        let [min_x, min_y, max_x, max_y] = points.fold(
+            [Real::INFINITY, Real::INFINITY, Real::NEG_INFINITY, Real::NEG_INFINITY],
+            |[min_x, min_y, max_x, max_y], [x, y]| [min_x.min(*x), min_y.min(*y), max_x.max(*x), max_y.max(*y)],
+        );
+        let center = [(min_x + max_x) / 2.0, (min_y + max_y) / 2.0];
+        Contour {
+            start: center,
+            curves: vec![[center; 3]; self.curves.len()],
+            closed: self.closed,
+        }
+    }
+
+    /// Split the longest curves in halves until there are `count` curves.
+    fn subdivide(&mut self, count: usize) {
+        if self.curves.is_empty() && count > 0 {
+            self.curves.push([self.start; 3]);
+        }
+        while self.curves.len() < count {
+            let mut from = self.start;
+            let mut longest = (0, -1.0);
+            for (idx, curve) in self.curves.iter().enumerate() {
+                let length = distance(from, curve[0]) + distance(curve[0], curve[1]) + distance(curve[1], curve[2]);
+                if length > longest.1 {
+                    longest = (idx, length);
+                }
+                from = curve[2];
+            }
+            let idx = longest.0;
+            let from = if idx == 0 { self.start } else { self.curves[idx - 1][2] };
+            let [ctrl1, ctrl2, to] = self.curves[idx];
+            let (a, b, c) = (lerp(from, ctrl1, 0.5), lerp(ctrl1, ctrl2, 0.5), lerp(ctrl2, to, 0.5));
+            let (d, e) = (lerp(a, b, 0.5), lerp(b, c, 0.5));
+            let mid = lerp(d, e, 0.5);
+            self.curves.splice(idx..=idx, [[a, d, mid], [e, c, to]]);
+        }
+    }
+
+    /// Rotate the closed contour to start at the curve ends that are the nearest to the curve ends of the other one.
+    fn align_start(&mut self, other: &Contour) {
+        let len = self.curves.len();
+        if len == 0 || len != other.curves.len() {
+            return;
+        }
+        let cost = |shift: usize| -> Real {
+            (0..len)
+                .map(|idx| {
+                    let [x, y] = other.curves[idx][2];
+                    let [to_x, to_y] = self.curves[(idx + shift) % len][2];
+                    (x - to_x).powi(2) + (y - to_y).powi(2)
+                })
+                .sum()
+        };
+        let shift = (0..len).min_by(|&a, &b| cost(a).total_cmp(&cost(b))).unwrap_or(0);
+        if shift > 0 {
+            self.start = self.curves[shift - 1][2];
+            self.curves.rotate_left(shift);
+        }
+    }
+
+    fn commands(&self, commands: &mut Vec<PathCommand>, closed: bool) {
+        commands.push(PathCommand::Move(self.start));
+        for &[ctrl1, ctrl2, to] in &self.curves {
+            commands.extend_from_slice(&[
+                PathCommand::BezCtrl(ctrl1),
+                PathCommand::BezCtrl(ctrl2),
+                PathCommand::CubBezTo(to),
+            ]);
+        }
+        if closed {
+            commands.push(PathCommand::Close);
+        }
+    }
+}
+
+/// Sub-paths of the outline with the lines and the quadratic curves converted into the cubic ones.
+/// The closed sub-paths end at their start points.
+fn contours(segments: &[PathSegment]) -> Vec<Contour> {
+    let mut contours: Vec<Contour> = Vec::new();
+    for segment in segments {
+        let contour = match *segment {
+            PathSegment::MoveTo(to) => {
+                contours.push(Contour::new(to));
+                continue;
+            },
+            _ => match contours.last_mut() {
+                Some(contour) if !contour.closed => contour,
+                last => {
+                    let start = last.map_or([0.0, 0.0], |contour| contour.start);
+                    contours.push(Contour::new(start));
+                    contours.last_mut().unwrap()
+                },
+            },
+        };
+        let from = contour.end();
+        match *segment {
+            PathSegment::MoveTo(_) => (),
+            PathSegment::LineTo(to) => contour
+                .curves
+                .push([lerp(from, to, 1.0 / 3.0), lerp(from, to, 2.0 / 3.0), to]),
+            PathSegment::QuadTo { ctrl, to } => {
+                contour
+                    .curves
+                    .push([lerp(from, ctrl, 2.0 / 3.0), lerp(to, ctrl, 2.0 / 3.0), to])
+            },
+            PathSegment::CubicTo { ctrl1, ctrl2, to } => contour.curves.push([ctrl1, ctrl2, to]),
+            PathSegment::Close => {
+                let start = contour.start;
+                if from != start {
+                    contour
+                        .curves
+                        .push([lerp(from, start, 1.0 / 3.0), lerp(from, start, 2.0 / 3.0), start]);
+                }
+                contour.closed = true;
+            },
+        }
+    }
+    contours.retain(|contour| !contour.curves.is_empty());
+    contours
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, Fill, PathBuilder};
+
+    #[test]
+    fn morph_outlines() {
+        // The triangle of the play icon into the two bars of the pause icon
+        let play = PathBuilder::new()
+            .move_to(0.0, 0.0)
+            .line_to(10.0, 5.0)
+            .line_to(0.0, 10.0)
+            .close()
+            .build();
+        let pause = PathBuilder::new()
+            .rect(0.0, 0.0, 3.0, 10.0)
+            .rect(7.0, 0.0, 3.0, 10.0)
+            .build();
+        let morph = PathMorph::new(&play, &pause).unwrap();
+        assert_eq!(morph.from().len(), morph.to().len());
+        assert_eq!(morph.from().iter().filter(|cmd| **cmd == PathCommand::Close).count(), 2);
+
+        let start = PathSegment::resolve(&morph.at(0.0)).unwrap();
+        let end = PathSegment::resolve(&morph.at(1.0)).unwrap();
+        let play_bounds = PathSegment::bounds(&PathSegment::resolve(&play).unwrap());
+        let pause_bounds = PathSegment::bounds(&PathSegment::resolve(&pause).unwrap());
+        assert_eq!(PathSegment::bounds(&start), play_bounds);
+        assert_eq!(PathSegment::bounds(&end), pause_bounds);
+
+        // The second bar grows from its center
+        let middle = PathSegment::resolve(&morph.at(0.5)).unwrap();
+        let second = middle
+            .split(|segment| matches!(segment, PathSegment::MoveTo(_)))
+            .nth(2)
+            .unwrap();
+        assert!(second.iter().all(|segment| {
+            segment
+                .to()
+                .is_none_or(|[x, y]| (7.75..=9.25).contains(&x) && (2.5..=7.5).contains(&y))
+        }));
+    }
+
+    #[test]
+    fn normalize_segments() {
+        let quad = PathSegment::resolve(&[
+            PathCommand::Move([0.0, 0.0]),
+            PathCommand::BezCtrl([5.0, 10.0]),
+            PathCommand::QuadBezTo([10.0, 0.0]),
+        ])
+        .unwrap();
+        let contours = contours(&quad);
+        assert_eq!(contours.len(), 1);
+        let contour = &contours[0];
+        assert_eq!(contour.curves[0][2], [10.0, 0.0]);
+        let cubic = PathSegment::CubicTo {
+            ctrl1: contour.curves[0][0],
+            ctrl2: contour.curves[0][1],
+            to: contour.curves[0][2],
+        };
+        let elevated = [PathSegment::MoveTo([0.0, 0.0]), cubic];
+        let ([x, y], [quad_x, quad_y]) = (
+            PathSegment::point_at(&elevated, 5.0).unwrap(),
+            PathSegment::point_at(&quad, 5.0).unwrap(),
+        );
+        assert!((x - quad_x).abs() < 1e-4 && (y - quad_y).abs() < 1e-4);
+
+        // Closed sub-paths start at the nearest corners
+        let square = |start: usize| {
+            let corners = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+            let mut builder = PathBuilder::new().move_to(corners[start][0], corners[start][1]);
+            for idx in 1..4 {
+                let [x, y] = corners[(start + idx) % 4];
+                builder = builder.line_to(x, y);
+            }
+            builder.close().build()
+        };
+        let morph = PathMorph::new(&square(0), &square(2)).unwrap();
+        assert_eq!(morph.from(), morph.to());
+    }
+
+    #[test]
+    fn interpolate_paths() {
+        let from = Path {
+            cmd: PathBuilder::new().move_to(0.0, 0.0).line_to(10.0, 0.0).build(),
+            fill: Some(Fill::color(Color::Black)),
+            ..Default::default()
+        };
+        let to = Path {
+            cmd: PathBuilder::new().move_to(0.0, 10.0).line_to(10.0, 10.0).build(),
+            fill: Some(Fill::color(Color::White)),
+            transparency: 1.0,
+            ..Default::default()
+        };
+        let middle = from.interpolate(&to, 0.5);
+        assert_eq!(middle.transparency, 0.5);
+        assert_eq!(
+            PathSegment::bounds(&middle.segments().unwrap()),
+            Some([0.0, 5.0, 10.0, 0.0])
+        );
+        assert_eq!(middle.fill.unwrap().paint, Color::RGBA(0.5, 0.5, 0.5, 1.0).into());
+    }
+}
@@ -0,0 +1,270 @@
+use std::f32::consts::PI;
+
+use super::{
+    boolean::BOOLEAN_TOLERANCE,
+    dash::subpaths,
+    flatten::{cross, sub},
+};
+use crate::{BooleanOp, FillRule, LineCap, LineJoin, PathSegment, Real, Stroke, StrokeAlignment};
+
+impl PathSegment {
+    /// Outline of the area covered by the stroke, so that filling the outline draws the stroke with its dashes,
+    /// caps, joins and alignment. The fill rule of the stroked path determines its inside for the aligned strokes.
+    /// The result is the outline of the boolean operation, see `PathSegment::boolean`.
+    pub fn stroke_outline(segments: &[PathSegment], stroke: &Stroke, fill_rule: FillRule) -> Vec<PathSegment> {
+        let lines = PathSegment::flatten(segments, BOOLEAN_TOLERANCE);
+        let closed = subpaths(&lines).iter().all(|(_, closed)| *closed);
+        let aligned = closed && stroke.alignment != StrokeAlignment::Center;
+        let half_width = if aligned { stroke.width } else { stroke.width / 2.0 };
+        if half_width <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut pieces = Vec::new();
+        for (points, closed) in subpaths(&PathSegment::dash(&lines, &stroke.dash, stroke.dash_offset)) {
+            stroke_pieces(&mut pieces, points, closed, half_width, stroke);
+        }
+        let mut outline = Vec::new();
+        for mut piece in pieces {
+            // The pieces go in the one direction to be merged by the nonzero fill rule
+            if signed_area(&piece) < 0.0 {
+                piece.reverse();
+            }
+            outline.push(PathSegment::MoveTo(piece[0]));
+            outline.extend(piece[1..].iter().map(|&point| PathSegment::LineTo(point)));
+            outline.push(PathSegment::Close);
+        }
+
+        let band = PathSegment::boolean(BooleanOp::Union, &outline, FillRule::NonZero, &[], FillRule::NonZero);
+        match stroke.alignment {
+            StrokeAlignment::Inside if aligned => {
+                PathSegment::boolean(BooleanOp::Intersection, &band, FillRule::NonZero, &lines, fill_rule)
+            },
+            StrokeAlignment::Outside if aligned => {
+                PathSegment::boolean(BooleanOp::Difference, &band, FillRule::NonZero, &lines, fill_rule)
+            },
+            _ => band,
+        }
+    }
+
+    /// Outline of the area grown by the distance, or shrunk by the negative distance. The corners of the grown area
+    /// are joined by the line join, the open sub-paths are closed implicitly.
+    pub fn offset(
+        segments: &[PathSegment], distance: Real, line_join: LineJoin, fill_rule: FillRule,
+    ) -> Vec<PathSegment> {
+        let mut lines = Vec::new();
+        for (points, _) in subpaths(&PathSegment::flatten(segments, BOOLEAN_TOLERANCE)) {
+            lines.push(PathSegment::MoveTo(points[0]));
+            lines.extend(points[1..].iter().map(|&point| PathSegment::LineTo(point)));
+            lines.push(PathSegment::Close);
+        }
+        let stroke = Stroke {
+            width: 2.0 * distance.abs(),
+            line_join,
+            ..Stroke::default()
+        };
+        let band = PathSegment::stroke_outline(&lines, &stroke, fill_rule);
+        let op = if distance < 0.0 {
+            BooleanOp::Difference
+        } else {
+            BooleanOp::Union
+        };
+        PathSegment::boolean(op, &lines, fill_rule, &band, FillRule::NonZero)
+    }
+}
+
+/// Add the polygons covering the stroke of the flattened sub-path: the quads along its lines, the joins
+/// at the outer sides of its corners and the caps at the ends of the open sub-path.
+fn stroke_pieces(
+    pieces: &mut Vec<Vec<[Real; 2]>>, mut points: Vec<[Real; 2]>, closed: bool, half_width: Real, stroke: &Stroke,
+) {
+    points.dedup();
+    if closed && points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    if let [point] = points[..] {
+        // The zero length dash is drawn by its caps only
+        match stroke.line_cap {
+            LineCap::Butt => (),
+            LineCap::Round => pieces.push(arc(point, half_width, 0.0, 2.0 * PI)),
+            LineCap::Square => pieces.push(cap(point, [1.0, 0.0], half_width, LineCap::Square)),
+        }
+        return;
+    }
+
+    let len = points.len();
+    let count = if closed { len } else { len - 1 };
+    let directions: Vec<_> = (0..count)
+        .map(|idx| {
+            let [dx, dy] = sub(points[(idx + 1) % len], points[idx]);
+            let length = dx.hypot(dy);
+            [dx / length, dy / length]
+        })
+        .collect();
+
+    for (idx, &direction) in directions.iter().enumerate() {
+        let (from, to) = (points[idx], points[(idx + 1) % len]);
+        let normal = normal(direction);
+        pieces.push(vec![
+            offset(from, normal, half_width),
+            offset(to, normal, half_width),
+            offset(to, normal, -half_width),
+            offset(from, normal, -half_width),
+        ]);
+    }
+    let first_join = if closed { 0 } else { 1 };
+    for idx in first_join..count {
+        let incoming = directions[(idx + count - 1) % count];
+        if let Some(join) = join(points[idx], incoming, directions[idx], half_width, stroke) {
+            pieces.push(join);
+        }
+    }
+    if !closed && stroke.line_cap != LineCap::Butt {
+        let [dx, dy] = directions[0];
+        pieces.push(cap(points[0], [-dx, -dy], half_width, stroke.line_cap));
+        pieces.push(cap(points[len - 1], directions[count - 1], half_width, stroke.line_cap));
+    }
+}
+
+/// Polygon filling the outer side of the corner between the lines, `None` if the lines go straight.
+fn join(
+    point: [Real; 2], incoming: [Real; 2], outgoing: [Real; 2], half_width: Real, stroke: &Stroke,
+) -> Option<Vec<[Real; 2]>> {
+    let turn = cross(incoming, outgoing);
+    let dot = incoming[0] * outgoing[0] + incoming[1] * outgoing[1];
+    if turn.abs() <= Real::EPSILON && dot > 0.0 {
+        return None;
+    }
+    // The outer side of the corner is opposite to the turn
+    let side = if turn > 0.0 { -1.0 } else { 1.0 };
+    let [first, second] = [incoming, outgoing].map(|direction| {
+        let [x, y] = normal(direction);
+        [x * side, y * side]
+    });
+    let (start, end) = (offset(point, first, half_width), offset(point, second, half_width));
+    match stroke.line_join {
+        LineJoin::Round => {
+            let sweep = if turn.abs() <= Real::EPSILON {
+                -side * PI
+            } else {
+                cross(first, second).atan2(first[0] * second[0] + first[1] * second[1])
+            };
+            let mut join = vec![point];
+            join.extend(arc(point, half_width, first[1].atan2(first[0]), sweep));
+            Some(join)
+        },
+        LineJoin::Miter => {
+            let sum = [first[0] + second[0], first[1] + second[1]];
+            let length = sum[0].hypot(sum[1]);
+            if length > Real::EPSILON && 2.0 / length <= stroke.miter_limit {
+                let miter = offset(point, sum, 2.0 * half_width / (length * length));
+                Some(vec![point, start, miter, end])
+            } else {
+                Some(vec![point, start, end])
+            }
+        },
+        LineJoin::Bevel => Some(vec![point, start, end]),
+    }
+}
+
+/// Polygon of the cap at the end of the line going in the direction.
+fn cap(point: [Real; 2], direction: [Real; 2], half_width: Real, line_cap: LineCap) -> Vec<[Real; 2]> {
+    let normal = normal(direction);
+    match line_cap {
+        LineCap::Butt => Vec::new(),
+        LineCap::Round => arc(point, half_width, normal[1].atan2(normal[0]), -PI),
+        LineCap::Square => {
+            let (left, right) = (offset(point, normal, half_width), offset(point, normal, -half_width));
+            vec![
+                left,
+                offset(left, direction, half_width),
+                offset(right, direction, half_width),
+                right,
+            ]
+        },
+    }
+}
+
+/// Points of the circle arc from the start angle by the sweep angle, including its ends.
+fn arc(center: [Real; 2], radius: Real, start: Real, sweep: Real) -> Vec<[Real; 2]> {
+    let max_step = if radius > BOOLEAN_TOLERANCE {
+        2.0 * (1.0 - BOOLEAN_TOLERANCE / radius).acos()
+    } else {
+        PI / 2.0
+    };
+    let steps = ((sweep.abs() / max_step).ceil() as usize).clamp(1, 256);
+    (0..=steps)
+        .map(|step| {
+            let angle = start + sweep * step as Real / steps as Real;
+            offset(center, [angle.cos(), angle.sin()], radius)
+        })
+        .collect()
+}
+
+fn normal([dx, dy]: [Real; 2]) -> [Real; 2] {
+    [-dy, dx]
+}
+
+fn offset([x, y]: [Real; 2], [dx, dy]: [Real; 2], distance: Real) -> [Real; 2] {
+    [x + dx * distance, y + dy * distance]
+}
+
+fn signed_area(points: &[[Real; 2]]) -> Real {
+    (0..points.len())
+        .map(|idx| cross(points[idx], points[(idx + 1) % points.len()]))
+        .sum::<Real>()
+        / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::boolean::tests::area;
+    use super::*;
+    use crate::{PathBuilder, PathCommand};
+
+    /// The flattened round parts are a bit smaller than the exact ones.
+    fn assert_area(segments: &[PathSegment], expected: Real) {
+        let actual = area(segments).abs();
+        assert!(
+            (actual - expected).abs() <= 0.02 * expected + 0.01,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn stroke_caps_joins_and_dashes() {
+        let line = PathSegment::resolve(&[PathCommand::Move([0.0, 0.0]), PathCommand::Line([10.0, 0.0])]).unwrap();
+        let outline = |stroke: Stroke| PathSegment::stroke_outline(&line, &stroke.width(2.0), FillRule::NonZero);
+        assert_area(&outline(Stroke::default()), 20.0);
+        assert_area(&outline(Stroke::default().line_cap(LineCap::Square)), 24.0);
+        assert_area(&outline(Stroke::default().line_cap(LineCap::Round)), 20.0 + PI);
+        assert_area(&outline(Stroke::default().dash([2.0, 2.0])), 12.0);
+
+        let square = PathSegment::resolve(&PathBuilder::new().rect(0.0, 0.0, 10.0, 10.0).build()).unwrap();
+        let outline = |stroke: Stroke| PathSegment::stroke_outline(&square, &stroke.width(2.0), FillRule::NonZero);
+        assert_area(&outline(Stroke::default()), 80.0);
+        assert_area(&outline(Stroke::default().line_join(LineJoin::Bevel)), 78.0);
+        assert_area(&outline(Stroke::default().line_join(LineJoin::Round)), 76.0 + PI);
+        assert_area(&outline(Stroke::default().alignment(StrokeAlignment::Inside)), 64.0);
+        assert_area(&outline(Stroke::default().alignment(StrokeAlignment::Outside)), 96.0);
+
+        // The stroke outline fills the same area by both fill rules
+        let band = outline(Stroke::default());
+        for rule in [FillRule::NonZero, FillRule::EvenOdd] {
+            assert!(rule.contains(PathSegment::winding_number(&band, [0.5, 5.0])));
+            assert!(!rule.contains(PathSegment::winding_number(&band, [5.0, 5.0])));
+        }
+    }
+
+    #[test]
+    fn offset() {
+        let square = PathSegment::resolve(&PathBuilder::new().rect(0.0, 0.0, 10.0, 10.0).build()).unwrap();
+        let offset = |distance, join| PathSegment::offset(&square, distance, join, FillRule::NonZero);
+        assert_area(&offset(2.0, LineJoin::Miter), 196.0);
+        assert_area(&offset(2.0, LineJoin::Round), 180.0 + 4.0 * PI);
+        assert_area(&offset(-2.0, LineJoin::Miter), 36.0);
+        assert_area(&offset(-6.0, LineJoin::Miter), 0.0);
+    }
+}
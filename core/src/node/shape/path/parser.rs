@@ -0,0 +1,366 @@
+use std::{error::Error, fmt, iter::Peekable, str::CharIndices};
+
+use crate::{PathCommand, Real};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathParseErrorKind {
+    UnexpectedChar(char),
+    UnexpectedEnd,
+    InvalidNumber,
+    InvalidFlag,
+    /// Path data must begin with a move command.
+    MissingMove,
+}
+
+/// An error of parsing the SVG path data, `position` is a byte offset in the source string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathParseError {
+    pub kind: PathParseErrorKind,
+    pub position: usize,
+}
+
+impl fmt::Display for PathParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            PathParseErrorKind::UnexpectedChar(ch) => write!(f, "Unexpected char '{}'", ch)?,
+            PathParseErrorKind::UnexpectedEnd => write!(f, "Unexpected end of data")?,
+            PathParseErrorKind::InvalidNumber => write!(f, "Invalid number")?,
+            PathParseErrorKind::InvalidFlag => write!(f, "Invalid flag, expected '0' or '1'")?,
+            PathParseErrorKind::MissingMove => write!(f, "Path data must begin with a move command")?,
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for PathParseError {}
+
+impl PathCommand {
+    /// Parse the SVG path data, such as the `d` attribute of the `<path>` element.
+    pub fn parse(data: &str) -> Result<Vec<PathCommand>, PathParseError> {
+        PathDataParser::new(data).parse()
+    }
+}
+
+struct PathDataParser<'a> {
+    data: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    cmd: Vec<PathCommand>,
+}
+
+impl<'a> PathDataParser<'a> {
+    fn new(data: &'a str) -> Self {
+        Self {
+            data,
+            chars: data.char_indices().peekable(),
+            cmd: Vec::new(),
+        }
+    }
+
+    fn parse(mut self) -> Result<Vec<PathCommand>, PathParseError> {
+        self.skip_separators();
+        while let Some((position, letter)) = self.chars.next() {
+            if self.cmd.is_empty() && letter != 'M' && letter != 'm' {
+                return Err(Self::error(PathParseErrorKind::MissingMove, position));
+            }
+
+            let mut first = true;
+            loop {
+                self.skip_separators();
+                let has_args = self.next_is_number();
+                if !first && !has_args {
+                    break;
+                }
+                match letter {
+                    'M' | 'm' => {
+                        let xy = self.point()?;
+                        self.cmd.push(match (letter, first) {
+                            ('M', true) => PathCommand::Move(xy),
+                            ('m', true) => PathCommand::MoveRel(xy),
+                            ('M', false) => PathCommand::Line(xy),
+                            _ => PathCommand::LineRel(xy),
+                        });
+                    }
+                    'L' => {
+                        let to = self.point()?;
+                        self.cmd.push(PathCommand::Line(to));
+                    }
+                    'l' => {
+                        let to = self.point()?;
+                        self.cmd.push(PathCommand::LineRel(to));
+                    }
+                    'H' => {
+                        let x = self.number()?;
+                        self.cmd.push(PathCommand::LineAlonX(x));
+                    }
+                    'h' => {
+                        let x = self.number()?;
+                        self.cmd.push(PathCommand::LineAlonXRel(x));
+                    }
+                    'V' => {
+                        let y = self.number()?;
+                        self.cmd.push(PathCommand::LineAlonY(y));
+                    }
+                    'v' => {
+                        let y = self.number()?;
+                        self.cmd.push(PathCommand::LineAlonYRel(y));
+                    }
+                    'C' | 'c' => {
+                        let ctrl1 = self.point()?;
+                        let ctrl2 = self.point()?;
+                        let to = self.point()?;
+                        if letter == 'C' {
+                            self.cmd.push(PathCommand::BezCtrl(ctrl1));
+                            self.cmd.push(PathCommand::BezCtrl(ctrl2));
+                            self.cmd.push(PathCommand::CubBezTo(to));
+                        } else {
+                            self.cmd.push(PathCommand::BezCtrlRel(ctrl1));
+                            self.cmd.push(PathCommand::BezCtrlRel(ctrl2));
+                            self.cmd.push(PathCommand::CubBezToRel(to));
+                        }
+                    }
+                    'S' | 's' => {
+                        let ctrl = self.point()?;
+                        let to = self.point()?;
+                        self.cmd.push(if letter == 'S' {
+                            PathCommand::SmoothCubBezTo { ctrl, to }
+                        } else {
+                            PathCommand::SmoothCubBezToRel { ctrl, to }
+                        });
+                    }
+                    'Q' | 'q' => {
+                        let ctrl = self.point()?;
+                        let to = self.point()?;
+                        if letter == 'Q' {
+                            self.cmd.push(PathCommand::BezCtrl(ctrl));
+                            self.cmd.push(PathCommand::QuadBezTo(to));
+                        } else {
+                            self.cmd.push(PathCommand::BezCtrlRel(ctrl));
+                            self.cmd.push(PathCommand::QuadBezToRel(to));
+                        }
+                    }
+                    'T' => {
+                        let to = self.point()?;
+                        self.cmd.push(PathCommand::SmoothQuadBezTo(to));
+                    }
+                    't' => {
+                        let to = self.point()?;
+                        self.cmd.push(PathCommand::SmoothQuadBezToRel(to));
+                    }
+                    'A' | 'a' => {
+                        let radius = self.point()?;
+                        let x_axis_rotation = self.number()?.to_radians();
+                        let large_arc = self.flag()?;
+                        let sweep = self.flag()?;
+                        let to = self.point()?;
+                        self.cmd.push(if letter == 'A' {
+                            PathCommand::Arc {
+                                radius,
+                                x_axis_rotation,
+                                large_arc,
+                                sweep,
+                                to,
+                            }
+                        } else {
+                            PathCommand::ArcRel {
+                                radius,
+                                x_axis_rotation,
+                                large_arc,
+                                sweep,
+                                to,
+                            }
+                        });
+                    }
+                    'Z' | 'z' => {
+                        self.cmd.push(PathCommand::Close);
+                        break;
+                    }
+                    _ => return Err(Self::error(PathParseErrorKind::UnexpectedChar(letter), position)),
+                }
+                first = false;
+            }
+        }
+        Ok(self.cmd)
+    }
+
+    fn error(kind: PathParseErrorKind, position: usize) -> PathParseError {
+        PathParseError { kind, position }
+    }
+
+    fn position(&mut self) -> usize {
+        self.chars.peek().map(|(position, _)| *position).unwrap_or(self.data.len())
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some((_, ch)) = self.chars.peek() {
+            if ch.is_ascii_whitespace() || *ch == ',' {
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn next_is_number(&mut self) -> bool {
+        matches!(self.chars.peek(), Some((_, ch)) if ch.is_ascii_digit() || matches!(ch, '-' | '+' | '.'))
+    }
+
+    fn point(&mut self) -> Result<[Real; 2], PathParseError> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Ok([x, y])
+    }
+
+    fn flag(&mut self) -> Result<bool, PathParseError> {
+        self.skip_separators();
+        match self.chars.next() {
+            Some((_, '0')) => Ok(false),
+            Some((_, '1')) => Ok(true),
+            Some((position, _)) => Err(Self::error(PathParseErrorKind::InvalidFlag, position)),
+            None => Err(Self::error(PathParseErrorKind::UnexpectedEnd, self.data.len())),
+        }
+    }
+
+    fn number(&mut self) -> Result<Real, PathParseError> {
+        self.skip_separators();
+        let start = self.position();
+        match self.chars.peek() {
+            None => return Err(Self::error(PathParseErrorKind::UnexpectedEnd, start)),
+            Some((_, ch)) if !ch.is_ascii_digit() && !matches!(ch, '-' | '+' | '.') => {
+                return Err(Self::error(PathParseErrorKind::UnexpectedChar(*ch), start));
+            }
+            _ => (),
+        }
+
+        if matches!(self.chars.peek(), Some((_, '-')) | Some((_, '+'))) {
+            self.chars.next();
+        }
+        let mut has_digits = self.skip_digits();
+        if let Some((_, '.')) = self.chars.peek() {
+            self.chars.next();
+            has_digits |= self.skip_digits();
+        }
+        if !has_digits {
+            return Err(Self::error(PathParseErrorKind::InvalidNumber, start));
+        }
+        if let Some((_, 'e')) | Some((_, 'E')) = self.chars.peek() {
+            self.chars.next();
+            if matches!(self.chars.peek(), Some((_, '-')) | Some((_, '+'))) {
+                self.chars.next();
+            }
+            if !self.skip_digits() {
+                let position = self.position();
+                return Err(Self::error(PathParseErrorKind::InvalidNumber, position));
+            }
+        }
+
+        let end = self.position();
+        self.data[start..end]
+            .parse()
+            .map_err(|_| Self::error(PathParseErrorKind::InvalidNumber, start))
+    }
+
+    fn skip_digits(&mut self) -> bool {
+        let mut has_digits = false;
+        while let Some((_, ch)) = self.chars.peek() {
+            if ch.is_ascii_digit() {
+                has_digits = true;
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        has_digits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PathCommand::*;
+
+    #[test]
+    fn parse_commands() {
+        let cmd = PathCommand::parse("M10,20 L30 40 h5v-5.5 C1 2 3 4 5 6 s1,2,3,4 Q1 2 3 4 T5 6 A5 5 90 0 1 -1e1 .5z").unwrap();
+        assert_eq!(cmd, vec![
+            Move([10.0, 20.0]),
+            Line([30.0, 40.0]),
+            LineAlonXRel(5.0),
+            LineAlonYRel(-5.5),
+            BezCtrl([1.0, 2.0]),
+            BezCtrl([3.0, 4.0]),
+            CubBezTo([5.0, 6.0]),
+            SmoothCubBezToRel {
+                ctrl: [1.0, 2.0],
+                to: [3.0, 4.0],
+            },
+            BezCtrl([1.0, 2.0]),
+            QuadBezTo([3.0, 4.0]),
+            SmoothQuadBezTo([5.0, 6.0]),
+            Arc {
+                radius: [5.0, 5.0],
+                x_axis_rotation: 90_f32.to_radians(),
+                large_arc: false,
+                sweep: true,
+                to: [-10.0, 0.5],
+            },
+            Close,
+        ]);
+    }
+
+    #[test]
+    fn parse_implicit_commands() {
+        let cmd = PathCommand::parse("m1-2 3.5.5l1 1 2 2a1 1 0 1012 3").unwrap();
+        assert_eq!(cmd, vec![
+            MoveRel([1.0, -2.0]),
+            LineRel([3.5, 0.5]),
+            LineRel([1.0, 1.0]),
+            LineRel([2.0, 2.0]),
+            ArcRel {
+                radius: [1.0, 1.0],
+                x_axis_rotation: 0.0,
+                large_arc: true,
+                sweep: false,
+                to: [12.0, 3.0],
+            },
+        ]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            PathCommand::parse("L10 10"),
+            Err(PathParseError {
+                kind: PathParseErrorKind::MissingMove,
+                position: 0,
+            })
+        );
+        assert_eq!(
+            PathCommand::parse("M10 10 L 5 x"),
+            Err(PathParseError {
+                kind: PathParseErrorKind::UnexpectedChar('x'),
+                position: 11,
+            })
+        );
+        assert_eq!(
+            PathCommand::parse("M10 10 L 5"),
+            Err(PathParseError {
+                kind: PathParseErrorKind::UnexpectedEnd,
+                position: 10,
+            })
+        );
+        assert_eq!(
+            PathCommand::parse("M10 10 A 5 5 0 2 0 1 1"),
+            Err(PathParseError {
+                kind: PathParseErrorKind::InvalidFlag,
+                position: 15,
+            })
+        );
+        assert_eq!(
+            PathCommand::parse("M10 10 L -. 1"),
+            Err(PathParseError {
+                kind: PathParseErrorKind::InvalidNumber,
+                position: 9,
+            })
+        );
+    }
+}
@@ -0,0 +1,390 @@
+use std::{error::Error, f32::consts::PI, fmt};
+
+use crate::{PathCommand, Real};
+
+/// Absolute drawing segment that renderers can consume directly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathSegment {
+    MoveTo([Real; 2]),
+    LineTo([Real; 2]),
+    QuadTo { ctrl: [Real; 2], to: [Real; 2] },
+    CubicTo { ctrl1: [Real; 2], ctrl2: [Real; 2], to: [Real; 2] },
+    Close,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathError {
+    /// The curve command at `index` has no enough preceding control point commands.
+    MissingControlPoint { index: usize, cmd: PathCommand },
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::MissingControlPoint { index, cmd } => {
+                write!(f, "Missing control point for path command {:?} at {}", cmd, index)
+            }
+        }
+    }
+}
+
+impl Error for PathError {}
+
+impl PathSegment {
+    /// Resolve relative, shortcut, reflected and arc commands into the absolute segments.
+    pub fn resolve(cmds: &[PathCommand]) -> Result<Vec<PathSegment>, PathError> {
+        let mut resolver = Resolver::default();
+        for (index, cmd) in cmds.iter().enumerate() {
+            resolver.push(index, *cmd)?;
+        }
+        Ok(resolver.segments)
+    }
+
+    /// The end point of the segment, if any.
+    pub fn to(&self) -> Option<[Real; 2]> {
+        match *self {
+            PathSegment::MoveTo(to)
+            | PathSegment::LineTo(to)
+            | PathSegment::QuadTo { to, .. }
+            | PathSegment::CubicTo { to, .. } => Some(to),
+            PathSegment::Close => None,
+        }
+    }
+
+    /// Bounds `[x, y, width, height]` of the segment points including the curve control points,
+    /// they contain the whole path.
+    pub fn bounds(segments: &[PathSegment]) -> Option<[Real; 4]> {
+        let mut points = segments.iter().flat_map(|segment| match *segment {
+            PathSegment::MoveTo(to) | PathSegment::LineTo(to) => vec![to],
+            PathSegment::QuadTo { ctrl, to } => vec![ctrl, to],
+            PathSegment::CubicTo { ctrl1, ctrl2, to } => vec![ctrl1, ctrl2, to],
+            PathSegment::Close => vec![],
+        });
+        let [x, y] = points.next()?;
+        let [min_x, min_y, max_x, max_y] = points.fold([x, y, x, y], |[min_x, min_y, max_x, max_y], [x, y]| {
+            [min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)]
+        });
+        Some([min_x, min_y, max_x - min_x, max_y - min_y])
+    }
+
+    /// The segment with all its points mapped by the function.
+    pub fn map_points(&self, f: impl Fn([Real; 2]) -> [Real; 2]) -> PathSegment {
+        match *self {
+            PathSegment::MoveTo(to) => PathSegment::MoveTo(f(to)),
+            PathSegment::LineTo(to) => PathSegment::LineTo(f(to)),
+            PathSegment::QuadTo { ctrl, to } => PathSegment::QuadTo {
+                ctrl: f(ctrl),
+                to: f(to),
+            },
+            PathSegment::CubicTo { ctrl1, ctrl2, to } => PathSegment::CubicTo {
+                ctrl1: f(ctrl1),
+                ctrl2: f(ctrl2),
+                to: f(to),
+            },
+            PathSegment::Close => PathSegment::Close,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Resolver {
+    segments: Vec<PathSegment>,
+    current: [Real; 2],
+    subpath_start: [Real; 2],
+    ctrls: Vec<[Real; 2]>,
+    last_curve_ctrl: Option<[Real; 2]>,
+}
+
+impl Resolver {
+    fn push(&mut self, index: usize, cmd: PathCommand) -> Result<(), PathError> {
+        use PathCommand::*;
+
+        let [x, y] = self.current;
+        let mut last_curve_ctrl = None;
+        match cmd {
+            Move(to) => self.move_to(to),
+            MoveRel([dx, dy]) => self.move_to([x + dx, y + dy]),
+            Line(to) => self.line_to(to),
+            LineRel([dx, dy]) => self.line_to([x + dx, y + dy]),
+            LineAlonX(to_x) => self.line_to([to_x, y]),
+            LineAlonXRel(dx) => self.line_to([x + dx, y]),
+            LineAlonY(to_y) => self.line_to([x, to_y]),
+            LineAlonYRel(dy) => self.line_to([x, y + dy]),
+            Close => {
+                self.segments.push(PathSegment::Close);
+                self.ctrls.clear();
+                self.current = self.subpath_start;
+            }
+            BezCtrl(ctrl) => {
+                self.ctrls.push(ctrl);
+                last_curve_ctrl = self.last_curve_ctrl;
+            }
+            BezCtrlRel([dx, dy]) => {
+                self.ctrls.push([x + dx, y + dy]);
+                last_curve_ctrl = self.last_curve_ctrl;
+            }
+            BezReflectCtrl => {
+                let ctrl = self.reflected_ctrl();
+                self.ctrls.push(ctrl);
+                last_curve_ctrl = self.last_curve_ctrl;
+            }
+            QuadBezTo(to) => last_curve_ctrl = Some(self.quad_to(index, cmd, to)?),
+            QuadBezToRel([dx, dy]) => last_curve_ctrl = Some(self.quad_to(index, cmd, [x + dx, y + dy])?),
+            CubBezTo(to) => last_curve_ctrl = Some(self.cubic_to(index, cmd, to)?),
+            CubBezToRel([dx, dy]) => last_curve_ctrl = Some(self.cubic_to(index, cmd, [x + dx, y + dy])?),
+            SmoothQuadBezTo(to) => {
+                let ctrl = self.reflected_ctrl();
+                self.ctrls.push(ctrl);
+                last_curve_ctrl = Some(self.quad_to(index, cmd, to)?);
+            }
+            SmoothQuadBezToRel([dx, dy]) => {
+                let ctrl = self.reflected_ctrl();
+                self.ctrls.push(ctrl);
+                last_curve_ctrl = Some(self.quad_to(index, cmd, [x + dx, y + dy])?);
+            }
+            SmoothCubBezTo { ctrl, to } => {
+                let ctrl1 = self.reflected_ctrl();
+                self.ctrls.push(ctrl1);
+                self.ctrls.push(ctrl);
+                last_curve_ctrl = Some(self.cubic_to(index, cmd, to)?);
+            }
+            SmoothCubBezToRel {
+                ctrl: [cdx, cdy],
+                to: [dx, dy],
+            } => {
+                let ctrl1 = self.reflected_ctrl();
+                self.ctrls.push(ctrl1);
+                self.ctrls.push([x + cdx, y + cdy]);
+                last_curve_ctrl = Some(self.cubic_to(index, cmd, [x + dx, y + dy])?);
+            }
+            Arc {
+                radius,
+                x_axis_rotation,
+                large_arc,
+                sweep,
+                to,
+            } => self.arc_to(radius, x_axis_rotation, large_arc, sweep, to),
+            ArcRel {
+                radius,
+                x_axis_rotation,
+                large_arc,
+                sweep,
+                to: [dx, dy],
+            } => self.arc_to(radius, x_axis_rotation, large_arc, sweep, [x + dx, y + dy]),
+        }
+        self.last_curve_ctrl = last_curve_ctrl;
+        Ok(())
+    }
+
+    fn move_to(&mut self, to: [Real; 2]) {
+        self.segments.push(PathSegment::MoveTo(to));
+        self.ctrls.clear();
+        self.current = to;
+        self.subpath_start = to;
+    }
+
+    fn line_to(&mut self, to: [Real; 2]) {
+        self.segments.push(PathSegment::LineTo(to));
+        self.ctrls.clear();
+        self.current = to;
+    }
+
+    fn quad_to(&mut self, index: usize, cmd: PathCommand, to: [Real; 2]) -> Result<[Real; 2], PathError> {
+        let ctrl = self
+            .ctrls
+            .last()
+            .copied()
+            .ok_or(PathError::MissingControlPoint { index, cmd })?;
+        self.ctrls.clear();
+        self.segments.push(PathSegment::QuadTo { ctrl, to });
+        self.current = to;
+        Ok(ctrl)
+    }
+
+    fn cubic_to(&mut self, index: usize, cmd: PathCommand, to: [Real; 2]) -> Result<[Real; 2], PathError> {
+        if self.ctrls.len() < 2 {
+            return Err(PathError::MissingControlPoint { index, cmd });
+        }
+        let ctrl2 = self.ctrls[self.ctrls.len() - 1];
+        let ctrl1 = self.ctrls[self.ctrls.len() - 2];
+        self.ctrls.clear();
+        self.segments.push(PathSegment::CubicTo { ctrl1, ctrl2, to });
+        self.current = to;
+        Ok(ctrl2)
+    }
+
+    fn reflected_ctrl(&self) -> [Real; 2] {
+        let [x, y] = self.current;
+        self.last_curve_ctrl
+            .map(|[cx, cy]| [2.0 * x - cx, 2.0 * y - cy])
+            .unwrap_or(self.current)
+    }
+
+    /// Approximate the elliptical arc by cubic curves, see the SVG implementation notes
+    /// (https://www.w3.org/TR/SVG/implnote.html#ArcImplementationNotes).
+    fn arc_to(&mut self, [rx, ry]: [Real; 2], x_axis_rotation: Real, large_arc: bool, sweep: bool, to: [Real; 2]) {
+        let from = self.current;
+        if from == to {
+            return;
+        }
+        let (mut rx, mut ry) = (rx.abs(), ry.abs());
+        if rx == 0.0 || ry == 0.0 {
+            self.line_to(to);
+            return;
+        }
+
+        let (sin_phi, cos_phi) = x_axis_rotation.sin_cos();
+        let dx2 = (from[0] - to[0]) / 2.0;
+        let dy2 = (from[1] - to[1]) / 2.0;
+        let x1 = cos_phi * dx2 + sin_phi * dy2;
+        let y1 = -sin_phi * dx2 + cos_phi * dy2;
+
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            let scale = lambda.sqrt();
+            rx *= scale;
+            ry *= scale;
+        }
+
+        let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut coef = (numerator / denominator).max(0.0).sqrt();
+        if large_arc == sweep {
+            coef = -coef;
+        }
+        let cx1 = coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+        let cx = cos_phi * cx1 - sin_phi * cy1 + (from[0] + to[0]) / 2.0;
+        let cy = sin_phi * cx1 + cos_phi * cy1 + (from[1] + to[1]) / 2.0;
+
+        let angle = |ux: Real, uy: Real, vx: Real, vy: Real| {
+            let sign = if ux * vy - uy * vx < 0.0 { -1.0 } else { 1.0 };
+            let cos = (ux * vx + uy * vy) / ((ux * ux + uy * uy).sqrt() * (vx * vx + vy * vy).sqrt());
+            sign * cos.clamp(-1.0, 1.0).acos()
+        };
+        let start_angle = angle(1.0, 0.0, (x1 - cx1) / rx, (y1 - cy1) / ry);
+        let mut sweep_angle = angle((x1 - cx1) / rx, (y1 - cy1) / ry, (-x1 - cx1) / rx, (-y1 - cy1) / ry);
+        if !sweep && sweep_angle > 0.0 {
+            sweep_angle -= 2.0 * PI;
+        } else if sweep && sweep_angle < 0.0 {
+            sweep_angle += 2.0 * PI;
+        }
+
+        let point = |theta: Real| {
+            let (sin, cos) = theta.sin_cos();
+            [
+                cx + rx * cos * cos_phi - ry * sin * sin_phi,
+                cy + rx * cos * sin_phi + ry * sin * cos_phi,
+            ]
+        };
+        let derivative = |theta: Real| {
+            let (sin, cos) = theta.sin_cos();
+            [
+                -rx * sin * cos_phi - ry * cos * sin_phi,
+                -rx * sin * sin_phi + ry * cos * cos_phi,
+            ]
+        };
+
+        let count = (sweep_angle.abs() / (PI / 2.0)).ceil().max(1.0) as usize;
+        let delta = sweep_angle / count as Real;
+        let k = 4.0 / 3.0 * (delta / 4.0).tan();
+        let mut theta = start_angle;
+        for idx in 0..count {
+            let p1 = point(theta);
+            let d1 = derivative(theta);
+            let next_theta = theta + delta;
+            let p2 = if idx + 1 == count { to } else { point(next_theta) };
+            let d2 = derivative(next_theta);
+            self.segments.push(PathSegment::CubicTo {
+                ctrl1: [p1[0] + k * d1[0], p1[1] + k * d1[1]],
+                ctrl2: [p2[0] - k * d2[0], p2[1] - k * d2[1]],
+                to: p2,
+            });
+            theta = next_theta;
+        }
+        self.ctrls.clear();
+        self.current = to;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PathCommand::*;
+
+    fn assert_point_eq(actual: [Real; 2], expected: [Real; 2]) {
+        assert!(
+            (actual[0] - expected[0]).abs() < 1e-3 && (actual[1] - expected[1]).abs() < 1e-3,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn reflect_ctrl() {
+        let segments = PathSegment::resolve(&[
+            Move([0.0, 0.0]),
+            BezCtrl([0.0, 10.0]),
+            BezCtrl([10.0, 10.0]),
+            CubBezTo([10.0, 0.0]),
+            SmoothCubBezTo {
+                ctrl: [20.0, -10.0],
+                to: [20.0, 0.0],
+            },
+            BezReflectCtrl,
+            QuadBezTo([30.0, 0.0]),
+            SmoothQuadBezToRel([10.0, 0.0]),
+        ])
+        .unwrap();
+
+        assert_eq!(segments[2], PathSegment::CubicTo {
+            ctrl1: [10.0, -10.0],
+            ctrl2: [20.0, -10.0],
+            to: [20.0, 0.0],
+        });
+        assert_eq!(segments[3], PathSegment::QuadTo {
+            ctrl: [20.0, 10.0],
+            to: [30.0, 0.0],
+        });
+        assert_eq!(segments[4], PathSegment::QuadTo {
+            ctrl: [40.0, -10.0],
+            to: [40.0, 0.0],
+        });
+    }
+
+    #[test]
+    fn missing_ctrl() {
+        let result = PathSegment::resolve(&[Move([0.0, 0.0]), BezCtrl([5.0, 5.0]), CubBezTo([10.0, 0.0])]);
+        assert_eq!(
+            result,
+            Err(PathError::MissingControlPoint {
+                index: 2,
+                cmd: CubBezTo([10.0, 0.0]),
+            })
+        );
+    }
+
+    #[test]
+    fn arc() {
+        let segments = PathSegment::resolve(&[
+            Move([0.0, 0.0]),
+            Arc {
+                radius: [10.0, 10.0],
+                x_axis_rotation: 0.0,
+                large_arc: false,
+                sweep: true,
+                to: [20.0, 0.0],
+            },
+        ])
+        .unwrap();
+
+        // Half of the circle is split into two quarters
+        assert_eq!(segments.len(), 3);
+        match segments[1] {
+            PathSegment::CubicTo { to, .. } => assert_point_eq(to, [10.0, -10.0]),
+            segment => panic!("Unexpected segment {:?}", segment),
+        }
+        assert_eq!(segments[2].to(), Some([20.0, 0.0]));
+    }
+}
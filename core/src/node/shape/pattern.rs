@@ -0,0 +1,253 @@
+use std::sync::Arc;
+
+use crate::{step, Color, Interpolate, Real, RealValue, UnitContext};
+
+/// Image pixels in the RGBA8 format with the straight alpha, rows go from the top to the bottom.
+/// The pixel data is shared by the clones of the image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    data: Arc<Vec<u8>>,
+}
+
+impl Image {
+    /// Image of the pixel data, `None` if the data length is not `width * height * 4` or the image is empty.
+    pub fn from_rgba8(width: u32, height: u32, data: impl Into<Vec<u8>>) -> Option<Self> {
+        let data = data.into();
+        if width == 0 || height == 0 || data.len() != width as usize * height as usize * 4 {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            data: Arc::new(data),
+        })
+    }
+
+    /// Image with the pixel colors calculated by the function of the pixel column and row.
+    pub fn from_fn(width: u32, height: u32, mut pixel: impl FnMut(u32, u32) -> Color) -> Self {
+        let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+        for y in 0..height {
+            for x in 0..width {
+                data.extend_from_slice(&pixel(x, y).to_rgba8());
+            }
+        }
+        Self {
+            width,
+            height,
+            data: Arc::new(data),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Identity of the pixel data shared by the clones of the image, renderers cache the uploaded images by it.
+    pub fn id(&self) -> usize {
+        Arc::as_ptr(&self.data) as usize
+    }
+
+    /// Whether the pixel data is shared with another image. The renderer cache keeps a clone of the image,
+    /// so the identity is not reused, and drops the uploaded image when its clone is no longer shared.
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.data) > 1
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let idx = (y as usize * self.width as usize + x as usize) * 4;
+        let [r, g, b, a] = [0, 1, 2, 3].map(|channel| self.data[idx + channel]);
+        Some(Color::from_rgba8(r, g, b, a))
+    }
+
+    /// Average of the pixels weighted by their alpha, the renderers fill the shape with it when they
+    /// are not able to draw the pattern.
+    pub fn average_color(&self) -> Color {
+        let mut sum = [0.0; 4];
+        for pixel in self.data.chunks_exact(4) {
+            let alpha = Real::from(pixel[3]) / 255.0;
+            for (sum, &channel) in sum.iter_mut().zip(&pixel[..3]) {
+                *sum += Real::from(channel) / 255.0 * alpha;
+            }
+            sum[3] += alpha;
+        }
+        let count = (self.width as usize * self.height as usize) as Real;
+        let [r, g, b, a] = sum;
+        if a > 0.0 {
+            Color::RGBA(r / a, g / a, b / a, a / count)
+        } else {
+            Color::RGBA(0.0, 0.0, 0.0, 0.0)
+        }
+    }
+}
+
+/// How the pattern image is tiled, as the CSS `background-repeat`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum PatternRepeat {
+    #[default]
+    Repeat,
+    RepeatX,
+    RepeatY,
+    /// The single image, the shape is transparent outside of it.
+    NoRepeat,
+}
+
+impl PatternRepeat {
+    pub fn repeat_x(self) -> bool {
+        matches!(self, PatternRepeat::Repeat | PatternRepeat::RepeatX)
+    }
+
+    pub fn repeat_y(self) -> bool {
+        matches!(self, PatternRepeat::Repeat | PatternRepeat::RepeatY)
+    }
+}
+
+/// Image paint used to fill or stroke paths with the tiled image. The image is scaled, rotated around its
+/// top left corner and then moved by the offset. The percentage offset is relative to the bounding box
+/// of the painted shape, so by default the image starts at the top left corner of the shape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    pub image: Image,
+    pub repeat: PatternRepeat,
+    /// Size of the image pixel in the shape coordinates.
+    pub scale: (Real, Real),
+    pub offset: (RealValue, RealValue),
+    /// Rotation of the image in radians, clockwise on the screen.
+    pub rotation: Real,
+    pub opacity: Real,
+}
+
+impl Pattern {
+    pub fn new(image: Image) -> Self {
+        Self {
+            image,
+            repeat: PatternRepeat::default(),
+            scale: (1.0, 1.0),
+            offset: (RealValue::pct(0.0), RealValue::pct(0.0)),
+            rotation: 0.0,
+            opacity: 1.0,
+        }
+    }
+
+    pub fn repeat(mut self, repeat: PatternRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn scale(mut self, scale_x: Real, scale_y: Real) -> Self {
+        self.scale = (scale_x, scale_y);
+        self
+    }
+
+    /// Scale the image to the tile size in the shape coordinates.
+    pub fn tile_size(mut self, width: Real, height: Real) -> Self {
+        self.scale = (width / self.image.width as Real, height / self.image.height as Real);
+        self
+    }
+
+    pub fn offset(mut self, x: impl Into<RealValue>, y: impl Into<RealValue>) -> Self {
+        self.offset = (x.into(), y.into());
+        self
+    }
+
+    pub fn rotation(mut self, rotation: Real) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn opacity(mut self, opacity: Real) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    /// Size of the scaled image in the shape coordinates.
+    pub fn size(&self) -> (Real, Real) {
+        (
+            self.image.width as Real * self.scale.0,
+            self.image.height as Real * self.scale.1,
+        )
+    }
+
+    /// Average color of the image with the pattern opacity.
+    pub fn average_color(&self) -> Color {
+        let color = self.image.average_color();
+        color.with_alpha(color.alpha() * self.opacity)
+    }
+
+    /// Resolve the relative offset by the bounding box `[x, y, width, height]` of the painted shape
+    /// and the unit sizes.
+    pub fn set_by_bounds(&mut self, [x, y, width, height]: [Real; 4], units: &UnitContext) {
+        let (offset_x, offset_y) = &mut self.offset;
+        offset_x.resolve_pos(x, width, units);
+        offset_y.resolve_pos(y, height, units);
+    }
+}
+
+/// The patterns of the same image and repeat are interpolated, the other ones switch in the middle.
+impl Interpolate for Pattern {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        if self.image.id() != other.image.id() || self.repeat != other.repeat {
+            return step(self, other, t);
+        }
+        Self {
+            image: self.image.clone(),
+            repeat: self.repeat,
+            scale: self.scale.interpolate(&other.scale, t),
+            offset: self.offset.interpolate(&other.offset, t),
+            rotation: self.rotation.interpolate(&other.rotation, t),
+            opacity: self.opacity.interpolate(&other.opacity, t),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_pixels() {
+        assert!(Image::from_rgba8(2, 2, vec![0; 15]).is_none());
+        assert!(Image::from_rgba8(0, 0, vec![]).is_none());
+
+        let image = Image::from_rgba8(2, 1, vec![255, 0, 0, 255, 0, 0, 255, 0]).unwrap();
+        assert_eq!(image.pixel(0, 0), Some(Color::from_rgba8(255, 0, 0, 255)));
+        assert_eq!(image.pixel(2, 0), None);
+        assert_eq!(image.average_color().as_arr(), [1.0, 0.0, 0.0, 0.5]);
+        assert_eq!(image.clone().id(), image.id());
+        assert!(!image.is_shared());
+        let shared = image.clone();
+        assert!(image.is_shared());
+        drop(shared);
+        assert!(!image.is_shared());
+
+        let checker = Image::from_fn(2, 2, |x, y| if (x + y) % 2 == 0 { Color::White } else { Color::Black });
+        assert_eq!(checker.pixel(1, 1), Some(Color::from_rgba8(255, 255, 255, 255)));
+        assert_eq!(checker.average_color().as_arr(), [0.5, 0.5, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn pattern_geometry() {
+        let image = Image::from_fn(4, 2, |_, _| Color::Red);
+        let mut pattern = Pattern::new(image).tile_size(8.0, 8.0).offset(RealValue::pct(50.0), 3);
+        assert_eq!(pattern.size(), (8.0, 8.0));
+        assert_eq!(pattern.scale, (2.0, 4.0));
+
+        pattern.set_by_bounds([10.0, 20.0, 40.0, 30.0], &UnitContext::default());
+        assert_eq!((pattern.offset.0.val(), pattern.offset.1.val()), (30.0, 3.0));
+
+        assert!(PatternRepeat::RepeatX.repeat_x() && !PatternRepeat::RepeatX.repeat_y());
+        assert!(!PatternRepeat::NoRepeat.repeat_x() && !PatternRepeat::NoRepeat.repeat_y());
+    }
+}
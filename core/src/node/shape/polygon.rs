@@ -0,0 +1,105 @@
+use super::polyline::{points_bounds, points_outline, set_points_by_bounds};
+use crate::node::{
+    Clip, Effect, Fill, FillRule, Path, PathCommand, Real, RealValue, Stroke, Transform, TransformMatrix, UnitContext,
+};
+
+/// Closed polygon of the points, such as the triangle or the star.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Polygon {
+    pub id: Option<String>,
+    pub points: Vec<(RealValue, RealValue)>,
+    pub transparency: Real,
+    pub stroke: Option<Stroke>,
+    pub fill: Option<Fill>,
+    /// Fill rule of the polygon, it overrides the rule of the fill.
+    pub fill_rule: Option<FillRule>,
+    pub effects: Vec<Effect>,
+    pub clip: Clip,
+    pub transform: Transform,
+}
+
+impl Polygon {
+    pub const NAME: &'static str = "polygon";
+
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn recalculate_transform(&mut self, parent_global: TransformMatrix) -> TransformMatrix {
+        if let Some(transform) = self.clip.transform_mut() {
+            transform.calculate_global(parent_global);
+        }
+        self.transform.calculate_global(parent_global)
+    }
+
+    /// Resolve the relative coordinates by the parent bounding box `[x, y, width, height]` and the unit sizes.
+    pub fn set_by_bounds(&mut self, bounds: [Real; 4], units: &UnitContext) {
+        set_points_by_bounds(&mut self.points, bounds, units);
+    }
+
+    /// Bounding box `[x, y, width, height]` of the points, `None` if there are no points.
+    pub fn bounds(&self) -> Option<[Real; 4]> {
+        points_bounds(&self.points)
+    }
+
+    pub fn outline(&self) -> Vec<PathCommand> {
+        points_outline(&self.points, true)
+    }
+
+    /// Path of the polygon outline with the polygon style, the renderers draw the polygon by it.
+    pub fn to_path(&self) -> Path {
+        Path {
+            id: self.id.clone(),
+            cmd: self.outline(),
+            transparency: self.transparency,
+            stroke: self.stroke.clone(),
+            fill: self.fill.clone(),
+            fill_rule: self.fill_rule,
+            effects: self.effects.clone(),
+            clip: self.clip.clone(),
+            transform: self.transform,
+        }
+    }
+
+    /// Hit test by the point in the global coordinates, it hits the polygon as the path.
+    pub fn intersect(&self, x: Real, y: Real) -> bool {
+        self.to_path().intersect(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    #[test]
+    fn polygon_hit() {
+        // Pentagram, its center is filled by the non-zero rule only
+        let points = [(50.0, 0.0), (79.0, 90.0), (2.0, 35.0), (98.0, 35.0), (21.0, 90.0)];
+        let star = Polygon {
+            points: points.iter().map(|&(x, y)| (x.into(), y.into())).collect(),
+            fill: Some(Color::Red.into()),
+            ..Default::default()
+        };
+        assert_eq!(star.bounds(), Some([2.0, 0.0, 96.0, 90.0]));
+        assert!(star.intersect(50.0, 50.0));
+        assert!(star.intersect(50.0, 20.0));
+        assert!(!star.intersect(5.0, 80.0));
+
+        let star = Polygon {
+            fill_rule: Some(FillRule::EvenOdd),
+            ..star
+        };
+        assert!(!star.intersect(50.0, 50.0));
+        assert!(star.intersect(50.0, 20.0));
+
+        // The closing edge is stroked
+        let triangle = Polygon {
+            points: vec![(0.into(), 0.into()), (10.into(), 0.into()), (0.into(), 10.into())],
+            stroke: Some(Stroke::default().width(2.0)),
+            ..Default::default()
+        };
+        assert!(triangle.intersect(5.0, 5.0));
+        assert!(!triangle.intersect(2.0, 2.0));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::node::{
+    Clip, Effect, Fill, FillRule, Path, PathBuilder, PathCommand, Real, RealValue, Stroke, Transform, TransformMatrix,
+    UnitContext,
+};
+
+/// Open polyline through the points. As the SVG `polyline`, it is filled as if it were closed
+/// by the straight line to the first point, but the closing line is not stroked.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Polyline {
+    pub id: Option<String>,
+    pub points: Vec<(RealValue, RealValue)>,
+    pub transparency: Real,
+    pub stroke: Option<Stroke>,
+    pub fill: Option<Fill>,
+    /// Fill rule of the polyline, it overrides the rule of the fill.
+    pub fill_rule: Option<FillRule>,
+    pub effects: Vec<Effect>,
+    pub clip: Clip,
+    pub transform: Transform,
+}
+
+impl Polyline {
+    pub const NAME: &'static str = "polyline";
+
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn recalculate_transform(&mut self, parent_global: TransformMatrix) -> TransformMatrix {
+        if let Some(transform) = self.clip.transform_mut() {
+            transform.calculate_global(parent_global);
+        }
+        self.transform.calculate_global(parent_global)
+    }
+
+    /// Resolve the relative coordinates by the parent bounding box `[x, y, width, height]` and the unit sizes.
+    pub fn set_by_bounds(&mut self, bounds: [Real; 4], units: &UnitContext) {
+        set_points_by_bounds(&mut self.points, bounds, units);
+    }
+
+    /// Bounding box `[x, y, width, height]` of the points, `None` if there are no points.
+    pub fn bounds(&self) -> Option<[Real; 4]> {
+        points_bounds(&self.points)
+    }
+
+    pub fn outline(&self) -> Vec<PathCommand> {
+        points_outline(&self.points, false)
+    }
+
+    /// Path of the polyline outline with the polyline style, the renderers draw the polyline by it.
+    pub fn to_path(&self) -> Path {
+        Path {
+            id: self.id.clone(),
+            cmd: self.outline(),
+            transparency: self.transparency,
+            stroke: self.stroke.clone(),
+            fill: self.fill.clone(),
+            fill_rule: self.fill_rule,
+            effects: self.effects.clone(),
+            clip: self.clip.clone(),
+            transform: self.transform,
+        }
+    }
+
+    /// Hit test by the point in the global coordinates, it hits the polyline as the path.
+    pub fn intersect(&self, x: Real, y: Real) -> bool {
+        self.to_path().intersect(x, y)
+    }
+}
+
+pub(crate) fn set_points_by_bounds(
+    points: &mut [(RealValue, RealValue)], [x, y, width, height]: [Real; 4], units: &UnitContext,
+) {
+    for (point_x, point_y) in points {
+        point_x.resolve_pos(x, width, units);
+        point_y.resolve_pos(y, height, units);
+    }
+}
+
+pub(crate) fn points_bounds(points: &[(RealValue, RealValue)]) -> Option<[Real; 4]> {
+    let mut points = points.iter().map(|(x, y)| (x.val(), y.val()));
+    let (x, y) = points.next()?;
+    let [min_x, min_y, max_x, max_y] = points.fold([x, y, x, y], |[min_x, min_y, max_x, max_y], (x, y)| {
+        [min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)]
+    });
+    Some([min_x, min_y, max_x - min_x, max_y - min_y])
+}
+
+pub(crate) fn points_outline(points: &[(RealValue, RealValue)], closed: bool) -> Vec<PathCommand> {
+    let mut builder = PathBuilder::new();
+    for (idx, (x, y)) in points.iter().enumerate() {
+        builder = if idx == 0 {
+            builder.move_to(x.val(), y.val())
+        } else {
+            builder.line_to(x.val(), y.val())
+        };
+    }
+    if closed && !points.is_empty() {
+        builder = builder.close();
+    }
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    #[test]
+    fn polyline_geometry() {
+        let mut polyline = Polyline {
+            points: vec![
+                (0.into(), 0.into()),
+                (RealValue::pct(100.0), 0.into()),
+                (RealValue::pct(100.0), RealValue::pct(100.0)),
+            ],
+            stroke: Some(Stroke::default().width(2.0)),
+            ..Default::default()
+        };
+        polyline.set_by_bounds([10.0, 10.0, 20.0, 20.0], &UnitContext::default());
+        assert_eq!(polyline.bounds(), Some([0.0, 0.0, 30.0, 30.0]));
+        assert_eq!(Polyline::default().bounds(), None);
+
+        // The closing line is not stroked, and the polyline is filled by the fill only
+        assert!(polyline.intersect(15.0, 0.5));
+        assert!(!polyline.intersect(15.0, 15.0));
+        assert!(!polyline.intersect(20.0, 10.0));
+
+        polyline.fill = Some(Color::Red.into());
+        assert!(polyline.intersect(20.0, 10.0));
+    }
+}
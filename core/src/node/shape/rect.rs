@@ -1,4 +1,7 @@
-use crate::{Clip, Fill, Padding, Real, RealValue, Rounding, Stroke, Transform, TransformMatrix};
+use crate::{
+    Clip, Effect, Fill, LimitedSize, Padding, PathBuilder, PathCommand, Real, RealValue, Rounding, SizeConstraints,
+    Stroke, Transform, TransformMatrix,
+};
 
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Rect {
@@ -7,11 +10,15 @@ pub struct Rect {
     pub y: RealValue,
     pub width: RealValue,
     pub height: RealValue,
+    pub constraints: SizeConstraints,
+    /// Size limited by the constraints, it is set by the layout.
+    pub limited: LimitedSize,
     pub rounding: Option<Rounding>,
     pub padding: Padding,
     pub transparency: Real,
     pub stroke: Option<Stroke>,
     pub fill: Option<Fill>,
+    pub effects: Vec<Effect>,
     pub clip: Clip,
     pub transform: Transform,
 }
@@ -30,6 +37,47 @@ impl Rect {
         self.transform.calculate_global(parent_global)
     }
 
+    /// Apply the size constraints, the auto size is set by the content size `(width, height)` if it is known.
+    pub fn constrain_size(&mut self, content: Option<(Real, Real)>) {
+        self.limited = self.constraints.apply(&self.width, &self.height, content);
+    }
+
+    /// Width of the rectangle limited by the constraints, or the declared one if it is not limited yet.
+    pub fn layout_width(&self) -> Real {
+        self.limited.width.unwrap_or_else(|| self.width.val())
+    }
+
+    /// Height of the rectangle limited by the constraints, or the declared one if it is not limited yet.
+    pub fn layout_height(&self) -> Real {
+        self.limited.height.unwrap_or_else(|| self.height.val())
+    }
+
+    /// Outline of the rectangle as the path commands, such as for the dashed stroke.
+    pub fn outline(&self) -> Vec<PathCommand> {
+        self.offset_outline(0.0)
+    }
+
+    /// Outline moved outward by the offset, or inward by the negative one. It is the centerline
+    /// of the aligned stroke, see `Stroke::center_offset`.
+    pub fn offset_outline(&self, offset: Real) -> Vec<PathCommand> {
+        let (x, y) = (self.x.val() - offset, self.y.val() - offset);
+        let width = (self.layout_width() + 2.0 * offset).max(0.0);
+        let height = (self.layout_height() + 2.0 * offset).max(0.0);
+        match self.rounding {
+            Some(rounding) => {
+                let radii = [
+                    rounding.top_left.val(),
+                    rounding.top_right.val(),
+                    rounding.bottom_right.val(),
+                    rounding.bottom_left.val(),
+                ]
+                .map(|radius| if radius > 0.0 { (radius + offset).max(0.0) } else { 0.0 });
+                PathBuilder::new().rounded_rect(x, y, width, height, radii).build()
+            },
+            None => PathBuilder::new().rect(x, y, width, height).build(),
+        }
+    }
+
     #[inline]
     pub fn intersect(&self, x: Real, y: Real) -> bool {
         let matrix = self
@@ -41,6 +89,12 @@ impl Rect {
         } else {
             (x, y)
         };
-        x >= self.x.val() && x <= self.width.val() && y >= self.y.val() && y <= self.height.val()
+        let extent = self.stroke.as_ref().map_or(0.0, Stroke::outer_extent);
+        let (left, top) = (self.x.val() - extent, self.y.val() - extent);
+        let (right, bottom) = (
+            self.x.val() + self.layout_width() + extent,
+            self.y.val() + self.layout_height() + extent,
+        );
+        x >= left && x <= right && y >= top && y <= bottom
     }
 }
@@ -1,4 +1,4 @@
-use crate::{Color, ConvertTo, Gradient, Paint, Real};
+use crate::{step, Color, ConvertTo, Gradient, Interpolate, Paint, Pattern, Real};
 
 /// Controls how the end of line is drawn.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -16,13 +16,102 @@ pub enum LineJoin {
     Bevel,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Position of the stroke relative to the outline. The inside and outside strokes of the open paths
+/// are centered, since such paths have no sides.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StrokeAlignment {
+    #[default]
+    Center,
+    Inside,
+    Outside,
+}
+
+/// Lengths of the alternating dashes and gaps of the stroke, as in SVG `stroke-dasharray`.
+/// An odd number of lengths is repeated to yield an even one.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct DashArray {
+    lengths: [Real; DashArray::MAX_LEN],
+    len: usize,
+}
+
+impl DashArray {
+    /// The maximum number of the lengths, the rest ones are ignored.
+    pub const MAX_LEN: usize = 8;
+
+    pub fn new(lengths: &[Real]) -> Self {
+        let len = lengths.len().min(Self::MAX_LEN);
+        let mut dash = Self {
+            lengths: [0.0; Self::MAX_LEN],
+            len,
+        };
+        dash.lengths[..len].copy_from_slice(&lengths[..len]);
+        dash
+    }
+
+    pub fn lengths(&self) -> &[Real] {
+        &self.lengths[..self.len]
+    }
+
+    /// The stroke is solid if there are no lengths, or they are negative or all zero.
+    pub fn is_solid(&self) -> bool {
+        let lengths = self.lengths();
+        lengths.iter().any(|length| *length < 0.0) || lengths.iter().all(|length| *length == 0.0)
+    }
+
+    /// The lengths with the even number of the values.
+    pub fn pattern(&self) -> Vec<Real> {
+        let lengths = self.lengths();
+        if lengths.len() % 2 == 1 {
+            lengths.iter().chain(lengths).copied().collect()
+        } else {
+            lengths.to_vec()
+        }
+    }
+}
+
+/// The patterns of the same length are interpolated, the other ones switch in the middle.
+impl Interpolate for DashArray {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        if self.len != other.len {
+            return step(self, other, t);
+        }
+        let mut dash = *self;
+        for (length, other) in dash.lengths.iter_mut().zip(other.lengths.iter()) {
+            *length = length.interpolate(other, t);
+        }
+        dash
+    }
+}
+
+impl From<&[Real]> for DashArray {
+    fn from(lengths: &[Real]) -> Self {
+        DashArray::new(lengths)
+    }
+}
+
+impl<const N: usize> From<[Real; N]> for DashArray {
+    fn from(lengths: [Real; N]) -> Self {
+        DashArray::new(&lengths)
+    }
+}
+
+impl From<Vec<Real>> for DashArray {
+    fn from(lengths: Vec<Real>) -> Self {
+        DashArray::new(&lengths)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Stroke {
     pub paint: Paint,
     pub width: Real,
     pub line_cap: LineCap,
     pub line_join: LineJoin,
     pub miter_limit: Real,
+    pub dash: DashArray,
+    /// Distance into the dash pattern to start the stroke at.
+    pub dash_offset: Real,
+    pub alignment: StrokeAlignment,
 }
 
 impl Stroke {
@@ -34,10 +123,70 @@ impl Stroke {
         Stroke::from(gradient.into())
     }
 
+    pub fn pattern<T: Into<Pattern>>(pattern: T) -> Self {
+        Stroke::from(pattern.into())
+    }
+
     pub fn width(mut self, width: Real) -> Self {
         self.width = width;
         self
     }
+
+    pub fn line_cap(mut self, line_cap: LineCap) -> Self {
+        self.line_cap = line_cap;
+        self
+    }
+
+    pub fn line_join(mut self, line_join: LineJoin) -> Self {
+        self.line_join = line_join;
+        self
+    }
+
+    pub fn dash(mut self, dash: impl Into<DashArray>) -> Self {
+        self.dash = dash.into();
+        self
+    }
+
+    pub fn dash_offset(mut self, offset: Real) -> Self {
+        self.dash_offset = offset;
+        self
+    }
+
+    /// Dashes with the length and gaps of twice the stroke width.
+    pub fn dashed(self) -> Self {
+        let width = self.width;
+        self.dash([width * 2.0, width * 2.0])
+    }
+
+    /// Round dots spaced by the stroke width.
+    pub fn dotted(self) -> Self {
+        let width = self.width;
+        self.dash([0.0, width * 2.0]).line_cap(LineCap::Round)
+    }
+
+    pub fn alignment(mut self, alignment: StrokeAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn is_dashed(&self) -> bool {
+        !self.dash.is_solid()
+    }
+
+    /// Distance by which the center line of the stroke is moved out of the outline, the negative distance
+    /// moves it inside.
+    pub fn center_offset(&self) -> Real {
+        match self.alignment {
+            StrokeAlignment::Center => 0.0,
+            StrokeAlignment::Inside => -self.width / 2.0,
+            StrokeAlignment::Outside => self.width / 2.0,
+        }
+    }
+
+    /// How far the stroke extends out of the outline.
+    pub fn outer_extent(&self) -> Real {
+        (self.center_offset() + self.width / 2.0).max(0.0)
+    }
 }
 
 impl Default for Stroke {
@@ -48,6 +197,25 @@ impl Default for Stroke {
             line_cap: LineCap::Butt,
             line_join: LineJoin::Miter,
             miter_limit: 10.0,
+            dash: DashArray::default(),
+            dash_offset: 0.0,
+            alignment: StrokeAlignment::default(),
+        }
+    }
+}
+
+/// The caps, joins and alignment switch in the middle, the rest is interpolated.
+impl Interpolate for Stroke {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        Self {
+            paint: self.paint.interpolate(&other.paint, t),
+            width: self.width.interpolate(&other.width, t),
+            line_cap: step(&self.line_cap, &other.line_cap, t),
+            line_join: step(&self.line_join, &other.line_join, t),
+            miter_limit: self.miter_limit.interpolate(&other.miter_limit, t),
+            dash: self.dash.interpolate(&other.dash, t),
+            dash_offset: self.dash_offset.interpolate(&other.dash_offset, t),
+            alignment: step(&self.alignment, &other.alignment, t),
         }
     }
 }
@@ -120,6 +288,25 @@ impl From<(Gradient, Real)> for Stroke {
     }
 }
 
+impl From<Pattern> for Stroke {
+    fn from(pattern: Pattern) -> Self {
+        Stroke {
+            paint: pattern.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<(Pattern, Real)> for Stroke {
+    fn from((pattern, width): (Pattern, Real)) -> Self {
+        Stroke {
+            paint: pattern.into(),
+            width,
+            ..Default::default()
+        }
+    }
+}
+
 impl ConvertTo<Option<Stroke>> for Color {
     fn convert(self) -> Option<Stroke> {
         Some(self.into())
@@ -161,3 +348,15 @@ impl ConvertTo<Option<Stroke>> for (Gradient, Real) {
         Some(self.into())
     }
 }
+
+impl ConvertTo<Option<Stroke>> for Pattern {
+    fn convert(self) -> Option<Stroke> {
+        Some(self.into())
+    }
+}
+
+impl ConvertTo<Option<Stroke>> for (Pattern, Real) {
+    fn convert(self) -> Option<Stroke> {
+        Some(self.into())
+    }
+}
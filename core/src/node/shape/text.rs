@@ -1,10 +1,23 @@
-use crate::node::{Clip, ConvertTo, Fill, Real, RealValue, Stroke, Transform, TransformMatrix};
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+pub use self::{layout::*, measure::*, path::*, span::*};
+use crate::node::{Clip, ConvertTo, Effect, Fill, Real, RealValue, Stroke, Transform, TransformMatrix};
+
+mod bidi;
+pub mod layout;
+pub mod measure;
+pub mod path;
+pub mod span;
 
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 pub struct GlyphPos {
     pub x: Real,
     pub y: Real,
     pub width: Real,
+    /// The char belongs to a right-to-left run, so its caret is at the right edge.
+    pub rtl: bool,
 }
 
 impl GlyphPos {
@@ -17,24 +30,89 @@ impl GlyphPos {
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 pub struct TextMetrics {
     pub ascender: f32,
+    /// Distance from the baseline to the bottom of the glyphs, it is negative.
     pub descender: f32,
     pub line_height: f32,
 }
 
+/// Lines drawn over the text, they are combined for the spans of the text.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextDecoration {
+    pub underline: bool,
+    pub strikethrough: bool,
+    pub overline: bool,
+}
+
+impl TextDecoration {
+    pub const UNDERLINE: TextDecoration = TextDecoration {
+        underline: true,
+        strikethrough: false,
+        overline: false,
+    };
+    pub const STRIKETHROUGH: TextDecoration = TextDecoration {
+        underline: false,
+        strikethrough: true,
+        overline: false,
+    };
+    pub const OVERLINE: TextDecoration = TextDecoration {
+        underline: false,
+        strikethrough: false,
+        overline: true,
+    };
+
+    pub fn is_none(&self) -> bool {
+        !(self.underline || self.strikethrough || self.overline)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            underline: self.underline || other.underline,
+            strikethrough: self.strikethrough || other.strikethrough,
+            overline: self.overline || other.overline,
+        }
+    }
+
+    /// Offsets of the decoration lines from the baseline (positive downwards) and their thickness,
+    /// approximated from the font size.
+    pub fn lines(&self, font_size: Real) -> Vec<(Real, Real)> {
+        let thickness = (font_size / 16.0).max(1.0);
+        [
+            (self.underline, 0.1),
+            (self.strikethrough, -0.3),
+            (self.overline, -0.9),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, offset)| (font_size * offset, thickness))
+        .collect()
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct Text {
     pub id: Option<String>,
     pub content: String,
+    /// Styled parts of the content, the content is the concatenation of the spans.
+    pub spans: Vec<TextSpan>,
     pub glyph_positions: Vec<GlyphPos>,
+    pub lines: Vec<TextLine>,
     pub metrics: Option<TextMetrics>,
     pub x: RealValue,
     pub y: RealValue,
+    /// Face or family name of the font, the default family of the font registry is used if it is empty.
     pub font_name: String,
     pub font_size: RealValue,
+    /// Extra space added after each char.
+    pub letter_spacing: Real,
     pub align: (AlignHor, AlignVer),
+    pub decoration: TextDecoration,
+    pub layout: TextLayout,
+    /// Path that the lines follow instead of going straight, see `TextPath`.
+    pub path: Option<TextPath>,
     pub transparency: Real,
     pub stroke: Option<Stroke>,
     pub fill: Option<Fill>,
+    pub effects: Vec<Effect>,
     pub clip: Clip,
     pub transform: Transform,
 }
@@ -53,34 +131,117 @@ impl Text {
         self.transform.calculate_global(parent_global)
     }
 
-    #[inline]
-    pub fn intersect(&self, _x: Real, _y: Real) -> bool {
-        // TODO: calvulate intersect
-        false
+    /// Hit test by the point in the global coordinates. The point hits the boxes of the laid out lines,
+    /// or the boxes of the clusters placed along the text path.
+    pub fn intersect(&self, x: Real, y: Real) -> bool {
+        let matrix = self
+            .transform
+            .global_matrix()
+            .unwrap_or_else(|| self.transform.matrix());
+        let (x, y) = if !matrix.is_identity() {
+            matrix.inverse() * (x, y)
+        } else {
+            (x, y)
+        };
+        if self.path.is_some() {
+            return self.path_contains(x, y);
+        }
+        let (x, y) = (x - self.x.val(), y - self.y.val());
+        self.lines
+            .iter()
+            .any(|line| x >= line.x && x <= line.max_x() && y >= line.top() && y <= line.bottom())
     }
 
-    pub fn insert(&mut self, idx: usize, ch: char) {
-        let mut content: String = self.content.chars().take(idx).collect();
-        let tail = &self.content[content.len()..];
-        content.push(ch);
-        content.push_str(tail);
-        self.content = content;
+    /// Insert the char at the caret position and return the caret position after it. The position
+    /// inside a grapheme cluster is moved to the end of the cluster.
+    pub fn insert(&mut self, idx: usize, ch: char) -> usize {
+        let boundaries = self.grapheme_boundaries();
+        let idx = boundaries.iter().copied().find(|&boundary| boundary >= idx).unwrap_or(idx);
+        insert_char(&mut self.content, idx, ch);
+
+        // The char at the boundary of the spans continues the preceding span
+        let span_ranges = self.span_ranges();
+        if let Some(span) = span_ranges.iter().position(|range| idx <= range.end) {
+            insert_char(&mut self.spans[span].content, idx - span_ranges[span].start, ch);
+        }
+        idx + 1
     }
 
     pub fn push(&mut self, ch: char) {
         self.content.push(ch);
+        if let Some(span) = self.spans.last_mut() {
+            span.content.push(ch);
+        }
+    }
+
+    /// Remove the grapheme cluster containing the char and return the char range of the cluster.
+    pub fn remove(&mut self, idx: usize) -> Range<usize> {
+        let boundaries = self.grapheme_boundaries();
+        let end = boundaries.iter().copied().find(|&end| end > idx).unwrap_or(idx);
+        let start = boundaries.iter().copied().rev().find(|&start| start <= idx).unwrap_or(0);
+        let range = start..end;
+        remove_chars(&mut self.content, range.clone());
+
+        let span_ranges = self.span_ranges();
+        for (span, span_range) in span_ranges.iter().enumerate().rev() {
+            let removed = span_range.start.max(range.start)..span_range.end.min(range.end);
+            if !removed.is_empty() {
+                let offset = span_range.start;
+                remove_chars(&mut self.spans[span].content, removed.start - offset..removed.end - offset);
+            }
+        }
+        range
+    }
+
+    /// The laid out text has the right-to-left chars.
+    pub fn has_rtl(&self) -> bool {
+        self.glyph_positions.iter().any(|pos| pos.rtl)
+    }
+
+    /// Char indices of the grapheme cluster boundaries including the start and the end of the content.
+    pub fn grapheme_boundaries(&self) -> Vec<usize> {
+        let mut idx = 0;
+        let mut boundaries = vec![0];
+        for grapheme in self.content.graphemes(true) {
+            idx += grapheme.chars().count();
+            boundaries.push(idx);
+        }
+        boundaries
+    }
+
+    /// Caret position of the previous grapheme cluster.
+    pub fn prev_caret(&self, idx: usize) -> usize {
+        self.grapheme_boundaries()
+            .into_iter()
+            .rev()
+            .find(|&boundary| boundary < idx)
+            .unwrap_or(0)
     }
 
-    pub fn remove(&mut self, idx: usize) {
-        self.content = self
-            .content
-            .chars()
-            .enumerate()
-            .filter_map(|(i, ch)| if i != idx { Some(ch) } else { None })
-            .collect();
+    /// Caret position of the next grapheme cluster, or the end of the content.
+    pub fn next_caret(&self, idx: usize) -> usize {
+        let boundaries = self.grapheme_boundaries();
+        let end = boundaries[boundaries.len() - 1];
+        boundaries.into_iter().find(|&boundary| boundary > idx).unwrap_or(end)
     }
 }
 
+fn insert_char(content: &mut String, idx: usize, ch: char) {
+    let mut result: String = content.chars().take(idx).collect();
+    let tail = &content[result.len()..];
+    result.push(ch);
+    result.push_str(tail);
+    *content = result;
+}
+
+fn remove_chars(content: &mut String, range: Range<usize>) {
+    *content = content
+        .chars()
+        .enumerate()
+        .filter_map(|(i, ch)| if !range.contains(&i) { Some(ch) } else { None })
+        .collect();
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AlignHor {
     Left,
@@ -131,3 +292,31 @@ impl ConvertTo<(AlignHor, AlignVer)> for AlignVer {
         self.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_graphemes() {
+        let mut text = Text {
+            content: "e\u{301}x".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(text.grapheme_boundaries(), vec![0, 2, 3]);
+        assert_eq!(text.next_caret(0), 2);
+        assert_eq!(text.prev_caret(3), 2);
+        assert_eq!(text.prev_caret(2), 0);
+        assert_eq!(text.next_caret(3), 3);
+
+        assert_eq!(text.insert(1, 'y'), 3);
+        assert_eq!(text.content, "e\u{301}yx");
+        assert_eq!(text.insert(3, '\u{308}'), 4);
+        assert_eq!(text.grapheme_boundaries(), vec![0, 2, 4, 5]);
+
+        assert_eq!(text.remove(3), 2..4);
+        assert_eq!(text.content, "e\u{301}x");
+        assert_eq!(text.remove(0), 0..2);
+        assert_eq!(text.content, "x");
+    }
+}
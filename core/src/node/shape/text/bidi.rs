@@ -0,0 +1,71 @@
+use std::{borrow::Cow, ops::Range};
+
+use unicode_bidi::BidiInfo;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Bidi embedding levels of the text content resolved by the Unicode bidi algorithm.
+pub(crate) struct Bidi<'a> {
+    info: BidiInfo<'a>,
+    /// Byte offsets of the chars followed by the content length.
+    offsets: Vec<usize>,
+}
+
+impl<'a> Bidi<'a> {
+    pub(crate) fn new(content: &'a str) -> Self {
+        let offsets = content
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(Some(content.len()))
+            .collect();
+        Self {
+            info: BidiInfo::new(content, None),
+            offsets,
+        }
+    }
+
+    /// Char indices of the line in the visual order, each with the flag of the right-to-left char.
+    pub(crate) fn visual_order(&self, range: Range<usize>) -> Vec<(usize, bool)> {
+        if range.is_empty() || !self.info.has_rtl() {
+            return range.map(|idx| (idx, false)).collect();
+        }
+        let bytes = self.offsets[range.start]..self.offsets[range.end];
+        let para = self
+            .info
+            .paragraphs
+            .iter()
+            .find(|para| bytes.start < para.range.end)
+            .unwrap_or(&self.info.paragraphs[self.info.paragraphs.len() - 1]);
+        let levels = self.info.reordered_levels_per_char(para, bytes);
+        let levels = &levels[range.clone()];
+        BidiInfo::reorder_visual(levels)
+            .into_iter()
+            .map(|offset| (range.start + offset, levels[offset].is_rtl()))
+            .collect()
+    }
+}
+
+/// Byte ranges of the content split into the runs of the grapheme clusters with the same direction,
+/// each with the flag of the right-to-left run.
+pub(crate) fn direction_runs(content: &str) -> Vec<(Range<usize>, bool)> {
+    let info = BidiInfo::new(content, None);
+    let mut runs: Vec<(Range<usize>, bool)> = Vec::new();
+    for (start, grapheme) in content.grapheme_indices(true) {
+        let rtl = info.levels[start].is_rtl();
+        let end = start + grapheme.len();
+        match runs.last_mut() {
+            Some((range, run_rtl)) if *run_rtl == rtl => range.end = end,
+            _ => runs.push((start..end, rtl)),
+        }
+    }
+    runs
+}
+
+/// Content of the run in the visual order, the grapheme clusters of the right-to-left run are reversed
+/// and the chars of each cluster keep their logical order.
+pub(crate) fn visual_content(content: &str, rtl: bool) -> Cow<'_, str> {
+    if rtl {
+        Cow::Owned(content.graphemes(true).rev().collect())
+    } else {
+        Cow::Borrowed(content)
+    }
+}
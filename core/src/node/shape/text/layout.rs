@@ -0,0 +1,619 @@
+use std::{borrow::Cow, ops::Range};
+
+use super::bidi::Bidi;
+use crate::{AlignHor, AlignVer, Clip, GlyphPos, Real, RealValue, Scissor, Text, TextMetrics};
+
+/// Content drawn in place of the chars elided by the overflow.
+pub const ELLIPSIS: &str = "\u{2026}";
+
+/// Controls where the lines of the text may be broken when they exceed the maximum width.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextWrap {
+    /// Lines are broken only by the explicit line breaks.
+    None,
+    /// Lines are broken between words, a word wider than the maximum width is broken by chars.
+    #[default]
+    Word,
+    /// Lines are broken between any chars.
+    Char,
+}
+
+/// Position of the ellipsis in the truncated line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EllipsisAt {
+    Start,
+    Middle,
+    #[default]
+    End,
+}
+
+/// Controls how the lines wider than the maximum width are displayed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextOverflow {
+    #[default]
+    Visible,
+    /// Lines are clipped by the maximum width.
+    Clip,
+    /// Lines are truncated with the ellipsis to fit the maximum width. The last visible line
+    /// always ends with the ellipsis if lines are hidden by `max_lines`.
+    Ellipsis(EllipsisAt),
+}
+
+/// Options of the multi-line text layout.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextLayout {
+    pub max_width: Option<RealValue>,
+    pub wrap: TextWrap,
+    /// Multiplier of the font line height.
+    pub line_height: Real,
+    pub max_lines: Option<usize>,
+    pub overflow: TextOverflow,
+}
+
+impl Default for TextLayout {
+    fn default() -> Self {
+        Self {
+            max_width: None,
+            wrap: TextWrap::default(),
+            line_height: 1.0,
+            max_lines: None,
+            overflow: TextOverflow::default(),
+        }
+    }
+}
+
+/// Laid out line of the text, offsets are relative to the text position.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextLine {
+    /// Char indices of the line in the text content, without the trailing line break.
+    pub range: Range<usize>,
+    pub x: Real,
+    /// Position to draw the line with the vertical alignment of the text.
+    pub y: Real,
+    pub baseline: Real,
+    /// Width of the line without the trailing whitespaces.
+    pub width: Real,
+    pub height: Real,
+    pub ascender: Real,
+    pub descender: Real,
+    /// Char indices replaced by the ellipsis, they may continue past the line to the hidden chars.
+    pub elided: Option<Range<usize>>,
+}
+
+impl TextLine {
+    #[inline]
+    pub fn max_x(&self) -> Real {
+        self.x + self.width
+    }
+
+    /// Top of the line box including the half-leading.
+    #[inline]
+    pub fn top(&self) -> Real {
+        self.baseline - (self.height + self.ascender + self.descender) / 2.0
+    }
+
+    #[inline]
+    pub fn bottom(&self) -> Real {
+        self.top() + self.height
+    }
+}
+
+impl Text {
+    /// Break the content into lines using the advances of the content chars and fill `lines`
+    /// and `glyph_positions`. Metrics are set for each span, or one for the text without spans.
+    pub fn break_lines(&mut self, advances: &[Real], metrics: &[TextMetrics]) {
+        let chars: Vec<char> = self.content.chars().collect();
+        let letter_spacing = self.letter_spacing;
+        let advance = |idx: usize| {
+            if chars[idx] == '\n' {
+                0.0
+            } else {
+                advances.get(idx).copied().unwrap_or_default() + letter_spacing
+            }
+        };
+        let max_width = match self.layout.wrap {
+            TextWrap::None => None,
+            _ => self.layout.max_width.map(|width| width.val()),
+        };
+
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        let mut width = 0.0;
+        let mut word_start = None;
+        for (idx, &ch) in chars.iter().enumerate() {
+            if ch == '\n' {
+                ranges.push(start..idx);
+                start = idx + 1;
+                width = 0.0;
+                word_start = None;
+                continue;
+            }
+            if let Some(max_width) = max_width {
+                // Trailing whitespaces are allowed to overflow the line
+                if !ch.is_whitespace() && idx > start && width + advance(idx) > max_width {
+                    let end = match (self.layout.wrap, word_start) {
+                        (TextWrap::Word, Some(word_start)) if word_start > start => word_start,
+                        _ => idx,
+                    };
+                    ranges.push(start..end);
+                    start = end;
+                    width = (end..idx).map(advance).sum();
+                    word_start = None;
+                }
+                if ch.is_whitespace() {
+                    word_start = None;
+                } else if word_start.is_none() {
+                    word_start = Some(idx);
+                }
+            }
+            width += advance(idx);
+        }
+        ranges.push(start..chars.len());
+
+        let visible = self.layout.max_lines.unwrap_or(usize::MAX).max(1).min(ranges.len());
+        let span_ranges = self.span_ranges();
+        let mut top = 0.0;
+        let mut lines: Vec<TextLine> = ranges[..visible]
+            .iter()
+            .map(|range| {
+                let metrics = line_metrics(&span_ranges, metrics, range);
+                let height = metrics.line_height * self.layout.line_height;
+                let baseline = top + metrics.ascender + (height - (metrics.ascender - metrics.descender)) / 2.0;
+                let y = baseline
+                    - match self.align.1 {
+                        AlignVer::Top => metrics.ascender,
+                        AlignVer::Baseline => 0.0,
+                        AlignVer::Middle => (metrics.ascender + metrics.descender) / 2.0,
+                        AlignVer::Bottom => metrics.descender,
+                    };
+                top += height;
+                TextLine {
+                    range: range.clone(),
+                    x: 0.0,
+                    y,
+                    baseline,
+                    width: 0.0,
+                    height,
+                    ascender: metrics.ascender,
+                    descender: metrics.descender,
+                    elided: None,
+                }
+            })
+            .collect();
+
+        // Shift the lines so that the text position is the anchor of the whole block
+        let (first_y, last_y) = (lines[0].y, lines[visible - 1].y);
+        let shift_y = match self.align.1 {
+            AlignVer::Top | AlignVer::Baseline => first_y,
+            AlignVer::Middle => (first_y + last_y) / 2.0,
+            AlignVer::Bottom => last_y,
+        };
+
+        let bidi = Bidi::new(&self.content);
+        let mut positions = vec![GlyphPos::default(); chars.len()];
+        let mut placed = 0;
+        for line in &mut lines {
+            line.y -= shift_y;
+            line.baseline -= shift_y;
+
+            let range = line.range.clone();
+            let trailing_whitespaces = chars[range.clone()].iter().rev().take_while(|ch| ch.is_whitespace());
+            let trimmed_end = range.end - trailing_whitespaces.count();
+            line.width = (range.start..trimmed_end).map(advance).sum();
+            line.x = match self.align.0 {
+                AlignHor::Left => 0.0,
+                AlignHor::Center => -line.width / 2.0,
+                AlignHor::Right => -line.width,
+            };
+
+            let mut glyph_x = line.x;
+            for (idx, rtl) in line_order(&bidi, &chars, range.clone()) {
+                let width = advance(idx);
+                positions[idx] = GlyphPos {
+                    x: glyph_x,
+                    y: line.y,
+                    width,
+                    rtl,
+                };
+                glyph_x += width;
+            }
+            placed = range.end;
+            if chars.get(range.end) == Some(&'\n') {
+                positions[range.end] = GlyphPos {
+                    x: glyph_x,
+                    y: line.y,
+                    width: 0.0,
+                    rtl: false,
+                };
+                placed += 1;
+            }
+        }
+
+        // Chars of the hidden lines collapse to the end of the last visible line
+        let last = &lines[visible - 1];
+        for pos in &mut positions[placed..] {
+            *pos = GlyphPos {
+                x: last.max_x(),
+                y: last.y,
+                width: 0.0,
+                rtl: false,
+            };
+        }
+        self.glyph_positions = positions;
+        self.lines = lines;
+    }
+
+    /// Truncate the lines with the ellipsis for the `TextOverflow::Ellipsis` mode, it is applied
+    /// after `break_lines` with the advance of the ellipsis.
+    pub fn truncate_lines(&mut self, ellipsis_width: Real) {
+        let at = match self.layout.overflow {
+            TextOverflow::Ellipsis(at) => at,
+            _ => return,
+        };
+        let max_width = self.layout.max_width.map(|width| width.val());
+        let chars: Vec<char> = self.content.chars().collect();
+        let widths: Vec<Real> = self.glyph_positions.iter().map(|pos| pos.width).collect();
+        let bidi = Bidi::new(&self.content);
+        let last_idx = self.lines.len().saturating_sub(1);
+
+        for (line_idx, line) in self.lines.iter_mut().enumerate() {
+            let hidden = line_idx == last_idx && line.range.end < chars.len();
+            let overflows = max_width.is_some_and(|max_width| line.width > max_width);
+            if !hidden && !overflows {
+                continue;
+            }
+
+            let range = line.range.clone();
+            let trailing_whitespaces = chars[range.clone()].iter().rev().take_while(|ch| ch.is_whitespace());
+            let trimmed_end = range.end - trailing_whitespaces.count();
+            let budget = max_width.unwrap_or(Real::INFINITY) - ellipsis_width;
+            let fit_forward = |from: usize, to: usize, budget: Real| {
+                let (mut idx, mut width) = (from, 0.0);
+                while idx < to && width + widths[idx] <= budget {
+                    width += widths[idx];
+                    idx += 1;
+                }
+                (idx, width)
+            };
+            let fit_backward = |from: usize, to: usize, budget: Real| {
+                let (mut idx, mut width) = (to, 0.0);
+                while idx > from && width + widths[idx - 1] <= budget {
+                    width += widths[idx - 1];
+                    idx -= 1;
+                }
+                idx
+            };
+            // The ellipsis of the last visible line also replaces the hidden chars
+            let elided = match (hidden, at) {
+                (true, _) => fit_forward(range.start, trimmed_end, budget).0..chars.len(),
+                (false, EllipsisAt::End) => fit_forward(range.start, trimmed_end, budget).0..range.end,
+                (false, EllipsisAt::Start) => range.start..fit_backward(range.start, trimmed_end, budget),
+                (false, EllipsisAt::Middle) => {
+                    let (head_end, head_width) = fit_forward(range.start, trimmed_end, budget / 2.0);
+                    head_end..fit_backward(head_end, trimmed_end, budget - head_width)
+                }
+            };
+
+            line.width = (range.start..trimmed_end)
+                .filter(|idx| !elided.contains(idx))
+                .map(|idx| widths[idx])
+                .sum::<Real>()
+                + ellipsis_width;
+            line.x = match self.align.0 {
+                AlignHor::Left => 0.0,
+                AlignHor::Center => -line.width / 2.0,
+                AlignHor::Right => -line.width,
+            };
+
+            // Elided chars past the line follow it, such as the chars of the hidden lines
+            let order = line_order(&bidi, &chars, range.clone())
+                .into_iter()
+                .map(|(idx, _)| idx)
+                .chain(range.end..range.end.max(elided.end));
+            let mut glyph_x = line.x;
+            for idx in order {
+                let pos = &mut self.glyph_positions[idx];
+                if idx == elided.start {
+                    pos.x = glyph_x;
+                    pos.width = ellipsis_width;
+                    glyph_x += ellipsis_width;
+                } else if elided.contains(&idx) {
+                    pos.x = glyph_x;
+                    pos.width = 0.0;
+                } else {
+                    pos.x = glyph_x;
+                    glyph_x += pos.width;
+                }
+            }
+            if !hidden && chars.get(range.end) == Some(&'\n') {
+                self.glyph_positions[range.end].x = glyph_x;
+            }
+            line.elided = Some(elided);
+        }
+    }
+
+    /// Content of the laid out line.
+    pub fn line_content(&self, line: &TextLine) -> &str {
+        self.chars_content(line.range.clone())
+    }
+
+    /// Displayed content of the laid out line with the elided chars replaced by the ellipsis.
+    pub fn line_text(&self, line: &TextLine) -> Cow<'_, str> {
+        match &line.elided {
+            Some(elided) => {
+                let mut text = self.chars_content(line.range.start..elided.start).to_string();
+                text.push_str(ELLIPSIS);
+                text.push_str(self.chars_content(elided.end.min(line.range.end)..line.range.end));
+                Cow::Owned(text)
+            }
+            None => Cow::Borrowed(self.line_content(line)),
+        }
+    }
+
+    /// Clip of the laid out text by the maximum width for the `TextOverflow::Clip` mode,
+    /// the text along the path is not clipped.
+    pub fn overflow_clip(&self) -> Clip {
+        if self.path.is_some() {
+            return Clip::None;
+        }
+        match (self.layout.overflow, self.layout.max_width, self.lines.first(), self.lines.last()) {
+            (TextOverflow::Clip, Some(max_width), Some(first), Some(last)) => {
+                let max_width = max_width.val();
+                let x = match self.align.0 {
+                    AlignHor::Left => 0.0,
+                    AlignHor::Center => -max_width / 2.0,
+                    AlignHor::Right => -max_width,
+                };
+                Clip::Scissor(Scissor {
+                    x: RealValue::px(self.x.val() + x),
+                    y: RealValue::px(self.y.val() + first.top()),
+                    width: RealValue::px(max_width),
+                    height: RealValue::px(last.bottom() - first.top()),
+                    transform: self.transform,
+                })
+            }
+            _ => Clip::None,
+        }
+    }
+
+    /// Bounds `[x, y, width, height]` of the laid out line boxes, the text paint gradients are relative to them.
+    pub fn line_bounds(&self) -> Option<[Real; 4]> {
+        let (first, last) = (self.lines.first()?, self.lines.last()?);
+        let min_x = self.lines.iter().map(|line| line.x).fold(Real::INFINITY, Real::min);
+        let max_x = self.lines.iter().map(TextLine::max_x).fold(Real::NEG_INFINITY, Real::max);
+        Some([
+            self.x.val() + min_x,
+            self.y.val() + first.top(),
+            max_x - min_x,
+            last.bottom() - first.top(),
+        ])
+    }
+}
+
+/// Char indices of the line in the visual order with the flag of the right-to-left chars,
+/// the trailing whitespaces are placed after the rest of the line.
+fn line_order(bidi: &Bidi, chars: &[char], range: Range<usize>) -> Vec<(usize, bool)> {
+    let trailing_whitespaces = chars[range.clone()].iter().rev().take_while(|ch| ch.is_whitespace());
+    let trimmed_end = range.end - trailing_whitespaces.count();
+    let mut order = bidi.visual_order(range.start..trimmed_end);
+    order.extend((trimmed_end..range.end).map(|idx| (idx, false)));
+    order
+}
+
+/// Maximal metrics of the spans in the line, or the metrics of the span at the line start for an empty line.
+fn line_metrics(span_ranges: &[Range<usize>], metrics: &[TextMetrics], range: &Range<usize>) -> TextMetrics {
+    let metrics_of = |span: usize| metrics.get(span).or_else(|| metrics.last()).copied().unwrap_or_default();
+    if span_ranges.is_empty() {
+        return metrics_of(0);
+    }
+    let spans: Vec<usize> = if range.is_empty() {
+        let span = span_ranges
+            .iter()
+            .position(|span| range.start < span.end)
+            .unwrap_or(span_ranges.len() - 1);
+        vec![span]
+    } else {
+        (0..span_ranges.len())
+            .filter(|&span| span_ranges[span].start < range.end && range.start < span_ranges[span].end)
+            .collect()
+    };
+    spans
+        .into_iter()
+        .map(metrics_of)
+        .fold(None, |max: Option<TextMetrics>, metrics| {
+            Some(match max {
+                Some(max) => TextMetrics {
+                    ascender: max.ascender.max(metrics.ascender),
+                    descender: max.descender.min(metrics.descender),
+                    line_height: max.line_height.max(metrics.line_height),
+                },
+                None => metrics,
+            })
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(content: &str, layout: TextLayout) -> Text {
+        Text {
+            content: content.to_string(),
+            layout,
+            ..Default::default()
+        }
+    }
+
+    const METRICS: TextMetrics = TextMetrics {
+        ascender: 8.0,
+        descender: -2.0,
+        line_height: 10.0,
+    };
+
+    fn line_contents(text: &Text) -> Vec<&str> {
+        text.lines.iter().map(|line| text.line_content(line)).collect()
+    }
+
+    #[test]
+    fn break_by_newlines() {
+        let mut text = text("ab\ncd\n", TextLayout::default());
+        text.break_lines(&[1.0; 6], &[METRICS]);
+
+        assert_eq!(line_contents(&text), vec!["ab", "cd", ""]);
+        assert_eq!(text.lines[1], TextLine {
+            range: 3..5,
+            x: 0.0,
+            y: 10.0,
+            baseline: 18.0,
+            width: 2.0,
+            height: 10.0,
+            ascender: 8.0,
+            descender: -2.0,
+            elided: None,
+        });
+        assert_eq!(text.glyph_positions.len(), 6);
+        assert_eq!(text.glyph_positions[2], GlyphPos {
+            x: 2.0,
+            y: 0.0,
+            width: 0.0,
+            rtl: false,
+        });
+        assert_eq!(text.glyph_positions[4], GlyphPos {
+            x: 1.0,
+            y: 10.0,
+            width: 1.0,
+            rtl: false,
+        });
+    }
+
+    #[test]
+    fn wrap_words_and_chars() {
+        let layout = TextLayout {
+            max_width: Some(RealValue::px(5.0)),
+            ..Default::default()
+        };
+        let mut text = text("one two three longword", layout);
+        text.break_lines(&[1.0; 22], &[METRICS]);
+        assert_eq!(line_contents(&text), vec!["one ", "two ", "three ", "longw", "ord"]);
+        assert_eq!(text.lines[0].width, 3.0);
+        assert_eq!(text.glyph_positions.len(), 22);
+
+        text.layout.wrap = TextWrap::Char;
+        text.break_lines(&[1.0; 22], &[METRICS]);
+        assert_eq!(line_contents(&text), vec!["one t", "wo th", "ree l", "ongwo", "rd"]);
+
+        text.layout.wrap = TextWrap::None;
+        text.break_lines(&[1.0; 22], &[METRICS]);
+        assert_eq!(line_contents(&text), vec!["one two three longword"]);
+    }
+
+    #[test]
+    fn align_and_limit_lines() {
+        let layout = TextLayout {
+            line_height: 1.5,
+            max_lines: Some(2),
+            ..Default::default()
+        };
+        let mut text = text("a\nbcd\nef", layout);
+        text.align = (AlignHor::Center, AlignVer::Bottom);
+        text.break_lines(&[2.0; 8], &[METRICS]);
+
+        assert_eq!(line_contents(&text), vec!["a", "bcd"]);
+        assert_eq!(text.lines[0].x, -1.0);
+        assert_eq!(text.lines[0].y, -15.0);
+        assert_eq!(text.lines[0].baseline, -17.0);
+        assert_eq!(text.lines[1].x, -3.0);
+        assert_eq!(text.lines[1].y, 0.0);
+        assert_eq!(text.lines[1].top(), -12.5);
+        assert_eq!(text.lines[1].bottom(), 2.5);
+        assert_eq!(text.line_bounds(), Some([-3.0, -27.5, 6.0, 30.0]));
+        assert_eq!(text.glyph_positions.len(), 8);
+        assert_eq!(text.glyph_positions[7], GlyphPos {
+            x: 3.0,
+            y: 0.0,
+            width: 0.0,
+            rtl: false,
+        });
+    }
+
+    #[test]
+    fn truncate_with_ellipsis() {
+        let layout = TextLayout {
+            max_width: Some(RealValue::px(6.0)),
+            wrap: TextWrap::None,
+            overflow: TextOverflow::Ellipsis(EllipsisAt::End),
+            ..Default::default()
+        };
+        let mut text = text("abcdefghij", layout);
+        let mut truncated = |at| {
+            text.layout.overflow = TextOverflow::Ellipsis(at);
+            text.break_lines(&[1.0; 10], &[METRICS]);
+            text.truncate_lines(1.0);
+            (text.line_text(&text.lines[0]).into_owned(), text.lines[0].width)
+        };
+        assert_eq!(truncated(EllipsisAt::End), ("abcde\u{2026}".to_string(), 6.0));
+        assert_eq!(truncated(EllipsisAt::Middle), ("ab\u{2026}hij".to_string(), 6.0));
+        assert_eq!(truncated(EllipsisAt::Start), ("\u{2026}fghij".to_string(), 6.0));
+
+        let runs: Vec<_> = text.line_runs(&text.lines[0]).into_iter().map(|run| run.content).collect();
+        assert_eq!(runs, vec![ELLIPSIS, "fghij"]);
+        assert_eq!(text.glyph_positions[4], GlyphPos {
+            x: 1.0,
+            y: 0.0,
+            width: 0.0,
+            rtl: false,
+        });
+        assert_eq!(text.glyph_positions[5].x, 1.0);
+    }
+
+    #[test]
+    fn ellipsis_for_hidden_lines_and_letter_spacing() {
+        let layout = TextLayout {
+            max_lines: Some(1),
+            overflow: TextOverflow::Ellipsis(EllipsisAt::Middle),
+            ..Default::default()
+        };
+        let mut text = text("ab\ncd", layout);
+        text.letter_spacing = 0.5;
+        text.break_lines(&[1.0; 5], &[METRICS]);
+        assert_eq!(text.lines[0].width, 3.0);
+        assert_eq!(text.glyph_positions[1].x, 1.5);
+
+        text.truncate_lines(1.0);
+        assert_eq!(text.line_text(&text.lines[0]), "ab\u{2026}");
+        assert_eq!(text.lines[0].elided, Some(2..5));
+        assert_eq!(text.lines[0].width, 4.0);
+        assert_eq!(text.glyph_positions[2], GlyphPos {
+            x: 3.0,
+            y: 0.0,
+            width: 1.0,
+            rtl: false,
+        });
+    }
+
+    #[test]
+    fn reorder_bidi_lines() {
+        // Latin word followed by the Hebrew alef and bet
+        let mut text = text("ab \u{5d0}\u{5d1}", TextLayout::default());
+        text.break_lines(&[1.0, 1.0, 1.0, 2.0, 2.0], &[METRICS]);
+        assert!(text.has_rtl());
+
+        let xs: Vec<_> = text.glyph_positions.iter().map(|pos| (pos.x, pos.rtl)).collect();
+        assert_eq!(xs, vec![(0.0, false), (1.0, false), (2.0, false), (5.0, true), (3.0, true)]);
+        assert_eq!(text.caret_x(3), 7.0);
+        assert_eq!(text.caret_x(4), 5.0);
+        assert_eq!(text.char_index_at(6.9, 0.0), 3);
+        assert_eq!(text.char_index_at(5.1, 0.0), 4);
+
+        let runs: Vec<_> = text
+            .visual_runs(&text.lines[0])
+            .iter()
+            .map(|run| (run.visual_content().into_owned(), run.rtl, text.run_x(&text.lines[0], run)))
+            .collect();
+        assert_eq!(runs, vec![
+            ("ab ".to_string(), false, 0.0),
+            ("\u{5d1}\u{5d0}".to_string(), true, 3.0)
+        ]);
+    }
+}
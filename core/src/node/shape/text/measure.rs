@@ -0,0 +1,99 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::bidi::{direction_runs, visual_content};
+use crate::{FontError, FontFace, FontRegistry, FontStyle, FontWeight, Real, Text, TextMetrics, TextOverflow, ELLIPSIS};
+
+/// Content measured with one font.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ShapedText {
+    /// Metrics of the first face of the font chain.
+    pub metrics: TextMetrics,
+    /// Advances of the content chars.
+    pub advances: Vec<Real>,
+}
+
+/// Renderer independent measurement of the texts, it allows to lay out the texts without
+/// the graphics context, such as in the unit tests and headless tools.
+pub trait TextMeasurer {
+    /// Measure the content with the font size. Faces are the font followed by its fallbacks,
+    /// each char is measured with the first face that has the glyph of the char.
+    fn shape(&self, faces: &[&FontFace], font_size: Real, content: &str) -> Result<ShapedText, FontError>;
+}
+
+/// Measure the content by the runs with the same direction, for the measurers of the renderers that shape
+/// and draw the runs in the visual order. `measure` returns the advances of the chars of the visual run content,
+/// in which the grapheme clusters of the right-to-left runs are reversed, and they are put back in the logical order.
+pub fn shape_runs(content: &str, mut measure: impl FnMut(&str) -> Vec<Real>) -> Vec<Real> {
+    let mut advances = Vec::with_capacity(content.chars().count());
+    for (range, rtl) in direction_runs(content) {
+        let run = &content[range];
+        let count = run.chars().count();
+        let mut run_advances = measure(&visual_content(run, rtl));
+        // Ligatures may produce fewer glyphs than chars
+        run_advances.resize(count, 0.0);
+        if rtl {
+            let mut end = count;
+            for grapheme in run.graphemes(true) {
+                let start = end - grapheme.chars().count();
+                advances.extend_from_slice(&run_advances[start..end]);
+                end = start;
+            }
+        } else {
+            advances.extend(run_advances);
+        }
+    }
+    advances
+}
+
+impl Text {
+    /// Measure the text and its spans with the fonts of the registry, then break the content
+    /// into lines and truncate them by the overflow, as the renderers do before drawing.
+    pub fn layout_with(&mut self, measurer: &impl TextMeasurer, fonts: &FontRegistry) -> Result<(), FontError> {
+        let faces = fonts.fallback_chain(&self.font_name, FontWeight::NORMAL, FontStyle::Normal)?;
+        let content = if self.spans.is_empty() { self.content.as_str() } else { "" };
+        let font_size = self.font_size.val();
+        let ShapedText { metrics, mut advances } = measurer.shape(&faces, font_size, content)?;
+        self.metrics = Some(metrics);
+
+        let mut span_metrics = vec![metrics];
+        if !self.spans.is_empty() {
+            span_metrics.clear();
+            for span in &self.spans {
+                let style = &span.style;
+                let font_name = style.font_name.as_deref().unwrap_or(&self.font_name);
+                let span_faces = fonts.fallback_chain(font_name, style.weight, style.font_style())?;
+                let span_size = style.font_size.unwrap_or(self.font_size).val();
+                let shaped = measurer.shape(&span_faces, span_size, &span.content)?;
+                span_metrics.push(shaped.metrics);
+                advances.extend(shaped.advances);
+            }
+        }
+        self.break_lines(&advances, &span_metrics);
+        if let TextOverflow::Ellipsis(_) = self.layout.overflow {
+            let ellipsis = measurer.shape(&faces, font_size, ELLIPSIS)?;
+            self.truncate_lines(ellipsis.advances.iter().sum());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shape_mixed_runs() {
+        // Latin word followed by the Hebrew alef with the point and bet
+        let content = "ab \u{5d0}\u{5b8}\u{5d1}";
+        let mut runs = Vec::new();
+        let advances = shape_runs(content, |run| {
+            runs.push(run.to_string());
+            run.chars().enumerate().map(|(idx, _)| idx as Real + 1.0).collect()
+        });
+        assert_eq!(runs, vec!["ab ", "\u{5d1}\u{5d0}\u{5b8}"]);
+        assert_eq!(advances, vec![1.0, 2.0, 3.0, 2.0, 3.0, 1.0]);
+
+        let advances = shape_runs("\u{5d0}\u{5d1}", |_| vec![1.0]);
+        assert_eq!(advances, vec![0.0, 1.0]);
+    }
+}
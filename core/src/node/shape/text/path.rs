@@ -0,0 +1,295 @@
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{PathCommand, PathSegment, Real, RealValue, Text, TransformMatrix, UnitContext};
+
+/// Side of the path that the text is placed on.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextPathSide {
+    /// The text goes in the path direction with the tops of the glyphs at the left side of the path.
+    #[default]
+    Left,
+    /// The text goes against the path direction, so it is placed at the right side of the path.
+    Right,
+}
+
+/// Path that the laid out lines of the text follow, it is relative to the text position. The baseline of the line
+/// at the text position goes along the path, the other lines are offset from the path by their distance from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextPath {
+    pub cmd: Vec<PathCommand>,
+    /// Distance along the path to the text position, percentages are relative to the path length.
+    /// It is resolved by the layout, see `resolve`.
+    pub start_offset: RealValue,
+    pub side: TextPathSide,
+    /// Glyphs are rotated to follow the path tangent, otherwise they stay upright.
+    pub rotate: bool,
+}
+
+impl Default for TextPath {
+    fn default() -> Self {
+        Self {
+            cmd: Vec::new(),
+            start_offset: RealValue::px(0.0),
+            side: TextPathSide::default(),
+            rotate: true,
+        }
+    }
+}
+
+impl TextPath {
+    pub fn new(cmd: impl Into<Vec<PathCommand>>) -> Self {
+        Self {
+            cmd: cmd.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn start_offset(mut self, offset: impl Into<RealValue>) -> Self {
+        self.start_offset = offset.into();
+        self
+    }
+
+    pub fn side(mut self, side: TextPathSide) -> Self {
+        self.side = side;
+        self
+    }
+
+    pub fn rotate(mut self, rotate: bool) -> Self {
+        self.rotate = rotate;
+        self
+    }
+
+    /// Resolve the relative start offset by the path length and the unit sizes of the text.
+    pub fn resolve(&mut self, units: &UnitContext) {
+        let length = PathSegment::resolve(&self.cmd).map_or(0.0, |segments| PathSegment::length(&segments));
+        self.start_offset.resolve(length, units);
+    }
+}
+
+impl<T: Into<Vec<PathCommand>>> From<T> for TextPath {
+    fn from(cmd: T) -> Self {
+        TextPath::new(cmd)
+    }
+}
+
+/// Grapheme cluster of the text placed along the text path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathGlyph<'a> {
+    /// Index of the span, it is always zero for the text without spans.
+    pub span: usize,
+    /// Char indices of the cluster in the text content.
+    pub range: Range<usize>,
+    pub content: &'a str,
+    /// Index of the laid out line of the cluster.
+    pub line: usize,
+    /// Left position of the cluster in the laid out line relative to the text position.
+    pub x: Real,
+    pub width: Real,
+    /// Transform of the cluster from its place in the laid out line to its place along the path,
+    /// both places are in the text coordinates.
+    pub matrix: TransformMatrix,
+}
+
+impl Text {
+    /// Grapheme clusters of the laid out lines placed along the text path, each cluster is centered
+    /// on the path at the distance of its center. The clusters past the ends of the path are hidden.
+    /// It is empty if the text has no path or the path commands are invalid.
+    pub fn path_glyphs(&self) -> Vec<PathGlyph<'_>> {
+        let text_path = match &self.path {
+            Some(text_path) => text_path,
+            None => return Vec::new(),
+        };
+        let segments = match PathSegment::resolve(&text_path.cmd) {
+            Ok(segments) => segments,
+            Err(_) => return Vec::new(),
+        };
+        let length = PathSegment::length(&segments);
+        let start_offset = text_path.start_offset;
+        let (x, y) = (self.x.val(), self.y.val());
+
+        let mut glyphs = Vec::new();
+        for (line_idx, line) in self.lines.iter().enumerate() {
+            for run in self.line_runs(line) {
+                let mut clusters = vec![(run.range.clone(), run.content)];
+                if Some(&run.range) != line.elided.as_ref() {
+                    let mut start = run.range.start;
+                    clusters = run
+                        .content
+                        .graphemes(true)
+                        .map(|grapheme| {
+                            let end = start + grapheme.chars().count();
+                            let range = start..end;
+                            start = end;
+                            (range, grapheme)
+                        })
+                        .collect();
+                }
+
+                for (range, content) in clusters {
+                    let positions = self.glyph_positions.get(range.clone()).unwrap_or_default();
+                    if positions.is_empty() {
+                        continue;
+                    }
+                    let glyph_x = positions.iter().map(|pos| pos.x).fold(Real::INFINITY, Real::min);
+                    let width: Real = positions.iter().map(|pos| pos.width).sum();
+                    let center = glyph_x + width / 2.0;
+                    let distance = match text_path.side {
+                        TextPathSide::Left => start_offset.val() + center,
+                        TextPathSide::Right => length - start_offset.val() - center,
+                    };
+                    if distance < 0.0 || distance > length {
+                        continue;
+                    }
+                    let (point, tangent) = match (
+                        PathSegment::point_at(&segments, distance),
+                        PathSegment::tangent_at(&segments, distance),
+                    ) {
+                        (Some(point), Some(tangent)) => (point, tangent),
+                        _ => continue,
+                    };
+                    let (cos, sin) = match (text_path.rotate, text_path.side) {
+                        (false, _) => (1.0, 0.0),
+                        (true, TextPathSide::Left) => (tangent[0], tangent[1]),
+                        (true, TextPathSide::Right) => (-tangent[0], -tangent[1]),
+                    };
+
+                    // The cluster center on the text position line is rotated around and moved to the path point
+                    let (origin_x, origin_y) = (x + center, y);
+                    let (point_x, point_y) = (x + point[0], y + point[1]);
+                    glyphs.push(PathGlyph {
+                        span: run.span,
+                        range,
+                        content,
+                        line: line_idx,
+                        x: glyph_x,
+                        width,
+                        matrix: TransformMatrix {
+                            matrix: [
+                                cos,
+                                sin,
+                                -sin,
+                                cos,
+                                point_x - cos * origin_x + sin * origin_y,
+                                point_y - sin * origin_x - cos * origin_y,
+                            ],
+                        },
+                    });
+                }
+            }
+        }
+        glyphs
+    }
+
+    /// Bounds `[x, y, width, height]` of the line boxes of the clusters placed along the text path,
+    /// `None` if no cluster is placed.
+    pub fn path_bounds(&self) -> Option<[Real; 4]> {
+        let mut corners = self.path_glyphs().into_iter().flat_map(|glyph| {
+            let [left, top, right, bottom] = self.glyph_box(&glyph);
+            let matrix = glyph.matrix;
+            [(left, top), (right, top), (right, bottom), (left, bottom)].map(|corner| matrix * corner)
+        });
+        let (x, y) = corners.next()?;
+        let [min_x, min_y, max_x, max_y] = corners.fold([x, y, x, y], |[min_x, min_y, max_x, max_y], (x, y)| {
+            [min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)]
+        });
+        Some([min_x, min_y, max_x - min_x, max_y - min_y])
+    }
+
+    /// The point in the text coordinates hits the line box of a cluster placed along the text path.
+    pub fn path_contains(&self, x: Real, y: Real) -> bool {
+        self.path_glyphs().iter().any(|glyph| {
+            let (x, y) = glyph.matrix.inverse() * (x, y);
+            let [left, top, right, bottom] = self.glyph_box(glyph);
+            x >= left && x <= right && y >= top && y <= bottom
+        })
+    }
+
+    /// Box `[left, top, right, bottom]` of the cluster in its laid out line in the text coordinates.
+    fn glyph_box(&self, glyph: &PathGlyph) -> [Real; 4] {
+        let line = &self.lines[glyph.line];
+        let (x, y) = (self.x.val() + glyph.x, self.y.val());
+        [x, y + line.top(), x + glyph.width, y + line.bottom()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AlignHor, AlignVer, PathBuilder, TextLayout, TextMetrics};
+
+    const METRICS: TextMetrics = TextMetrics {
+        ascender: 8.0,
+        descender: -2.0,
+        line_height: 10.0,
+    };
+
+    fn assert_near((x, y): (Real, Real), [expected_x, expected_y]: [Real; 2]) {
+        assert!(
+            (x - expected_x).abs() < 1e-2 && (y - expected_y).abs() < 1e-2,
+            "{:?} != {:?}",
+            (x, y),
+            (expected_x, expected_y)
+        );
+    }
+
+    fn text_on(mut path: TextPath, content: &str) -> Text {
+        path.resolve(&UnitContext::default());
+        let mut text = Text {
+            content: content.to_string(),
+            x: RealValue::px(100.0),
+            y: RealValue::px(50.0),
+            align: (AlignHor::Left, AlignVer::Baseline),
+            layout: TextLayout::default(),
+            path: Some(path),
+            ..Default::default()
+        };
+        text.break_lines(&vec![2.0; content.chars().count()], &[METRICS]);
+        text
+    }
+
+    #[test]
+    fn place_glyphs_along_path() {
+        let line = vec![PathCommand::Move([0.0, 0.0]), PathCommand::Line([0.0, 10.0])];
+        let text = text_on(TextPath::new(line.clone()).start_offset(RealValue::pct(50.0)), "abcd");
+        let glyphs = text.path_glyphs();
+
+        // The line goes down, so the glyphs are rotated by the right angle and the last one is past its end
+        assert_eq!(glyphs.len(), 3);
+        assert_eq!((glyphs[0].content, glyphs[0].x, glyphs[0].width), ("a", 0.0, 2.0));
+        assert_near(glyphs[0].matrix * (101.0, 50.0), [100.0, 56.0]);
+        assert_near(glyphs[1].matrix * (103.0, 50.0), [100.0, 58.0]);
+        assert_near(glyphs[0].matrix * (101.0, 42.0), [108.0, 56.0]);
+
+        let right = text_on(TextPath::new(line.clone()).side(TextPathSide::Right), "ab");
+        let glyphs = right.path_glyphs();
+        assert_near(glyphs[0].matrix * (101.0, 50.0), [100.0, 59.0]);
+        assert_near(glyphs[0].matrix * (101.0, 42.0), [92.0, 59.0]);
+
+        let upright = text_on(TextPath::new(line).rotate(false), "a");
+        assert_near(upright.path_glyphs()[0].matrix * (101.0, 42.0), [100.0, 43.0]);
+    }
+
+    #[test]
+    fn hit_glyphs_along_arc() {
+        // The circle starts at its right point and goes clockwise, so its bottom point is at the quarter of it
+        let circle = PathBuilder::new().ellipse(0.0, 0.0, 20.0, 20.0).build();
+        let mut text = text_on(TextPath::new(circle).start_offset(RealValue::pct(25.0)), "ab");
+        text.align.0 = AlignHor::Center;
+        text.break_lines(&[2.0, 2.0], &[METRICS]);
+
+        // The glyphs go to the left upside down, so their tops are outside the circle
+        let glyphs = text.path_glyphs();
+        assert_eq!(glyphs.len(), 2);
+        assert_near(glyphs[0].matrix * (99.0, 50.0), [101.0, 69.975]);
+        assert!(text.path_contains(101.0, 74.0));
+        assert!(text.path_contains(101.0, 69.0));
+        assert!(!text.path_contains(101.0, 67.0));
+        assert!(!text.path_contains(100.0, 50.0));
+
+        let [min_x, min_y, width, height] = text.path_bounds().unwrap();
+        assert!((min_x - 98.0).abs() < 1.0 && (min_y - 68.0).abs() < 0.5);
+        assert!((width - 4.0).abs() < 1.5 && (height - 10.0).abs() < 0.5);
+    }
+}
@@ -0,0 +1,353 @@
+use std::{borrow::Cow, ops::Range};
+
+use super::bidi::visual_content;
+use crate::{Color, FontStyle, FontWeight, Real, RealValue, Text, TextDecoration, TextLine, ELLIPSIS};
+
+/// Style of the text span, unset properties are inherited from the text.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SpanStyle {
+    pub font_name: Option<String>,
+    pub font_size: Option<RealValue>,
+    pub color: Option<Color>,
+    pub weight: FontWeight,
+    pub italic: bool,
+    pub decoration: TextDecoration,
+    /// Shift of the span baseline, positive values raise the span.
+    pub baseline_offset: Real,
+}
+
+impl SpanStyle {
+    pub fn font_name(mut self, name: impl Into<String>) -> Self {
+        self.font_name = Some(name.into());
+        self
+    }
+
+    pub fn font_size(mut self, size: impl Into<RealValue>) -> Self {
+        self.font_size = Some(size.into());
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn bold(self) -> Self {
+        self.weight(FontWeight::BOLD)
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    pub fn decoration(mut self, decoration: TextDecoration) -> Self {
+        self.decoration = self.decoration.union(decoration);
+        self
+    }
+
+    pub fn underline(self) -> Self {
+        self.decoration(TextDecoration::UNDERLINE)
+    }
+
+    pub fn strikethrough(self) -> Self {
+        self.decoration(TextDecoration::STRIKETHROUGH)
+    }
+
+    pub fn overline(self) -> Self {
+        self.decoration(TextDecoration::OVERLINE)
+    }
+
+    pub fn baseline_offset(mut self, offset: Real) -> Self {
+        self.baseline_offset = offset;
+        self
+    }
+
+    pub fn font_style(&self) -> FontStyle {
+        if self.italic {
+            FontStyle::Italic
+        } else {
+            FontStyle::Normal
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextSpan {
+    pub content: String,
+    pub style: SpanStyle,
+}
+
+impl TextSpan {
+    pub fn new(content: impl Into<String>, style: SpanStyle) -> Self {
+        Self {
+            content: content.into(),
+            style,
+        }
+    }
+}
+
+/// Part of the laid out line that belongs to one span.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextRun<'a> {
+    /// Index of the span, it is always zero for the text without spans.
+    pub span: usize,
+    /// Char indices of the run in the text content.
+    pub range: Range<usize>,
+    pub content: &'a str,
+    /// Chars of the run are right-to-left, it is drawn by its visual content.
+    pub rtl: bool,
+}
+
+impl<'a> TextRun<'a> {
+    /// Content of the run in the order of drawing, the grapheme clusters of the right-to-left run are reversed.
+    pub fn visual_content(&self) -> Cow<'a, str> {
+        visual_content(self.content, self.rtl)
+    }
+}
+
+impl Text {
+    /// Append the span and its content to the text.
+    pub fn push_span(&mut self, span: TextSpan) {
+        self.content.push_str(&span.content);
+        self.spans.push(span);
+    }
+
+    /// Char ranges of the spans in the text content.
+    pub fn span_ranges(&self) -> Vec<Range<usize>> {
+        let mut start = 0;
+        self.spans
+            .iter()
+            .map(|span| {
+                let end = start + span.content.chars().count();
+                let range = start..end;
+                start = end;
+                range
+            })
+            .collect()
+    }
+
+    /// Split the laid out line into the runs of the same span. The chars elided by the overflow
+    /// are replaced by one run with the ellipsis content and the style of the first elided char.
+    pub fn line_runs(&self, line: &TextLine) -> Vec<TextRun<'_>> {
+        let span_ranges = if self.spans.is_empty() {
+            let whole = 0..self.content.chars().count();
+            vec![whole]
+        } else {
+            self.span_ranges()
+        };
+        let span_runs = |range: Range<usize>| {
+            span_ranges.iter().enumerate().filter_map(move |(span, span_range)| {
+                let range = span_range.start.max(range.start)..span_range.end.min(range.end);
+                if range.is_empty() {
+                    None
+                } else {
+                    Some(TextRun {
+                        span,
+                        content: self.chars_content(range.clone()),
+                        range,
+                        rtl: false,
+                    })
+                }
+            })
+        };
+
+        match &line.elided {
+            Some(elided) => {
+                let mut runs: Vec<_> = span_runs(line.range.start..elided.start).collect();
+                let span = span_ranges
+                    .iter()
+                    .position(|range| elided.start < range.end)
+                    .unwrap_or(span_ranges.len() - 1);
+                runs.push(TextRun {
+                    span,
+                    range: elided.clone(),
+                    content: ELLIPSIS,
+                    rtl: false,
+                });
+                runs.extend(span_runs(elided.end..line.range.end));
+                runs
+            }
+            None => span_runs(line.range.clone()).collect(),
+        }
+    }
+
+    /// Runs of the line that the renderers draw as one piece at `run_x`. The runs of the spans are split
+    /// where the direction of the chars changes, the right-to-left runs are drawn by their visual content.
+    pub fn visual_runs(&self, line: &TextLine) -> Vec<TextRun<'_>> {
+        let is_rtl = |idx: usize| matches!(self.glyph_positions.get(idx), Some(pos) if pos.rtl);
+        let mut runs = Vec::new();
+        for run in self.line_runs(line) {
+            if Some(&run.range) == line.elided.as_ref() {
+                runs.push(run);
+                continue;
+            }
+            let (mut start, mut start_offset) = (run.range.start, 0);
+            for (offset, (byte_offset, _)) in run.content.char_indices().enumerate().skip(1) {
+                let idx = run.range.start + offset;
+                if is_rtl(idx) != is_rtl(start) {
+                    runs.push(TextRun {
+                        span: run.span,
+                        range: start..idx,
+                        content: &run.content[start_offset..byte_offset],
+                        rtl: is_rtl(start),
+                    });
+                    start = idx;
+                    start_offset = byte_offset;
+                }
+            }
+            runs.push(TextRun {
+                span: run.span,
+                range: start..run.range.end,
+                content: &run.content[start_offset..],
+                rtl: is_rtl(start),
+            });
+        }
+        runs
+    }
+
+    /// Left position of the run relative to the text position.
+    pub fn run_x(&self, line: &TextLine, run: &TextRun) -> Real {
+        let range = if Some(&run.range) == line.elided.as_ref() {
+            run.range.start..run.range.start + 1
+        } else {
+            run.range.clone()
+        };
+        self.glyph_positions
+            .get(range)
+            .and_then(|positions| positions.iter().map(|pos| pos.x).reduce(Real::min))
+            .unwrap_or(line.x)
+    }
+
+    /// Horizontal position of the caret before the char relative to the text position.
+    pub fn caret_x(&self, idx: usize) -> Real {
+        match self.glyph_positions.get(idx) {
+            Some(pos) if pos.rtl => pos.max_x(),
+            Some(pos) => pos.x,
+            None => match idx.checked_sub(1).and_then(|prev| self.glyph_positions.get(prev)) {
+                Some(pos) if pos.rtl => pos.x,
+                Some(pos) => pos.max_x(),
+                None => self.lines.first().map_or(0.0, |line| line.x),
+            },
+        }
+    }
+
+    /// Caret position nearest to the point, the point is relative to the text position.
+    /// Carets are placed only at the grapheme cluster boundaries.
+    pub fn char_index_at(&self, x: Real, y: Real) -> usize {
+        let line = self
+            .lines
+            .iter()
+            .find(|line| y < line.baseline - line.descender)
+            .or_else(|| self.lines.last());
+        let line = match line {
+            Some(line) => line,
+            None => return 0,
+        };
+        // The caret at the line end is placed after the last char of the line
+        let line_caret_x = |idx: usize| {
+            if idx == line.range.end && idx > line.range.start {
+                match self.glyph_positions.get(idx - 1) {
+                    Some(pos) if pos.rtl => pos.x,
+                    Some(pos) => pos.max_x(),
+                    None => line.max_x(),
+                }
+            } else {
+                self.caret_x(idx)
+            }
+        };
+        self.grapheme_boundaries()
+            .into_iter()
+            .filter(|idx| line.range.start <= *idx && *idx <= line.range.end)
+            .map(|idx| (idx, (line_caret_x(idx) - x).abs()))
+            .fold(None, |nearest: Option<(usize, Real)>, (idx, distance)| match nearest {
+                Some((_, nearest_distance)) if nearest_distance <= distance => nearest,
+                _ => Some((idx, distance)),
+            })
+            .map_or(line.range.start, |(idx, _)| idx)
+    }
+
+    pub(crate) fn chars_content(&self, range: Range<usize>) -> &str {
+        if range.is_empty() {
+            return "";
+        }
+        let mut indices = self.content.char_indices().map(|(idx, _)| idx).chain(Some(self.content.len()));
+        let start = indices.nth(range.start).unwrap_or(self.content.len());
+        let end = indices.nth(range.end - range.start - 1).unwrap_or(self.content.len());
+        &self.content[start..end]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TextMetrics;
+
+    #[test]
+    fn mixed_spans() {
+        let mut text = Text::default();
+        text.push_span(TextSpan::new("ab ", SpanStyle::default()));
+        text.push_span(TextSpan::new("cd ef", SpanStyle::default().font_size(20.0)));
+        text.layout.max_width = Some(RealValue::px(7.0));
+        assert_eq!(text.content, "ab cd ef");
+
+        let small = TextMetrics {
+            ascender: 8.0,
+            descender: -2.0,
+            line_height: 10.0,
+        };
+        let large = TextMetrics {
+            ascender: 16.0,
+            descender: -4.0,
+            line_height: 20.0,
+        };
+        text.break_lines(&[1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 2.0, 2.0], &[small, large]);
+
+        assert_eq!(text.lines.len(), 2);
+        assert_eq!(text.lines[0].height, 20.0);
+        assert_eq!(text.lines[0].baseline, 16.0);
+        assert_eq!(text.lines[1].baseline, 36.0);
+
+        let runs = text.line_runs(&text.lines[0]);
+        assert_eq!(runs, vec![
+            TextRun {
+                span: 0,
+                range: 0..3,
+                content: "ab ",
+                rtl: false,
+            },
+            TextRun {
+                span: 1,
+                range: 3..6,
+                content: "cd ",
+                rtl: false,
+            },
+        ]);
+
+        assert_eq!(text.char_index_at(-5.0, 5.0), 0);
+        assert_eq!(text.char_index_at(3.9, 5.0), 3);
+        assert_eq!(text.char_index_at(4.1, 5.0), 4);
+        assert_eq!(text.char_index_at(1.1, 30.0), 7);
+        assert_eq!(text.char_index_at(100.0, 30.0), 8);
+    }
+
+    #[test]
+    fn edit_spans() {
+        let mut text = Text::default();
+        text.push_span(TextSpan::new("ab", SpanStyle::default()));
+        text.push_span(TextSpan::new("cd", SpanStyle::default().bold()));
+
+        text.insert(3, 'x');
+        assert_eq!(text.content, "abcxd");
+        assert_eq!(text.spans[1].content, "cxd");
+
+        text.remove(1);
+        assert_eq!(text.content, "acxd");
+        assert_eq!(text.spans[0].content, "a");
+    }
+}
@@ -1,4 +1,6 @@
-use crate::Real;
+use std::f32::consts::PI;
+
+use crate::{Interpolate, Real};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Transform {
@@ -16,6 +18,16 @@ impl Default for Transform {
     }
 }
 
+/// The local matrices are interpolated if both transforms are relative, otherwise the absolute matrices are.
+impl Interpolate for Transform {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        match (self.local_matrix(), other.local_matrix()) {
+            (Some(from), Some(to)) => Transform::Local(from.interpolate(&to, t)),
+            _ => Transform::Global(self.matrix().interpolate(&other.matrix(), t)),
+        }
+    }
+}
+
 impl Transform {
     /// Construct a new transform with an identity matrix.
     pub fn new() -> Self {
@@ -223,20 +235,75 @@ impl TransformMatrix {
         self
     }
 
-    pub fn inverse(mut self) -> Self {
-        let inv_det = 1.0 / (self.matrix[0] * self.matrix[3] - self.matrix[2] * self.matrix[1]);
-        self.matrix[0] = self.matrix[3] * inv_det;
-        self.matrix[1] = -self.matrix[1] * inv_det;
-        self.matrix[2] = -self.matrix[2] * inv_det;
-        self.matrix[3] = self.matrix[0] * inv_det;
-        self.matrix[4] = (self.matrix[2] * self.matrix[5] - self.matrix[3] * self.matrix[4]) * inv_det;
-        self.matrix[5] = (self.matrix[1] * self.matrix[4] - self.matrix[0] * self.matrix[5]) * inv_det;
-        self
+    pub fn inverse(self) -> Self {
+        let [a, b, c, d, e, f] = self.matrix;
+        let inv_det = 1.0 / (a * d - c * b);
+        Self {
+            matrix: [
+                d * inv_det,
+                -b * inv_det,
+                -c * inv_det,
+                a * inv_det,
+                (c * f - d * e) * inv_det,
+                (b * e - a * f) * inv_det,
+            ],
+        }
     }
 
     pub fn is_identity(&self) -> bool {
         self.matrix == [1.0, 0.0, 0.0, 1.0, 0.0, 0.0]
     }
+
+    /// Split the matrix into `[translate_x, translate_y, rotation, skew_x, scale_x, scale_y]`, they are applied
+    /// in the reverse order: the scale, then the skew along the x axis, the rotation and the translation.
+    /// The mirroring is the negative y scale.
+    pub fn decompose(&self) -> [Real; 6] {
+        let [a, b, c, d, e, f] = self.matrix;
+        let scale_x = a.hypot(b);
+        let (cos, sin) = if scale_x != 0.0 {
+            (a / scale_x, b / scale_x)
+        } else {
+            (1.0, 0.0)
+        };
+        let shear = cos * c + sin * d;
+        let scale_y = (c - cos * shear).hypot(d - sin * shear) * if a * d - b * c < 0.0 { -1.0 } else { 1.0 };
+        let skew = if scale_y != 0.0 { shear / scale_y } else { 0.0 };
+        [e, f, sin.atan2(cos), skew, scale_x, scale_y]
+    }
+
+    /// Compose the matrix of the parts as returned by `decompose`.
+    pub fn compose([translate_x, translate_y, rotation, skew, scale_x, scale_y]: [Real; 6]) -> Self {
+        let (sin, cos) = rotation.sin_cos();
+        Self {
+            matrix: [
+                scale_x * cos,
+                scale_x * sin,
+                scale_y * (skew * cos - sin),
+                scale_y * (skew * sin + cos),
+                translate_x,
+                translate_y,
+            ],
+        }
+    }
+}
+
+/// The decomposed parts of the matrices are interpolated, so the rotation goes along the shorter arc
+/// and keeps the size of the shape.
+impl Interpolate for TransformMatrix {
+    fn interpolate(&self, other: &Self, t: Real) -> Self {
+        let (from, mut to) = (self.decompose(), other.decompose());
+        let turn = to[2] - from[2];
+        if turn > PI {
+            to[2] -= 2.0 * PI;
+        } else if turn < -PI {
+            to[2] += 2.0 * PI;
+        }
+        let mut parts = from;
+        for (part, to) in parts.iter_mut().zip(to.iter()) {
+            *part = part.interpolate(to, t);
+        }
+        TransformMatrix::compose(parts)
+    }
 }
 
 /// Implementation of multiplication Trait for Transform.
@@ -334,5 +401,49 @@ mod tests {
         let a = TransformMatrix::identity().with_rotation(123.0);
         let b = TransformMatrix::identity().with_skew(66.6, 1337.2);
         trans_not_eq!(a * b, b * a);
+
+        // Inverse
+        let scaled = TransformMatrix::identity()
+            .with_scale(2.0, 4.0)
+            .with_translation(10.0, 20.0);
+        trans_eq!(scaled * scaled.inverse(), identity);
+        assert_eq!(scaled.inverse() * (30.0, 60.0), (10.0, 10.0));
+    }
+
+    #[test]
+    fn interpolate_matrices() {
+        let assert_near = |actual: TransformMatrix, expected: TransformMatrix| {
+            let near = actual
+                .matrix
+                .iter()
+                .zip(&expected.matrix)
+                .all(|(a, b)| (a - b).abs() < 1e-5);
+            assert!(near, "{:?} != {:?}", actual, expected);
+        };
+        let mut matrix = TransformMatrix::identity().with_translation(10.0, 20.0);
+        matrix = matrix * TransformMatrix::identity().with_rotation(0.5);
+        matrix = matrix * TransformMatrix::identity().with_skew(0.3, 0.0);
+        matrix = matrix * TransformMatrix::identity().with_scale(2.0, -3.0);
+        assert_near(TransformMatrix::compose(matrix.decompose()), matrix);
+
+        // The rotation keeps the size and goes the shorter way
+        let quarter = TransformMatrix::identity().with_rotation(PI / 2.0);
+        let eighth = TransformMatrix::identity().with_rotation(PI / 4.0);
+        assert_near(TransformMatrix::identity().interpolate(&quarter, 0.5), eighth);
+        let almost_half = TransformMatrix::identity().with_rotation(0.9 * PI);
+        let back = TransformMatrix::identity().with_rotation(-0.9 * PI);
+        assert_near(
+            almost_half.interpolate(&back, 0.5),
+            TransformMatrix::identity().with_rotation(PI),
+        );
+
+        let moved = Transform::new().with_translation(10.0, 0.0).with_scale(3.0, 3.0);
+        let tweened = Transform::new().interpolate(&moved, 0.5);
+        assert_near(
+            tweened.local_matrix().unwrap(),
+            TransformMatrix::identity()
+                .with_translation(5.0, 0.0)
+                .with_scale(2.0, 2.0),
+        );
     }
 }
@@ -1,6 +1,6 @@
 use std::{
     fmt::Debug,
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
 use crate::ConvertTo;
@@ -76,11 +76,156 @@ impl From<i32> for Pct<Real> {
     }
 }
 
+/// Length in the font sizes.
+#[derive(Debug, Default, Clone, Copy, PartialOrd, PartialEq)]
+pub struct Em<T>(pub T);
+
+/// Length in the root font sizes.
+#[derive(Debug, Default, Clone, Copy, PartialOrd, PartialEq)]
+pub struct Rem<T>(pub T);
+
+/// Length in the percentages of the viewport width.
+#[derive(Debug, Default, Clone, Copy, PartialOrd, PartialEq)]
+pub struct Vw<T>(pub T);
+
+/// Length in the percentages of the viewport height.
+#[derive(Debug, Default, Clone, Copy, PartialOrd, PartialEq)]
+pub struct Vh<T>(pub T);
+
+/// Length in the percentages of the smaller viewport dimension.
+#[derive(Debug, Default, Clone, Copy, PartialOrd, PartialEq)]
+pub struct Vmin<T>(pub T);
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ValueType {
     Auto,
     Px,
     Pct(Real),
+    Em(Real),
+    Rem(Real),
+    Vw(Real),
+    Vh(Real),
+    Vmin(Real),
+    /// Sum of the lengths in the different units, such as `50% - 10px`.
+    Calc(Calc),
+}
+
+/// Length of the calc expression combined from the lengths in the different units, the fields are the amounts
+/// of the units. The expression is kept as the sum of the units, since the lengths are only added together
+/// and multiplied by the numbers.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Calc {
+    pub px: Real,
+    pub pct: Real,
+    pub em: Real,
+    pub rem: Real,
+    pub vw: Real,
+    pub vh: Real,
+    pub vmin: Real,
+}
+
+impl Calc {
+    /// Length by the base of the percentages and the sizes of the relative units.
+    pub fn resolve(&self, base: Real, units: &UnitContext) -> Real {
+        self.px
+            + self.pct / 100.0 * base
+            + self.em * units.font_size
+            + self.rem * units.root_font_size
+            + (self.vw * units.viewport_width + self.vh * units.viewport_height) / 100.0
+            + self.vmin / 100.0 * units.viewport_width.min(units.viewport_height)
+    }
+
+    /// The simplest value of the length, it is in the one unit if the others are not used.
+    pub fn to_value(self) -> RealValue {
+        let units = [self.pct, self.em, self.rem, self.vw, self.vh, self.vmin];
+        let value_type = match units.iter().filter(|amount| **amount != 0.0).count() {
+            0 => return RealValue::px(self.px),
+            1 if self.px == 0.0 => match self {
+                Calc { pct, .. } if pct != 0.0 => ValueType::Pct(pct),
+                Calc { em, .. } if em != 0.0 => ValueType::Em(em),
+                Calc { rem, .. } if rem != 0.0 => ValueType::Rem(rem),
+                Calc { vw, .. } if vw != 0.0 => ValueType::Vw(vw),
+                Calc { vh, .. } if vh != 0.0 => ValueType::Vh(vh),
+                Calc { vmin, .. } => ValueType::Vmin(vmin),
+            },
+            _ => ValueType::Calc(self),
+        };
+        Value(0.0, value_type)
+    }
+}
+
+impl Add for Calc {
+    type Output = Calc;
+
+    fn add(self, other: Self) -> Self::Output {
+        Calc {
+            px: self.px + other.px,
+            pct: self.pct + other.pct,
+            em: self.em + other.em,
+            rem: self.rem + other.rem,
+            vw: self.vw + other.vw,
+            vh: self.vh + other.vh,
+            vmin: self.vmin + other.vmin,
+        }
+    }
+}
+
+impl Mul<Real> for Calc {
+    type Output = Calc;
+
+    fn mul(self, rhs: Real) -> Self::Output {
+        Calc {
+            px: self.px * rhs,
+            pct: self.pct * rhs,
+            em: self.em * rhs,
+            rem: self.rem * rhs,
+            vw: self.vw * rhs,
+            vh: self.vh * rhs,
+            vmin: self.vmin * rhs,
+        }
+    }
+}
+
+/// Sizes that the relative units are resolved by, they are set by the renderer for the layout.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitContext {
+    /// Size of the `Em` unit, it is the font size of the text or the root font size for the other shapes.
+    pub font_size: Real,
+    /// Size of the `Rem` unit.
+    pub root_font_size: Real,
+    pub viewport_width: Real,
+    pub viewport_height: Real,
+}
+
+impl Default for UnitContext {
+    fn default() -> Self {
+        Self {
+            font_size: 16.0,
+            root_font_size: 16.0,
+            viewport_width: 0.0,
+            viewport_height: 0.0,
+        }
+    }
+}
+
+impl UnitContext {
+    pub fn with_font_size(mut self, font_size: Real) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Set the root font size, which is also the font size of the shapes other than the texts.
+    pub fn with_root_font_size(mut self, root_font_size: Real) -> Self {
+        self.font_size = root_font_size;
+        self.root_font_size = root_font_size;
+        self
+    }
+
+    pub fn with_viewport(mut self, width: Real, height: Real) -> Self {
+        self.viewport_width = width;
+        self.viewport_height = height;
+        self
+    }
 }
 
 impl Default for ValueType {
@@ -101,6 +246,26 @@ impl<T: Debug + Default + Clone + Copy + PartialEq> Value<T> {
         Value(Default::default(), ValueType::Pct(pct))
     }
 
+    pub fn em(em: Real) -> Self {
+        Value(Default::default(), ValueType::Em(em))
+    }
+
+    pub fn rem(rem: Real) -> Self {
+        Value(Default::default(), ValueType::Rem(rem))
+    }
+
+    pub fn vw(vw: Real) -> Self {
+        Value(Default::default(), ValueType::Vw(vw))
+    }
+
+    pub fn vh(vh: Real) -> Self {
+        Value(Default::default(), ValueType::Vh(vh))
+    }
+
+    pub fn vmin(vmin: Real) -> Self {
+        Value(Default::default(), ValueType::Vmin(vmin))
+    }
+
     pub fn auto() -> Self {
         Value(Default::default(), ValueType::Auto)
     }
@@ -124,33 +289,122 @@ impl<T: Debug + Default + Clone + Copy + PartialEq> Value<T> {
 }
 
 impl Value<Real> {
+    /// Resolve the value by the base of the percentages with the default unit context, see `resolve`.
     pub fn set_by_pct(&mut self, source: Real) -> bool {
-        if let Value(ref mut v, ValueType::Pct(pct)) = self {
-            *v = *pct / 100.0 * source;
-            true
-        } else {
-            false
+        self.resolve(source, &UnitContext::default())
+    }
+
+    /// Resolve the percentage coordinate by the parent range along the axis with the default unit context,
+    /// see `resolve_pos`.
+    pub fn set_pos_by_pct(&mut self, start: Real, length: Real) -> bool {
+        self.resolve_pos(start, length, &UnitContext::default())
+    }
+
+    /// Resolve the relative units by the base of the percentages and the sizes of the unit context. Returns `true`
+    /// if the value depends on the base, that is it has the percentage part.
+    pub fn resolve(&mut self, base: Real, units: &UnitContext) -> bool {
+        match self.calc() {
+            Some(calc) if self.1 != ValueType::Px => {
+                let is_pct = matches!(self.1, ValueType::Pct(_)) || calc.pct != 0.0;
+                self.0 = calc.resolve(base, units);
+                is_pct
+            },
+            _ => false,
+        }
+    }
+
+    /// Resolve the coordinate by the parent range along the axis, the coordinate with the percentage part
+    /// is offset by the range start.
+    pub fn resolve_pos(&mut self, start: Real, length: Real, units: &UnitContext) -> bool {
+        let is_pct = self.resolve(length, units);
+        if is_pct {
+            self.0 += start;
+        }
+        is_pct
+    }
+
+    /// Length of the value as the calc expression, `None` for the auto value.
+    pub fn calc(&self) -> Option<Calc> {
+        let mut calc = Calc::default();
+        match self.1 {
+            ValueType::Auto => return None,
+            ValueType::Px => calc.px = self.0,
+            ValueType::Pct(pct) => calc.pct = pct,
+            ValueType::Em(em) => calc.em = em,
+            ValueType::Rem(rem) => calc.rem = rem,
+            ValueType::Vw(vw) => calc.vw = vw,
+            ValueType::Vh(vh) => calc.vh = vh,
+            ValueType::Vmin(vmin) => calc.vmin = vmin,
+            ValueType::Calc(value) => calc = value,
+        }
+        Some(calc)
+    }
+
+    /// Combine the lengths of the values, the resolved values are combined as well. The auto value is taken
+    /// as the zero length when it is combined with the length, as the auto values are only combined together.
+    fn combine(self, rhs: Self, rhs_factor: Real) -> Self {
+        match (self.calc(), rhs.calc()) {
+            (None, None) => Value(self.0 + rhs.0 * rhs_factor, ValueType::Auto),
+            (calc, rhs_calc) => {
+                let length = |value: Self| if value.1 == ValueType::Auto { 0.0 } else { value.0 };
+                let resolved = length(self) + length(rhs) * rhs_factor;
+                let value = (calc.unwrap_or_default() + rhs_calc.unwrap_or_default() * rhs_factor).to_value();
+                match value.1 {
+                    ValueType::Px => value,
+                    value_type => Value(resolved, value_type),
+                }
+            },
         }
     }
 }
 
-impl<T: Copy + Add<Output = T>> Add for Value<T> {
+/// The lengths in the different units are combined into the calc expression, such as `Pct(50) - 10`.
+impl<R: Into<RealValue>> Add<R> for Value<Real> {
     type Output = Self;
 
-    fn add(mut self, rhs: Self) -> Self::Output {
-        assert_eq!(self.1, rhs.1);
-        self.0 = self.0 + rhs.0;
-        self
+    fn add(self, rhs: R) -> Self::Output {
+        self.combine(rhs.into(), 1.0)
     }
 }
 
-impl<T: Copy + Sub<Output = T>> Sub for Value<T> {
+impl<R: Into<RealValue>> Sub<R> for Value<Real> {
     type Output = Self;
 
-    fn sub(mut self, rhs: Self) -> Self::Output {
-        assert_eq!(self.1, rhs.1);
-        self.0 = self.0 - rhs.0;
-        self
+    fn sub(self, rhs: R) -> Self::Output {
+        self.combine(rhs.into(), -1.0)
+    }
+}
+
+impl Mul<Real> for Value<Real> {
+    type Output = Self;
+
+    fn mul(self, rhs: Real) -> Self::Output {
+        match self.calc() {
+            Some(calc) => {
+                let value = (calc * rhs).to_value();
+                match value.1 {
+                    ValueType::Px => value,
+                    value_type => Value(self.0 * rhs, value_type),
+                }
+            },
+            None => Value(self.0 * rhs, ValueType::Auto),
+        }
+    }
+}
+
+impl Div<Real> for Value<Real> {
+    type Output = Self;
+
+    fn div(self, rhs: Real) -> Self::Output {
+        self * (1.0 / rhs)
+    }
+}
+
+impl Neg for Value<Real> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self * -1.0
     }
 }
 
@@ -270,3 +524,127 @@ impl ConvertTo<Option<RealValue>> for Pct<i32> {
         Some(self.into())
     }
 }
+
+macro_rules! unit_conversions {
+    ($($unit:ident => $constructor:ident),*) => {
+        $(
+            impl From<$unit<Real>> for RealValue {
+                fn from(v: $unit<Real>) -> Self {
+                    RealValue::$constructor(v.0)
+                }
+            }
+
+            impl From<$unit<i32>> for RealValue {
+                fn from(v: $unit<i32>) -> Self {
+                    RealValue::$constructor(v.0 as Real)
+                }
+            }
+
+            impl ConvertTo<RealValue> for $unit<Real> {
+                fn convert(self) -> RealValue {
+                    self.into()
+                }
+            }
+
+            impl ConvertTo<Option<RealValue>> for $unit<Real> {
+                fn convert(self) -> Option<RealValue> {
+                    Some(self.into())
+                }
+            }
+
+            impl ConvertTo<RealValue> for $unit<i32> {
+                fn convert(self) -> RealValue {
+                    self.into()
+                }
+            }
+
+            impl ConvertTo<Option<RealValue>> for $unit<i32> {
+                fn convert(self) -> Option<RealValue> {
+                    Some(self.into())
+                }
+            }
+        )*
+    };
+}
+
+unit_conversions!(Em => em, Rem => rem, Vw => vw, Vh => vh, Vmin => vmin);
+
+/// Add and subtract the lengths in the units, the result is the calc expression value.
+macro_rules! unit_arithmetic {
+    ($($lhs:ty),* => $rhs:tt) => {
+        $(unit_arithmetic!(@lhs $lhs => $rhs);)*
+    };
+    (@lhs $lhs:ty => [$($rhs:ty),*]) => {
+        $(
+            impl Add<$rhs> for $lhs {
+                type Output = RealValue;
+
+                fn add(self, rhs: $rhs) -> Self::Output {
+                    RealValue::from(self) + rhs
+                }
+            }
+
+            impl Sub<$rhs> for $lhs {
+                type Output = RealValue;
+
+                fn sub(self, rhs: $rhs) -> Self::Output {
+                    RealValue::from(self) - rhs
+                }
+            }
+        )*
+    };
+}
+
+unit_arithmetic!(Pct<i32>, Pct<Real> => [
+    i32, Real, RealValue, Em<i32>, Em<Real>, Rem<i32>, Rem<Real>, Vw<i32>, Vw<Real>, Vh<i32>, Vh<Real>, Vmin<i32>,
+    Vmin<Real>
+]);
+unit_arithmetic!(
+    Em<i32>, Em<Real>, Rem<i32>, Rem<Real>, Vw<i32>, Vw<Real>, Vh<i32>, Vh<Real>, Vmin<i32>, Vmin<Real> => [
+        i32, Real, RealValue, Pct<i32>, Pct<Real>, Em<i32>, Em<Real>, Rem<i32>, Rem<Real>, Vw<i32>, Vw<Real>,
+        Vh<i32>, Vh<Real>, Vmin<i32>, Vmin<Real>
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combine_units() {
+        assert_eq!(RealValue::px(10.0) + 5, RealValue::px(15.0));
+        assert_eq!(
+            Pct(30) + 20,
+            Value(
+                20.0,
+                ValueType::Calc(Calc {
+                    px: 20.0,
+                    pct: 30.0,
+                    ..Default::default()
+                })
+            )
+        );
+        assert_eq!(Pct(50) - 50 - Pct(50), RealValue::px(-50.0));
+        assert_eq!(RealValue::pct(10.0) + Pct(20), RealValue::pct(30.0));
+        assert_eq!((Em(2) + Em(1.0)) * 2.0, RealValue::em(6.0));
+        assert_eq!(-(RealValue::from(Vh(100)) / 2.0), RealValue::vh(-50.0));
+        assert_eq!(Pct(50) - RealValue::auto(), RealValue::pct(50.0));
+        assert_eq!(RealValue::auto() + 10, RealValue::px(10.0));
+
+        let mut height = Vh(100) - 60;
+        let units = UnitContext::default().with_viewport(800.0, 600.0);
+        assert!(!height.resolve(1000.0, &units));
+        assert_eq!(height.val(), 540.0);
+
+        let mut width = Pct(50) - Em(1) + Vmin(10) - Rem(1.0);
+        assert!(width.resolve(200.0, &units.with_font_size(20.0)));
+        assert_eq!(width.val(), 100.0 - 20.0 + 60.0 - 16.0);
+
+        let mut x = Pct(50) - 10;
+        assert!(x.resolve_pos(100.0, 200.0, &units));
+        assert_eq!(x.val(), 190.0);
+        let mut px = RealValue::px(7.0);
+        assert!(!px.resolve(100.0, &units));
+        assert_eq!(px.val(), 7.0);
+    }
+}
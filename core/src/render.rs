@@ -1,6 +1,18 @@
 use std::fmt::Debug;
 
-use crate::{Color, CompositeShape};
+use crate::{BlendMode, Color, CompositeShape};
+
+/// Optional features of the renderers, the tree is drawn with a fallback if a feature is not supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderFeature {
+    /// Offscreen layers of the groups, without them the group transparency is applied to each child.
+    Layers,
+    /// Blending of the group layers by the mode, without it the layers are drawn with the normal blending.
+    BlendMode(BlendMode),
+    /// Clips by the circle, the rounded rectangle and the path outlines, and the intersection of the nested clips.
+    /// Without it the clips are approximated by their bounds.
+    ShapedClips,
+}
 
 pub trait Render {
     type Error: Debug;
@@ -12,5 +24,11 @@ pub trait Render {
     #[allow(unused_variables)]
     fn set_dimensions(&mut self, physical_width: u32, physical_height: u32, device_pixel_ratio: f64) {}
 
+    /// Whether the renderer supports the feature, no optional features are supported by default.
+    #[allow(unused_variables)]
+    fn supports(&self, feature: RenderFeature) -> bool {
+        false
+    }
+
     fn render(&mut self, node: &mut dyn CompositeShape) -> Result<bool, Self::Error>;
 }
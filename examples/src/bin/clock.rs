@@ -1,10 +1,10 @@
-use std::{env, f32::consts::PI};
+use std::f32::consts::PI;
 
 use chrono::{DateTime, Datelike, Local, Timelike};
 
 use exgui::{
-    builder::*, AlignHor::*, AlignVer::*, ChangeView, Color, Comp, Gradient, Model, Node, PathCommand::*, Real,
-    SystemMessage,
+    builder::*, AlignHor::*, AlignVer::*, ChangeView, Color, ColorInterpolation, Comp, Gradient, Model, Node, Pct,
+    Real, RealValue, SystemMessage, Vmin,
 };
 use exgui_controller_glutin::{glutin, App};
 // use exgui_render_nanovg::NanovgRender as Render;
@@ -15,10 +15,6 @@ const TWO_PI: Real = 2.0 * PI;
 
 #[derive(Debug, Default)]
 struct Clock {
-    clock_size: i32,
-    dial_radius: Real,
-    dial_center: (Real, Real),
-
     am: bool,
     hour: Real,
     minute: Real,
@@ -36,7 +32,6 @@ struct Clock {
 
 #[derive(Clone)]
 pub enum Msg {
-    ResizeWindow((u32, u32)),
     Tick,
 }
 
@@ -45,23 +40,18 @@ impl Model for Clock {
     type Properties = ();
 
     fn create(_props: Self::Properties) -> Self {
-        let (width, height) = INIT_WINDOW_SIZE;
-        let mut clock = Clock::default();
-        clock.size_recalc(width, height);
-        clock
+        Clock::default()
     }
 
     fn system_update(&mut self, msg: SystemMessage) -> Option<Self::Message> {
         match msg {
             SystemMessage::Draw(_) => Some(Msg::Tick),
-            SystemMessage::WindowResized { width, height } => Some(Msg::ResizeWindow((width, height))),
             _ => None,
         }
     }
 
     fn update(&mut self, msg: Self::Message) -> ChangeView {
         match msg {
-            Msg::ResizeWindow((w, h)) => self.size_recalc(w, h),
             Msg::Tick => {
                 let dt: DateTime<Local> = Local::now(); // e.g. `2018-11-28T21:45:59.324310806+09:00`
 
@@ -101,33 +91,33 @@ impl Model for Clock {
     }
 
     fn build_view(&self) -> Node<Self> {
-        let second_hand_len = self.dial_radius * 0.9;
+        let second_hand_len = Self::dial_radius() * 0.9;
         let second_hand_props = HandProperties {
             length: second_hand_len,
             width: 1.0,
             theta: self.second_angle,
         };
         let minute_hand_props = HandProperties {
-            length: self.dial_radius * 0.8,
+            length: Self::dial_radius() * 0.8,
             width: 3.0,
             theta: self.minute_angle,
         };
         let hour_hand_props = HandProperties {
-            length: self.dial_radius * 0.6,
+            length: Self::dial_radius() * 0.6,
             width: 5.0,
             theta: self.hour_angle,
         };
 
-        let silver = Color::RGB(196.0 / 255.0, 199.0 / 255.0, 206.0 / 255.0);
-        let darksilver = Color::RGB(148.0 / 255.0, 152.0 / 255.0, 161.0 / 255.0);
-        let darkgray = Color::RGB(169.0 / 255.0, 169.0 / 255.0, 169.0 / 255.0);
-        let boss_rad = 6.0;
+        let silver = Color::from_rgb8(196, 199, 206);
+        let darksilver = Color::from_rgb8(148, 152, 161);
+        let darkgray = Color::named("darkgray").unwrap();
+        let boss_rad: Real = 6.0;
 
         let mut set = vec![];
 
         let dial = circle()
             .center(0, 0)
-            .radius(self.dial_radius)
+            .radius(Self::dial_radius())
             .stroke((silver, 3))
             .fill(Color::RGB(0.2, 0.0, 0.8))
             .build();
@@ -147,8 +137,8 @@ impl Model for Clock {
 
         let date = text(format!("{:4}-{:02}-{:02}", self.year, self.month, self.day))
             .id("date")
-            .pos(0, self.dial_radius * 0.7)
-            .font_name("Roboto-Regular")
+            .pos(0, Self::dial_radius() * 0.5)
+            .font_name("Roboto")
             .font_size(24)
             .align((Center, Baseline))
             .fill(silver)
@@ -168,18 +158,22 @@ impl Model for Clock {
             .center(0, 0)
             .radius(boss_rad)
             .stroke(darkgray)
-            .fill(Gradient::Radial {
-                center: (0.0, 0.0),
-                inner_radius: 0.0,
-                outer_radius: boss_rad,
-                start_color: silver,
-                end_color: darksilver,
-            })
+            .fill(
+                Gradient::radial((0, 0), 0, boss_rad)
+                    .stop(0.0, silver)
+                    .stop(1.0, darksilver)
+                    .interpolation(ColorInterpolation::LinearLight),
+            )
             .build();
         set.push(boss);
 
-        group()
-            .transform(translate(self.dial_center.0, self.dial_center.1))
+        // The padding moves the origin of the clock to the center of the window
+        rect()
+            .width(Pct(100))
+            .height(Pct(100))
+            .padding_left(Pct(50))
+            .padding_top(Pct(50))
+            .transparency(1.0)
             .children(set)
             .build()
     }
@@ -214,28 +208,23 @@ impl Model for Clock {
 }
 
 impl Clock {
-    fn size_recalc(&mut self, width: u32, height: u32) -> ChangeView {
-        let clock_size = width.min(height) as i32 - 2;
-        let dial_center = (width as f32 / 2.0, height as f32 / 2.0);
-        if self.clock_size != clock_size || self.dial_center != dial_center {
-            self.clock_size = clock_size;
-            self.dial_center = dial_center;
-            self.dial_radius = self.clock_size as f32 / 2.0;
-            ChangeView::Rebuild
-        } else {
-            ChangeView::None
-        }
+    /// The dial fits the window, so the sizes of the clock parts are relative to the smaller window side.
+    fn dial_radius() -> RealValue {
+        Vmin(50) - 1
     }
 
-    fn build_num(&self, n: i32, len: Real, font_size: Real) -> Node<Clock> {
+    /// The hour label stays upright at its point of the dial circle. It is not laid out along the text path,
+    /// as the path geometry is in px while the dial is sized in vmin, and the path would turn the lower labels
+    /// upside down.
+    fn build_num(&self, n: i32, len: RealValue, font_size: Real) -> Node<Clock> {
         let radians_per_hour = TWO_PI / 12.0;
         let x = len * (n as Real * radians_per_hour).sin();
         let y = -len * (n as Real * radians_per_hour).cos();
-        let silver = Color::RGB(196.0 / 255.0, 199.0 / 255.0, 206.0 / 255.0);
+        let silver = Color::from_rgb8(196, 199, 206);
 
         text(format!("{}", n))
             .pos(x, y)
-            .font_name("Roboto-Regular")
+            .font_name("Roboto")
             .font_size(font_size)
             .align((Center, Middle))
             .fill(silver)
@@ -244,17 +233,15 @@ impl Clock {
 
     fn build_tick(&self, m: Real, len: Real, width: Real) -> Node<Clock> {
         let radians_per_sec = TWO_PI / 60.0;
-        let ticks_radius = self.dial_radius * 0.925;
-
-        path(vec![
-            Move([0.0, -ticks_radius]),
-            Line([0.0, -ticks_radius - len]),
-            Close,
-        ])
-        .fill(Color::White)
-        .stroke((Color::White, width))
-        .transform(rotate(m * radians_per_sec))
-        .build()
+        let ticks_radius = Self::dial_radius() * 0.925;
+
+        rect()
+            .left_top_pos(-width / 2.0, -ticks_radius - len)
+            .width(width)
+            .height(len)
+            .fill(Color::White)
+            .transform(rotate(m * radians_per_sec))
+            .build()
     }
 }
 
@@ -265,7 +252,7 @@ struct Hand {
 
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
 struct HandProperties {
-    length: Real,
+    length: RealValue,
     width: Real,
     theta: Real,
 }
@@ -296,9 +283,11 @@ impl Model for Hand {
     }
 
     fn build_view(&self) -> Node<Hand> {
-        path(vec![Move([0.0, 0.0]), Line([0.0, -self.props.length]), Close])
+        rect()
+            .left_top_pos(-self.props.width / 2.0, -self.props.length)
+            .width(self.props.width)
+            .height(self.props.length)
             .fill(Color::White)
-            .stroke((Color::White, self.props.width))
             .transform(rotate(self.theta))
             .build()
     }
@@ -322,13 +311,6 @@ fn main() {
     .unwrap();
     app.init().unwrap();
 
-    let font_path = env::current_dir()
-        .unwrap()
-        .join("examples")
-        .join("resources")
-        .join("Roboto-Regular.ttf");
-    app.renderer_mut().load_font("Roboto-Regular", font_path).unwrap();
-
     let comp = Comp::new(Clock::create(()));
     app.run(comp);
 }
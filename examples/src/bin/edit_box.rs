@@ -1,4 +1,4 @@
-use std::{env, mem, time::Duration};
+use std::{mem, time::Duration};
 
 use exgui::{
     builder::*, ChangeView, Color, Comp, Model, MousePos, Node, PathCommand::*, Real, Shaped, SystemMessage, Text,
@@ -210,7 +210,7 @@ impl Model for EditBox {
                             text(&self.initial_text)
                                 .fill(Color::Black)
                                 .id("text")
-                                .font_name("Roboto-Regular")
+                                .font_name("Roboto")
                                 .font_size(25),
                         ),
                     ),
@@ -230,39 +230,17 @@ impl Model for EditBox {
                     focus_pos = focus_pos - matrix.translate_xy().0;
                 }
 
-                if let Some(idx) = text
-                    .glyph_positions
-                    .iter()
-                    .position(|pos| focus_pos >= pos.x && focus_pos <= pos.max_x())
-                {
-                    let pos = text.glyph_positions[idx];
-                    let before = focus_pos - pos.x;
-                    let after = pos.max_x() - focus_pos;
-                    if before < after {
-                        self.caret.idx = idx;
-                    } else {
-                        self.caret.idx = idx + 1;
-                    }
-                }
-                if let Some(last) = text.glyph_positions.last() {
-                    if focus_pos > last.max_x() {
-                        self.caret.idx = text.glyph_positions.len();
-                    }
-                }
+                self.caret.idx = text.char_index_at(focus_pos, 0.0);
                 self.caret.update_action(CaretAction::Redraw);
             }
             CaretAction::MoveLeft => {
-                self.caret.idx = self.caret.idx.checked_sub(1).unwrap_or(0);
+                let text = Self::get_text_mut(view);
+                self.caret.idx = text.prev_caret(self.caret.idx);
                 self.caret.update_action(CaretAction::Redraw);
             }
             CaretAction::MoveRight => {
                 let text = Self::get_text_mut(view);
-                self.caret.idx = self.caret.idx
-                    + if text.glyph_positions.len() > self.caret.idx {
-                        1
-                    } else {
-                        0
-                    };
+                self.caret.idx = text.next_caret(self.caret.idx);
                 self.caret.update_action(CaretAction::Redraw);
             }
             CaretAction::MoveStart => {
@@ -277,11 +255,11 @@ impl Model for EditBox {
             CaretAction::Input(ch) => {
                 let text = Self::get_text_mut(view);
                 if self.caret.idx < text.glyph_positions.len() {
-                    text.insert(self.caret.idx, ch);
+                    self.caret.idx = text.insert(self.caret.idx, ch);
                 } else {
                     text.push(ch);
+                    self.caret.idx += 1;
                 }
-                self.caret.idx += 1;
                 self.caret.update_action(CaretAction::Redraw);
             }
             CaretAction::Delete => {
@@ -294,18 +272,13 @@ impl Model for EditBox {
             CaretAction::Backspace => {
                 let text = Self::get_text_mut(view);
                 if self.caret.idx > 0 {
-                    self.caret.idx -= 1;
-                    text.remove(self.caret.idx);
+                    self.caret.idx = text.remove(self.caret.idx - 1).start;
                 }
                 self.caret.update_action(CaretAction::Redraw);
             }
             CaretAction::Redraw => {
                 let text = Self::get_text_mut(view);
-                let caret_pos = if self.caret.idx > 0 {
-                    text.glyph_positions[self.caret.idx - 1].max_x()
-                } else {
-                    0.0
-                };
+                let caret_pos = text.caret_x(self.caret.idx);
                 let text_end_pos = text.glyph_positions.last().map(|pos| pos.max_x()).unwrap_or(0.0);
                 let line_height = text.metrics.map(|m| m.line_height).unwrap_or(text.font_size.0);
                 Self::draw_caret(view, caret_pos, text_end_pos, line_height, self.caret.show);
@@ -382,13 +355,6 @@ fn main() {
     .unwrap();
     app.init().unwrap();
 
-    let font_path = env::current_dir()
-        .unwrap()
-        .join("examples")
-        .join("resources")
-        .join("Roboto-Regular.ttf");
-    app.renderer_mut().load_font("Roboto-Regular", font_path).unwrap();
-
     let comp = Comp::new(EditBox::create(()));
     app.run(comp);
 }
@@ -1,8 +1,8 @@
-use std::{env, time::Duration};
+use std::time::Duration;
 
 use exgui::{
-    builder::*, ChangeView, Color, Comp, LineCap, LineJoin, Model, Node, PathCommand::*, Pct, Real, Shaped, Stroke,
-    SystemMessage, Transform, VirtualKeyCode,
+    builder::*, ChangeView, Color, Comp, Image, LineCap, LineJoin, Model, Node, PathCommand::*, Pattern, Pct, Real,
+    Shaped, Stroke, SystemMessage, Transform, VirtualKeyCode,
 };
 use exgui_controller_glutin::{glutin, App};
 // use exgui_render_nanovg::NanovgRender as Render;
@@ -16,9 +16,8 @@ use self::{
 mod animate;
 mod levels;
 
+/// The field is laid out around the center of the canvas, so the canvas size is only known by the layout.
 struct Canvas {
-    width: Real,
-    height: Real,
     cell_size: Real,
     scale_factor: Animate<Real>,
 }
@@ -26,24 +25,56 @@ struct Canvas {
 impl Canvas {
     const HEIGHT: Real = 600.0;
     const WIDTH: Real = 800.0;
+    const CELL_SIZE: Real = 25.0;
 
-    fn calc_cell_size(_width: Real, height: Real) -> Real {
-        height / 24.0
+    fn new() -> Self {
+        Self {
+            cell_size: Self::CELL_SIZE,
+            scale_factor: Animate::new(0.01, 1.0, 0.002),
+        }
     }
+}
+
+/// Images of the tiles, they are made once and shared by all the shapes filled with them.
+struct Textures {
+    brick: Image,
+    floor: Image,
+}
+
+impl Textures {
+    const SIZE: u32 = 30;
 
     fn new() -> Self {
         Self {
-            width: Self::WIDTH,
-            height: Self::HEIGHT,
-            cell_size: Self::calc_cell_size(Self::WIDTH, Self::HEIGHT),
-            scale_factor: Animate::new(0.01, 1.0, 0.002),
+            brick: Self::brick(),
+            floor: Self::floor(),
         }
     }
 
-    fn resize(&mut self, width: Real, height: Real) {
-        self.width = width;
-        self.height = height;
-        self.cell_size = Self::calc_cell_size(self.width, self.height);
+    /// Two rows of bricks with the joints shifted, the mortar is transparent.
+    fn brick() -> Image {
+        let row_height = Self::SIZE / 2;
+        Image::from_fn(Self::SIZE, Self::SIZE, |x, y| {
+            let (row, row_y) = (y / row_height, y % row_height);
+            let joint = if row == 0 { Self::SIZE / 3 } else { Self::SIZE * 2 / 3 };
+            if row_y < 1 || row_y >= row_height - 1 || (joint - 1..=joint).contains(&x) {
+                Color::RGBA(0.0, 0.0, 0.0, 0.0)
+            } else {
+                let grain = ((x * 7919 + y * 104_729) % 13) as Real / 12.0;
+                let shade = 0.9 + 0.1 * grain;
+                Color::RGB(shade, 0.4 * shade, 0.2 * shade)
+            }
+        })
+    }
+
+    fn floor() -> Image {
+        Image::from_fn(Self::SIZE, Self::SIZE, |x, y| {
+            if x == 0 || y == 0 {
+                Color::RGB(0.75, 0.85, 0.95)
+            } else {
+                Color::RGB(0.8, 0.9, 1.0)
+            }
+        })
     }
 }
 
@@ -102,7 +133,6 @@ enum Direction {
 }
 
 enum Msg {
-    Resize { width: Real, height: Real },
     Draw(Duration),
     Scroll(Real),
     KeyDown(VirtualKeyCode),
@@ -121,6 +151,7 @@ struct Game {
     level: Level,
     state: GameState,
     docker: Docker,
+    textures: Textures,
 }
 
 impl Game {
@@ -133,19 +164,16 @@ impl Game {
         self.docker.animate(elapsed);
     }
 
+    /// The field is scaled around its origin, which is the center of the canvas.
     fn field_transform(&self) -> Transform {
         let scale_factor = self.canvas.scale_factor.val();
-        Transform::new()
-            .with_scale(scale_factor, scale_factor)
-            .with_translation(
-                -(scale_factor * self.canvas.width - self.canvas.width) / 2.0,
-                -(scale_factor * self.canvas.height - self.canvas.height) / 2.0,
-            )
+        Transform::new().with_scale(scale_factor, scale_factor)
     }
 
+    /// Position of the field relative to the center of the canvas.
     fn field_pos(&self) -> (Real, Real) {
-        let field_x = (self.canvas.width - self.level.cols() as Real * self.canvas.cell_size) / 2.0;
-        let field_y = (self.canvas.height - self.level.rows() as Real * self.canvas.cell_size) / 2.0;
+        let field_x = -(self.level.cols() as Real * self.canvas.cell_size) / 2.0;
+        let field_y = -(self.level.rows() as Real * self.canvas.cell_size) / 2.0;
         (field_x, field_y)
     }
 
@@ -234,6 +262,7 @@ impl Model for Game {
             level: Level::new(),
             state: GameState::Run,
             docker: Default::default(),
+            textures: Textures::new(),
         };
         game.reset_docker();
         game
@@ -241,10 +270,6 @@ impl Model for Game {
 
     fn system_update(&mut self, msg: SystemMessage) -> Option<Self::Message> {
         match msg {
-            SystemMessage::WindowResized { width, height } => Some(Msg::Resize {
-                width: width as Real,
-                height: height as Real,
-            }),
             SystemMessage::Draw(elapsed) => Some(Msg::Draw(elapsed)),
             _ => None,
         }
@@ -252,11 +277,6 @@ impl Model for Game {
 
     fn update(&mut self, msg: Self::Message) -> ChangeView {
         match msg {
-            Msg::Resize { width, height } => {
-                self.canvas.resize(width, height);
-                self.reset_docker();
-                ChangeView::Rebuild
-            }
             Msg::Draw(elapsed) => {
                 if self.is_transient() {
                     self.animate(elapsed);
@@ -333,10 +353,18 @@ impl Model for Game {
             cells.push(docker);
         }
 
+        let cell_size = self.canvas.cell_size;
+        // The padding moves the origin of the field to the center of the canvas
         rect()
             .width(Pct(100))
             .height(Pct(100))
-            .fill(Color::RGB(0.8, 0.9, 1.0))
+            .padding_left(Pct(50))
+            .padding_top(Pct(50))
+            .fill(
+                Pattern::new(self.textures.floor.clone())
+                    .tile_size(cell_size, cell_size)
+                    .offset(Pct(50) + field_x, Pct(50) + field_y),
+            )
             .on_mouse_scroll(|case| Msg::Scroll(case.event.delta.1 as Real))
             .child(
                 group()
@@ -349,13 +377,10 @@ impl Model for Game {
                                 .fill(Color::RGBA(0.0, 0.3, 0.0, 0.7))
                                 .stroke((Color::RGB(0.0, 0.3, 0.0), 1))
                                 .padding(10)
-                                .transform(translate(
-                                    self.canvas.width / 2.0 - 108.0,
-                                    self.canvas.height / 2.0 - 25.0,
-                                ))
+                                .transform(translate(-108.0, -25.0))
                                 .child(
                                     text(format!("Level {} completed", self.level.number()))
-                                        .font_name("Roboto-Regular")
+                                        .font_name("Roboto")
                                         .font_size(24)
                                         .fill(Color::White),
                                 ),
@@ -398,58 +423,12 @@ impl Model for Game {
 
 impl Game {
     fn build_wall(&self, x: Real, y: Real) -> Node<Self> {
-        let brick_color = Color::RGB(1.0, 0.4, 0.2);
-        let brick_space = self.canvas.cell_size / 15.0;
-        let brick_height = self.canvas.cell_size / 2.0 - brick_space;
-        let brick_chunk_size = (self.canvas.cell_size - brick_space) / 3.0;
-        let round_radius = brick_space / 1.5;
-        let epsilon = self.canvas.cell_size / 100.0;
-
         rect()
             .id("wall")
             .width(self.canvas.cell_size)
-            .height(self.canvas.cell_size)
-            .transparency(1.0)
+            .aspect_ratio(1.0)
+            .fill(Pattern::new(self.textures.brick.clone()).tile_size(self.canvas.cell_size, self.canvas.cell_size))
             .transform(translate(x, y))
-            .child(
-                rect()
-                    .width(brick_chunk_size + epsilon)
-                    .height(brick_height)
-                    .fill(brick_color)
-                    .rounding_top_right(round_radius)
-                    .rounding_bottom_right(round_radius)
-                    .transform(translate(-epsilon, brick_space / 2.0)),
-            )
-            .child(
-                rect()
-                    .width(brick_chunk_size * 2.0 + epsilon)
-                    .height(brick_height)
-                    .fill(brick_color)
-                    .rounding_top_left(round_radius)
-                    .rounding_bottom_left(round_radius)
-                    .transform(translate(brick_chunk_size + epsilon + brick_space, brick_space / 2.0)),
-            )
-            .child(
-                rect()
-                    .width(brick_chunk_size * 2.0 + epsilon)
-                    .height(brick_height)
-                    .fill(brick_color)
-                    .rounding_top_right(round_radius)
-                    .rounding_bottom_right(round_radius)
-                    .transform(translate(-epsilon, brick_height + brick_space * 1.5)),
-            )
-            .child(
-                rect()
-                    .width(brick_chunk_size + epsilon)
-                    .height(brick_height)
-                    .fill(brick_color)
-                    .rounding_top_left(round_radius)
-                    .rounding_bottom_left(round_radius)
-                    .transform(translate(
-                        brick_chunk_size * 2.0 + epsilon + brick_space,
-                        brick_height + brick_space * 1.5,
-                    )),
-            )
             .build()
     }
 
@@ -463,7 +442,7 @@ impl Game {
         rect()
             .id(format!("box_{}_{}", row, col))
             .width(self.canvas.cell_size)
-            .height(self.canvas.cell_size)
+            .aspect_ratio(1.0)
             .transparency(1.0)
             .transform(translate(x, y))
             .child(
@@ -529,7 +508,7 @@ impl Game {
         rect()
             .id("docker")
             .width(self.canvas.cell_size)
-            .height(self.canvas.cell_size)
+            .aspect_ratio(1.0)
             .transparency(1.0)
             .transform(translate(x, y))
             .child(
@@ -569,7 +548,7 @@ impl Game {
         rect()
             .id("place")
             .width(self.canvas.cell_size)
-            .height(self.canvas.cell_size)
+            .aspect_ratio(1.0)
             .transparency(1.0)
             .transform(translate(x, y))
             .child(
@@ -606,13 +585,6 @@ fn main() {
     .unwrap();
     app.init().unwrap();
 
-    let font_path = env::current_dir()
-        .unwrap()
-        .join("examples")
-        .join("resources")
-        .join("Roboto-Regular.ttf");
-    app.renderer_mut().load_font("Roboto-Regular", font_path).unwrap();
-
     let comp = Comp::new(Game::create(()));
     app.run(comp);
 }
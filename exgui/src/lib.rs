@@ -1,5 +1,7 @@
 pub use exgui_builder as builder;
 pub use exgui_core::*;
+#[cfg(feature = "svg")]
+pub use exgui_svg as svg;
 
 #[cfg(test)]
 mod tests {
@@ -1,84 +1,310 @@
-use std::{ops::Mul, path::Path};
+use std::{cell::RefCell, collections::HashMap, fmt, ops::Mul, path::Path, rc::Rc, sync::Arc};
 
 use exgui_core::{
-    AlignHor, AlignVer, Clip, Color, CompositeShape, Fill, GlyphPos, Gradient, LineCap, LineJoin, Padding, Paint, Real,
-    Render, Shape, Stroke, Text, TextMetrics, Transform, TransformMatrix,
+    shape_runs, AlignHor, AlignVer, Clip, Color, ColorStop, CompositeShape, Effect, Fill, FillRule, FontError, FontFace,
+    FontRegistry, FontStyle, FontWeight, Gradient, Image, LineCap, LineJoin, Padding, Paint, Path as PathShape,
+    PathError, PathSegment, Pattern, PatternRepeat, Real, Rect, Render, Shadow, Shape, ShapedText, SpanStyle, Stroke,
+    Text, TextDecoration, TextMeasurer, TextMetrics, Transform, TransformMatrix, UnitContext,
 };
 use nanovg::{
     Alignment, Clip as NanovgClip, Color as NanovgColor, Context, ContextBuilder, CreateFontError, Font as NanovgFont,
-    Frame, Gradient as NanovgGradient, LineCap as NanovgLineCap, LineJoin as NanovgLineJoin, Paint as NanovgPaint,
-    PathOptions, Scissor as NanovgScissor, StrokeOptions, TextOptions, Transform as NanovgTransform,
+    Frame, Gradient as NanovgGradient, Image as NanovgImage, ImagePattern, LineCap as NanovgLineCap,
+    LineJoin as NanovgLineJoin, Paint as NanovgPaint, Path as NanovgPath, PathOptions, Scissor as NanovgScissor,
+    Solidity, StrokeOptions, TextOptions, Transform as NanovgTransform, Winding,
 };
 
-struct ToNanovgPaint(Paint);
+/// Tolerance of the curves flattened to find the sub-path holes, the sub-paths rarely come close to each other.
+const HOLE_TOLERANCE: Real = 1.0;
 
-impl ToNanovgPaint {
+/// Padded pixels of the pattern images, by the image identity and the repeat mode. They are kept across
+/// the frames and dropped after the frame in which their images are gone.
+#[derive(Default)]
+struct PatternImageCache {
+    pixels: RefCell<HashMap<(usize, PatternRepeat), CachedPixels>>,
+}
+
+/// Padded pixels along with the clone of the pattern image, the clone keeps the identity from being reused.
+struct CachedPixels {
+    source: Image,
+    width: usize,
+    height: usize,
+    pixels: Rc<Vec<u32>>,
+}
+
+impl PatternImageCache {
+    /// Drop the pixels of the images that are no longer used by the patterns.
+    fn evict(&self) {
+        self.pixels.borrow_mut().retain(|_, cached| cached.source.is_shared());
+    }
+
+    /// Nanovg clamps the image to its edge pixels, so the image that is not repeated along an axis
+    /// is padded with the transparent pixels along the axis.
+    fn padding(repeat: PatternRepeat) -> (usize, usize) {
+        (!repeat.repeat_x() as usize, !repeat.repeat_y() as usize)
+    }
+
+    /// Padded pixels of the pattern image with their width and height.
+    fn pixels(&self, pattern: &Pattern) -> (usize, usize, Rc<Vec<u32>>) {
+        let key = (pattern.image.id(), pattern.repeat);
+        if let Some(cached) = self.pixels.borrow().get(&key) {
+            return (cached.width, cached.height, cached.pixels.clone());
+        }
+
+        let (pad_x, pad_y) = Self::padding(pattern.repeat);
+        let (width, height) = (pattern.image.width() as usize, pattern.image.height() as usize);
+        let (padded_width, padded_height) = (width + pad_x * 2, height + pad_y * 2);
+        let mut pixels = vec![0; padded_width * padded_height];
+        for (y, row) in pattern.image.data().chunks_exact(width * 4).enumerate() {
+            let start = (y + pad_y) * padded_width + pad_x;
+            for (pixel, rgba) in pixels[start..start + width].iter_mut().zip(row.chunks_exact(4)) {
+                *pixel = u32::from_ne_bytes([rgba[0], rgba[1], rgba[2], rgba[3]]);
+            }
+        }
+        let pixels = Rc::new(pixels);
+        let cached = CachedPixels {
+            source: pattern.image.clone(),
+            width: padded_width,
+            height: padded_height,
+            pixels: pixels.clone(),
+        };
+        self.pixels.borrow_mut().insert(key, cached);
+        (padded_width, padded_height, pixels)
+    }
+}
+
+impl fmt::Debug for PatternImageCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PatternImageCache")
+            .field("len", &self.pixels.borrow().len())
+            .finish()
+    }
+}
+
+/// Images of the patterns created in the context for a frame from the cached pixels.
+///
+/// Nanovg images borrow the context, so they can't be kept in the renderer along with it. They are dropped
+/// after the frame is finished, since nanovg draws the frame on its end.
+struct PatternImages<'a> {
+    context: &'a Context,
+    cache: &'a PatternImageCache,
+    images: RefCell<HashMap<(usize, PatternRepeat), Rc<NanovgImage<'a>>>>,
+}
+
+impl<'a> PatternImages<'a> {
+    fn new(context: &'a Context, cache: &'a PatternImageCache) -> Self {
+        Self {
+            context,
+            cache,
+            images: Default::default(),
+        }
+    }
+
+    fn image(&self, pattern: &Pattern) -> Option<Rc<NanovgImage<'a>>> {
+        let key = (pattern.image.id(), pattern.repeat);
+        if let Some(image) = self.images.borrow().get(&key) {
+            return Some(image.clone());
+        }
+
+        let (width, height, pixels) = self.cache.pixels(pattern);
+        let mut builder = NanovgImage::new(self.context);
+        if pattern.repeat.repeat_x() {
+            builder = builder.repeat_x();
+        }
+        if pattern.repeat.repeat_y() {
+            builder = builder.repeat_y();
+        }
+        let image = Rc::new(builder.build_from_rgba(width, height, &pixels).ok()?);
+        self.images.borrow_mut().insert(key, image.clone());
+        Some(image)
+    }
+}
+
+struct ToNanovgPaint<'a> {
+    paint: Paint,
+    image: Option<Rc<NanovgImage<'a>>>,
+}
+
+impl<'a> ToNanovgPaint<'a> {
     fn to_nanovg_color(color: Color) -> NanovgColor {
         let [r, g, b, a] = color.as_arr();
         NanovgColor::new(r, g, b, a)
     }
 
-    fn to_nanovg_gradient(gradient: Gradient) -> NanovgGradient {
+    /// Paint with the coordinates resolved by the bounding box of the painted shape and the unit sizes.
+    fn new(paint: &Paint, bounds: [Real; 4], units: &UnitContext, images: &PatternImages<'a>) -> Self {
+        let image = match paint {
+            Paint::Pattern(pattern) => images.image(pattern),
+            _ => None,
+        };
+        Self {
+            paint: paint.with_bounds(bounds, units),
+            image,
+        }
+    }
+
+    /// The pattern is drawn with the padded image, so its origin is moved by the padding along the image axes.
+    fn to_nanovg_pattern<'b>(pattern: &Pattern, image: &'b NanovgImage<'b>) -> ImagePattern<'b> {
+        let (pad_x, pad_y) = PatternImages::padding(pattern.repeat);
+        let (pad_x, pad_y) = (pad_x as Real, pad_y as Real);
+        let (scale_x, scale_y) = pattern.scale;
+        let (shift_x, shift_y) = (-pad_x * scale_x, -pad_y * scale_y);
+        let (sin, cos) = pattern.rotation.sin_cos();
+        let width = (pattern.image.width() as Real + pad_x * 2.0) * scale_x;
+        let height = (pattern.image.height() as Real + pad_y * 2.0) * scale_y;
+        ImagePattern {
+            image,
+            origin: (
+                (pattern.offset.0.val() + shift_x * cos - shift_y * sin) as f32,
+                (pattern.offset.1.val() + shift_x * sin + shift_y * cos) as f32,
+            ),
+            size: (width as f32, height as f32),
+            angle: pattern.rotation as f32,
+            alpha: pattern.opacity as f32,
+        }
+    }
+
+    /// Nanovg gradients have two colors interpolated in sRGB and padded at the ends. So the first and the last
+    /// stops are drawn at their offsets, while the intermediate stops, the linear light interpolation and
+    /// the repeat and reflect spread are ignored. The conic gradient is filled with its average color.
+    fn to_nanovg_gradient(gradient: &Gradient) -> NanovgGradient {
+        let stops = gradient.normalized_stops();
+        let (first, last) = match (stops.first(), stops.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => {
+                let transparent = Color::RGBA(0.0, 0.0, 0.0, 0.0);
+                (ColorStop::new(0.0, transparent), ColorStop::new(1.0, transparent))
+            }
+        };
+        let lerp = |from: Real, to: Real, t: Real| (from + (to - from) * t) as f32;
         match gradient {
             Gradient::Linear {
                 start: (start_x, start_y),
                 end: (end_x, end_y),
-                start_color,
-                end_color,
-            } => NanovgGradient::Linear {
-                start: (start_x as f32, start_y as f32),
-                end: (end_x as f32, end_y as f32),
-                start_color: Self::to_nanovg_color(start_color),
-                end_color: Self::to_nanovg_color(end_color),
-            },
+                ..
+            } => {
+                let (start_x, start_y, end_x, end_y) = (start_x.val(), start_y.val(), end_x.val(), end_y.val());
+                NanovgGradient::Linear {
+                    start: (lerp(start_x, end_x, first.offset), lerp(start_y, end_y, first.offset)),
+                    end: (lerp(start_x, end_x, last.offset), lerp(start_y, end_y, last.offset)),
+                    start_color: Self::to_nanovg_color(first.color),
+                    end_color: Self::to_nanovg_color(last.color),
+                }
+            }
             Gradient::Box {
                 position: (x, y),
                 size: (width, height),
                 radius,
                 feather,
-                start_color,
-                end_color,
+                ..
             } => NanovgGradient::Box {
-                position: (x as f32, y as f32),
-                size: (width as f32, height as f32),
-                radius: radius as f32,
-                feather: feather as f32,
-                start_color: Self::to_nanovg_color(start_color),
-                end_color: Self::to_nanovg_color(end_color),
+                position: (*x as f32, *y as f32),
+                size: (*width as f32, *height as f32),
+                radius: *radius as f32,
+                feather: *feather as f32,
+                start_color: Self::to_nanovg_color(first.color),
+                end_color: Self::to_nanovg_color(last.color),
             },
             Gradient::Radial {
                 center: (x, y),
                 inner_radius,
                 outer_radius,
-                start_color,
-                end_color,
-            } => NanovgGradient::Radial {
-                center: (x as f32, y as f32),
-                inner_radius: inner_radius as f32,
-                outer_radius: outer_radius as f32,
-                start_color: Self::to_nanovg_color(start_color),
-                end_color: Self::to_nanovg_color(end_color),
-            },
+                ..
+            } => {
+                let (inner_radius, outer_radius) = (inner_radius.val(), outer_radius.val());
+                NanovgGradient::Radial {
+                    center: (x.val() as f32, y.val() as f32),
+                    inner_radius: lerp(inner_radius, outer_radius, first.offset),
+                    outer_radius: lerp(inner_radius, outer_radius, last.offset),
+                    start_color: Self::to_nanovg_color(first.color),
+                    end_color: Self::to_nanovg_color(last.color),
+                }
+            }
+            Gradient::Conic { .. } => {
+                let color = Self::to_nanovg_color(gradient.average_color());
+                NanovgGradient::Linear {
+                    start: (0.0, 0.0),
+                    end: (1.0, 0.0),
+                    start_color: color,
+                    end_color: color,
+                }
+            }
         }
     }
 }
 
-impl NanovgPaint for ToNanovgPaint {
+impl From<Paint> for ToNanovgPaint<'_> {
+    fn from(paint: Paint) -> Self {
+        Self { paint, image: None }
+    }
+}
+
+impl NanovgPaint for ToNanovgPaint<'_> {
     fn fill(&self, context: &Context) {
-        match self.0 {
+        match self.paint {
             Paint::Color(ref color) => Self::to_nanovg_color(*color).fill(context),
-            Paint::Gradient(ref gradient) => Self::to_nanovg_gradient(*gradient).fill(context),
+            Paint::Gradient(ref gradient) => Self::to_nanovg_gradient(gradient).fill(context),
+            // The pattern image that failed to be created is replaced by its average color
+            Paint::Pattern(ref pattern) => match &self.image {
+                Some(image) => Self::to_nanovg_pattern(pattern, image).fill(context),
+                None => Self::to_nanovg_color(pattern.average_color()).fill(context),
+            },
         }
     }
 
     fn stroke(&self, context: &Context) {
-        match self.0 {
+        match self.paint {
             Paint::Color(ref color) => Self::to_nanovg_color(*color).stroke(context),
-            Paint::Gradient(ref gradient) => Self::to_nanovg_gradient(*gradient).stroke(context),
+            Paint::Gradient(ref gradient) => Self::to_nanovg_gradient(gradient).stroke(context),
+            Paint::Pattern(ref pattern) => match &self.image {
+                Some(image) => Self::to_nanovg_pattern(pattern, image).stroke(context),
+                None => Self::to_nanovg_color(pattern.average_color()).stroke(context),
+            },
         }
     }
 }
 
+/// Measurement of the texts by the fonts of the frame context, the layout of the texts is done by the core.
+struct FrameMeasurer<'a> {
+    frame: &'a Frame<'a>,
+}
+
+impl TextMeasurer for FrameMeasurer<'_> {
+    /// Measure the content with the font of the first face, the fallbacks are attached to the font on its creation.
+    /// Runs of the content with the same direction are measured at once in the order they are drawn.
+    fn shape(&self, faces: &[&FontFace], font_size: Real, content: &str) -> Result<ShapedText, FontError> {
+        let face = match faces.first() {
+            Some(face) => face,
+            None => return Ok(ShapedText::default()),
+        };
+        let font = NanovgFont::find(self.frame.context(), &face.name)
+            .map_err(|_| FontError::NotFound(face.name.clone()))?;
+        // Letter spacing is added by the layout to the advances
+        let options = TextOptions {
+            size: font_size as f32,
+            ..Default::default()
+        };
+        let metrics = self.frame.text_metrics(font, options);
+        let metrics = TextMetrics {
+            ascender: metrics.ascender,
+            descender: metrics.descender,
+            line_height: metrics.line_height,
+        };
+
+        let advances = shape_runs(content, |run| {
+            let positions: Vec<_> = self.frame.text_glyph_positions((0.0, 0.0), run).collect();
+            positions
+                .iter()
+                .enumerate()
+                .map(|(idx, pos)| match positions.get(idx + 1) {
+                    Some(next) => (next.x - pos.x) as Real,
+                    None => (pos.max_x - pos.x.min(pos.min_x)) as Real,
+                })
+                .collect()
+        });
+        Ok(ShapedText { metrics, advances })
+    }
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 pub struct BoundingBox {
     pub min_x: Real,
@@ -95,6 +321,20 @@ impl BoundingBox {
     pub fn height(&self) -> Real {
         self.max_y - self.min_y
     }
+
+    pub fn from_bounds([x, y, width, height]: [Real; 4]) -> Self {
+        Self {
+            min_x: x,
+            min_y: y,
+            max_x: x + width,
+            max_y: y + height,
+        }
+    }
+
+    /// Bounds as `[x, y, width, height]`.
+    pub fn bounds(&self) -> [Real; 4] {
+        [self.min_x, self.min_y, self.width(), self.height()]
+    }
 }
 
 impl Mul<BoundingBox> for TransformMatrix {
@@ -115,6 +355,8 @@ pub enum NanovgRenderError {
     ContextIsNotInit,
     InitNanovgContextFailed,
     CreateFontError(CreateFontError, String),
+    Font(FontError),
+    InvalidPath(PathError),
 }
 
 #[derive(Debug, Default)]
@@ -123,6 +365,13 @@ pub struct NanovgRender {
     pub width: f32,
     pub height: f32,
     pub device_pixel_ratio: f32,
+    pub fonts: FontRegistry,
+    /// Data of the faces loaded into the context by the face names, nanovg does not copy it.
+    loaded_fonts: HashMap<String, Arc<Vec<u8>>>,
+    /// Sizes of the relative units, the viewport is set by the render size.
+    units: UnitContext,
+    /// Padded pixels of the pattern images, the images are created from them in each frame.
+    pattern_images: PatternImageCache,
 }
 
 impl Render for NanovgRender {
@@ -146,35 +395,51 @@ impl Render for NanovgRender {
     }
 
     fn render(&mut self, node: &mut dyn CompositeShape) -> Result<bool, Self::Error> {
+        self.load_new_fonts()?;
+
         let need_recalc = node.need_recalc().unwrap_or(true);
         let need_redraw = node.need_redraw().unwrap_or(true);
+        let mut result = Ok(need_redraw);
         let shared_self = &*self;
-        shared_self
-            .context
-            .as_ref()
-            .ok_or(NanovgRenderError::ContextIsNotInit)?
-            .frame(
-                (shared_self.width, shared_self.height),
-                shared_self.device_pixel_ratio,
-                move |frame| {
-                    let bound = BoundingBox {
-                        min_x: 0.0,
-                        min_y: 0.0,
-                        max_x: shared_self.width as Real,
-                        max_y: shared_self.height as Real,
-                    };
+        let context = shared_self.context.as_ref().ok_or(NanovgRenderError::ContextIsNotInit)?;
+        let images = PatternImages::new(context, &shared_self.pattern_images);
+        context.frame(
+            (shared_self.width, shared_self.height),
+            shared_self.device_pixel_ratio,
+            |frame| {
+                let bound = BoundingBox {
+                    min_x: 0.0,
+                    min_y: 0.0,
+                    max_x: shared_self.width as Real,
+                    max_y: shared_self.height as Real,
+                };
 
-                    if need_recalc {
-                        let mut defaults = ShapeDefaults::default();
-                        Self::recalc_composite(&frame, node, bound, TransformMatrix::identity(), &mut defaults);
+                let fonts = &shared_self.fonts;
+                let units = shared_self.units.with_viewport(bound.width(), bound.height());
+                if need_recalc {
+                    let mut defaults = ShapeDefaults {
+                        units,
+                        ..Default::default()
+                    };
+                    let transform = TransformMatrix::identity();
+                    if let Err(err) = Self::recalc_composite(&frame, fonts, node, bound, transform, &mut defaults) {
+                        result = Err(err);
+                        return;
                     }
-                    if need_redraw {
-                        let mut defaults = ShapeDefaults::default();
-                        Self::render_composite(&frame, node, None, &mut defaults);
+                }
+                if need_redraw {
+                    let mut defaults = ShapeDefaults {
+                        units,
+                        ..Default::default()
+                    };
+                    if let Err(err) = Self::render_composite(&frame, fonts, &images, node, None, &mut defaults) {
+                        result = Err(err);
                     }
-                },
-            );
-        Ok(need_redraw)
+                }
+            },
+        );
+        shared_self.pattern_images.evict();
+        result
     }
 }
 
@@ -183,7 +448,11 @@ pub struct ShapeDefaults {
     pub transparency: Real,
     pub fill: Option<Fill>,
     pub stroke: Option<Stroke>,
-    pub clip: Clip,
+    /// Clips of the ancestor groups, the shapes are clipped by their intersection.
+    pub clips: Vec<Clip>,
+    pub effects: Vec<Effect>,
+    /// Sizes of the relative units, the viewport is set by the render size.
+    pub units: UnitContext,
 }
 
 impl NanovgRender {
@@ -193,6 +462,7 @@ impl NanovgRender {
             width,
             height,
             device_pixel_ratio,
+            ..Default::default()
         }
     }
 
@@ -216,46 +486,100 @@ impl NanovgRender {
         self
     }
 
+    pub fn with_fonts(mut self, fonts: FontRegistry) -> Self {
+        self.fonts = fonts;
+        self
+    }
+
+    pub fn with_root_font_size(mut self, root_font_size: Real) -> Self {
+        self.set_root_font_size(root_font_size);
+        self
+    }
+
+    /// Set the size of the `Rem` unit, it is the `Em` unit of the shapes other than the texts as well.
+    pub fn set_root_font_size(&mut self, root_font_size: Real) {
+        self.units = self.units.with_root_font_size(root_font_size);
+    }
+
+    /// Register the font file in the font registry as the family with the normal weight and style.
     pub fn load_font(
         &mut self, name: impl Into<String>, path: impl AsRef<Path>,
     ) -> Result<(), <Self as Render>::Error> {
-        let name = name.into();
-        let display_path = path.as_ref().display();
-        NanovgFont::from_file(
-            self.context.as_ref().ok_or(NanovgRenderError::ContextIsNotInit)?,
-            name.as_str(),
-            path.as_ref(),
-        )
-        .map_err(|e| NanovgRenderError::CreateFontError(e, format!("{}", display_path)))?;
+        self.fonts
+            .load_file(name, FontWeight::NORMAL, FontStyle::Normal, path)
+            .map_err(NanovgRenderError::Font)?;
+        Ok(())
+    }
+
+    /// Create the fonts of the faces added to the registry since the last render. Fallbacks are
+    /// attached to the fonts on their creation, so they should be registered before the faces are used.
+    fn load_new_fonts(&mut self) -> Result<(), NanovgRenderError> {
+        let context = self.context.as_ref().ok_or(NanovgRenderError::ContextIsNotInit)?;
+        let new_faces: Vec<_> = self
+            .fonts
+            .faces()
+            .iter()
+            .filter(|face| !self.loaded_fonts.contains_key(&face.name))
+            .collect();
+        for face in &new_faces {
+            NanovgFont::from_memory(context, &face.name, &face.data)
+                .map_err(|err| NanovgRenderError::CreateFontError(err, face.name.clone()))?;
+        }
+        for face in &new_faces {
+            let font = Self::find_font(context, &self.fonts, &face.name, face.weight, face.style)?;
+            let chain = self
+                .fonts
+                .fallback_chain(&face.name, face.weight, face.style)
+                .map_err(NanovgRenderError::Font)?;
+            for fallback in chain.into_iter().skip(1) {
+                let fallback_font =
+                    Self::find_font(context, &self.fonts, &fallback.name, fallback.weight, fallback.style)?;
+                font.add_fallback(fallback_font);
+            }
+        }
+
+        let loaded: Vec<_> = new_faces.iter().map(|face| (face.name.clone(), face.data.clone())).collect();
+        self.loaded_fonts.extend(loaded);
         Ok(())
     }
 
+    fn find_font<'a>(
+        context: &'a Context, fonts: &FontRegistry, name: &str, weight: FontWeight, style: FontStyle,
+    ) -> Result<NanovgFont<'a>, NanovgRenderError> {
+        let face = fonts.resolve(name, weight, style).map_err(NanovgRenderError::Font)?;
+        NanovgFont::find(context, &face.name)
+            .map_err(|_| NanovgRenderError::Font(FontError::NotFound(face.name.clone())))
+    }
+
     fn recalc_composite(
-        frame: &Frame, composite: &mut dyn CompositeShape, parent_bound: BoundingBox,
+        frame: &Frame, fonts: &FontRegistry, composite: &mut dyn CompositeShape, parent_bound: BoundingBox,
         mut parent_global_transform: TransformMatrix, defaults: &mut ShapeDefaults,
-    ) -> BoundingBox {
+    ) -> Result<BoundingBox, NanovgRenderError> {
         let mut bound = parent_bound;
+        let units = defaults.units;
 
         if let Some(shape) = composite.shape_mut() {
             match shape {
                 Shape::Rect(rect) => {
-                    if rect.x.set_by_pct(parent_bound.width()) {
+                    if rect.x.resolve(parent_bound.width(), &units) {
                         rect.x.0 += parent_bound.min_x;
                     }
-                    if rect.y.set_by_pct(parent_bound.height()) {
+                    if rect.y.resolve(parent_bound.height(), &units) {
                         rect.y.0 += parent_bound.min_y;
                     }
-                    rect.width.set_by_pct(parent_bound.width());
-                    rect.height.set_by_pct(parent_bound.height());
+                    rect.width.resolve(parent_bound.width(), &units);
+                    rect.height.resolve(parent_bound.height(), &units);
+                    rect.constraints.resolve(parent_bound.width(), parent_bound.height(), &units);
+                    rect.constrain_size(None);
                     if let Some(rounding) = &mut rect.rounding {
                         let radius = parent_bound.width().min(parent_bound.height());
-                        rounding.top_left.set_by_pct(radius);
-                        rounding.top_right.set_by_pct(radius);
-                        rounding.bottom_left.set_by_pct(radius);
-                        rounding.bottom_right.set_by_pct(radius);
+                        rounding.top_left.resolve(radius, &units);
+                        rounding.top_right.resolve(radius, &units);
+                        rounding.bottom_left.resolve(radius, &units);
+                        rounding.bottom_right.resolve(radius, &units);
                     }
-                    Self::set_by_pct_padding(&mut rect.padding, &parent_bound);
-                    Self::set_by_pct_clip(&mut rect.clip, &parent_bound);
+                    Self::resolve_padding(&mut rect.padding, &parent_bound, &units);
+                    Self::resolve_clip(&mut rect.clip, &parent_bound, &units);
 
                     parent_global_transform = rect.recalculate_transform(parent_global_transform);
                     let (scale_x, scale_y) = parent_global_transform.scale_xy();
@@ -265,27 +589,29 @@ impl NanovgRender {
                     bound = BoundingBox {
                         min_x: rect.x.val(),
                         min_y: rect.y.val(),
-                        max_x: rect.x.val() + rect.width.val(),
-                        max_y: rect.y.val() + rect.height.val(),
+                        max_x: rect.x.val() + rect.layout_width(),
+                        max_y: rect.y.val() + rect.layout_height(),
                     };
                 }
                 Shape::Circle(circle) => {
-                    if circle.cx.set_by_pct(parent_bound.width()) {
+                    if circle.cx.resolve(parent_bound.width(), &units) {
                         circle.cx.0 += parent_bound.min_x;
                     }
-                    if circle.cy.set_by_pct(parent_bound.height()) {
+                    if circle.cy.resolve(parent_bound.height(), &units) {
                         circle.cy.0 += parent_bound.min_y;
                     }
-                    circle.r.set_by_pct(parent_bound.width().min(parent_bound.height()));
-                    Self::set_by_pct_padding(&mut circle.padding, &parent_bound);
-                    Self::set_by_pct_clip(&mut circle.clip, &parent_bound);
+                    circle.r.resolve(parent_bound.width().min(parent_bound.height()), &units);
+                    circle.constraints.resolve(parent_bound.width(), parent_bound.height(), &units);
+                    circle.constrain_radius(None);
+                    Self::resolve_padding(&mut circle.padding, &parent_bound, &units);
+                    Self::resolve_clip(&mut circle.clip, &parent_bound, &units);
 
                     parent_global_transform = circle.recalculate_transform(parent_global_transform);
                     let (scale_x, scale_y) = parent_global_transform.scale_xy();
                     parent_global_transform
                         .translate_add(circle.padding.left.val() * scale_x, circle.padding.top.val() * scale_y);
 
-                    let (cx, cy, r) = (circle.cx.val(), circle.cy.val(), circle.r.val());
+                    let (cx, cy, r) = (circle.cx.val(), circle.cy.val(), circle.layout_r());
                     bound = BoundingBox {
                         min_x: cx - r,
                         min_y: cy - r,
@@ -293,99 +619,120 @@ impl NanovgRender {
                         max_y: cy + r,
                     };
                 }
+                Shape::Ellipse(ellipse) => {
+                    ellipse.set_by_bounds(parent_bound.bounds(), &units);
+                    Self::resolve_clip(&mut ellipse.clip, &parent_bound, &units);
+                    parent_global_transform = ellipse.recalculate_transform(parent_global_transform);
+                    bound = BoundingBox::from_bounds(ellipse.bounds());
+                }
+                Shape::Line(line) => {
+                    line.set_by_bounds(parent_bound.bounds(), &units);
+                    Self::resolve_clip(&mut line.clip, &parent_bound, &units);
+                    parent_global_transform = line.recalculate_transform(parent_global_transform);
+                    bound = BoundingBox::from_bounds(line.bounds());
+                }
+                Shape::Polyline(polyline) => {
+                    polyline.set_by_bounds(parent_bound.bounds(), &units);
+                    Self::resolve_clip(&mut polyline.clip, &parent_bound, &units);
+                    parent_global_transform = polyline.recalculate_transform(parent_global_transform);
+                    if let Some(bounds) = polyline.bounds() {
+                        bound = BoundingBox::from_bounds(bounds);
+                    }
+                }
+                Shape::Polygon(polygon) => {
+                    polygon.set_by_bounds(parent_bound.bounds(), &units);
+                    Self::resolve_clip(&mut polygon.clip, &parent_bound, &units);
+                    parent_global_transform = polygon.recalculate_transform(parent_global_transform);
+                    if let Some(bounds) = polygon.bounds() {
+                        bound = BoundingBox::from_bounds(bounds);
+                    }
+                }
                 Shape::Text(text) => {
-                    if text.x.set_by_pct(parent_bound.width()) {
+                    text.font_size.resolve(units.font_size, &units);
+                    for span in &mut text.spans {
+                        if let Some(font_size) = &mut span.style.font_size {
+                            font_size.resolve(units.font_size, &units);
+                        }
+                    }
+                    let units = units.with_font_size(text.font_size.val());
+                    if text.x.resolve(parent_bound.width(), &units) {
                         text.x.0 += parent_bound.min_x;
                     }
-                    if text.y.set_by_pct(parent_bound.height()) {
+                    if text.y.resolve(parent_bound.height(), &units) {
                         text.y.0 += parent_bound.min_y;
                     }
-                    Self::set_by_pct_clip(&mut text.clip, &parent_bound);
+                    if let Some(max_width) = &mut text.layout.max_width {
+                        max_width.resolve(parent_bound.width(), &units);
+                    }
+                    if let Some(path) = &mut text.path {
+                        path.resolve(&units);
+                    }
+                    Self::resolve_clip(&mut text.clip, &parent_bound, &units);
 
                     parent_global_transform = text.recalculate_transform(parent_global_transform);
 
-                    let nanovg_font = NanovgFont::find(frame.context(), &text.font_name)
-                        .expect(&format!("Font '{}' not found", text.font_name));
-                    let text_options = Self::text_options(text, defaults);
-
-                    let metrics = frame.text_metrics(nanovg_font, text_options);
-                    text.metrics = Some(TextMetrics {
-                        ascender: metrics.ascender,
-                        descender: metrics.descender,
-                        line_height: metrics.line_height,
-                    });
-
-                    text.glyph_positions = frame
-                        .text_glyph_positions((text.x.val() as f32, text.y.val() as f32), &text.content)
-                        .map(|pos| {
-                            let x = pos.x.min(pos.min_x);
-                            GlyphPos {
-                                x,
-                                y: 0.0,
-                                width: pos.max_x - x,
-                            }
-                        })
-                        .collect();
-                    bound = BoundingBox {
-                        min_x: text.x.val(),
-                        min_y: text.y.val(),
-                        max_x: text.x.val() + text.glyph_positions.last().map(|pos| pos.max_x()).unwrap_or(0.0),
-                        max_y: text.y.val() + metrics.line_height as Real,
+                    text.layout_with(&FrameMeasurer { frame }, fonts).map_err(NanovgRenderError::Font)?;
+                    bound = match text.path_bounds() {
+                        Some(bounds) => BoundingBox::from_bounds(bounds),
+                        None => Self::text_bound(text, text.metrics.unwrap_or_default().line_height),
                     };
                 }
                 Shape::Path(path) => {
-                    Self::set_by_pct_clip(&mut path.clip, &parent_bound);
+                    Self::resolve_clip(&mut path.clip, &parent_bound, &units);
                     parent_global_transform = path.recalculate_transform(parent_global_transform);
+                    if let Some(bounds) = path.bounds() {
+                        bound = BoundingBox::from_bounds(bounds);
+                    }
                 }
                 Shape::Group(group) => {
-                    Self::set_by_pct_clip(&mut group.clip, &parent_bound);
+                    Self::resolve_clip(&mut group.clip, &parent_bound, &units);
                     parent_global_transform = group.recalculate_transform(parent_global_transform);
 
                     if let Some(transparency) = group.transparency {
                         defaults.transparency = transparency;
                     }
-                    if let Some(fill) = group.fill {
-                        defaults.fill = Some(fill);
+                    if let Some(fill) = &group.fill {
+                        defaults.fill = Some(fill.clone());
                     }
-                    if let Some(stroke) = group.stroke {
-                        defaults.stroke = Some(stroke);
+                    if let Some(stroke) = &group.stroke {
+                        defaults.stroke = Some(stroke.clone());
                     }
-                    if !group.clip.is_none() {
-                        defaults.clip = group.clip;
+                    if !group.effects.is_empty() {
+                        defaults.effects = group.effects.clone();
                     }
                 }
             }
         }
 
-        let inner_bound = Self::calc_inner_bound(frame, composite, bound, parent_global_transform, defaults);
+        let inner_bound =
+            Self::calc_inner_bound(frame, fonts, composite, bound, parent_global_transform, defaults)?;
 
         if let Some(shape) = composite.shape_mut() {
             match shape {
                 Shape::Rect(rect) => {
                     rect.x.set_by_auto(inner_bound.min_x);
                     rect.y.set_by_auto(inner_bound.min_y);
-                    rect.width
-                        .set_by_auto(inner_bound.max_x - rect.x.val() + rect.padding.left_and_right().val());
-                    rect.height
-                        .set_by_auto(inner_bound.max_y - rect.y.val() + rect.padding.top_and_bottom().val());
+                    let content_width = inner_bound.max_x - rect.x.val() + rect.padding.left_and_right().val();
+                    let content_height = inner_bound.max_y - rect.y.val() + rect.padding.top_and_bottom().val();
+                    rect.constrain_size(Some((content_width, content_height)));
 
                     bound = BoundingBox {
                         min_x: rect.x.val(),
                         min_y: rect.y.val(),
-                        max_x: rect.x.val() + rect.width.val(),
-                        max_y: rect.y.val() + rect.height.val(),
+                        max_x: rect.x.val() + rect.layout_width(),
+                        max_y: rect.y.val() + rect.layout_height(),
                     };
                 }
                 Shape::Circle(circle) => {
                     circle.cx.set_by_auto(inner_bound.min_x + inner_bound.width() / 2.0);
                     circle.cy.set_by_auto(inner_bound.min_y + inner_bound.height() / 2.0);
-                    circle.r.set_by_auto(
+                    circle.constrain_radius(Some(
                         (inner_bound.width() + circle.padding.left_and_right().val())
                             .max(inner_bound.height() + circle.padding.top_and_bottom().val())
                             / 2.0,
-                    );
+                    ));
 
-                    let (cx, cy, r) = (circle.cx.val(), circle.cy.val(), circle.r.val());
+                    let (cx, cy, r) = (circle.cx.val(), circle.cy.val(), circle.layout_r());
                     bound = BoundingBox {
                         min_x: cx - r,
                         min_y: cy - r,
@@ -412,27 +759,28 @@ impl NanovgRender {
                 _ => (),
             }
         }
-        bound
+        Ok(bound)
     }
 
     fn calc_inner_bound(
-        frame: &Frame, composite: &mut dyn CompositeShape, bound: BoundingBox,
+        frame: &Frame, fonts: &FontRegistry, composite: &mut dyn CompositeShape, bound: BoundingBox,
         parent_global_transform: TransformMatrix, defaults: &mut ShapeDefaults,
-    ) -> BoundingBox {
+    ) -> Result<BoundingBox, NanovgRenderError> {
         let mut child_bounds = Vec::new();
         if let Some(children) = composite.children_mut() {
             for child in children {
                 child_bounds.push(Self::recalc_composite(
                     frame,
+                    fonts,
                     child,
                     bound,
                     parent_global_transform,
                     defaults,
-                ));
+                )?);
             }
         }
 
-        if child_bounds.is_empty() {
+        Ok(if child_bounds.is_empty() {
             BoundingBox::default()
         } else {
             let mut inner_bound = child_bounds[0];
@@ -451,195 +799,605 @@ impl NanovgRender {
                 }
             }
             inner_bound
-        }
+        })
     }
 
     fn render_composite<'a>(
-        frame: &Frame, composite: &'a dyn CompositeShape, mut text: Option<&'a Text>, defaults: &mut ShapeDefaults,
-    ) {
+        frame: &Frame, fonts: &FontRegistry, images: &PatternImages, composite: &'a dyn CompositeShape,
+        mut text: Option<&'a Text>, defaults: &mut ShapeDefaults,
+    ) -> Result<(), NanovgRenderError> {
+        let clips_len = defaults.clips.len();
         if let Some(shape) = composite.shape() {
             match shape {
                 Shape::Rect(rect) => {
+                    let stroke = rect.stroke.as_ref().or(defaults.stroke.as_ref());
+                    let effects = Self::effects(&rect.effects, defaults);
+                    let options = || Self::path_options(rect.transparency, &rect.clip, &rect.transform, defaults);
+                    let bounds = [rect.x.val(), rect.y.val(), rect.layout_width(), rect.layout_height()];
+                    let units = &defaults.units;
+                    let radius = rect.rounding.map_or(0.0, |rounding| rounding.top_left.val());
+                    let fill = rect.fill.as_ref().or(defaults.fill.as_ref());
+                    let fill = Self::render_box_shadows(frame, effects, fill, bounds, radius, options);
                     frame.path(
                         |path| {
-                            let rect_pos = (rect.x.val() as f32, rect.y.val() as f32);
-                            let rect_size = (rect.width.val() as f32, rect.height.val() as f32);
-                            if let Some(rounding) = rect.rounding {
-                                path.rounded_rect_varying(
-                                    rect_pos,
-                                    rect_size,
-                                    (rounding.top_left.val() as f32, rounding.top_right.val() as f32),
-                                    (rounding.bottom_left.val() as f32, rounding.bottom_right.val() as f32),
-                                );
-                            } else {
-                                path.rect(rect_pos, rect_size);
-                            }
-                            if let Some(fill) = rect.fill.as_ref().or(defaults.fill.as_ref()) {
-                                path.fill(ToNanovgPaint(fill.paint), Default::default());
+                            Self::add_rect(&path, rect);
+                            if let Some(fill) = fill {
+                                path.fill(ToNanovgPaint::new(&fill.paint, bounds, units, images), Default::default());
                             };
-                            if let Some(stroke) = rect.stroke.as_ref().or(defaults.stroke.as_ref()) {
-                                path.stroke(ToNanovgPaint(stroke.paint), Self::stroke_option(&stroke));
+                            if let Some(stroke) = stroke.filter(|stroke| Self::is_plain_stroke(stroke)) {
+                                path.stroke(
+                                    ToNanovgPaint::new(&stroke.paint, bounds, units, images),
+                                    Self::stroke_option(stroke),
+                                );
                             }
                         },
-                        Self::path_options(rect.transparency, rect.clip, &rect.transform, defaults),
+                        options(),
                     );
+                    if let Some(stroke) = stroke.filter(|stroke| !Self::is_plain_stroke(stroke)) {
+                        let outline = rect.offset_outline(stroke.center_offset());
+                        let segments = PathSegment::resolve(&outline).map_err(NanovgRenderError::InvalidPath)?;
+                        Self::render_stroke(frame, images, &segments, stroke, units, options());
+                    }
+                    let outline = |path: &NanovgPath| Self::add_rect(path, rect);
+                    Self::render_inner_shadows(frame, effects, bounds, radius, outline, options);
                 }
                 Shape::Circle(circle) => {
+                    let stroke = circle.stroke.as_ref().or(defaults.stroke.as_ref());
+                    let effects = Self::effects(&circle.effects, defaults);
+                    let options = || Self::path_options(circle.transparency, &circle.clip, &circle.transform, defaults);
+                    let (cx, cy, r) = (circle.cx.val(), circle.cy.val(), circle.layout_r());
+                    let bounds = [cx - r, cy - r, 2.0 * r, 2.0 * r];
+                    let units = &defaults.units;
+                    let fill = circle.fill.as_ref().or(defaults.fill.as_ref());
+                    let fill = Self::render_box_shadows(frame, effects, fill, bounds, r, options);
+                    let outline = |path: &NanovgPath| path.circle((cx as f32, cy as f32), r as f32);
                     frame.path(
                         |path| {
-                            path.circle((circle.cx.val() as f32, circle.cy.val() as f32), circle.r.val() as f32);
-                            if let Some(fill) = circle.fill.as_ref().or(defaults.fill.as_ref()) {
-                                path.fill(ToNanovgPaint(fill.paint), Default::default());
-                            };
-                            if let Some(stroke) = circle.stroke.as_ref().or(defaults.stroke.as_ref()) {
-                                path.stroke(ToNanovgPaint(stroke.paint), Self::stroke_option(&stroke));
-                            }
-                        },
-                        Self::path_options(circle.transparency, circle.clip, &circle.transform, defaults),
-                    );
-                }
-                Shape::Path(path) => {
-                    frame.path(
-                        |nvg_path| {
-                            use exgui_core::PathCommand::*;
-
-                            let mut last_xy = [0.0, 0.0];
-                            let mut bez_ctrls = [(0.0, 0.0), (0.0, 0.0)];
-
-                            for cmd in path.cmd.iter() {
-                                match cmd {
-                                    Move(ref xy) => {
-                                        last_xy = *xy;
-                                        nvg_path.move_to((last_xy[0] as f32, last_xy[1] as f32));
-                                    }
-                                    MoveRel(ref xy) => {
-                                        last_xy = [last_xy[0] + xy[0], last_xy[1] + xy[1]];
-                                        nvg_path.move_to((last_xy[0] as f32, last_xy[1] as f32));
-                                    }
-                                    Line(ref xy) => {
-                                        last_xy = *xy;
-                                        nvg_path.line_to((last_xy[0] as f32, last_xy[1] as f32));
-                                    }
-                                    LineRel(ref xy) => {
-                                        last_xy = [last_xy[0] + xy[0], last_xy[1] + xy[1]];
-                                        nvg_path.line_to((last_xy[0] as f32, last_xy[1] as f32));
-                                    }
-                                    LineAlonX(ref x) => {
-                                        last_xy[0] = *x;
-                                        nvg_path.line_to((last_xy[0] as f32, last_xy[1] as f32));
-                                    }
-                                    LineAlonXRel(ref x) => {
-                                        last_xy[0] += *x;
-                                        nvg_path.line_to((last_xy[0] as f32, last_xy[1] as f32));
-                                    }
-                                    LineAlonY(ref y) => {
-                                        last_xy[1] = *y;
-                                        nvg_path.line_to((last_xy[0] as f32, last_xy[1] as f32));
-                                    }
-                                    LineAlonYRel(ref y) => {
-                                        last_xy[1] += *y;
-                                        nvg_path.line_to((last_xy[0] as f32, last_xy[1] as f32));
-                                    }
-                                    Close => nvg_path.close(),
-                                    BezCtrl(ref xy) => {
-                                        bez_ctrls = [bez_ctrls[1], (xy[0], xy[1])];
-                                    }
-                                    BezCtrlRel(ref xy) => {
-                                        bez_ctrls = [bez_ctrls[1], (last_xy[0] + xy[0], last_xy[1] + xy[1])];
-                                    }
-                                    QuadBezTo(ref xy) => {
-                                        last_xy = *xy;
-                                        nvg_path.quad_bezier_to(
-                                            (last_xy[0] as f32, last_xy[1] as f32),
-                                            (bez_ctrls[1].0 as f32, bez_ctrls[1].1 as f32),
-                                        );
-                                    }
-                                    QuadBezToRel(ref xy) => {
-                                        last_xy = [last_xy[0] + xy[0], last_xy[1] + xy[1]];
-                                        nvg_path.quad_bezier_to(
-                                            (last_xy[0] as f32, last_xy[1] as f32),
-                                            (bez_ctrls[1].0 as f32, bez_ctrls[1].1 as f32),
-                                        );
-                                    }
-                                    CubBezTo(ref xy) => {
-                                        last_xy = *xy;
-                                        nvg_path.cubic_bezier_to(
-                                            (last_xy[0] as f32, last_xy[1] as f32),
-                                            (bez_ctrls[0].0 as f32, bez_ctrls[0].1 as f32),
-                                            (bez_ctrls[1].0 as f32, bez_ctrls[1].1 as f32),
-                                        );
-                                    }
-                                    CubBezToRel(ref xy) => {
-                                        last_xy = [last_xy[0] + xy[0], last_xy[1] + xy[1]];
-                                        nvg_path.cubic_bezier_to(
-                                            (last_xy[0] as f32, last_xy[1] as f32),
-                                            (bez_ctrls[0].0 as f32, bez_ctrls[0].1 as f32),
-                                            (bez_ctrls[1].0 as f32, bez_ctrls[1].1 as f32),
-                                        );
-                                    }
-                                    _ => panic!("Not impl rendering cmd {:?}", cmd), // TODO: need refl impl
-                                }
-                            }
-                            if let Some(fill) = path.fill.as_ref().or(defaults.fill.as_ref()) {
-                                nvg_path.fill(ToNanovgPaint(fill.paint), Default::default());
+                            outline(&path);
+                            if let Some(fill) = fill {
+                                path.fill(ToNanovgPaint::new(&fill.paint, bounds, units, images), Default::default());
                             };
-                            if let Some(stroke) = path.stroke.as_ref().or(defaults.stroke.as_ref()) {
-                                nvg_path.stroke(ToNanovgPaint(stroke.paint), Self::stroke_option(&stroke));
+                            if let Some(stroke) = stroke.filter(|stroke| Self::is_plain_stroke(stroke)) {
+                                path.stroke(
+                                    ToNanovgPaint::new(&stroke.paint, bounds, units, images),
+                                    Self::stroke_option(stroke),
+                                );
                             }
                         },
-                        Self::path_options(path.transparency, path.clip, &path.transform, defaults),
+                        options(),
                     );
+                    if let Some(stroke) = stroke.filter(|stroke| !Self::is_plain_stroke(stroke)) {
+                        let outline = circle.offset_outline(stroke.center_offset());
+                        let segments = PathSegment::resolve(&outline).map_err(NanovgRenderError::InvalidPath)?;
+                        Self::render_stroke(frame, images, &segments, stroke, units, options());
+                    }
+                    Self::render_inner_shadows(frame, effects, bounds, r, outline, options);
                 }
+                Shape::Ellipse(ellipse) => Self::render_path(frame, images, &ellipse.to_path(), defaults)?,
+                Shape::Line(line) => Self::render_path(frame, images, &line.to_path(), defaults)?,
+                Shape::Polyline(polyline) => Self::render_path(frame, images, &polyline.to_path(), defaults)?,
+                Shape::Polygon(polygon) => Self::render_path(frame, images, &polygon.to_path(), defaults)?,
+                Shape::Path(path) => Self::render_path(frame, images, path, defaults)?,
                 Shape::Text(this_text) => {
                     text = Some(this_text);
 
-                    let nanovg_font = NanovgFont::find(frame.context(), &this_text.font_name)
-                        .expect(&format!("Font '{}' not found", this_text.font_name));
-                    let text_options = Self::text_options(this_text, defaults);
-
-                    frame.text(
-                        nanovg_font,
-                        (this_text.x.val() as f32, this_text.y.val() as f32),
-                        &this_text.content,
-                        text_options,
-                    );
+                    for effect in Self::effects(&this_text.effects, defaults) {
+                        if let Effect::Shadow(shadow) = effect {
+                            Self::render_text(frame, fonts, this_text, defaults, Some(shadow))?;
+                        }
+                    }
+                    Self::render_text(frame, fonts, this_text, defaults, None)?;
                 }
                 Shape::Group(group) => {
+                    // Nanovg has no offscreen layers, so the layer transparency is applied to each child
+                    // and the blend mode is ignored
                     if let Some(transparency) = group.transparency {
                         defaults.transparency = transparency;
                     }
-                    if let Some(fill) = group.fill {
-                        defaults.fill = Some(fill);
+                    if let Some(fill) = &group.fill {
+                        defaults.fill = Some(fill.clone());
                     }
-                    if let Some(stroke) = group.stroke {
-                        defaults.stroke = Some(stroke);
+                    if let Some(stroke) = &group.stroke {
+                        defaults.stroke = Some(stroke.clone());
                     }
                     if !group.clip.is_none() {
-                        defaults.clip = group.clip;
+                        defaults.clips.push(group.clip.clone());
+                    }
+                    if !group.effects.is_empty() {
+                        defaults.effects = group.effects.clone();
                     }
                 }
             }
         }
         if let Some(children) = composite.children() {
             for child in children {
-                Self::render_composite(frame, child, text, defaults);
+                Self::render_composite(frame, fonts, images, child, text, defaults)?;
+            }
+        }
+        defaults.clips.truncate(clips_len);
+        Ok(())
+    }
+
+    /// Draw the path, the other outline shapes are drawn as the paths too.
+    fn render_path(
+        frame: &Frame, images: &PatternImages, path: &PathShape, defaults: &ShapeDefaults,
+    ) -> Result<(), NanovgRenderError> {
+        let segments = path.segments().map_err(NanovgRenderError::InvalidPath)?;
+        let bounds = PathSegment::bounds(&segments).unwrap_or_default();
+        let units = &defaults.units;
+        let stroke = path.stroke.as_ref().or(defaults.stroke.as_ref());
+        let options = || Self::path_options(path.transparency, &path.clip, &path.transform, defaults);
+        let fill = path.fill.as_ref().or(defaults.fill.as_ref());
+        let fill_rule = path.resolve_fill_rule(fill);
+        let holes = Self::subpath_holes(&segments, fill_rule);
+        let effects = Self::effects(&path.effects, defaults);
+        Self::render_path_shadows(frame, effects, &segments, &holes, options);
+        frame.path(
+            |nvg_path| {
+                Self::add_filled_segments(&nvg_path, &segments, &holes);
+                if let Some(fill) = fill {
+                    nvg_path.fill(ToNanovgPaint::new(&fill.paint, bounds, units, images), Default::default());
+                };
+                if let Some(stroke) = stroke.filter(|stroke| Self::is_plain_stroke(stroke)) {
+                    let paint = ToNanovgPaint::new(&stroke.paint, bounds, units, images);
+                    nvg_path.stroke(paint, Self::stroke_option(stroke));
+                }
+            },
+            options(),
+        );
+        match stroke {
+            Some(stroke) if stroke.center_offset() != 0.0 => {
+                // Nanovg is not able to offset the arbitrary outlines, so the aligned stroke is drawn
+                // by filling the outline of its area, as it is hit
+                let outline = PathSegment::stroke_outline(&segments, stroke, fill_rule);
+                let holes = Self::subpath_holes(&outline, FillRule::NonZero);
+                frame.path(
+                    |nvg_path| {
+                        Self::add_filled_segments(&nvg_path, &outline, &holes);
+                        nvg_path.fill(ToNanovgPaint::new(&stroke.paint, bounds, units, images), Default::default());
+                    },
+                    options(),
+                );
+            },
+            Some(stroke) if stroke.is_dashed() => {
+                Self::render_stroke(frame, images, &segments, stroke, units, options())
+            },
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// Effects of the shape, or the group ones if the shape has no own effects.
+    fn effects<'a>(effects: &'a [Effect], defaults: &'a ShapeDefaults) -> &'a [Effect] {
+        if effects.is_empty() {
+            &defaults.effects
+        } else {
+            effects
+        }
+    }
+
+    /// Draw the shadows of the rounded box by the box gradients. The color fill of the blurred
+    /// box is drawn as its shadow too, so the fill left to draw over the shadows is returned.
+    fn render_box_shadows<'a>(
+        frame: &Frame, effects: &[Effect], fill: Option<&'a Fill>, bounds: [Real; 4], radius: Real,
+        options: impl Fn() -> PathOptions,
+    ) -> Option<&'a Fill> {
+        let fill_box = |shadow: Shadow| {
+            let [x, y, width, height] = shadow.outer_bounds(bounds, radius);
+            frame.path(
+                |path| {
+                    path.rect((x as f32, y as f32), (width as f32, height as f32));
+                    let gradient = shadow.outer_gradient(bounds, radius);
+                    path.fill(ToNanovgPaint::from(Paint::Gradient(gradient)), Default::default());
+                },
+                options(),
+            )
+        };
+        let mut blur = 0.0;
+        for effect in effects {
+            match *effect {
+                Effect::Shadow(shadow) => fill_box(shadow),
+                Effect::Blur(radius) => blur = radius,
+                Effect::InnerShadow(_) => (),
+            }
+        }
+        match fill {
+            Some(Fill {
+                paint: Paint::Color(color),
+                ..
+            }) if blur > 0.0 => {
+                fill_box(Shadow::new(0.0, 0.0, blur, *color));
+                None
+            }
+            _ => fill,
+        }
+    }
+
+    /// Fill the outline of the rounded box by the box gradients of the inner shadows.
+    fn render_inner_shadows(
+        frame: &Frame, effects: &[Effect], bounds: [Real; 4], radius: Real, outline: impl Fn(&NanovgPath),
+        options: impl Fn() -> PathOptions,
+    ) {
+        for effect in effects {
+            if let Effect::InnerShadow(shadow) = effect {
+                frame.path(
+                    |path| {
+                        outline(&path);
+                        let gradient = shadow.inner_gradient(bounds, radius);
+                        path.fill(ToNanovgPaint::from(Paint::Gradient(gradient)), Default::default());
+                    },
+                    options(),
+                );
+            }
+        }
+    }
+
+    /// Draw the outer shadows of the path without the blur, nanovg is not able to blur the arbitrary shapes.
+    /// The spread is drawn as the stroke of the shadow.
+    fn render_path_shadows(
+        frame: &Frame, effects: &[Effect], segments: &[PathSegment], holes: &[bool], options: impl Fn() -> PathOptions,
+    ) {
+        for effect in effects {
+            if let Effect::Shadow(shadow) = effect {
+                let shifted: Vec<_> = segments
+                    .iter()
+                    .map(|segment| segment.map_points(|[x, y]| [x + shadow.offset_x, y + shadow.offset_y]))
+                    .collect();
+                frame.path(
+                    |path| {
+                        Self::add_filled_segments(&path, &shifted, holes);
+                        path.fill(ToNanovgPaint::from(Paint::Color(shadow.color)), Default::default());
+                        if shadow.spread > 0.0 {
+                            let stroke = StrokeOptions {
+                                width: 2.0 * shadow.spread as f32,
+                                line_join: NanovgLineJoin::Round,
+                                ..Default::default()
+                            };
+                            path.stroke(ToNanovgPaint::from(Paint::Color(shadow.color)), stroke);
+                        }
+                    },
+                    options(),
+                );
+            }
+        }
+    }
+
+    fn add_rect(path: &NanovgPath, rect: &Rect) {
+        let rect_pos = (rect.x.val() as f32, rect.y.val() as f32);
+        let rect_size = (rect.layout_width() as f32, rect.layout_height() as f32);
+        if let Some(rounding) = rect.rounding {
+            path.rounded_rect_varying(
+                rect_pos,
+                rect_size,
+                (rounding.top_left.val() as f32, rounding.top_right.val() as f32),
+                (rounding.bottom_left.val() as f32, rounding.bottom_right.val() as f32),
+            );
+        } else {
+            path.rect(rect_pos, rect_size);
+        }
+    }
+
+    /// Stroke drawn along the shape outline in the same path with the fill.
+    fn is_plain_stroke(stroke: &Stroke) -> bool {
+        !stroke.is_dashed() && stroke.center_offset() == 0.0
+    }
+
+    /// Nanovg fills by the non-zero rule, but it makes all of the sub-paths solid by default, reversing
+    /// the ones drawn counterclockwise. The sub-paths to be filled as holes are found here: the non-zero rule
+    /// keeps the drawn directions and the even-odd one makes holes of the sub-paths nested in an odd number
+    /// of others. The self-intersections of a sub-path are filled by the non-zero rule anyway.
+    fn subpath_holes(segments: &[PathSegment], fill_rule: FillRule) -> Vec<bool> {
+        let lines = PathSegment::flatten(segments, HOLE_TOLERANCE);
+        let mut subpaths = Vec::new();
+        for (idx, segment) in lines.iter().enumerate() {
+            if let PathSegment::MoveTo(_) = segment {
+                subpaths.push(idx);
+            }
+        }
+        let subpaths: Vec<_> = subpaths
+            .iter()
+            .enumerate()
+            .map(|(idx, &start)| &lines[start..subpaths.get(idx + 1).copied().unwrap_or(lines.len())])
+            .collect();
+
+        subpaths
+            .iter()
+            .enumerate()
+            .map(|(idx, subpath)| match fill_rule {
+                FillRule::NonZero => Self::signed_area(subpath) > 0.0,
+                FillRule::EvenOdd => {
+                    let start = match subpath.first() {
+                        Some(PathSegment::MoveTo(point)) => *point,
+                        _ => return false,
+                    };
+                    let depth = subpaths
+                        .iter()
+                        .enumerate()
+                        .filter(|&(other_idx, other)| {
+                            other_idx != idx && PathSegment::winding_number(other, start) != 0
+                        })
+                        .count();
+                    depth % 2 == 1
+                }
+            })
+            .collect()
+    }
+
+    /// Twice the area of the flattened sub-path by the shoelace formula, it is positive for the sub-paths
+    /// drawn clockwise on the screen.
+    fn signed_area(lines: &[PathSegment]) -> Real {
+        let points: Vec<_> = lines
+            .iter()
+            .filter_map(|segment| match *segment {
+                PathSegment::MoveTo(point) | PathSegment::LineTo(point) => Some(point),
+                _ => None,
+            })
+            .collect();
+        points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|([x1, y1], [x2, y2])| x1 * y2 - x2 * y1)
+            .sum()
+    }
+
+    /// Add the segments with the solidity of each sub-path, see `subpath_holes`.
+    fn add_filled_segments(path: &NanovgPath, segments: &[PathSegment], holes: &[bool]) {
+        let mut holes = holes.iter();
+        for segment in segments {
+            Self::add_segments(path, std::slice::from_ref(segment));
+            if let PathSegment::MoveTo(_) = segment {
+                let solidity = if holes.next() == Some(&true) {
+                    Solidity::Hole
+                } else {
+                    Solidity::Solid
+                };
+                path.winding(Winding::Solidity(solidity));
+            }
+        }
+    }
+
+    fn add_segments(path: &NanovgPath, segments: &[PathSegment]) {
+        for segment in segments {
+            match *segment {
+                PathSegment::MoveTo([x, y]) => path.move_to((x as f32, y as f32)),
+                PathSegment::LineTo([x, y]) => path.line_to((x as f32, y as f32)),
+                PathSegment::QuadTo {
+                    ctrl: [ctrl_x, ctrl_y],
+                    to: [x, y],
+                } => path.quad_bezier_to((x as f32, y as f32), (ctrl_x as f32, ctrl_y as f32)),
+                PathSegment::CubicTo {
+                    ctrl1: [ctrl1_x, ctrl1_y],
+                    ctrl2: [ctrl2_x, ctrl2_y],
+                    to: [x, y],
+                } => path.cubic_bezier_to(
+                    (x as f32, y as f32),
+                    (ctrl1_x as f32, ctrl1_y as f32),
+                    (ctrl2_x as f32, ctrl2_y as f32),
+                ),
+                PathSegment::Close => path.close(),
+            }
+        }
+    }
+
+    /// Stroke the segments by a separate path. The dashes are split in core, nanovg has no native dashes.
+    fn render_stroke(
+        frame: &Frame, images: &PatternImages, segments: &[PathSegment], stroke: &Stroke, units: &UnitContext,
+        options: PathOptions,
+    ) {
+        let bounds = PathSegment::bounds(segments).unwrap_or_default();
+        let dashes;
+        let segments = if stroke.is_dashed() {
+            dashes = PathSegment::dash(segments, &stroke.dash, stroke.dash_offset);
+            &dashes
+        } else {
+            segments
+        };
+        frame.path(
+            |path| {
+                Self::add_segments(&path, segments);
+                path.stroke(ToNanovgPaint::new(&stroke.paint, bounds, units, images), Self::stroke_option(stroke));
+            },
+            options,
+        );
+    }
+
+    /// Draw the text, or its shadow offset by the shadow and blurred with the shadow color.
+    fn render_text(
+        frame: &Frame, fonts: &FontRegistry, text: &Text, defaults: &ShapeDefaults, shadow: Option<&Shadow>,
+    ) -> Result<(), NanovgRenderError> {
+        if text.path.is_some() {
+            return Self::render_path_glyphs(frame, fonts, text, defaults, shadow);
+        }
+        if !text.spans.is_empty() || text.has_rtl() {
+            return Self::render_spans(frame, fonts, text, defaults, shadow);
+        }
+        let nanovg_font = Self::text_font(frame.context(), fonts, text)?;
+        let text_options = Self::shadow_text_options(Self::text_options(text, defaults), text, defaults, shadow);
+        let (x, y) = Self::text_origin(text, shadow);
+        let (x, y) = (x as f32, y as f32);
+        if text.lines.is_empty() {
+            frame.text(nanovg_font, (x, y), &text.content, text_options);
+        } else {
+            for line in &text.lines {
+                let content = text.line_text(line);
+                frame.text(nanovg_font, (x, y + line.y as f32), &*content, text_options);
+                Self::render_decoration(
+                    frame,
+                    text.decoration,
+                    (x + line.x as f32, y + line.baseline as f32),
+                    line.width as f32,
+                    text_options,
+                );
             }
         }
+        Ok(())
+    }
+
+    fn text_origin(text: &Text, shadow: Option<&Shadow>) -> (Real, Real) {
+        let (dx, dy) = shadow.map_or((0.0, 0.0), |shadow| (shadow.offset_x, shadow.offset_y));
+        (text.x.val() + dx, text.y.val() + dy)
+    }
+
+    /// Options of the text shadow with the shadow color and the font blur, the spread is not supported.
+    fn shadow_text_options(
+        options: TextOptions, text: &Text, defaults: &ShapeDefaults, shadow: Option<&Shadow>,
+    ) -> TextOptions {
+        match shadow {
+            Some(shadow) => TextOptions {
+                color: Self::text_color(shadow.color, text, defaults),
+                blur: shadow.blur as f32,
+                ..options
+            },
+            None => options,
+        }
+    }
+
+    /// Draw the text by the visual runs of the spans, the text without spans is drawn as one span.
+    /// Each run is drawn at once by its visual content, as it is measured.
+    fn render_spans(
+        frame: &Frame, fonts: &FontRegistry, text: &Text, defaults: &ShapeDefaults, shadow: Option<&Shadow>,
+    ) -> Result<(), NanovgRenderError> {
+        let plain_style = SpanStyle::default();
+        let (x, y) = Self::text_origin(text, shadow);
+        for line in &text.lines {
+            for run in text.visual_runs(line) {
+                let style = text.spans.get(run.span).map_or(&plain_style, |span| &span.style);
+                let (font, options) = Self::span_text_options(frame.context(), fonts, text, style, defaults)?;
+                let options = Self::shadow_text_options(options, text, defaults, shadow);
+                let run_x = x + text.run_x(line, &run);
+                let run_y = y + line.baseline - style.baseline_offset;
+                frame.text(font, (run_x as f32, run_y as f32), &*run.visual_content(), options);
+
+                let width: Real = text.glyph_positions[run.range.clone()].iter().map(|pos| pos.width).sum();
+                Self::render_decoration(
+                    frame,
+                    text.decoration.union(style.decoration),
+                    (run_x as f32, run_y as f32),
+                    width as f32,
+                    options,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw the grapheme clusters of the text placed along the text path one by one with their transforms.
+    fn render_path_glyphs(
+        frame: &Frame, fonts: &FontRegistry, text: &Text, defaults: &ShapeDefaults, shadow: Option<&Shadow>,
+    ) -> Result<(), NanovgRenderError> {
+        let plain_style = SpanStyle::default();
+        let (dx, dy) = shadow.map_or((0.0, 0.0), |shadow| (shadow.offset_x, shadow.offset_y));
+        let global = text
+            .transform
+            .calculated_matrix()
+            .unwrap_or_else(|| text.transform.matrix());
+        for glyph in text.path_glyphs() {
+            let line = &text.lines[glyph.line];
+            let style = text.spans.get(glyph.span).map_or(&plain_style, |span| &span.style);
+            let (font, options) = Self::span_text_options(frame.context(), fonts, text, style, defaults)?;
+            let mut options = Self::shadow_text_options(options, text, defaults, shadow);
+
+            // The shadow is offset in the text coordinates, as the shadow of the straight text
+            let offset = TransformMatrix::identity().with_translation(dx, dy);
+            options.transform = Self::nanovg_transform(&Transform::Global(global * offset * glyph.matrix));
+            let x = text.x.val() + glyph.x;
+            let y = text.y.val() + line.baseline - style.baseline_offset;
+            frame.text(font, (x as f32, y as f32), glyph.content, options);
+            Self::render_decoration(
+                frame,
+                text.decoration.union(style.decoration),
+                (x as f32, y as f32),
+                glyph.width as f32,
+                options,
+            );
+        }
+        Ok(())
+    }
+
+    /// Draw the decoration lines of the text with the width from the baseline position.
+    fn render_decoration(
+        frame: &Frame, decoration: TextDecoration, (x, y): (f32, f32), width: f32, options: TextOptions,
+    ) {
+        if decoration.is_none() {
+            return;
+        }
+        frame.path(
+            |path| {
+                for (offset, thickness) in decoration.lines(options.size as Real) {
+                    let (offset, thickness) = (offset as f32, thickness as f32);
+                    path.rect((x, y + offset - thickness / 2.0), (width, thickness));
+                }
+                path.fill(options.color, Default::default());
+            },
+            PathOptions {
+                clip: options.clip,
+                transform: options.transform,
+                ..Default::default()
+            },
+        );
     }
 
-    fn set_by_pct_padding(padding: &mut Padding, parent_bound: &BoundingBox) {
-        padding.left.set_by_pct(parent_bound.width());
-        padding.right.set_by_pct(parent_bound.width());
-        padding.top.set_by_pct(parent_bound.height());
-        padding.bottom.set_by_pct(parent_bound.height());
+    fn text_bound(text: &Text, font_line_height: Real) -> BoundingBox {
+        let line_height = font_line_height * text.layout.line_height;
+        let (x, y) = (text.x.val(), text.y.val());
+        let (first, last) = match (text.lines.first(), text.lines.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => {
+                return BoundingBox {
+                    min_x: x,
+                    min_y: y,
+                    max_x: x,
+                    max_y: y + line_height,
+                }
+            }
+        };
+        text.lines.iter().fold(
+            BoundingBox {
+                min_x: x + first.x,
+                min_y: y + first.top(),
+                max_x: x + first.max_x(),
+                max_y: y + last.bottom(),
+            },
+            |bound, line| BoundingBox {
+                min_x: bound.min_x.min(x + line.x),
+                max_x: bound.max_x.max(x + line.max_x()),
+                ..bound
+            },
+        )
+    }
+
+    fn resolve_padding(padding: &mut Padding, parent_bound: &BoundingBox, units: &UnitContext) {
+        padding.left.resolve(parent_bound.width(), units);
+        padding.right.resolve(parent_bound.width(), units);
+        padding.top.resolve(parent_bound.height(), units);
+        padding.bottom.resolve(parent_bound.height(), units);
     }
 
-    fn set_by_pct_clip(clip: &mut Clip, parent_bound: &BoundingBox) {
-        if let Clip::Scissor(scissor) = clip {
-            scissor.x.set_by_pct(parent_bound.width());
-            scissor.y.set_by_pct(parent_bound.height());
-            scissor.width.set_by_pct(parent_bound.width());
-            scissor.height.set_by_pct(parent_bound.height());
+    fn resolve_clip(clip: &mut Clip, parent_bound: &BoundingBox, units: &UnitContext) {
+        match clip {
+            Clip::Scissor(scissor) => {
+                scissor.x.resolve(parent_bound.width(), units);
+                scissor.y.resolve(parent_bound.height(), units);
+                scissor.width.resolve(parent_bound.width(), units);
+                scissor.height.resolve(parent_bound.height(), units);
+            }
+            Clip::RoundedRect(rounded) => {
+                rounded.x.resolve(parent_bound.width(), units);
+                rounded.y.resolve(parent_bound.height(), units);
+                rounded.width.resolve(parent_bound.width(), units);
+                rounded.height.resolve(parent_bound.height(), units);
+                let radius = parent_bound.width().min(parent_bound.height());
+                rounded.rounding.top_left.resolve(radius, units);
+                rounded.rounding.top_right.resolve(radius, units);
+                rounded.rounding.bottom_left.resolve(radius, units);
+                rounded.rounding.bottom_right.resolve(radius, units);
+            }
+            Clip::Circle(circle) => {
+                circle.cx.resolve(parent_bound.width(), units);
+                circle.cy.resolve(parent_bound.height(), units);
+                circle.r.resolve(parent_bound.width().min(parent_bound.height()), units);
+            }
+            Clip::Path(_) | Clip::None => (),
         }
     }
 
@@ -667,23 +1425,45 @@ impl NanovgRender {
         }
     }
 
-    fn nanovg_clip(clip: &Clip) -> NanovgClip {
-        match clip {
-            Clip::Scissor(scissor) => NanovgClip::Scissor(NanovgScissor {
+    /// Nanovg has the scissor only, so the single scissor keeps its transform, while the other clips
+    /// and the nested clips are approximated by the intersection of their global axis aligned bounds,
+    /// see `RenderFeature::ShapedClips`. The path clip without points has no area, so it clips everything.
+    fn nanovg_clip<'a>(clips: impl IntoIterator<Item = &'a Clip>) -> NanovgClip {
+        let clips: Vec<&Clip> = clips.into_iter().filter(|clip| !clip.is_none()).collect();
+        match clips.as_slice() {
+            [] => NanovgClip::None,
+            [Clip::Scissor(scissor)] => NanovgClip::Scissor(NanovgScissor {
                 x: scissor.x.val() as f32,
                 y: scissor.y.val() as f32,
                 width: scissor.width.val() as f32,
                 height: scissor.height.val() as f32,
                 transform: Self::nanovg_transform(&scissor.transform),
             }),
-            Clip::None => NanovgClip::None,
+            _ => {
+                let [min_x, min_y, max_x, max_y] = clips
+                    .iter()
+                    .try_fold([Real::MIN, Real::MIN, Real::MAX, Real::MAX], |[min_x, min_y, max_x, max_y], clip| {
+                        let [x, y, width, height] = clip.global_bounds()?;
+                        Some([min_x.max(x), min_y.max(y), max_x.min(x + width), max_y.min(y + height)])
+                    })
+                    .unwrap_or_default();
+                let mut transform = NanovgTransform::new();
+                transform.absolute();
+                NanovgClip::Scissor(NanovgScissor {
+                    x: min_x as f32,
+                    y: min_y as f32,
+                    width: (max_x - min_x).max(0.0) as f32,
+                    height: (max_y - min_y).max(0.0) as f32,
+                    transform: Some(transform),
+                })
+            }
         }
     }
 
-    fn path_options(transparency: Real, clip: Clip, transform: &Transform, defaults: &ShapeDefaults) -> PathOptions {
+    fn path_options(transparency: Real, clip: &Clip, transform: &Transform, defaults: &ShapeDefaults) -> PathOptions {
         PathOptions {
             alpha: ((1.0 - transparency) * (1.0 - defaults.transparency)) as f32,
-            clip: Self::nanovg_clip(&clip.or(defaults.clip)),
+            clip: Self::nanovg_clip(defaults.clips.iter().chain(Some(clip))),
             transform: Self::nanovg_transform(transform),
             ..Default::default()
         }
@@ -709,21 +1489,50 @@ impl NanovgRender {
         }
     }
 
+    fn text_font<'a>(
+        context: &'a Context, fonts: &FontRegistry, text: &Text,
+    ) -> Result<NanovgFont<'a>, NanovgRenderError> {
+        Self::find_font(context, fonts, &text.font_name, FontWeight::NORMAL, FontStyle::Normal)
+    }
+
+    /// Font and options of the span, the face nearest to the span weight and style is used.
+    fn span_text_options<'a>(
+        context: &'a Context, fonts: &FontRegistry, text: &Text, style: &SpanStyle, defaults: &ShapeDefaults,
+    ) -> Result<(NanovgFont<'a>, TextOptions), NanovgRenderError> {
+        let font_name = style.font_name.as_deref().unwrap_or(&text.font_name);
+        let font = Self::find_font(context, fonts, font_name, style.weight, style.font_style())?;
+
+        let mut options = Self::text_options(text, defaults);
+        if let Some(color) = style.color {
+            options.color = Self::text_color(color, text, defaults);
+        }
+        options.size = style.font_size.unwrap_or(text.font_size).val() as f32;
+        options.align = Alignment::new().left().baseline();
+        Ok((font, options))
+    }
+
+    fn text_color(color: Color, text: &Text, defaults: &ShapeDefaults) -> NanovgColor {
+        let mut color = ToNanovgPaint::to_nanovg_color(color);
+        color.set_alpha(color.alpha() * (1.0 - defaults.transparency) * (1.0 - text.transparency));
+        color
+    }
+
     fn text_options(text: &Text, defaults: &ShapeDefaults) -> TextOptions {
-        let mut color = ToNanovgPaint::to_nanovg_color(
+        let color = Self::text_color(
             text.fill
                 .as_ref()
                 .or(defaults.fill.as_ref())
                 .and_then(|fill| {
-                    if let Paint::Color(color) = fill.paint {
-                        Some(color)
+                    if let Paint::Color(color) = &fill.paint {
+                        Some(*color)
                     } else {
                         None
                     }
                 })
                 .unwrap_or_default(),
+            text,
+            defaults,
         );
-        color.set_alpha(color.alpha() * (1.0 - defaults.transparency) * (1.0 - text.transparency));
 
         let mut align = Alignment::new();
         align = match text.align.0 {
@@ -738,12 +1547,20 @@ impl NanovgRender {
             AlignVer::Top => align.top(),
         };
 
+        let overflow_clip = text.overflow_clip();
         TextOptions {
             color,
             size: text.font_size.val() as f32,
+            letter_spacing: text.letter_spacing as f32,
             align,
-            clip: Self::nanovg_clip(&text.clip.or(defaults.clip)),
+            clip: Self::nanovg_clip(defaults.clips.iter().chain(Some(&text.clip)).chain(Some(&overflow_clip))),
             transform: Self::nanovg_transform(&text.transform),
+            blur: Self::effects(&text.effects, defaults)
+                .iter()
+                .fold(0.0, |blur, effect| match *effect {
+                    Effect::Blur(radius) => radius as f32,
+                    _ => blur,
+                }),
             ..Default::default()
         }
     }
@@ -1,24 +1,22 @@
-use std::{
-    fmt,
-    fs::File,
-    io::{self, Read},
-    iter,
-    ops::Mul,
-    path::Path,
-    sync::Arc,
-};
+use std::{borrow::Cow, cell::RefCell, collections::HashMap, fmt, iter, mem, ops::Mul, path::Path, sync::Arc};
 
 use exgui_core::{
-    AlignHor, AlignVer, Clip, Color, CompositeShape, Fill, GlyphPos, Gradient, LineCap, LineJoin, Padding, Paint, Real,
-    Render, Rounding, Shape, Stroke, Text, TextMetrics, Transform, TransformMatrix,
+    shape_runs, AlignHor, AlignVer, BlendMode, Clip, Color, CompositeShape, CompositeShapeIter, Effect, Fill, FillRule,
+    FontError, FontFace, FontRegistry, FontStyle, FontWeight, Gradient, Image, LineCap, LineJoin, Padding, Paint,
+    Path as PathShape, PathCommand, PathError, PathSegment, Pattern, PatternRepeat, Real, Render, RenderFeature,
+    Rounding, Shadow, Shape, ShapedText, SpanStyle, SpreadMode, Stroke, Text, TextDecoration, TextMeasurer,
+    TextMetrics, Transform, TransformMatrix, UnitContext,
 };
-use font_kit::handle::Handle;
+use font_kit::{handle::Handle, loaders::default::Font as FontKitFont};
 use pathfinder_canvas::{
-    vec2f, vec2i, Canvas, CanvasFontContext, CanvasRenderingContext2D, ColorF, FillRule, FillStyle,
-    LineCap as PathfinderLineCap, LineJoin as PathfinderLineJoin, Path2D, RectF, TextAlign, TextBaseline, Transform2F,
-    Vector2F, Vector2I,
+    vec2f, vec2i, Canvas, CanvasFontContext, CanvasRenderingContext2D, ColorF, ColorU, CompositeOperation,
+    FillRule as PathfinderFillRule, FillStyle, LineCap as PathfinderLineCap, LineJoin as PathfinderLineJoin, Path2D,
+    RectF, TextAlign, TextBaseline, Transform2F, Vector2F, Vector2I,
+};
+use pathfinder_content::{
+    gradient::{Gradient as PathfinderGradient, GradientWrap},
+    pattern::{Image as PathfinderImage, Pattern as PathfinderPattern},
 };
-use pathfinder_content::gradient::Gradient as PathfinderGradient;
 use pathfinder_gl::{GLDevice, GLVersion};
 use pathfinder_renderer::{
     concurrent::{rayon::RayonExecutor, scene_proxy::SceneProxy},
@@ -50,6 +48,20 @@ impl BoundingBox {
     pub fn height(&self) -> Real {
         self.max_y - self.min_y
     }
+
+    pub fn from_bounds([x, y, width, height]: [Real; 4]) -> Self {
+        Self {
+            min_x: x,
+            min_y: y,
+            max_x: x + width,
+            max_y: y + height,
+        }
+    }
+
+    /// Bounds as `[x, y, width, height]`.
+    pub fn bounds(&self) -> [Real; 4] {
+        [self.min_x, self.min_y, self.width(), self.height()]
+    }
 }
 
 impl Mul<BoundingBox> for TransformMatrix {
@@ -68,13 +80,18 @@ impl Mul<BoundingBox> for TransformMatrix {
 #[derive(Debug)]
 pub enum PathfinderRenderError {
     ContextIsNotInit,
-    CreateFontError(io::Error, String),
+    /// The font data of the face can not be loaded or has no PostScript name.
+    LoadFontError(String),
+    Font(FontError),
+    InvalidPath(PathError),
 }
 
 pub struct RendererContext {
     pub renderer: Renderer<GLDevice>,
     pub font_context: CanvasFontContext,
     pub font_handles: Vec<Handle>,
+    /// PostScript names of the loaded faces by the face names, the canvas looks fonts up by them.
+    pub postscript_names: HashMap<String, String>,
 }
 
 impl fmt::Debug for RendererContext {
@@ -83,6 +100,72 @@ impl fmt::Debug for RendererContext {
             .field("renderer", &"...")
             .field("font_context", &"...")
             .field("font_handles", &self.font_handles)
+            .field("postscript_names", &self.postscript_names)
+            .finish()
+    }
+}
+
+/// Images of the patterns by the image identity and the repeat mode. They are kept across the frames
+/// and dropped after the frame in which their images are gone.
+#[derive(Default)]
+struct PatternImages {
+    images: RefCell<HashMap<(usize, PatternRepeat), CachedImage>>,
+}
+
+/// Padded image along with the clone of the pattern image, the clone keeps the identity from being reused.
+/// The image is made anew when the pattern opacity is changed, as the opacity is applied to its pixels.
+struct CachedImage {
+    source: Image,
+    opacity: Real,
+    image: PathfinderImage,
+}
+
+impl PatternImages {
+    /// The image that is not repeated along an axis is padded with the transparent pixels along the axis.
+    fn padding(repeat: PatternRepeat) -> (usize, usize) {
+        (!repeat.repeat_x() as usize, !repeat.repeat_y() as usize)
+    }
+
+    /// Pathfinder patterns have no opacity, so it is applied to the image pixels.
+    fn image(&self, pattern: &Pattern) -> PathfinderImage {
+        let key = (pattern.image.id(), pattern.repeat);
+        let opacity = pattern.opacity.clamp(0.0, 1.0);
+        if let Some(cached) = self.images.borrow().get(&key).filter(|cached| cached.opacity == opacity) {
+            return cached.image.clone();
+        }
+
+        let (pad_x, pad_y) = Self::padding(pattern.repeat);
+        let (width, height) = (pattern.image.width() as usize, pattern.image.height() as usize);
+        let (padded_width, padded_height) = (width + pad_x * 2, height + pad_y * 2);
+        let mut pixels = vec![ColorU::transparent_black(); padded_width * padded_height];
+        for (y, row) in pattern.image.data().chunks_exact(width * 4).enumerate() {
+            let start = (y + pad_y) * padded_width + pad_x;
+            for (pixel, rgba) in pixels[start..start + width].iter_mut().zip(row.chunks_exact(4)) {
+                let alpha = (Real::from(rgba[3]) * opacity).round() as u8;
+                *pixel = ColorU::new(rgba[0], rgba[1], rgba[2], alpha);
+            }
+        }
+
+        let image = PathfinderImage::new(vec2i(padded_width as i32, padded_height as i32), Arc::new(pixels));
+        let cached = CachedImage {
+            source: pattern.image.clone(),
+            opacity,
+            image: image.clone(),
+        };
+        self.images.borrow_mut().insert(key, cached);
+        image
+    }
+
+    /// Drop the images that are no longer used by the patterns.
+    fn evict(&self) {
+        self.images.borrow_mut().retain(|_, cached| cached.source.is_shared());
+    }
+}
+
+impl fmt::Debug for PatternImages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(stringify!(PatternImages))
+            .field("len", &self.images.borrow().len())
             .finish()
     }
 }
@@ -93,6 +176,10 @@ pub struct PathfinderRender {
     pub width: u32,
     pub height: u32,
     pub framebuffer_size: Vector2I,
+    pub fonts: FontRegistry,
+    /// Sizes of the relative units, the viewport is set by the render size.
+    units: UnitContext,
+    pattern_images: PatternImages,
 }
 
 impl Render for PathfinderRender {
@@ -115,11 +202,16 @@ impl Render for PathfinderRender {
                 renderer,
                 font_context,
                 font_handles: vec![],
+                postscript_names: HashMap::new(),
             });
         }
         Ok(())
     }
 
+    fn supports(&self, feature: RenderFeature) -> bool {
+        matches!(feature, RenderFeature::Layers | RenderFeature::BlendMode(_) | RenderFeature::ShapedClips)
+    }
+
     fn set_dimensions(&mut self, physical_width: u32, physical_height: u32, _device_pixel_ratio: f64) {
         if self.width != physical_width || self.height != physical_height {
             let framebuffer_size = vec2i(physical_width as i32, physical_height as i32);
@@ -135,7 +227,12 @@ impl Render for PathfinderRender {
     }
 
     fn render(&mut self, node: &mut dyn CompositeShape) -> Result<bool, Self::Error> {
+        self.load_new_fonts()?;
         let renderer_context = self.context.as_mut().ok_or(PathfinderRenderError::ContextIsNotInit)?;
+        let fonts = Fonts {
+            registry: &self.fonts,
+            postscript_names: &renderer_context.postscript_names,
+        };
         let mut canvas_context =
             Canvas::new(self.framebuffer_size.to_f32()).get_context_2d(renderer_context.font_context.clone());
 
@@ -147,20 +244,30 @@ impl Render for PathfinderRender {
         };
 
         // Recalculate tree data and fill canvas
+        let units = self.units.with_viewport(bound.width(), bound.height());
         if node.need_recalc().unwrap_or(true) {
-            let mut defaults = ShapeDefaults::default();
+            let mut defaults = ShapeDefaults {
+                units,
+                ..Default::default()
+            };
             Self::recalc_composite(
                 &mut canvas_context,
+                &fonts,
                 node,
                 bound,
                 TransformMatrix::identity(),
                 &mut defaults,
-            );
+            )?;
         }
 
         if node.need_redraw().unwrap_or(true) {
-            let mut defaults = ShapeDefaults::default();
-            Self::render_composite(&mut canvas_context, node, None, &mut defaults);
+            let mut defaults = ShapeDefaults {
+                units,
+                ..Default::default()
+            };
+            let images = &self.pattern_images;
+            Self::render_composite(&mut canvas_context, &fonts, images, node, None, &mut defaults)?;
+            images.evict();
 
             // Render the canvas to screen.
             let scene = SceneProxy::from_scene(canvas_context.into_canvas().into_scene(), RayonExecutor);
@@ -177,7 +284,11 @@ pub struct ShapeDefaults {
     pub transparency: Real,
     pub fill: Option<Fill>,
     pub stroke: Option<Stroke>,
+    /// Clip of the ancestor groups in the current canvas, the nested clips are drawn to the layers.
     pub clip: Clip,
+    pub effects: Vec<Effect>,
+    /// Sizes of the relative units, the viewport is set by the render size.
+    pub units: UnitContext,
 }
 
 impl PathfinderRender {
@@ -187,48 +298,82 @@ impl PathfinderRender {
         render
     }
 
-    pub fn load_font(&mut self, _name: impl AsRef<str>, path: impl AsRef<Path>) -> Result<(), <Self as Render>::Error> {
-        let context = self.context.as_mut().ok_or(PathfinderRenderError::ContextIsNotInit)?;
+    pub fn with_fonts(mut self, fonts: FontRegistry) -> Self {
+        self.fonts = fonts;
+        self
+    }
+
+    pub fn with_root_font_size(mut self, root_font_size: Real) -> Self {
+        self.set_root_font_size(root_font_size);
+        self
+    }
+
+    /// Set the size of the `Rem` unit, it is the `Em` unit of the shapes other than the texts as well.
+    pub fn set_root_font_size(&mut self, root_font_size: Real) {
+        self.units = self.units.with_root_font_size(root_font_size);
+    }
 
-        let display_path = path.as_ref().display();
-        let mut font_file_data = vec![];
-        File::open(&path)
-            .and_then(|mut file| file.read_to_end(&mut font_file_data))
-            .map_err(|err| PathfinderRenderError::CreateFontError(err, format!("{}", display_path)))?;
-        context
-            .font_handles
-            .push(Handle::from_memory(Arc::new(font_file_data), 0));
-        context.font_context = CanvasFontContext::from_fonts(context.font_handles.clone().into_iter());
+    /// Register the font file in the font registry as the family with the normal weight and style.
+    pub fn load_font(
+        &mut self, name: impl Into<String>, path: impl AsRef<Path>,
+    ) -> Result<(), <Self as Render>::Error> {
+        self.fonts
+            .load_file(name, FontWeight::NORMAL, FontStyle::Normal, path)
+            .map_err(PathfinderRenderError::Font)?;
+        Ok(())
+    }
 
+    /// Add the faces registered since the last render to the font context.
+    fn load_new_fonts(&mut self) -> Result<(), PathfinderRenderError> {
+        let context = self.context.as_mut().ok_or(PathfinderRenderError::ContextIsNotInit)?;
+        let mut loaded = false;
+        for face in self.fonts.faces() {
+            if context.postscript_names.contains_key(&face.name) {
+                continue;
+            }
+            let postscript_name = FontKitFont::from_bytes(face.data.clone(), 0)
+                .ok()
+                .and_then(|font| font.postscript_name())
+                .ok_or_else(|| PathfinderRenderError::LoadFontError(face.name.clone()))?;
+            context.font_handles.push(Handle::from_memory(face.data.clone(), 0));
+            context.postscript_names.insert(face.name.clone(), postscript_name);
+            loaded = true;
+        }
+        if loaded {
+            context.font_context = CanvasFontContext::from_fonts(context.font_handles.clone().into_iter());
+        }
         Ok(())
     }
 
     fn recalc_composite(
-        canvas: &mut CanvasRenderingContext2D, composite: &mut dyn CompositeShape, parent_bound: BoundingBox,
-        mut parent_global_transform: TransformMatrix, defaults: &mut ShapeDefaults,
-    ) -> BoundingBox {
+        canvas: &mut CanvasRenderingContext2D, fonts: &Fonts, composite: &mut dyn CompositeShape,
+        parent_bound: BoundingBox, mut parent_global_transform: TransformMatrix, defaults: &mut ShapeDefaults,
+    ) -> Result<BoundingBox, PathfinderRenderError> {
         let mut bound = parent_bound;
+        let units = defaults.units;
 
         if let Some(shape) = composite.shape_mut() {
             match shape {
                 Shape::Rect(rect) => {
-                    if rect.x.set_by_pct(parent_bound.width()) {
+                    if rect.x.resolve(parent_bound.width(), &units) {
                         rect.x.0 += parent_bound.min_x;
                     }
-                    if rect.y.set_by_pct(parent_bound.height()) {
+                    if rect.y.resolve(parent_bound.height(), &units) {
                         rect.y.0 += parent_bound.min_y;
                     }
-                    rect.width.set_by_pct(parent_bound.width());
-                    rect.height.set_by_pct(parent_bound.height());
+                    rect.width.resolve(parent_bound.width(), &units);
+                    rect.height.resolve(parent_bound.height(), &units);
+                    rect.constraints.resolve(parent_bound.width(), parent_bound.height(), &units);
+                    rect.constrain_size(None);
                     if let Some(rounding) = &mut rect.rounding {
                         let radius = parent_bound.width().min(parent_bound.height());
-                        rounding.top_left.set_by_pct(radius);
-                        rounding.top_right.set_by_pct(radius);
-                        rounding.bottom_left.set_by_pct(radius);
-                        rounding.bottom_right.set_by_pct(radius);
+                        rounding.top_left.resolve(radius, &units);
+                        rounding.top_right.resolve(radius, &units);
+                        rounding.bottom_left.resolve(radius, &units);
+                        rounding.bottom_right.resolve(radius, &units);
                     }
-                    Self::set_by_pct_padding(&mut rect.padding, &parent_bound);
-                    Self::set_by_pct_clip(&mut rect.clip, &parent_bound);
+                    Self::resolve_padding(&mut rect.padding, &parent_bound, &units);
+                    Self::resolve_clip(&mut rect.clip, &parent_bound, &units);
 
                     parent_global_transform = rect.recalculate_transform(parent_global_transform);
                     let (scale_x, scale_y) = parent_global_transform.scale_xy();
@@ -238,27 +383,29 @@ impl PathfinderRender {
                     bound = BoundingBox {
                         min_x: rect.x.val(),
                         min_y: rect.y.val(),
-                        max_x: rect.x.val() + rect.width.val(),
-                        max_y: rect.y.val() + rect.height.val(),
+                        max_x: rect.x.val() + rect.layout_width(),
+                        max_y: rect.y.val() + rect.layout_height(),
                     };
                 }
                 Shape::Circle(circle) => {
-                    if circle.cx.set_by_pct(parent_bound.width()) {
+                    if circle.cx.resolve(parent_bound.width(), &units) {
                         circle.cx.0 += parent_bound.min_x;
                     }
-                    if circle.cy.set_by_pct(parent_bound.height()) {
+                    if circle.cy.resolve(parent_bound.height(), &units) {
                         circle.cy.0 += parent_bound.min_y;
                     }
-                    circle.r.set_by_pct(parent_bound.width().min(parent_bound.height()));
-                    Self::set_by_pct_padding(&mut circle.padding, &parent_bound);
-                    Self::set_by_pct_clip(&mut circle.clip, &parent_bound);
+                    circle.r.resolve(parent_bound.width().min(parent_bound.height()), &units);
+                    circle.constraints.resolve(parent_bound.width(), parent_bound.height(), &units);
+                    circle.constrain_radius(None);
+                    Self::resolve_padding(&mut circle.padding, &parent_bound, &units);
+                    Self::resolve_clip(&mut circle.clip, &parent_bound, &units);
 
                     parent_global_transform = circle.recalculate_transform(parent_global_transform);
                     let (scale_x, scale_y) = parent_global_transform.scale_xy();
                     parent_global_transform
                         .translate_add(circle.padding.left.val() * scale_x, circle.padding.top.val() * scale_y);
 
-                    let (cx, cy, r) = (circle.cx.val(), circle.cy.val(), circle.r.val());
+                    let (cx, cy, r) = (circle.cx.val(), circle.cy.val(), circle.layout_r());
                     bound = BoundingBox {
                         min_x: cx - r,
                         min_y: cy - r,
@@ -266,120 +413,121 @@ impl PathfinderRender {
                         max_y: cy + r,
                     };
                 }
+                Shape::Ellipse(ellipse) => {
+                    ellipse.set_by_bounds(parent_bound.bounds(), &units);
+                    Self::resolve_clip(&mut ellipse.clip, &parent_bound, &units);
+                    parent_global_transform = ellipse.recalculate_transform(parent_global_transform);
+                    bound = BoundingBox::from_bounds(ellipse.bounds());
+                }
+                Shape::Line(line) => {
+                    line.set_by_bounds(parent_bound.bounds(), &units);
+                    Self::resolve_clip(&mut line.clip, &parent_bound, &units);
+                    parent_global_transform = line.recalculate_transform(parent_global_transform);
+                    bound = BoundingBox::from_bounds(line.bounds());
+                }
+                Shape::Polyline(polyline) => {
+                    polyline.set_by_bounds(parent_bound.bounds(), &units);
+                    Self::resolve_clip(&mut polyline.clip, &parent_bound, &units);
+                    parent_global_transform = polyline.recalculate_transform(parent_global_transform);
+                    if let Some(bounds) = polyline.bounds() {
+                        bound = BoundingBox::from_bounds(bounds);
+                    }
+                }
+                Shape::Polygon(polygon) => {
+                    polygon.set_by_bounds(parent_bound.bounds(), &units);
+                    Self::resolve_clip(&mut polygon.clip, &parent_bound, &units);
+                    parent_global_transform = polygon.recalculate_transform(parent_global_transform);
+                    if let Some(bounds) = polygon.bounds() {
+                        bound = BoundingBox::from_bounds(bounds);
+                    }
+                }
                 Shape::Text(text) => {
-                    if text.x.set_by_pct(parent_bound.width()) {
+                    text.font_size.resolve(units.font_size, &units);
+                    for span in &mut text.spans {
+                        if let Some(font_size) = &mut span.style.font_size {
+                            font_size.resolve(units.font_size, &units);
+                        }
+                    }
+                    let units = units.with_font_size(text.font_size.val());
+                    if text.x.resolve(parent_bound.width(), &units) {
                         text.x.0 += parent_bound.min_x;
                     }
-                    if text.y.set_by_pct(parent_bound.height()) {
+                    if text.y.resolve(parent_bound.height(), &units) {
                         text.y.0 += parent_bound.min_y;
                     }
-                    Self::set_by_pct_clip(&mut text.clip, &parent_bound);
+                    if let Some(max_width) = &mut text.layout.max_width {
+                        max_width.resolve(parent_bound.width(), &units);
+                    }
+                    if let Some(path) = &mut text.path {
+                        path.resolve(&units);
+                    }
+                    Self::resolve_clip(&mut text.clip, &parent_bound, &units);
 
                     parent_global_transform = text.recalculate_transform(parent_global_transform);
 
                     canvas.save();
-                    Self::set_text_options(canvas, text, defaults);
-
-                    let content = if text.content.is_empty() { "a" } else { &text.content };
-                    let metrics = canvas.measure_text(content);
-                    let ascend = metrics.font_bounding_box_ascent.abs();
-                    let descend = metrics.font_bounding_box_descent.abs();
-                    let line_height = ascend + descend;
-                    text.metrics = Some(TextMetrics {
-                        ascender: ascend,
-                        descender: descend,
-                        line_height,
-                    });
-
-                    // todo: cache the glyph_positions
-                    let layout = skribo::layout(
-                        &TextStyle {
-                            size: canvas.font_size(),
-                        },
-                        &canvas.font(),
-                        &text.content,
-                    );
-
-                    text.glyph_positions.clear();
-                    let mut prev_pos: Option<Vector2F> = None;
-                    for pos in layout
-                        .glyphs
-                        .iter()
-                        .map(|glyph| glyph.offset)
-                        .chain(iter::once(layout.advance))
-                    {
-                        if let Some(prev_pos) = prev_pos {
-                            text.glyph_positions.push(GlyphPos {
-                                x: prev_pos.x(),
-                                y: prev_pos.y(),
-                                width: pos.x() - prev_pos.x(),
-                            });
-                        }
-                        prev_pos = Some(pos);
-                    }
-
+                    let measured = text.layout_with(&CanvasMeasurer::new(canvas, fonts), fonts.registry);
                     canvas.restore();
+                    measured.map_err(PathfinderRenderError::Font)?;
 
-                    bound = BoundingBox {
-                        min_x: text.x.val(),
-                        min_y: text.y.val(),
-                        max_x: text.x.val() + text.glyph_positions.last().map(|pos| pos.max_x()).unwrap_or(0.0),
-                        max_y: text.y.val() + line_height,
+                    bound = match text.path_bounds() {
+                        Some(bounds) => BoundingBox::from_bounds(bounds),
+                        None => Self::text_bound(text, text.metrics.unwrap_or_default().line_height),
                     };
                 }
                 Shape::Path(path) => {
-                    Self::set_by_pct_clip(&mut path.clip, &parent_bound);
+                    Self::resolve_clip(&mut path.clip, &parent_bound, &units);
                     parent_global_transform = path.recalculate_transform(parent_global_transform);
+                    if let Some(bounds) = path.bounds() {
+                        bound = BoundingBox::from_bounds(bounds);
+                    }
                 }
                 Shape::Group(group) => {
-                    Self::set_by_pct_clip(&mut group.clip, &parent_bound);
+                    Self::resolve_clip(&mut group.clip, &parent_bound, &units);
                     parent_global_transform = group.recalculate_transform(parent_global_transform);
 
                     if let Some(transparency) = group.transparency {
                         defaults.transparency = transparency;
                     }
-                    if let Some(fill) = group.fill {
-                        defaults.fill = Some(fill);
+                    if let Some(fill) = &group.fill {
+                        defaults.fill = Some(fill.clone());
                     }
-                    if let Some(stroke) = group.stroke {
-                        defaults.stroke = Some(stroke);
-                    }
-                    if !group.clip.is_none() {
-                        defaults.clip = group.clip;
+                    if let Some(stroke) = &group.stroke {
+                        defaults.stroke = Some(stroke.clone());
                     }
                 }
             }
         }
 
-        let inner_bound = Self::calc_inner_bound(canvas, composite, bound, parent_global_transform, defaults);
+        let inner_bound =
+            Self::calc_inner_bound(canvas, fonts, composite, bound, parent_global_transform, defaults)?;
 
         if let Some(shape) = composite.shape_mut() {
             match shape {
                 Shape::Rect(rect) => {
                     rect.x.set_by_auto(inner_bound.min_x);
                     rect.y.set_by_auto(inner_bound.min_y);
-                    rect.width
-                        .set_by_auto(inner_bound.max_x - rect.x.val() + rect.padding.left_and_right().val());
-                    rect.height
-                        .set_by_auto(inner_bound.max_y - rect.y.val() + rect.padding.top_and_bottom().val());
+                    let content_width = inner_bound.max_x - rect.x.val() + rect.padding.left_and_right().val();
+                    let content_height = inner_bound.max_y - rect.y.val() + rect.padding.top_and_bottom().val();
+                    rect.constrain_size(Some((content_width, content_height)));
 
                     bound = BoundingBox {
                         min_x: rect.x.val(),
                         min_y: rect.y.val(),
-                        max_x: rect.x.val() + rect.width.val(),
-                        max_y: rect.y.val() + rect.height.val(),
+                        max_x: rect.x.val() + rect.layout_width(),
+                        max_y: rect.y.val() + rect.layout_height(),
                     };
                 }
                 Shape::Circle(circle) => {
                     circle.cx.set_by_auto(inner_bound.min_x + inner_bound.width() / 2.0);
                     circle.cy.set_by_auto(inner_bound.min_y + inner_bound.height() / 2.0);
-                    circle.r.set_by_auto(
+                    circle.constrain_radius(Some(
                         (inner_bound.width() + circle.padding.left_and_right().val())
                             .max(inner_bound.height() + circle.padding.top_and_bottom().val())
                             / 2.0,
-                    );
+                    ));
 
-                    let (cx, cy, r) = (circle.cx.val(), circle.cy.val(), circle.r.val());
+                    let (cx, cy, r) = (circle.cx.val(), circle.cy.val(), circle.layout_r());
                     bound = BoundingBox {
                         min_x: cx - r,
                         min_y: cy - r,
@@ -406,27 +554,28 @@ impl PathfinderRender {
                 _ => (),
             }
         }
-        bound
+        Ok(bound)
     }
 
     fn calc_inner_bound(
-        canvas: &mut CanvasRenderingContext2D, composite: &mut dyn CompositeShape, bound: BoundingBox,
-        parent_global_transform: TransformMatrix, defaults: &mut ShapeDefaults,
-    ) -> BoundingBox {
+        canvas: &mut CanvasRenderingContext2D, fonts: &Fonts, composite: &mut dyn CompositeShape,
+        bound: BoundingBox, parent_global_transform: TransformMatrix, defaults: &mut ShapeDefaults,
+    ) -> Result<BoundingBox, PathfinderRenderError> {
         let mut child_bounds = Vec::new();
         if let Some(children) = composite.children_mut() {
             for child in children {
                 child_bounds.push(Self::recalc_composite(
                     canvas,
+                    fonts,
                     child,
                     bound,
                     parent_global_transform,
                     defaults,
-                ));
+                )?);
             }
         }
 
-        if child_bounds.is_empty() {
+        Ok(if child_bounds.is_empty() {
             BoundingBox::default()
         } else {
             let mut inner_bound = child_bounds[0];
@@ -445,19 +594,29 @@ impl PathfinderRender {
                 }
             }
             inner_bound
-        }
+        })
     }
 
     fn render_composite<'a>(
-        canvas: &mut CanvasRenderingContext2D, composite: &'a dyn CompositeShape, mut text: Option<&'a Text>,
-        defaults: &mut ShapeDefaults,
-    ) {
+        canvas: &mut CanvasRenderingContext2D, fonts: &Fonts, images: &PatternImages, composite: &'a dyn CompositeShape,
+        mut text: Option<&'a Text>, defaults: &mut ShapeDefaults,
+    ) -> Result<(), PathfinderRenderError> {
+        if let Some(shape) = composite.shape() {
+            let own_clip = match shape {
+                Shape::Text(this_text) => !this_text.clip.is_none() || !this_text.overflow_clip().is_none(),
+                shape => !shape.clip().is_none(),
+            };
+            if own_clip && !defaults.clip.is_none() {
+                return Self::render_clipped_layer(canvas, fonts, images, composite, text, defaults);
+            }
+        }
+
         canvas.save();
         if let Some(shape) = composite.shape() {
             match shape {
                 Shape::Rect(rect) => {
                     let rect_pos = Vector2F::new(rect.x.val() as f32, rect.y.val() as f32);
-                    let rect_size = Vector2F::new(rect.width.val() as f32, rect.height.val() as f32);
+                    let rect_size = Vector2F::new(rect.layout_width() as f32, rect.layout_height() as f32);
 
                     let rect_path = if let Some(rounding) = rect.rounding {
                         create_rounded_rect_path(rect_pos, rect_size, rounding)
@@ -466,140 +625,104 @@ impl PathfinderRender {
                         path.rect(RectF::new(rect_pos, rect_size));
                         path
                     };
-                    Self::set_path_options(canvas, rect.transparency, rect.clip, &rect.transform, defaults);
+                    Self::set_path_options(canvas, rect.transparency, &rect.clip, &rect.transform, defaults);
+                    let effects = Self::effects(&rect.effects, defaults);
+                    let rule = PathfinderFillRule::Winding;
+                    Self::render_shadows(canvas, effects, &rect_path, rule);
+                    let bounds = [rect.x.val(), rect.y.val(), rect.layout_width(), rect.layout_height()];
+                    let units = &defaults.units;
                     if let Some(fill) = rect.fill.as_ref().or(defaults.fill.as_ref()) {
-                        Self::set_fill_option(canvas, fill);
-                        canvas.fill_path(rect_path.clone(), FillRule::Winding);
+                        Self::fill_path(canvas, effects, fill, bounds, units, images, rect_path.clone(), rule);
                     };
+                    Self::render_inner_shadows(canvas, effects, &rect_path, RectF::new(rect_pos, rect_size), rule);
                     if let Some(stroke) = rect.stroke.as_ref().or(defaults.stroke.as_ref()) {
-                        Self::set_stroke_option(canvas, stroke);
-                        canvas.stroke_path(rect_path);
+                        Self::set_stroke_option(canvas, stroke, bounds, units, images);
+                        let stroke_path = match stroke.center_offset() {
+                            offset if offset != 0.0 => outline_path(&rect.offset_outline(offset))?,
+                            _ => rect_path,
+                        };
+                        canvas.stroke_path(stroke_path);
                     }
                 }
                 Shape::Circle(circle) => {
                     let center = Vector2F::new(circle.cx.val(), circle.cy.val());
-                    let axes = Vector2F::new(circle.r.val(), circle.r.val());
+                    let axes = Vector2F::new(circle.layout_r(), circle.layout_r());
                     let circle_path = {
                         let mut path = Path2D::new();
                         path.ellipse(center, axes, 0.0, 0.0, PI_2);
                         path
                     };
 
-                    Self::set_path_options(canvas, circle.transparency, circle.clip, &circle.transform, defaults);
+                    Self::set_path_options(canvas, circle.transparency, &circle.clip, &circle.transform, defaults);
+                    let effects = Self::effects(&circle.effects, defaults);
+                    let rule = PathfinderFillRule::Winding;
+                    Self::render_shadows(canvas, effects, &circle_path, rule);
+                    let (cx, cy, r) = (circle.cx.val(), circle.cy.val(), circle.layout_r());
+                    let bounds = [cx - r, cy - r, 2.0 * r, 2.0 * r];
+                    let units = &defaults.units;
                     if let Some(fill) = circle.fill.as_ref().or(defaults.fill.as_ref()) {
-                        Self::set_fill_option(canvas, fill);
-                        canvas.fill_path(circle_path.clone(), FillRule::Winding);
+                        Self::fill_path(canvas, effects, fill, bounds, units, images, circle_path.clone(), rule);
                     };
+                    let inner_bounds = RectF::new(center - axes, axes * 2.0);
+                    Self::render_inner_shadows(canvas, effects, &circle_path, inner_bounds, rule);
                     if let Some(stroke) = circle.stroke.as_ref().or(defaults.stroke.as_ref()) {
-                        Self::set_stroke_option(canvas, stroke);
-                        canvas.stroke_path(circle_path);
-                    }
-                }
-                Shape::Path(path) => {
-                    use exgui_core::PathCommand::*;
-
-                    let mut last_xy = Vector2F::new(0.0, 0.0);
-                    let mut bez_ctrls = [Vector2F::new(0.0, 0.0), Vector2F::new(0.0, 0.0)];
-                    let mut draw_path = Path2D::new();
-
-                    for cmd in path.cmd.iter() {
-                        match cmd {
-                            Move(ref xy) => {
-                                last_xy = Vector2F::new(xy[0], xy[1]);
-                                draw_path.move_to(last_xy);
-                            }
-                            MoveRel(ref xy) => {
-                                last_xy = Vector2F::new(last_xy.x() + xy[0], last_xy.y() + xy[1]);
-                                draw_path.move_to(last_xy);
-                            }
-                            Line(ref xy) => {
-                                last_xy = Vector2F::new(xy[0], xy[1]);
-                                draw_path.line_to(last_xy);
-                            }
-                            LineRel(ref xy) => {
-                                last_xy = Vector2F::new(last_xy.x() + xy[0], last_xy.y() + xy[1]);
-                                draw_path.line_to(last_xy);
-                            }
-                            LineAlonX(ref x) => {
-                                last_xy.set_x(*x);
-                                draw_path.line_to(last_xy);
-                            }
-                            LineAlonXRel(ref x) => {
-                                last_xy.set_x(last_xy.x() + *x);
-                                draw_path.line_to(last_xy);
-                            }
-                            LineAlonY(ref y) => {
-                                last_xy.set_y(*y);
-                                draw_path.line_to(last_xy);
-                            }
-                            LineAlonYRel(ref y) => {
-                                last_xy.set_y(last_xy.y() + *y);
-                                draw_path.line_to(last_xy);
-                            }
-                            Close => draw_path.close_path(),
-                            BezCtrl(ref xy) => {
-                                bez_ctrls = [bez_ctrls[1], Vector2F::new(xy[0], xy[1])];
-                            }
-                            BezCtrlRel(ref xy) => {
-                                bez_ctrls = [bez_ctrls[1], Vector2F::new(last_xy.x() + xy[0], last_xy.y() + xy[1])];
-                            }
-                            QuadBezTo(ref xy) => {
-                                last_xy = Vector2F::new(xy[0], xy[1]);
-                                draw_path.quadratic_curve_to(bez_ctrls[1], last_xy);
-                            }
-                            QuadBezToRel(ref xy) => {
-                                last_xy = Vector2F::new(last_xy.x() + xy[0], last_xy.y() + xy[1]);
-                                draw_path.quadratic_curve_to(bez_ctrls[1], last_xy);
-                            }
-                            CubBezTo(ref xy) => {
-                                last_xy = Vector2F::new(xy[0], xy[1]);
-                                draw_path.bezier_curve_to(bez_ctrls[0], bez_ctrls[1], last_xy);
-                            }
-                            CubBezToRel(ref xy) => {
-                                last_xy = Vector2F::new(last_xy.x() + xy[0], last_xy.y() + xy[1]);
-                                draw_path.bezier_curve_to(bez_ctrls[0], bez_ctrls[1], last_xy);
-                            }
-                            _ => panic!("Not impl rendering cmd {:?}", cmd), // TODO: need refl impl
-                        }
-                    }
-
-                    Self::set_path_options(canvas, path.transparency, path.clip, &path.transform, defaults);
-                    if let Some(fill) = path.fill.as_ref().or(defaults.fill.as_ref()) {
-                        Self::set_fill_option(canvas, fill);
-                        canvas.fill_path(draw_path.clone(), FillRule::Winding);
-                    };
-                    if let Some(stroke) = path.stroke.as_ref().or(defaults.stroke.as_ref()) {
-                        Self::set_stroke_option(canvas, stroke);
-                        canvas.stroke_path(draw_path);
+                        Self::set_stroke_option(canvas, stroke, bounds, units, images);
+                        let stroke_path = match stroke.center_offset() {
+                            offset if offset != 0.0 => outline_path(&circle.offset_outline(offset))?,
+                            _ => circle_path,
+                        };
+                        canvas.stroke_path(stroke_path);
                     }
                 }
+                Shape::Ellipse(ellipse) => Self::render_path(canvas, images, &ellipse.to_path(), defaults)?,
+                Shape::Line(line) => Self::render_path(canvas, images, &line.to_path(), defaults)?,
+                Shape::Polyline(polyline) => Self::render_path(canvas, images, &polyline.to_path(), defaults)?,
+                Shape::Polygon(polygon) => Self::render_path(canvas, images, &polygon.to_path(), defaults)?,
+                Shape::Path(path) => Self::render_path(canvas, images, path, defaults)?,
                 Shape::Text(this_text) => {
                     text = Some(this_text);
 
-                    let pos = Vector2F::new(this_text.x.val(), this_text.y.val());
-
-                    Self::set_text_options(canvas, this_text, defaults);
-                    if let Some(fill) = this_text.fill.as_ref().or(defaults.fill.as_ref()) {
-                        Self::set_fill_option(canvas, fill);
-                        canvas.fill_text(&this_text.content, pos);
+                    Self::set_text_options(canvas, fonts, this_text, defaults)?;
+                    let effects = Self::effects(&this_text.effects, defaults);
+
+                    // The blurred text is drawn as its shadow of the fill color without the offset
+                    let fill = this_text.fill.as_ref().or(defaults.fill.as_ref());
+                    let blurred = match (Self::blur_radius(effects), fill) {
+                        (Some(radius), Some(Fill {
+                            paint: Paint::Color(color),
+                            ..
+                        })) => Some(Shadow::new(0.0, 0.0, radius, *color)),
+                        _ => None,
                     };
-                    if let Some(stroke) = this_text.stroke.as_ref().or(defaults.stroke.as_ref()) {
-                        Self::set_stroke_option(canvas, stroke);
-                        canvas.stroke_text(&this_text.content, pos);
+                    let shadows = effects.iter().filter_map(|effect| match effect {
+                        Effect::Shadow(shadow) => Some(*shadow),
+                        _ => None,
+                    });
+                    for shadow in shadows.chain(blurred) {
+                        canvas.save();
+                        Self::set_shadow(canvas, &shadow);
+                        Self::render_text(canvas, fonts, images, this_text, defaults, true)?;
+                        canvas.restore();
+                    }
+                    if blurred.is_none() {
+                        Self::render_text(canvas, fonts, images, this_text, defaults, false)?;
                     }
                 }
                 Shape::Group(group) => {
                     if let Some(transparency) = group.transparency {
                         defaults.transparency = transparency;
                     }
-                    if let Some(fill) = group.fill {
-                        defaults.fill = Some(fill);
+                    if let Some(fill) = &group.fill {
+                        defaults.fill = Some(fill.clone());
                     }
-                    if let Some(stroke) = group.stroke {
-                        defaults.stroke = Some(stroke);
+                    if let Some(stroke) = &group.stroke {
+                        defaults.stroke = Some(stroke.clone());
                     }
                     if !group.clip.is_none() {
-                        defaults.clip = group.clip;
+                        defaults.clip = group.clip.clone();
+                    }
+                    if !group.effects.is_empty() {
+                        defaults.effects = group.effects.clone();
                     }
                 }
             }
@@ -607,25 +730,411 @@ impl PathfinderRender {
         canvas.restore();
 
         if let Some(children) = composite.children() {
-            for child in children {
-                Self::render_composite(canvas, child, text, defaults);
+            match composite.shape() {
+                Some(Shape::Group(group)) if group.is_layer() => {
+                    Self::render_layer(canvas, fonts, images, group.blend_mode, children, text, defaults)?
+                }
+                _ => {
+                    for child in children {
+                        Self::render_composite(canvas, fonts, images, child, text, defaults)?;
+                    }
+                }
+            }
+        }
+        if let Some(Shape::Group(group)) = composite.shape() {
+            if !group.clip.is_none() {
+                defaults.clip = Clip::None;
+            }
+        }
+        Ok(())
+    }
+
+    /// Pathfinder has the single clip path, so the shape with its own clip inside the clipped group is drawn
+    /// to the offscreen canvas, that is composited by filling the group clip outline. The nested layers
+    /// intersect the nested clips.
+    fn render_clipped_layer<'a>(
+        canvas: &mut CanvasRenderingContext2D, fonts: &Fonts, images: &PatternImages, composite: &'a dyn CompositeShape,
+        text: Option<&'a Text>, defaults: &mut ShapeDefaults,
+    ) -> Result<(), PathfinderRenderError> {
+        let size = canvas.canvas().size().to_f32();
+        let mut layer = Canvas::new(size).get_context_2d(canvas.font_context());
+        let clip = mem::take(&mut defaults.clip);
+        let result = Self::render_composite(&mut layer, fonts, images, composite, text, defaults);
+        defaults.clip = clip;
+        result?;
+
+        if let Some(clip_path) = Self::clip_path(&defaults.clip, canvas.transform()) {
+            let pattern = canvas.create_pattern_from_canvas(layer.into_canvas(), Transform2F::default());
+            canvas.save();
+            canvas.set_transform(&Transform2F::default());
+            canvas.set_fill_style(FillStyle::Pattern(pattern));
+            canvas.fill_path(clip_path, PathfinderFillRule::Winding);
+            canvas.restore();
+        }
+        Ok(())
+    }
+
+    /// Draw the children to the offscreen canvas and composite it at once with the group transparency and blend mode.
+    fn render_layer<'a>(
+        canvas: &mut CanvasRenderingContext2D, fonts: &Fonts, images: &PatternImages, blend_mode: BlendMode,
+        children: CompositeShapeIter<'a>, text: Option<&'a Text>, defaults: &mut ShapeDefaults,
+    ) -> Result<(), PathfinderRenderError> {
+        let size = canvas.canvas().size().to_f32();
+        let mut layer = Canvas::new(size).get_context_2d(canvas.font_context());
+        let transparency = defaults.transparency;
+        defaults.transparency = 0.0;
+        for child in children {
+            Self::render_composite(&mut layer, fonts, images, child, text, defaults)?;
+        }
+        defaults.transparency = transparency;
+
+        let pattern = canvas.create_pattern_from_canvas(layer.into_canvas(), Transform2F::default());
+        canvas.save();
+        canvas.set_global_alpha(1.0 - transparency);
+        canvas.set_global_composite_operation(composite_operation(blend_mode));
+        canvas.set_fill_style(FillStyle::Pattern(pattern));
+        canvas.fill_rect(RectF::new(Vector2F::zero(), size));
+        canvas.restore();
+        Ok(())
+    }
+
+    /// Draw the path, the other outline shapes are drawn as the paths too.
+    fn render_path(
+        canvas: &mut CanvasRenderingContext2D, images: &PatternImages, path: &PathShape, defaults: &ShapeDefaults,
+    ) -> Result<(), PathfinderRenderError> {
+        let segments = path.segments().map_err(PathfinderRenderError::InvalidPath)?;
+        let draw_path = segments_path(&segments);
+
+        Self::set_path_options(canvas, path.transparency, &path.clip, &path.transform, defaults);
+        let effects = Self::effects(&path.effects, defaults);
+        let fill = path.fill.as_ref().or(defaults.fill.as_ref());
+        let rule = fill_rule(path.resolve_fill_rule(fill));
+        Self::render_shadows(canvas, effects, &draw_path, rule);
+        let bounds = PathSegment::bounds(&segments).unwrap_or_default();
+        let units = &defaults.units;
+        if let Some(fill) = fill {
+            Self::fill_path(canvas, effects, fill, bounds, units, images, draw_path.clone(), rule);
+        };
+        if let Some(bounds) = segments_bounds(&segments) {
+            Self::render_inner_shadows(canvas, effects, &draw_path, bounds, rule);
+        }
+        match path.stroke.as_ref().or(defaults.stroke.as_ref()) {
+            Some(stroke) if stroke.center_offset() != 0.0 => {
+                // The arbitrary outlines are not offset and the single clip path of the canvas is taken
+                // by the shape clips, so the aligned stroke is drawn by filling the outline of its area, as it is hit
+                let outline = PathSegment::stroke_outline(&segments, stroke, path.resolve_fill_rule(fill));
+                canvas.set_fill_style(ToPathfinderPaint::new(&stroke.paint, bounds, units, images));
+                canvas.fill_path(segments_path(&outline), PathfinderFillRule::Winding);
+            },
+            Some(stroke) => {
+                Self::set_stroke_option(canvas, stroke, bounds, units, images);
+                canvas.stroke_path(draw_path);
+            },
+            None => (),
+        }
+        Ok(())
+    }
+
+    /// Effects of the shape, or the group ones if the shape has no own effects.
+    fn effects<'a>(effects: &'a [Effect], defaults: &'a ShapeDefaults) -> &'a [Effect] {
+        if effects.is_empty() {
+            &defaults.effects
+        } else {
+            effects
+        }
+    }
+
+    fn blur_radius(effects: &[Effect]) -> Option<Real> {
+        effects.iter().rev().find_map(|effect| match *effect {
+            Effect::Blur(radius) if radius > 0.0 => Some(radius),
+            _ => None,
+        })
+    }
+
+    /// Set the canvas shadow, its offset is transformed to the canvas space as the shape.
+    fn set_shadow(canvas: &mut CanvasRenderingContext2D, shadow: &Shadow) {
+        let transform = canvas.transform();
+        let offset = transform * vec2f(shadow.offset_x, shadow.offset_y) - transform * Vector2F::zero();
+        canvas.set_shadow_color(ToPathfinderPaint::to_color(shadow.color).to_u8());
+        canvas.set_shadow_blur(shadow.blur);
+        canvas.set_shadow_offset(offset);
+    }
+
+    /// Draw the shadow of the path only, the path itself is painted by the transparent paint.
+    /// The spread is drawn as the shadow of the path stroke.
+    fn cast_shadow(
+        canvas: &mut CanvasRenderingContext2D, shadow: &Shadow, path: Path2D, fill_rule: PathfinderFillRule,
+    ) {
+        canvas.save();
+        Self::set_shadow(canvas, shadow);
+        canvas.set_fill_style(ColorU::transparent_black());
+        canvas.fill_path(path.clone(), fill_rule);
+        if shadow.spread > 0.0 {
+            canvas.set_stroke_style(ColorU::transparent_black());
+            canvas.set_line_width(2.0 * shadow.spread);
+            canvas.set_line_join(PathfinderLineJoin::Round);
+            canvas.set_line_dash(Vec::new());
+            canvas.stroke_path(path);
+        }
+        canvas.restore();
+    }
+
+    fn render_shadows(
+        canvas: &mut CanvasRenderingContext2D, effects: &[Effect], path: &Path2D, fill_rule: PathfinderFillRule,
+    ) {
+        for effect in effects {
+            if let Effect::Shadow(shadow) = effect {
+                Self::cast_shadow(canvas, shadow, path.clone(), fill_rule);
+            }
+        }
+    }
+
+    /// Draw the inner shadows as the shadows of the frame around the path clipped by the path.
+    fn render_inner_shadows(
+        canvas: &mut CanvasRenderingContext2D, effects: &[Effect], path: &Path2D, bounds: RectF,
+        fill_rule: PathfinderFillRule,
+    ) {
+        for effect in effects {
+            if let Effect::InnerShadow(shadow) = effect {
+                let margin = shadow.blur + shadow.spread.abs() + shadow.offset_x.abs().max(shadow.offset_y.abs());
+                let margin = vec2f(margin, margin);
+                let mut frame = path.clone();
+                frame.rect(RectF::new(bounds.origin() - margin, bounds.size() + margin * 2.0));
+
+                canvas.save();
+                canvas.clip_path(path.clone(), fill_rule);
+                Self::cast_shadow(canvas, shadow, frame, PathfinderFillRule::EvenOdd);
+                canvas.restore();
+            }
+        }
+    }
+
+    /// Fill the path, the color fill blurred by the effects is drawn as the path shadow without the offset.
+    fn fill_path(
+        canvas: &mut CanvasRenderingContext2D, effects: &[Effect], fill: &Fill, bounds: [Real; 4], units: &UnitContext,
+        images: &PatternImages, path: Path2D, fill_rule: PathfinderFillRule,
+    ) {
+        match (Self::blur_radius(effects), &fill.paint) {
+            (Some(radius), Paint::Color(color)) => {
+                Self::cast_shadow(canvas, &Shadow::new(0.0, 0.0, radius, *color), path, fill_rule)
+            }
+            _ => {
+                Self::set_fill_option(canvas, fill, bounds, units, images);
+                canvas.fill_path(path, fill_rule);
+            }
+        }
+    }
+
+    /// Draw the text lines, or the text shadow only with the transparent fill and without the stroke.
+    fn render_text(
+        canvas: &mut CanvasRenderingContext2D, fonts: &Fonts, images: &PatternImages, text: &Text,
+        defaults: &ShapeDefaults, shadow_only: bool,
+    ) -> Result<(), PathfinderRenderError> {
+        if text.path.is_some() {
+            return Self::render_path_glyphs(canvas, fonts, images, text, defaults, shadow_only);
+        }
+        let pos = Vector2F::new(text.x.val(), text.y.val());
+        if !text.spans.is_empty() || text.letter_spacing != 0.0 || text.has_rtl() {
+            return Self::render_runs(canvas, fonts, images, text, pos, defaults, shadow_only);
+        }
+        let mut lines: Vec<_> = text
+            .lines
+            .iter()
+            .map(|line| (text.line_text(line), pos + vec2f(0.0, line.y)))
+            .collect();
+        if lines.is_empty() {
+            lines.push((Cow::Borrowed(text.content.as_str()), pos));
+        }
+
+        let bounds = text.line_bounds().unwrap_or_default();
+        let units = &defaults.units.with_font_size(text.font_size.val());
+        if let Some(fill) = text.fill.as_ref().or(defaults.fill.as_ref()) {
+            if shadow_only {
+                canvas.set_fill_style(ColorU::transparent_black());
+            } else {
+                Self::set_fill_option(canvas, fill, bounds, units, images);
+            }
+            for (content, pos) in &lines {
+                canvas.fill_text(content, *pos);
+            }
+            for line in &text.lines {
+                let line_pos = pos + vec2f(line.x, line.baseline);
+                Self::render_decoration(canvas, text.decoration, line_pos, line.width);
+            }
+        };
+        if let Some(stroke) = text.stroke.as_ref().or(defaults.stroke.as_ref()).filter(|_| !shadow_only) {
+            Self::set_stroke_option(canvas, stroke, bounds, units, images);
+            for (content, pos) in &lines {
+                canvas.stroke_text(content, *pos);
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw the text by the visual runs of the spans, each run is drawn at once by its visual content as it is shaped.
+    /// The run chars are drawn one by one if the letter spacing is set.
+    fn render_runs(
+        canvas: &mut CanvasRenderingContext2D, fonts: &Fonts, images: &PatternImages, text: &Text, pos: Vector2F,
+        defaults: &ShapeDefaults, shadow_only: bool,
+    ) -> Result<(), PathfinderRenderError> {
+        let plain_style = SpanStyle::default();
+        let fill = text.fill.as_ref().or(defaults.fill.as_ref());
+        let stroke = text.stroke.as_ref().or(defaults.stroke.as_ref()).filter(|_| !shadow_only);
+        let bounds = text.line_bounds().unwrap_or_default();
+        let units = &defaults.units.with_font_size(text.font_size.val());
+        for line in &text.lines {
+            let glyph_x = |idx: usize| text.glyph_positions.get(idx).map(|pos| pos.x).unwrap_or(line.x);
+            for run in text.visual_runs(line) {
+                let style = text.spans.get(run.span).map_or(&plain_style, |span| &span.style);
+                Self::set_span_options(canvas, fonts, text, style)?;
+                let baseline = line.baseline - style.baseline_offset;
+                let run_x = text.run_x(line, &run);
+                let glyphs: Vec<_> = if text.letter_spacing == 0.0 {
+                    vec![(run_x, run.visual_content())]
+                } else {
+                    run.content
+                        .char_indices()
+                        .enumerate()
+                        .map(|(offset, (start, ch))| {
+                            let content = &run.content[start..start + ch.len_utf8()];
+                            (glyph_x(run.range.start + offset), Cow::Borrowed(content))
+                        })
+                        .collect()
+                };
+
+                let paint = style.color.map(Paint::Color).or_else(|| fill.map(|fill| fill.paint.clone()));
+                if let Some(paint) = paint {
+                    if shadow_only {
+                        canvas.set_fill_style(ColorU::transparent_black());
+                    } else {
+                        canvas.set_fill_style(ToPathfinderPaint::new(&paint, bounds, units, images));
+                    }
+                    for (x, content) in &glyphs {
+                        canvas.fill_text(content, pos + vec2f(*x, baseline));
+                    }
+                    let width: Real = text.glyph_positions[run.range.clone()].iter().map(|pos| pos.width).sum();
+                    let decoration = text.decoration.union(style.decoration);
+                    Self::render_decoration(canvas, decoration, pos + vec2f(run_x, baseline), width);
+                }
+                if let Some(stroke) = stroke {
+                    Self::set_stroke_option(canvas, stroke, bounds, units, images);
+                    for (x, content) in &glyphs {
+                        canvas.stroke_text(content, pos + vec2f(*x, baseline));
+                    }
+                }
             }
         }
+        Ok(())
     }
 
-    fn set_by_pct_padding(padding: &mut Padding, parent_bound: &BoundingBox) {
-        padding.left.set_by_pct(parent_bound.width());
-        padding.right.set_by_pct(parent_bound.width());
-        padding.top.set_by_pct(parent_bound.height());
-        padding.bottom.set_by_pct(parent_bound.height());
+    /// Fill the decoration lines of the text with the width from the baseline position.
+    /// Draw the grapheme clusters of the text placed along the text path one by one with their transforms.
+    fn render_path_glyphs(
+        canvas: &mut CanvasRenderingContext2D, fonts: &Fonts, images: &PatternImages, text: &Text,
+        defaults: &ShapeDefaults, shadow_only: bool,
+    ) -> Result<(), PathfinderRenderError> {
+        let plain_style = SpanStyle::default();
+        let fill = text.fill.as_ref().or(defaults.fill.as_ref());
+        let stroke = text.stroke.as_ref().or(defaults.stroke.as_ref()).filter(|_| !shadow_only);
+        let bounds = text.line_bounds().unwrap_or_default();
+        let units = &defaults.units.with_font_size(text.font_size.val());
+        let text_transform = canvas.transform();
+        for glyph in text.path_glyphs() {
+            let line = &text.lines[glyph.line];
+            let style = text.spans.get(glyph.span).map_or(&plain_style, |span| &span.style);
+            Self::set_span_options(canvas, fonts, text, style)?;
+            let [a, b, c, d, e, f] = glyph.matrix.matrix;
+            canvas.set_transform(&(text_transform * Transform2F::row_major(a, c, b, d, e, f)));
+            let pos = vec2f(text.x.val() + glyph.x, text.y.val() + line.baseline - style.baseline_offset);
+
+            let paint = style.color.map(Paint::Color).or_else(|| fill.map(|fill| fill.paint.clone()));
+            if let Some(paint) = paint {
+                if shadow_only {
+                    canvas.set_fill_style(ColorU::transparent_black());
+                } else {
+                    canvas.set_fill_style(ToPathfinderPaint::new(&paint, bounds, units, images));
+                }
+                canvas.fill_text(glyph.content, pos);
+                Self::render_decoration(canvas, text.decoration.union(style.decoration), pos, glyph.width);
+            }
+            if let Some(stroke) = stroke {
+                Self::set_stroke_option(canvas, stroke, bounds, units, images);
+                canvas.stroke_text(glyph.content, pos);
+            }
+        }
+        canvas.set_transform(&text_transform);
+        Ok(())
     }
 
-    fn set_by_pct_clip(clip: &mut Clip, parent_bound: &BoundingBox) {
-        if let Clip::Scissor(scissor) = clip {
-            scissor.x.set_by_pct(parent_bound.width());
-            scissor.y.set_by_pct(parent_bound.height());
-            scissor.width.set_by_pct(parent_bound.width());
-            scissor.height.set_by_pct(parent_bound.height());
+    fn render_decoration(
+        canvas: &mut CanvasRenderingContext2D, decoration: TextDecoration, pos: Vector2F, width: Real,
+    ) {
+        for (offset, thickness) in decoration.lines(canvas.font_size()) {
+            canvas.fill_rect(RectF::new(pos + vec2f(0.0, offset - thickness / 2.0), vec2f(width, thickness)));
+        }
+    }
+
+    fn text_bound(text: &Text, font_line_height: Real) -> BoundingBox {
+        let line_height = font_line_height * text.layout.line_height;
+        let (x, y) = (text.x.val(), text.y.val());
+        let (first, last) = match (text.lines.first(), text.lines.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => {
+                return BoundingBox {
+                    min_x: x,
+                    min_y: y,
+                    max_x: x,
+                    max_y: y + line_height,
+                }
+            }
+        };
+        text.lines.iter().fold(
+            BoundingBox {
+                min_x: x + first.x,
+                min_y: y + first.top(),
+                max_x: x + first.max_x(),
+                max_y: y + last.bottom(),
+            },
+            |bound, line| BoundingBox {
+                min_x: bound.min_x.min(x + line.x),
+                max_x: bound.max_x.max(x + line.max_x()),
+                ..bound
+            },
+        )
+    }
+
+    fn resolve_padding(padding: &mut Padding, parent_bound: &BoundingBox, units: &UnitContext) {
+        padding.left.resolve(parent_bound.width(), units);
+        padding.right.resolve(parent_bound.width(), units);
+        padding.top.resolve(parent_bound.height(), units);
+        padding.bottom.resolve(parent_bound.height(), units);
+    }
+
+    fn resolve_clip(clip: &mut Clip, parent_bound: &BoundingBox, units: &UnitContext) {
+        match clip {
+            Clip::Scissor(scissor) => {
+                scissor.x.resolve(parent_bound.width(), units);
+                scissor.y.resolve(parent_bound.height(), units);
+                scissor.width.resolve(parent_bound.width(), units);
+                scissor.height.resolve(parent_bound.height(), units);
+            }
+            Clip::RoundedRect(rounded) => {
+                rounded.x.resolve(parent_bound.width(), units);
+                rounded.y.resolve(parent_bound.height(), units);
+                rounded.width.resolve(parent_bound.width(), units);
+                rounded.height.resolve(parent_bound.height(), units);
+                let radius = parent_bound.width().min(parent_bound.height());
+                rounded.rounding.top_left.resolve(radius, units);
+                rounded.rounding.top_right.resolve(radius, units);
+                rounded.rounding.bottom_left.resolve(radius, units);
+                rounded.rounding.bottom_right.resolve(radius, units);
+            }
+            Clip::Circle(circle) => {
+                circle.cx.resolve(parent_bound.width(), units);
+                circle.cy.resolve(parent_bound.height(), units);
+                circle.r.resolve(parent_bound.width().min(parent_bound.height()), units);
+            }
+            Clip::Path(_) | Clip::None => (),
         }
     }
 
@@ -653,27 +1162,29 @@ impl PathfinderRender {
         }
     }
 
+    /// Outline of the clip, the path clip with the invalid commands clips everything as it has no area.
     fn clip_path(clip: &Clip, current_transform: Transform2F) -> Option<Path2D> {
-        match clip {
-            Clip::Scissor(scissor) => {
-                let mut clip_rect = RectF::new(
-                    vec2f(scissor.x.val() as f32, scissor.y.val() as f32),
-                    vec2f(scissor.width.val() as f32, scissor.height.val() as f32),
-                );
-                if let Some(transform) = Self::pathfinder_transform(&scissor.transform, current_transform) {
-                    clip_rect = transform * clip_rect;
-                }
-
-                let mut clip_path = Path2D::new();
-                clip_path.rect(clip_rect);
-                Some(clip_path)
+        let transform = Self::pathfinder_transform(clip.transform()?, current_transform);
+        let segments = PathSegment::resolve(&clip.outline()).unwrap_or_default();
+        Some(match transform {
+            Some(transform) => {
+                let segments: Vec<PathSegment> = segments
+                    .iter()
+                    .map(|segment| {
+                        segment.map_points(|[x, y]| {
+                            let point = transform * vec2f(x, y);
+                            [point.x(), point.y()]
+                        })
+                    })
+                    .collect();
+                segments_path(&segments)
             }
-            Clip::None => None,
-        }
+            None => segments_path(&segments),
+        })
     }
 
     fn set_path_options(
-        canvas: &mut CanvasRenderingContext2D, transparency: Real, clip: Clip, transform: &Transform,
+        canvas: &mut CanvasRenderingContext2D, transparency: Real, clip: &Clip, transform: &Transform,
         defaults: &ShapeDefaults,
     ) {
         let transparency = if transparency != 0.0 {
@@ -683,20 +1194,27 @@ impl PathfinderRender {
         };
         canvas.set_global_alpha(1.0 - transparency);
         let current_transform = canvas.transform();
-        if let Some(clip_path) = Self::clip_path(&clip.or(defaults.clip), current_transform) {
-            canvas.clip_path(clip_path, FillRule::Winding);
+        let clip = if clip.is_none() { &defaults.clip } else { clip };
+        if let Some(clip_path) = Self::clip_path(clip, current_transform) {
+            canvas.clip_path(clip_path, PathfinderFillRule::Winding);
         }
         if let Some(transform) = Self::pathfinder_transform(transform, current_transform) {
             canvas.set_transform(&transform);
         }
     }
 
-    fn set_fill_option(canvas: &mut CanvasRenderingContext2D, fill: &Fill) {
-        canvas.set_fill_style(ToPathfinderPaint(fill.paint));
+    fn set_fill_option(
+        canvas: &mut CanvasRenderingContext2D, fill: &Fill, bounds: [Real; 4], units: &UnitContext,
+        images: &PatternImages,
+    ) {
+        canvas.set_fill_style(ToPathfinderPaint::new(&fill.paint, bounds, units, images));
     }
 
-    fn set_stroke_option(canvas: &mut CanvasRenderingContext2D, stroke: &Stroke) {
-        canvas.set_stroke_style(ToPathfinderPaint(stroke.paint));
+    fn set_stroke_option(
+        canvas: &mut CanvasRenderingContext2D, stroke: &Stroke, bounds: [Real; 4], units: &UnitContext,
+        images: &PatternImages,
+    ) {
+        canvas.set_stroke_style(ToPathfinderPaint::new(&stroke.paint, bounds, units, images));
         canvas.set_line_width(stroke.width);
         canvas.set_miter_limit(stroke.miter_limit);
         let line_cap = match stroke.line_cap {
@@ -711,16 +1229,21 @@ impl PathfinderRender {
         };
         canvas.set_line_cap(line_cap);
         canvas.set_line_join(line_join);
+        let line_dash = if stroke.is_dashed() { stroke.dash.pattern() } else { Vec::new() };
+        canvas.set_line_dash(line_dash);
+        canvas.set_line_dash_offset(stroke.dash_offset);
     }
 
-    fn set_text_options(canvas: &mut CanvasRenderingContext2D, text: &Text, defaults: &ShapeDefaults) {
+    fn set_text_options(
+        canvas: &mut CanvasRenderingContext2D, fonts: &Fonts, text: &Text, defaults: &ShapeDefaults,
+    ) -> Result<(), PathfinderRenderError> {
         let transparency = if text.transparency != 0.0 {
             text.transparency
         } else {
             defaults.transparency
         };
         canvas.set_global_alpha(1.0 - transparency);
-        canvas.set_font(&[text.font_name.as_str()][..]);
+        fonts.set_font(canvas, &text.font_name, FontWeight::NORMAL, FontStyle::Normal)?;
         canvas.set_font_size(text.font_size.val());
         canvas.set_text_align(match text.align.0 {
             AlignHor::Left => TextAlign::Left,
@@ -734,15 +1257,178 @@ impl PathfinderRender {
             AlignVer::Top => TextBaseline::Top,
         });
         let current_transform = canvas.transform();
-        if let Some(clip_path) = Self::clip_path(&text.clip.or(defaults.clip), current_transform) {
-            canvas.clip_path(clip_path, FillRule::Winding);
+        let clip = text.clip.clone().or(text.overflow_clip()).or(defaults.clip.clone());
+        if let Some(clip_path) = Self::clip_path(&clip, current_transform) {
+            canvas.clip_path(clip_path, PathfinderFillRule::Winding);
         }
         if let Some(transform) = Self::pathfinder_transform(&text.transform, current_transform) {
             canvas.set_transform(&transform);
         }
+        Ok(())
+    }
+
+    /// Set the font of the span, the face nearest to the span weight and style is used.
+    fn set_span_options(
+        canvas: &mut CanvasRenderingContext2D, fonts: &Fonts, text: &Text, style: &SpanStyle,
+    ) -> Result<(), PathfinderRenderError> {
+        let font_name = style.font_name.as_deref().unwrap_or(&text.font_name);
+        fonts.set_font(canvas, font_name, style.weight, style.font_style())?;
+        canvas.set_font_size(style.font_size.unwrap_or(text.font_size).val());
+        canvas.set_text_align(TextAlign::Left);
+        canvas.set_text_baseline(TextBaseline::Alphabetic);
+        Ok(())
+    }
+}
+
+/// Registered fonts with the names of the faces loaded into the font context.
+struct Fonts<'a> {
+    registry: &'a FontRegistry,
+    postscript_names: &'a HashMap<String, String>,
+}
+
+impl Fonts<'_> {
+    /// Set the resolved face with its fallbacks as the canvas font.
+    fn set_font(
+        &self, canvas: &mut CanvasRenderingContext2D, name: &str, weight: FontWeight, style: FontStyle,
+    ) -> Result<(), PathfinderRenderError> {
+        let chain = self
+            .registry
+            .fallback_chain(name, weight, style)
+            .map_err(PathfinderRenderError::Font)?;
+        self.set_faces(canvas, &chain).map_err(PathfinderRenderError::Font)
+    }
+
+    /// Set the faces as the canvas font, the first face is the font and the rest are its fallbacks.
+    fn set_faces(&self, canvas: &mut CanvasRenderingContext2D, faces: &[&FontFace]) -> Result<(), FontError> {
+        let names = faces
+            .iter()
+            .map(|face| {
+                self.postscript_names
+                    .get(&face.name)
+                    .map(String::as_str)
+                    .ok_or_else(|| FontError::NotFound(face.name.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        canvas.set_font(&names[..]);
+        Ok(())
+    }
+}
+
+/// Measurement of the texts by the fonts of the canvas, the layout of the texts is done by the core.
+struct CanvasMeasurer<'a> {
+    canvas: RefCell<&'a mut CanvasRenderingContext2D>,
+    fonts: &'a Fonts<'a>,
+}
+
+impl<'a> CanvasMeasurer<'a> {
+    fn new(canvas: &'a mut CanvasRenderingContext2D, fonts: &'a Fonts<'a>) -> Self {
+        Self {
+            canvas: RefCell::new(canvas),
+            fonts,
+        }
+    }
+}
+
+impl TextMeasurer for CanvasMeasurer<'_> {
+    /// Measure the content with the faces set as the canvas font, the runs of the content with the same direction
+    /// are shaped at once in the order they are drawn.
+    fn shape(&self, faces: &[&FontFace], font_size: Real, content: &str) -> Result<ShapedText, FontError> {
+        let mut canvas = self.canvas.borrow_mut();
+        self.fonts.set_faces(&mut canvas, faces)?;
+        canvas.set_font_size(font_size);
+
+        let metrics = canvas.measure_text(if content.is_empty() { "a" } else { content });
+        let ascend = metrics.font_bounding_box_ascent.abs();
+        let descend = metrics.font_bounding_box_descent.abs();
+        let metrics = TextMetrics {
+            ascender: ascend,
+            descender: -descend,
+            line_height: ascend + descend,
+        };
+
+        // todo: cache the glyph_positions
+        let (style, font) = (TextStyle { size: font_size }, canvas.font());
+        let advances = shape_runs(content, |run| {
+            let layout = skribo::layout(&style, &font, run);
+            let mut advances = Vec::with_capacity(layout.glyphs.len());
+            let mut prev_pos: Option<Vector2F> = None;
+            for pos in layout
+                .glyphs
+                .iter()
+                .map(|glyph| glyph.offset)
+                .chain(iter::once(layout.advance))
+            {
+                if let Some(prev_pos) = prev_pos {
+                    advances.push(pos.x() - prev_pos.x());
+                }
+                prev_pos = Some(pos);
+            }
+            advances
+        });
+        Ok(ShapedText { metrics, advances })
     }
 }
 
+fn fill_rule(fill_rule: FillRule) -> PathfinderFillRule {
+    match fill_rule {
+        FillRule::NonZero => PathfinderFillRule::Winding,
+        FillRule::EvenOdd => PathfinderFillRule::EvenOdd,
+    }
+}
+
+fn composite_operation(blend_mode: BlendMode) -> CompositeOperation {
+    match blend_mode {
+        BlendMode::Normal => CompositeOperation::SourceOver,
+        BlendMode::Multiply => CompositeOperation::Multiply,
+        BlendMode::Screen => CompositeOperation::Screen,
+        BlendMode::Overlay => CompositeOperation::Overlay,
+        BlendMode::Darken => CompositeOperation::Darken,
+        BlendMode::Lighten => CompositeOperation::Lighten,
+        BlendMode::ColorDodge => CompositeOperation::ColorDodge,
+        BlendMode::ColorBurn => CompositeOperation::ColorBurn,
+        BlendMode::HardLight => CompositeOperation::HardLight,
+        BlendMode::SoftLight => CompositeOperation::SoftLight,
+        BlendMode::Difference => CompositeOperation::Difference,
+        BlendMode::Exclusion => CompositeOperation::Exclusion,
+        BlendMode::Hue => CompositeOperation::Hue,
+        BlendMode::Saturation => CompositeOperation::Saturation,
+        BlendMode::Color => CompositeOperation::Color,
+        BlendMode::Luminosity => CompositeOperation::Luminosity,
+    }
+}
+
+/// Bounds of the path points including the curve control points.
+/// Path of the shape outline, as the aligned stroke outline of the rect or circle.
+fn outline_path(outline: &[PathCommand]) -> Result<Path2D, PathfinderRenderError> {
+    let segments = PathSegment::resolve(outline).map_err(PathfinderRenderError::InvalidPath)?;
+    Ok(segments_path(&segments))
+}
+
+fn segments_path(segments: &[PathSegment]) -> Path2D {
+    let mut path = Path2D::new();
+    for &segment in segments {
+        match segment {
+            PathSegment::MoveTo([x, y]) => path.move_to(vec2f(x, y)),
+            PathSegment::LineTo([x, y]) => path.line_to(vec2f(x, y)),
+            PathSegment::QuadTo {
+                ctrl: [ctrl_x, ctrl_y],
+                to: [x, y],
+            } => path.quadratic_curve_to(vec2f(ctrl_x, ctrl_y), vec2f(x, y)),
+            PathSegment::CubicTo {
+                ctrl1: [ctrl1_x, ctrl1_y],
+                ctrl2: [ctrl2_x, ctrl2_y],
+                to: [x, y],
+            } => path.bezier_curve_to(vec2f(ctrl1_x, ctrl1_y), vec2f(ctrl2_x, ctrl2_y), vec2f(x, y)),
+            PathSegment::Close => path.close_path(),
+        }
+    }
+    path
+}
+
+fn segments_bounds(segments: &[PathSegment]) -> Option<RectF> {
+    PathSegment::bounds(segments).map(|[x, y, width, height]| RectF::new(vec2f(x, y), vec2f(width, height)))
+}
+
 fn create_rounded_rect_path(rect_pos: Vector2F, rect_size: Vector2F, rounding: Rounding) -> Path2D {
     let rect = RectF::new(rect_pos, rect_size);
     let mut path = Path2D::new();
@@ -774,7 +1460,10 @@ fn create_rounded_rect_path(rect_pos: Vector2F, rect_size: Vector2F, rounding: R
     path
 }
 
-struct ToPathfinderPaint(Paint);
+struct ToPathfinderPaint {
+    paint: Paint,
+    image: Option<PathfinderImage>,
+}
 
 impl ToPathfinderPaint {
     fn to_color(color: Color) -> ColorF {
@@ -782,43 +1471,98 @@ impl ToPathfinderPaint {
         ColorF::new(r, g, b, a)
     }
 
-    fn to_gradient(gradient: Gradient) -> PathfinderGradient {
-        match gradient {
+    /// Paint with the coordinates resolved by the bounding box of the painted shape and the unit sizes.
+    fn new(paint: &Paint, bounds: [Real; 4], units: &UnitContext, images: &PatternImages) -> Self {
+        let image = match paint {
+            Paint::Pattern(pattern) => Some(images.image(pattern)),
+            _ => None,
+        };
+        Self {
+            paint: paint.with_bounds(bounds, units),
+            image,
+        }
+    }
+
+    /// Pathfinder gradients are able to pad or repeat, so the reflected gradient is drawn as the repeated one
+    /// of the double length with the mirrored stops. Pathfinder has no conic gradient, so it is filled with
+    /// the average color. It has no box gradient as well, so the box gradient is drawn as the radial one
+    /// from the inner edge of the feather of the smaller side to the outer edge of the feather of the larger side,
+    /// as in the SVG export.
+    fn gradient_style(gradient: &Gradient) -> FillStyle {
+        let reflect = gradient.spread_mode() == SpreadMode::Reflect;
+        let scale = if reflect { 2.0 } else { 1.0 };
+        let mut pathfinder_gradient = match gradient {
             Gradient::Linear {
                 start: (start_x, start_y),
                 end: (end_x, end_y),
-                start_color,
-                end_color,
+                ..
+            } => {
+                let start = vec2f(start_x.val(), start_y.val());
+                let end = vec2f(end_x.val(), end_y.val());
+                PathfinderGradient::linear_from_points(start, start + (end - start) * scale)
+            }
+            Gradient::Box {
+                position: (x, y),
+                size: (width, height),
+                feather,
+                ..
             } => {
-                let mut gradient = PathfinderGradient::linear_from_points(
-                    Vector2F::new(start_x as f32, start_y as f32),
-                    Vector2F::new(end_x as f32, end_y as f32),
-                );
-                gradient.add_color_stop(Self::to_color(start_color).to_u8(), 0.0);
-                gradient.add_color_stop(Self::to_color(end_color).to_u8(), 1.0);
-                gradient
-            }
-            Gradient::Box { .. } => todo!("The Box gradient is not support"),
+                let inner_radius = (width.min(*height) / 2.0 - feather / 2.0).max(0.0);
+                let outer_radius = width.max(*height) / 2.0 + feather / 2.0;
+                let radii = F32x2::new(inner_radius, inner_radius + (outer_radius - inner_radius) * scale);
+                PathfinderGradient::radial(vec2f(x + width / 2.0, y + height / 2.0), radii)
+            }
             Gradient::Radial {
                 center: (x, y),
                 inner_radius,
                 outer_radius,
-                start_color,
-                end_color,
+                ..
             } => {
-                let mut gradient =
-                    PathfinderGradient::radial(Vector2F::new(x, y), F32x2::new(inner_radius, outer_radius));
-                gradient.add_color_stop(Self::to_color(start_color).to_u8(), 0.0);
-                gradient.add_color_stop(Self::to_color(end_color).to_u8(), 1.0);
-                gradient
+                let (inner_radius, outer_radius) = (inner_radius.val(), outer_radius.val());
+                let radii = F32x2::new(inner_radius, inner_radius + (outer_radius - inner_radius) * scale);
+                PathfinderGradient::radial(vec2f(x.val(), y.val()), radii)
             }
+            Gradient::Conic { .. } => return FillStyle::Color(Self::to_color(gradient.average_color()).to_u8()),
+        };
+        pathfinder_gradient.wrap = match gradient.spread_mode() {
+            SpreadMode::Pad => GradientWrap::Clamp,
+            SpreadMode::Repeat | SpreadMode::Reflect => GradientWrap::Repeat,
+        };
+        let stops = gradient.color_stops();
+        for stop in &stops {
+            pathfinder_gradient.add_color_stop(Self::to_color(stop.color).to_u8(), stop.offset / scale);
         }
+        if reflect {
+            for stop in stops.iter().rev() {
+                pathfinder_gradient.add_color_stop(Self::to_color(stop.color).to_u8(), 1.0 - stop.offset / scale);
+            }
+        }
+        FillStyle::Gradient(pathfinder_gradient)
+    }
+
+    /// The pattern is drawn with the padded image of `PatternImages`, so it is transparent outside of the image
+    /// rather than extends its edge pixels, and its origin is moved by the padding.
+    fn pattern_style(pattern: &Pattern, image: PathfinderImage) -> FillStyle {
+        let (pad_x, pad_y) = PatternImages::padding(pattern.repeat);
+        let mut pathfinder_pattern = PathfinderPattern::from_image(image);
+        let (scale_x, scale_y) = pattern.scale;
+        pathfinder_pattern.apply_transform(
+            Transform2F::from_translation(vec2f(pattern.offset.0.val(), pattern.offset.1.val()))
+                * Transform2F::from_rotation(pattern.rotation)
+                * Transform2F::from_scale(vec2f(scale_x, scale_y))
+                * Transform2F::from_translation(vec2f(-(pad_x as f32), -(pad_y as f32))),
+        );
+        pathfinder_pattern.set_repeat_x(pattern.repeat.repeat_x());
+        pathfinder_pattern.set_repeat_y(pattern.repeat.repeat_y());
+        FillStyle::Pattern(pathfinder_pattern)
     }
 
     fn into_fill_style(self) -> FillStyle {
-        match self.0 {
-            Paint::Color(color) => FillStyle::Color(Self::to_color(color).to_u8()),
-            Paint::Gradient(gradient) => FillStyle::Gradient(Self::to_gradient(gradient)),
+        match (self.paint, self.image) {
+            (Paint::Color(color), _) => FillStyle::Color(Self::to_color(color).to_u8()),
+            (Paint::Gradient(gradient), _) => Self::gradient_style(&gradient),
+            (Paint::Pattern(pattern), Some(image)) => Self::pattern_style(&pattern, image),
+            (Paint::Pattern(pattern), None) => FillStyle::Color(Self::to_color(pattern.average_color()).to_u8()),
         }
     }
 }
@@ -0,0 +1,1134 @@
+use std::{error::Error, fmt, fmt::Write};
+
+use exgui_core::{
+    AlignHor, AlignVer, BlendMode, Circle, Clip, Color, ColorStop, CompositeShape, Effect, Ellipse, Fill, FillRule,
+    FontWeight, Gradient, Line, LineCap, LineJoin, Paint, Path, PathError, PathSegment, Polygon, Polyline, Real,
+    RealValue, Rect, Shadow, Shape, SpanStyle, SpreadMode, Stroke, Text, TextDecoration, Transform, UnitContext,
+};
+
+#[derive(Debug)]
+pub enum ExportError {
+    InvalidPath(PathError),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::InvalidPath(err) => write!(f, "Invalid path: {}", err),
+        }
+    }
+}
+
+impl Error for ExportError {}
+
+/// Export the laid out tree into the standalone SVG document of the given size.
+///
+/// The tree is walked the same way as the renderers do, so the shape defaults of the groups are
+/// applied to the shapes and every shape is written with its calculated global transform.
+/// The layer groups are written as the `g` elements with the layer opacity and blend mode, and the clips
+/// of the groups are written as the `g` elements with the clip path, so the nested clips are intersected.
+/// The box gradient has no SVG equivalent and is approximated by the radial one.
+pub fn export(composite: &dyn CompositeShape, width: Real, height: Real) -> Result<String, ExportError> {
+    let mut exporter = Exporter::default();
+    let mut defaults = ShapeDefaults {
+        units: UnitContext::default().with_viewport(width, height),
+        ..Default::default()
+    };
+    exporter.export_composite(composite, &mut defaults)?;
+
+    let mut document = String::new();
+    writeln!(
+        document,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = width,
+        h = height
+    )
+    .unwrap();
+    if !exporter.defs.is_empty() {
+        document.push_str("<defs>\n");
+        document.push_str(&exporter.defs);
+        document.push_str("</defs>\n");
+    }
+    document.push_str(&exporter.body);
+    document.push_str("</svg>\n");
+    Ok(document)
+}
+
+#[derive(Default, Clone)]
+struct ShapeDefaults {
+    transparency: Real,
+    fill: Option<Fill>,
+    stroke: Option<Stroke>,
+    effects: Vec<Effect>,
+    units: UnitContext,
+}
+
+#[derive(Default)]
+struct Exporter {
+    defs: String,
+    body: String,
+    next_id: usize,
+}
+
+impl Exporter {
+    fn export_composite(
+        &mut self, composite: &dyn CompositeShape, defaults: &mut ShapeDefaults,
+    ) -> Result<(), ExportError> {
+        if let Some(shape) = composite.shape() {
+            match shape {
+                Shape::Rect(rect) => self.export_rect(rect, defaults),
+                Shape::Circle(circle) => self.export_circle(circle, defaults),
+                Shape::Ellipse(ellipse) => self.export_ellipse(ellipse, defaults),
+                Shape::Line(line) => self.export_line(line, defaults),
+                Shape::Polyline(polyline) => self.export_polyline(polyline, defaults),
+                Shape::Polygon(polygon) => self.export_polygon(polygon, defaults),
+                Shape::Path(path) => self.export_path(path, defaults)?,
+                Shape::Text(text) => self.export_text(text, defaults),
+                Shape::Group(group) => {
+                    if let Some(transparency) = group.transparency {
+                        defaults.transparency = transparency;
+                    }
+                    if let Some(fill) = &group.fill {
+                        defaults.fill = Some(fill.clone());
+                    }
+                    if let Some(stroke) = &group.stroke {
+                        defaults.stroke = Some(stroke.clone());
+                    }
+                    if !group.effects.is_empty() {
+                        defaults.effects = group.effects.clone();
+                    }
+                }
+            }
+        }
+        let clip = match composite.shape() {
+            Some(Shape::Group(group)) if !group.clip.is_none() => Some(self.write_clip_path(&group.clip)),
+            _ => None,
+        };
+        if let Some(id) = &clip {
+            writeln!(self.body, r#"<g clip-path="url(#{})">"#, id).unwrap();
+        }
+        let layer = match composite.shape() {
+            Some(Shape::Group(group)) if group.is_layer() => Some(group),
+            _ => None,
+        };
+        if let Some(group) = layer {
+            self.body.push_str("<g");
+            let opacity = 1.0 - defaults.transparency;
+            if opacity < 1.0 {
+                write!(self.body, r#" opacity="{}""#, opacity).unwrap();
+            }
+            if group.blend_mode != BlendMode::Normal {
+                write!(self.body, r#" style="mix-blend-mode:{}""#, group.blend_mode.name()).unwrap();
+            }
+            self.body.push_str(">\n");
+        }
+
+        // The layer transparency is applied to the layer group instead of the children
+        let transparency = defaults.transparency;
+        if layer.is_some() {
+            defaults.transparency = 0.0;
+        }
+        if let Some(children) = composite.children() {
+            for child in children {
+                self.export_composite(child, defaults)?;
+            }
+        }
+        if layer.is_some() {
+            defaults.transparency = transparency;
+            self.body.push_str("</g>\n");
+        }
+        if clip.is_some() {
+            self.body.push_str("</g>\n");
+        }
+        Ok(())
+    }
+
+    fn export_rect(&mut self, rect: &Rect, defaults: &ShapeDefaults) {
+        let (x, y) = (rect.x.val(), rect.y.val());
+        let (width, height) = (rect.layout_width(), rect.layout_height());
+        let element = match rect.rounding {
+            Some(rounding) => {
+                let top_left = rounding.top_left.val();
+                let top_right = rounding.top_right.val();
+                let bottom_left = rounding.bottom_left.val();
+                let bottom_right = rounding.bottom_right.val();
+                if top_left == top_right && top_left == bottom_left && top_left == bottom_right {
+                    format!(
+                        r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}""#,
+                        x, y, width, height, top_left
+                    )
+                } else {
+                    let mut data = String::new();
+                    write!(data, "M{} {}", x + top_left, y).unwrap();
+                    write!(data, " H{}", x + width - top_right).unwrap();
+                    write!(data, " A{r} {r} 0 0 1 {} {}", x + width, y + top_right, r = top_right).unwrap();
+                    write!(data, " V{}", y + height - bottom_right).unwrap();
+                    write!(data, " A{r} {r} 0 0 1 {} {}", x + width - bottom_right, y + height, r = bottom_right)
+                        .unwrap();
+                    write!(data, " H{}", x + bottom_left).unwrap();
+                    write!(data, " A{r} {r} 0 0 1 {} {}", x, y + height - bottom_left, r = bottom_left).unwrap();
+                    write!(data, " V{}", y + top_left).unwrap();
+                    write!(data, " A{r} {r} 0 0 1 {} {} Z", x + top_left, y, r = top_left).unwrap();
+                    format!(r#"<path d="{}""#, data)
+                }
+            }
+            None => format!(r#"<rect x="{}" y="{}" width="{}" height="{}""#, x, y, width, height),
+        };
+        let fill = rect.fill.as_ref().or(defaults.fill.as_ref());
+        let stroke = rect.stroke.as_ref().or(defaults.stroke.as_ref());
+        self.write_shape(
+            element,
+            rect.id(),
+            fill,
+            stroke,
+            [x, y, width, height],
+            rect.transparency,
+            &rect.clip,
+            &rect.transform,
+            &rect.effects,
+            defaults,
+        );
+    }
+
+    fn export_circle(&mut self, circle: &Circle, defaults: &ShapeDefaults) {
+        let (cx, cy, r) = (circle.cx.val(), circle.cy.val(), circle.layout_r());
+        let element = format!(r#"<circle cx="{}" cy="{}" r="{}""#, cx, cy, r);
+        let fill = circle.fill.as_ref().or(defaults.fill.as_ref());
+        let stroke = circle.stroke.as_ref().or(defaults.stroke.as_ref());
+        self.write_shape(
+            element,
+            circle.id(),
+            fill,
+            stroke,
+            [cx - r, cy - r, 2.0 * r, 2.0 * r],
+            circle.transparency,
+            &circle.clip,
+            &circle.transform,
+            &circle.effects,
+            defaults,
+        );
+    }
+
+    fn export_ellipse(&mut self, ellipse: &Ellipse, defaults: &ShapeDefaults) {
+        let (cx, cy, rx, ry) = (ellipse.cx.val(), ellipse.cy.val(), ellipse.rx.val(), ellipse.ry.val());
+        let element = format!(r#"<ellipse cx="{}" cy="{}" rx="{}" ry="{}""#, cx, cy, rx, ry);
+        let fill = ellipse.fill.as_ref().or(defaults.fill.as_ref());
+        let stroke = ellipse.stroke.as_ref().or(defaults.stroke.as_ref());
+        self.write_shape(
+            element,
+            ellipse.id(),
+            fill,
+            stroke,
+            ellipse.bounds(),
+            ellipse.transparency,
+            &ellipse.clip,
+            &ellipse.transform,
+            &ellipse.effects,
+            defaults,
+        );
+    }
+
+    fn export_line(&mut self, line: &Line, defaults: &ShapeDefaults) {
+        let (x1, y1, x2, y2) = (line.x1.val(), line.y1.val(), line.x2.val(), line.y2.val());
+        let element = format!(r#"<line x1="{}" y1="{}" x2="{}" y2="{}""#, x1, y1, x2, y2);
+        let stroke = line.stroke.as_ref().or(defaults.stroke.as_ref());
+        self.write_shape(
+            element,
+            line.id(),
+            None,
+            stroke,
+            line.bounds(),
+            line.transparency,
+            &line.clip,
+            &line.transform,
+            &line.effects,
+            defaults,
+        );
+    }
+
+    fn export_polyline(&mut self, polyline: &Polyline, defaults: &ShapeDefaults) {
+        let fill = polyline.fill.as_ref().or(defaults.fill.as_ref());
+        let element = points_element("polyline", &polyline.points, fill, polyline.fill_rule);
+        let stroke = polyline.stroke.as_ref().or(defaults.stroke.as_ref());
+        self.write_shape(
+            element,
+            polyline.id(),
+            fill,
+            stroke,
+            polyline.bounds().unwrap_or_default(),
+            polyline.transparency,
+            &polyline.clip,
+            &polyline.transform,
+            &polyline.effects,
+            defaults,
+        );
+    }
+
+    fn export_polygon(&mut self, polygon: &Polygon, defaults: &ShapeDefaults) {
+        let fill = polygon.fill.as_ref().or(defaults.fill.as_ref());
+        let element = points_element("polygon", &polygon.points, fill, polygon.fill_rule);
+        let stroke = polygon.stroke.as_ref().or(defaults.stroke.as_ref());
+        self.write_shape(
+            element,
+            polygon.id(),
+            fill,
+            stroke,
+            polygon.bounds().unwrap_or_default(),
+            polygon.transparency,
+            &polygon.clip,
+            &polygon.transform,
+            &polygon.effects,
+            defaults,
+        );
+    }
+
+    fn export_path(&mut self, path: &Path, defaults: &ShapeDefaults) -> Result<(), ExportError> {
+        let segments = path.segments().map_err(ExportError::InvalidPath)?;
+        let mut element = format!(r#"<path d="{}""#, path_data(&segments));
+        let fill = path.fill.as_ref().or(defaults.fill.as_ref());
+        if fill.is_some() && path.resolve_fill_rule(fill) == FillRule::EvenOdd {
+            element.push_str(r#" fill-rule="evenodd""#);
+        }
+        let stroke = path.stroke.as_ref().or(defaults.stroke.as_ref());
+        self.write_shape(
+            element,
+            path.id(),
+            fill,
+            stroke,
+            PathSegment::bounds(&segments).unwrap_or_default(),
+            path.transparency,
+            &path.clip,
+            &path.transform,
+            &path.effects,
+            defaults,
+        );
+        Ok(())
+    }
+
+    fn export_text(&mut self, text: &Text, defaults: &ShapeDefaults) {
+        let mut element = format!(r#"<text x="{}" y="{}""#, text.x.val(), text.y.val());
+        if !text.font_name.is_empty() {
+            write!(element, r#" font-family="{}""#, escape(&text.font_name)).unwrap();
+        }
+        write!(element, r#" font-size="{}""#, text.font_size.val()).unwrap();
+        if text.letter_spacing != 0.0 {
+            write!(element, r#" letter-spacing="{}""#, text.letter_spacing).unwrap();
+        }
+        write_decoration(&mut element, text.decoration);
+        // The glyphs along the path are placed by their baseline positions, so they are not aligned
+        let align = if text.path.is_some() {
+            (AlignHor::Left, AlignVer::Baseline)
+        } else {
+            text.align
+        };
+        match align.0 {
+            AlignHor::Left => (),
+            AlignHor::Center => element.push_str(r#" text-anchor="middle""#),
+            AlignHor::Right => element.push_str(r#" text-anchor="end""#),
+        }
+        match align.1 {
+            AlignVer::Baseline => (),
+            AlignVer::Top => element.push_str(r#" dominant-baseline="text-before-edge""#),
+            AlignVer::Middle => element.push_str(r#" dominant-baseline="middle""#),
+            AlignVer::Bottom => element.push_str(r#" dominant-baseline="text-after-edge""#),
+        }
+
+        // Renderers draw the text with the solid color only, as well as here
+        let color = text
+            .fill
+            .as_ref()
+            .or(defaults.fill.as_ref())
+            .and_then(|fill| {
+                if let Paint::Color(color) = &fill.paint {
+                    Some(*color)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_default();
+        write_color(&mut element, "fill", color);
+        self.write_common(&mut element, text.id(), text.transparency, &text.transform, &text.effects, defaults);
+        element.push('>');
+        if text.path.is_some() {
+            for glyph in text.path_glyphs() {
+                let baseline = text.y.val() + text.lines[glyph.line].baseline;
+                let (x, y) = glyph.matrix * (text.x.val() + glyph.x, baseline);
+                let [cos, sin, ..] = glyph.matrix.matrix;
+                write!(element, r#"<tspan x="{}" y="{}" rotate="{}">"#, x, y, sin.atan2(cos).to_degrees()).unwrap();
+                match text.spans.get(glyph.span) {
+                    Some(span) => write_span(&mut element, &span.style, glyph.content),
+                    None => element.push_str(&escape(glyph.content)),
+                }
+                element.push_str("</tspan>");
+            }
+        } else if !text.spans.is_empty() && !text.lines.is_empty() {
+            for line in &text.lines {
+                write!(element, r#"<tspan x="{}" y="{}">"#, text.x.val(), text.y.val() + line.y).unwrap();
+                for run in text.line_runs(line) {
+                    write_span(&mut element, &text.spans[run.span].style, run.content);
+                }
+                element.push_str("</tspan>");
+            }
+        } else if !text.spans.is_empty() {
+            for span in &text.spans {
+                write_span(&mut element, &span.style, &span.content);
+            }
+        } else if text.lines.len() > 1 {
+            for line in &text.lines {
+                write!(
+                    element,
+                    r#"<tspan x="{}" y="{}">{}</tspan>"#,
+                    text.x.val(),
+                    text.y.val() + line.y,
+                    escape(&text.line_text(line))
+                )
+                .unwrap();
+            }
+        } else if let Some(line) = text.lines.first() {
+            element.push_str(&escape(&text.line_text(line)));
+        } else {
+            element.push_str(&escape(&text.content));
+        }
+        element.push_str("</text>");
+        self.write_clipped(element, &[&text.clip, &text.overflow_clip()]);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn write_shape(
+        &mut self, mut element: String, id: Option<&str>, fill: Option<&Fill>, stroke: Option<&Stroke>,
+        bounds: [Real; 4], transparency: Real, clip: &Clip, transform: &Transform, effects: &[Effect],
+        defaults: &ShapeDefaults,
+    ) {
+        match fill {
+            Some(fill) => self.write_paint(&mut element, "fill", fill.paint.with_bounds(bounds, &defaults.units)),
+            None => element.push_str(r#" fill="none""#),
+        }
+        // SVG has no stroke alignment, so the strokes are exported centered
+        if let Some(stroke) = stroke {
+            self.write_paint(&mut element, "stroke", stroke.paint.with_bounds(bounds, &defaults.units));
+            write!(element, r#" stroke-width="{}""#, stroke.width).unwrap();
+            match stroke.line_cap {
+                LineCap::Butt => (),
+                LineCap::Round => element.push_str(r#" stroke-linecap="round""#),
+                LineCap::Square => element.push_str(r#" stroke-linecap="square""#),
+            }
+            match stroke.line_join {
+                LineJoin::Miter => (),
+                LineJoin::Round => element.push_str(r#" stroke-linejoin="round""#),
+                LineJoin::Bevel => element.push_str(r#" stroke-linejoin="bevel""#),
+            }
+            write!(element, r#" stroke-miterlimit="{}""#, stroke.miter_limit).unwrap();
+            if stroke.is_dashed() {
+                let lengths: Vec<String> = stroke.dash.lengths().iter().map(ToString::to_string).collect();
+                write!(element, r#" stroke-dasharray="{}""#, lengths.join(" ")).unwrap();
+                if stroke.dash_offset != 0.0 {
+                    write!(element, r#" stroke-dashoffset="{}""#, stroke.dash_offset).unwrap();
+                }
+            }
+        }
+        self.write_common(&mut element, id, transparency, transform, effects, defaults);
+        element.push_str("/>");
+        self.write_clipped(element, &[clip]);
+    }
+
+    fn write_common(
+        &mut self, element: &mut String, id: Option<&str>, transparency: Real, transform: &Transform,
+        effects: &[Effect], defaults: &ShapeDefaults,
+    ) {
+        if let Some(id) = id {
+            write!(element, r#" id="{}""#, escape(id)).unwrap();
+        }
+        let opacity = (1.0 - transparency) * (1.0 - defaults.transparency);
+        if opacity < 1.0 {
+            write!(element, r#" opacity="{}""#, opacity).unwrap();
+        }
+        write_transform(element, transform);
+        let effects = if effects.is_empty() { &defaults.effects } else { effects };
+        if !effects.is_empty() {
+            let id = self.next_id("filter");
+            self.write_filter(&id, effects);
+            write!(element, r#" filter="url(#{})""#, id).unwrap();
+        }
+    }
+
+    /// Write the filter merging the outer shadows, the content blurred by the blur effect and the inner shadows.
+    fn write_filter(&mut self, id: &str, effects: &[Effect]) {
+        let defs = &mut self.defs;
+        writeln!(defs, r#"<filter id="{}" x="-50%" y="-50%" width="200%" height="200%">"#, id).unwrap();
+        let mut shadows = Vec::new();
+        let mut inner_shadows = Vec::new();
+        let mut content = "SourceGraphic".to_string();
+        for (idx, effect) in effects.iter().enumerate() {
+            match effect {
+                Effect::Shadow(shadow) => {
+                    let input = write_spread(defs, "SourceAlpha", shadow.spread);
+                    write_shadow_blur(defs, &input, shadow, &format!("offset{}", idx));
+                    write_flood(defs, shadow.color);
+                    writeln!(
+                        defs,
+                        r#"<feComposite in2="offset{}" operator="in" result="shadow{}"/>"#,
+                        idx, idx
+                    )
+                    .unwrap();
+                    shadows.push(format!("shadow{}", idx));
+                }
+                Effect::InnerShadow(shadow) => {
+                    write_flood(defs, shadow.color);
+                    defs.push_str(r#"<feComposite in2="SourceAlpha" operator="out" result="outside"/>"#);
+                    defs.push('\n');
+                    let input = write_spread(defs, "outside", shadow.spread);
+                    write_shadow_blur(defs, &input, shadow, &format!("offset{}", idx));
+                    writeln!(defs, r#"<feComposite in2="SourceAlpha" operator="in" result="inner{}"/>"#, idx).unwrap();
+                    inner_shadows.push(format!("inner{}", idx));
+                }
+                Effect::Blur(radius) => {
+                    let std_deviation = Effect::std_deviation(*radius);
+                    writeln!(
+                        defs,
+                        r#"<feGaussianBlur in="SourceGraphic" stdDeviation="{}" result="blur{}"/>"#,
+                        std_deviation, idx
+                    )
+                    .unwrap();
+                    content = format!("blur{}", idx);
+                }
+            }
+        }
+        defs.push_str("<feMerge>");
+        for node in shadows.iter().chain(Some(&content)).chain(&inner_shadows) {
+            write!(defs, r#"<feMergeNode in="{}"/>"#, node).unwrap();
+        }
+        defs.push_str("</feMerge>\n</filter>\n");
+    }
+
+    /// Write the element wrapped by the `g` elements of the clips, so it is clipped by their intersection.
+    fn write_clipped(&mut self, element: String, clips: &[&Clip]) {
+        let ids: Vec<String> = clips
+            .iter()
+            .filter(|clip| !clip.is_none())
+            .map(|clip| self.write_clip_path(clip))
+            .collect();
+        for id in &ids {
+            write!(self.body, r#"<g clip-path="url(#{})">"#, id).unwrap();
+        }
+        self.body.push_str(&element);
+        for _ in &ids {
+            self.body.push_str("</g>");
+        }
+        self.body.push('\n');
+    }
+
+    /// Write the clip path definition of the clip and return its id.
+    fn write_clip_path(&mut self, clip: &Clip) -> String {
+        let id = self.next_id("clip");
+        let mut element = match clip {
+            Clip::Scissor(scissor) => format!(
+                r#"<rect x="{}" y="{}" width="{}" height="{}""#,
+                scissor.x.val(),
+                scissor.y.val(),
+                scissor.width.val(),
+                scissor.height.val()
+            ),
+            Clip::RoundedRect(rounded)
+                if [rounded.rounding.top_right, rounded.rounding.bottom_left, rounded.rounding.bottom_right]
+                    .iter()
+                    .all(|radius| radius.val() == rounded.rounding.top_left.val()) =>
+            {
+                format!(
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}""#,
+                    rounded.x.val(),
+                    rounded.y.val(),
+                    rounded.width.val(),
+                    rounded.height.val(),
+                    rounded.rounding.top_left.val()
+                )
+            }
+            Clip::Circle(circle) => format!(
+                r#"<circle cx="{}" cy="{}" r="{}""#,
+                circle.cx.val(),
+                circle.cy.val(),
+                circle.r.val()
+            ),
+            // The path with the invalid commands clips everything as it has no area
+            _ => {
+                let segments = PathSegment::resolve(&clip.outline()).unwrap_or_default();
+                format!(r#"<path d="{}""#, path_data(&segments))
+            }
+        };
+        if let Some(transform) = clip.transform() {
+            write_transform(&mut element, transform);
+        }
+        writeln!(self.defs, r#"<clipPath id="{}">{}/></clipPath>"#, id, element).unwrap();
+        id
+    }
+
+    fn write_paint(&mut self, element: &mut String, attribute: &str, paint: Paint) {
+        match paint {
+            Paint::Color(color) => write_color(element, attribute, color),
+            // SVG has no conic gradient, so it is exported as the solid average color
+            Paint::Gradient(gradient @ Gradient::Conic { .. }) => {
+                write_color(element, attribute, gradient.average_color())
+            }
+            Paint::Gradient(gradient) => {
+                let id = self.next_id("gradient");
+                self.write_gradient(&id, gradient);
+                write!(element, r#" {}="url(#{})""#, attribute, id).unwrap();
+            }
+            // The image is not encoded to embed it into the SVG pattern, so it is exported as its average color
+            Paint::Pattern(pattern) => write_color(element, attribute, pattern.average_color()),
+        }
+    }
+
+    fn write_gradient(&mut self, id: &str, gradient: Gradient) {
+        let stops = gradient.color_stops();
+        let spread = match gradient.spread_mode() {
+            SpreadMode::Pad => "",
+            SpreadMode::Repeat => r#" spreadMethod="repeat""#,
+            SpreadMode::Reflect => r#" spreadMethod="reflect""#,
+        };
+        match gradient {
+            Gradient::Linear {
+                start: (start_x, start_y),
+                end: (end_x, end_y),
+                ..
+            } => {
+                writeln!(
+                    self.defs,
+                    r#"<linearGradient id="{}" gradientUnits="userSpaceOnUse" x1="{}" y1="{}" x2="{}" y2="{}"{}>"#,
+                    id,
+                    start_x.val(),
+                    start_y.val(),
+                    end_x.val(),
+                    end_y.val(),
+                    spread
+                )
+                .unwrap();
+                write_stops(&mut self.defs, 0.0, &stops);
+                self.defs.push_str("</linearGradient>\n");
+            }
+            Gradient::Radial {
+                center: (cx, cy),
+                inner_radius,
+                outer_radius,
+                ..
+            } => {
+                let (inner_radius, outer_radius) = (inner_radius.val(), outer_radius.val());
+                let offset = if outer_radius > 0.0 {
+                    (inner_radius / outer_radius).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let center = (cx.val(), cy.val());
+                self.write_radial_gradient(id, center, outer_radius, offset, &stops, spread);
+            }
+            Gradient::Box {
+                position: (x, y),
+                size: (width, height),
+                feather,
+                ..
+            } => {
+                let radius = width.max(height) / 2.0 + feather / 2.0;
+                let offset = if radius > 0.0 {
+                    ((width.min(height) / 2.0 - feather / 2.0) / radius).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let center = (x + width / 2.0, y + height / 2.0);
+                self.write_radial_gradient(id, center, radius, offset, &stops, spread);
+            }
+            Gradient::Conic { .. } => (),
+        }
+    }
+
+    fn write_radial_gradient(
+        &mut self, id: &str, (cx, cy): (Real, Real), radius: Real, offset: Real, stops: &[ColorStop], spread: &str,
+    ) {
+        writeln!(
+            self.defs,
+            r#"<radialGradient id="{}" gradientUnits="userSpaceOnUse" cx="{}" cy="{}" r="{}"{}>"#,
+            id, cx, cy, radius, spread
+        )
+        .unwrap();
+        write_stops(&mut self.defs, offset, stops);
+        self.defs.push_str("</radialGradient>\n");
+    }
+
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{}{}", prefix, self.next_id)
+    }
+}
+
+/// Start of the `polyline` or `polygon` element with its points, the fill rule is written for the filled one.
+fn points_element(
+    name: &str, points: &[(RealValue, RealValue)], fill: Option<&Fill>, fill_rule: Option<FillRule>,
+) -> String {
+    let points: Vec<String> = points.iter().map(|(x, y)| format!("{},{}", x.val(), y.val())).collect();
+    let mut element = format!(r#"<{} points="{}""#, name, points.join(" "));
+    if fill.is_some_and(|fill| fill_rule.unwrap_or(fill.fill_rule) == FillRule::EvenOdd) {
+        element.push_str(r#" fill-rule="evenodd""#);
+    }
+    element
+}
+
+fn path_data(segments: &[PathSegment]) -> String {
+    let mut data = String::new();
+    for &segment in segments {
+        if !data.is_empty() {
+            data.push(' ');
+        }
+        match segment {
+            PathSegment::MoveTo([x, y]) => write!(data, "M{} {}", x, y),
+            PathSegment::LineTo([x, y]) => write!(data, "L{} {}", x, y),
+            PathSegment::QuadTo {
+                ctrl: [ctrl_x, ctrl_y],
+                to: [x, y],
+            } => write!(data, "Q{} {} {} {}", ctrl_x, ctrl_y, x, y),
+            PathSegment::CubicTo {
+                ctrl1: [ctrl1_x, ctrl1_y],
+                ctrl2: [ctrl2_x, ctrl2_y],
+                to: [x, y],
+            } => write!(data, "C{} {} {} {} {} {}", ctrl1_x, ctrl1_y, ctrl2_x, ctrl2_y, x, y),
+            PathSegment::Close => write!(data, "Z"),
+        }
+        .unwrap();
+    }
+    data
+}
+
+fn write_transform(element: &mut String, transform: &Transform) {
+    if !transform.is_not_exist() {
+        let matrix = transform
+            .calculated_matrix()
+            .unwrap_or_else(|| transform.matrix());
+        if !matrix.is_identity() {
+            let [a, b, c, d, e, f] = matrix.matrix;
+            write!(element, r#" transform="matrix({} {} {} {} {} {})""#, a, b, c, d, e, f).unwrap();
+        }
+    }
+}
+
+fn write_span(element: &mut String, style: &SpanStyle, content: &str) {
+    element.push_str("<tspan");
+    if let Some(font_name) = &style.font_name {
+        write!(element, r#" font-family="{}""#, escape(font_name)).unwrap();
+    }
+    if let Some(font_size) = style.font_size {
+        write!(element, r#" font-size="{}""#, font_size.val()).unwrap();
+    }
+    if style.weight != FontWeight::NORMAL {
+        write!(element, r#" font-weight="{}""#, style.weight.0).unwrap();
+    }
+    if style.italic {
+        element.push_str(r#" font-style="italic""#);
+    }
+    write_decoration(element, style.decoration);
+    if style.baseline_offset != 0.0 {
+        write!(element, r#" baseline-shift="{}""#, style.baseline_offset).unwrap();
+    }
+    if let Some(color) = style.color {
+        write_color(element, "fill", color);
+    }
+    write!(element, ">{}</tspan>", escape(content)).unwrap();
+}
+
+fn write_decoration(element: &mut String, decoration: TextDecoration) {
+    if decoration.is_none() {
+        return;
+    }
+    let lines = [
+        (decoration.underline, "underline"),
+        (decoration.strikethrough, "line-through"),
+        (decoration.overline, "overline"),
+    ];
+    let values: Vec<_> = lines.iter().filter(|(enabled, _)| *enabled).map(|(_, value)| *value).collect();
+    write!(element, r#" text-decoration="{}""#, values.join(" ")).unwrap();
+}
+
+fn write_color(element: &mut String, attribute: &str, color: Color) {
+    write!(element, r#" {}="{}""#, attribute, rgb(color)).unwrap();
+    let [_, _, _, alpha] = color.as_arr();
+    if alpha < 1.0 {
+        write!(element, r#" {}-opacity="{}""#, attribute, alpha).unwrap();
+    }
+}
+
+/// Expand the input by the positive spread or shrink it by the negative one, the result name is returned.
+fn write_spread(defs: &mut String, input: &str, spread: Real) -> String {
+    if spread == 0.0 {
+        return input.to_string();
+    }
+    let operator = if spread < 0.0 { "erode" } else { "dilate" };
+    writeln!(
+        defs,
+        r#"<feMorphology in="{}" operator="{}" radius="{}" result="spread"/>"#,
+        input,
+        operator,
+        spread.abs()
+    )
+    .unwrap();
+    "spread".to_string()
+}
+
+fn write_shadow_blur(defs: &mut String, input: &str, shadow: &Shadow, result: &str) {
+    let std_deviation = Effect::std_deviation(shadow.blur);
+    writeln!(defs, r#"<feGaussianBlur in="{}" stdDeviation="{}"/>"#, input, std_deviation).unwrap();
+    writeln!(
+        defs,
+        r#"<feOffset dx="{}" dy="{}" result="{}"/>"#,
+        shadow.offset_x, shadow.offset_y, result
+    )
+    .unwrap();
+}
+
+fn write_flood(defs: &mut String, color: Color) {
+    let [_, _, _, alpha] = color.as_arr();
+    write!(defs, r#"<feFlood flood-color="{}""#, rgb(color)).unwrap();
+    if alpha < 1.0 {
+        write!(defs, r#" flood-opacity="{}""#, alpha).unwrap();
+    }
+    defs.push_str("/>\n");
+}
+
+/// Write the gradient stops, the stop offsets are mapped to the range from the start offset to 1.
+fn write_stops(defs: &mut String, start: Real, stops: &[ColorStop]) {
+    for stop in stops {
+        write_stop(defs, start + stop.offset * (1.0 - start), stop.color);
+    }
+}
+
+fn write_stop(defs: &mut String, offset: Real, color: Color) {
+    let [_, _, _, alpha] = color.as_arr();
+    write!(defs, r#"<stop offset="{}" stop-color="{}""#, offset, rgb(color)).unwrap();
+    if alpha < 1.0 {
+        write!(defs, r#" stop-opacity="{}""#, alpha).unwrap();
+    }
+    defs.push_str("/>\n");
+}
+
+fn rgb(color: Color) -> String {
+    let [r, g, b, _] = color.as_arr();
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", channel(r), channel(g), channel(b))
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, collections::HashMap};
+
+    use exgui_core::{
+        ChangeView, Group, Image, Model, Node, PathCommand, Pattern, Prim, RealValue, TextMetrics, TextPath,
+        TransformMatrix,
+    };
+
+    use super::*;
+
+    struct Screen;
+
+    impl Model for Screen {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_props: Self::Properties) -> Self {
+            Screen
+        }
+
+        fn update(&mut self, _msg: Self::Message) -> ChangeView {
+            ChangeView::None
+        }
+
+        fn build_view(&self) -> Node<Self> {
+            unimplemented!()
+        }
+    }
+
+    fn prim(name: &'static str, shape: Shape, children: Vec<Node<Screen>>) -> Node<Screen> {
+        Node::Prim(Prim::new(Cow::Borrowed(name), shape, children, HashMap::new()))
+    }
+
+    #[test]
+    fn export_shapes() {
+        let rect = Rect {
+            id: Some("panel".to_string()),
+            x: RealValue::px(10.0),
+            y: RealValue::px(20.0),
+            width: RealValue::px(30.0),
+            height: RealValue::px(40.0),
+            stroke: Some(Stroke {
+                width: 2.0,
+                line_join: LineJoin::Round,
+                ..Stroke::color(Color::Red).dash([4.0, 2.0]).dash_offset(1.0)
+            }),
+            clip: Clip::new_scissor(RealValue::px(0.0), RealValue::px(0.0), RealValue::px(20.0), RealValue::px(20.0)),
+            transform: Transform::Calculated {
+                local: None,
+                global: TransformMatrix::identity().with_translation(5.0, 5.0),
+            },
+            ..Default::default()
+        };
+        let text = Text {
+            content: "A & B".to_string(),
+            x: RealValue::px(1.0),
+            y: RealValue::px(2.0),
+            font_name: "Roboto".to_string(),
+            font_size: RealValue::px(12.0),
+            align: (AlignHor::Center, AlignVer::Baseline),
+            ..Default::default()
+        };
+        let group = Group {
+            transparency: Some(0.5),
+            fill: Some(Fill::gradient(
+                Gradient::linear((0, 0), (10, 0))
+                    .stop(0.0, Color::White)
+                    .stop(1.0, Color::Black.with_alpha(0.5)),
+            )),
+            ..Default::default()
+        };
+        let node = prim(Group::NAME, Shape::Group(group), vec![
+            prim(Rect::NAME, Shape::Rect(rect), vec![]),
+            prim(Text::NAME, Shape::Text(text), vec![]),
+        ]);
+
+        let svg = export(&node, 100.0, 50.0).unwrap();
+        assert_eq!(
+            svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50" viewBox="0 0 100 50">
+<defs>
+<linearGradient id="gradient1" gradientUnits="userSpaceOnUse" x1="0" y1="0" x2="10" y2="0">
+<stop offset="0" stop-color="#ffffff"/>
+<stop offset="1" stop-color="#000000" stop-opacity="0.5"/>
+</linearGradient>
+<clipPath id="clip2"><rect x="0" y="0" width="20" height="20"/></clipPath>
+</defs>
+<g clip-path="url(#clip2)"><rect x="10" y="20" width="30" height="40" fill="url(#gradient1)" stroke="#ff0000" stroke-width="2" stroke-linejoin="round" stroke-miterlimit="10" stroke-dasharray="4 2" stroke-dashoffset="1" id="panel" opacity="0.5" transform="matrix(1 0 0 1 5 5)"/></g>
+<text x="1" y="2" font-family="Roboto" font-size="12" text-anchor="middle" fill="#000000" opacity="0.5">A &amp; B</text>
+</svg>
+"##
+        );
+    }
+
+    #[test]
+    fn export_effects() {
+        let circle = Circle {
+            cx: RealValue::px(5.0),
+            cy: RealValue::px(5.0),
+            r: RealValue::px(4.0),
+            fill: Some(Fill::color(Color::Red)),
+            effects: vec![
+                Shadow::new(0.0, 2.0, 4.0, Color::Black.with_alpha(0.5)).spread(1.0).into(),
+                Effect::blur(2.0),
+            ],
+            ..Default::default()
+        };
+        let group = Group {
+            effects: vec![Effect::inner_shadow(1.0, 1.0, 0.0, Color::Black)],
+            ..Default::default()
+        };
+        let node = prim(Group::NAME, Shape::Group(group), vec![
+            prim(Circle::NAME, Shape::Circle(circle), vec![]),
+            prim(Path::NAME, Shape::Path(Path::default()), vec![]),
+        ]);
+
+        let svg = export(&node, 10.0, 10.0).unwrap();
+        assert_eq!(
+            svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10" viewBox="0 0 10 10">
+<defs>
+<filter id="filter1" x="-50%" y="-50%" width="200%" height="200%">
+<feMorphology in="SourceAlpha" operator="dilate" radius="1" result="spread"/>
+<feGaussianBlur in="spread" stdDeviation="2"/>
+<feOffset dx="0" dy="2" result="offset0"/>
+<feFlood flood-color="#000000" flood-opacity="0.5"/>
+<feComposite in2="offset0" operator="in" result="shadow0"/>
+<feGaussianBlur in="SourceGraphic" stdDeviation="1" result="blur1"/>
+<feMerge><feMergeNode in="shadow0"/><feMergeNode in="blur1"/></feMerge>
+</filter>
+<filter id="filter2" x="-50%" y="-50%" width="200%" height="200%">
+<feFlood flood-color="#000000"/>
+<feComposite in2="SourceAlpha" operator="out" result="outside"/>
+<feGaussianBlur in="outside" stdDeviation="0"/>
+<feOffset dx="1" dy="1" result="offset0"/>
+<feComposite in2="SourceAlpha" operator="in" result="inner0"/>
+<feMerge><feMergeNode in="SourceGraphic"/><feMergeNode in="inner0"/></feMerge>
+</filter>
+</defs>
+<circle cx="5" cy="5" r="4" fill="#ff0000" filter="url(#filter1)"/>
+<path d="" fill="none" filter="url(#filter2)"/>
+</svg>
+"##
+        );
+    }
+
+    #[test]
+    fn export_layers() {
+        let circle = |cx: Real| Circle {
+            cx: RealValue::px(cx),
+            cy: RealValue::px(5.0),
+            r: RealValue::px(4.0),
+            ..Default::default()
+        };
+        let layer = Group {
+            transparency: Some(0.5),
+            blend_mode: BlendMode::Multiply,
+            ..Default::default()
+        };
+        let node = prim(Group::NAME, Shape::Group(layer), vec![
+            prim(Circle::NAME, Shape::Circle(circle(4.0)), vec![]),
+            prim(Circle::NAME, Shape::Circle(circle(6.0)), vec![]),
+        ]);
+
+        let svg = export(&node, 10.0, 10.0).unwrap();
+        assert_eq!(
+            svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10" viewBox="0 0 10 10">
+<g opacity="0.5" style="mix-blend-mode:multiply">
+<circle cx="4" cy="5" r="4" fill="none"/>
+<circle cx="6" cy="5" r="4" fill="none"/>
+</g>
+</svg>
+"##
+        );
+    }
+
+    #[test]
+    fn export_nested_clips() {
+        let card = Group {
+            clip: Clip::new_rounded_rect(
+                RealValue::px(0.0),
+                RealValue::px(0.0),
+                RealValue::px(10.0),
+                RealValue::px(10.0),
+                RealValue::px(2.0),
+            ),
+            ..Default::default()
+        };
+        let scroll = Group {
+            clip: Clip::new_scissor(RealValue::px(0.0), RealValue::px(2.0), RealValue::px(10.0), RealValue::px(6.0)),
+            ..Default::default()
+        };
+        let avatar = Circle {
+            cx: RealValue::px(5.0),
+            cy: RealValue::px(5.0),
+            r: RealValue::px(4.0),
+            clip: Clip::new_circle(RealValue::px(5.0), RealValue::px(5.0), RealValue::px(3.0)),
+            ..Default::default()
+        };
+        let node = prim(Group::NAME, Shape::Group(card), vec![prim(
+            Group::NAME,
+            Shape::Group(scroll),
+            vec![prim(Circle::NAME, Shape::Circle(avatar), vec![])],
+        )]);
+
+        let svg = export(&node, 10.0, 10.0).unwrap();
+        assert_eq!(
+            svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10" viewBox="0 0 10 10">
+<defs>
+<clipPath id="clip1"><rect x="0" y="0" width="10" height="10" rx="2"/></clipPath>
+<clipPath id="clip2"><rect x="0" y="2" width="10" height="6"/></clipPath>
+<clipPath id="clip3"><circle cx="5" cy="5" r="3"/></clipPath>
+</defs>
+<g clip-path="url(#clip1)">
+<g clip-path="url(#clip2)">
+<g clip-path="url(#clip3)"><circle cx="5" cy="5" r="4" fill="none"/></g>
+</g>
+</g>
+</svg>
+"##
+        );
+    }
+
+    #[test]
+    fn export_gradient_stops() {
+        let radial = Gradient::radial((RealValue::pct(50.0), RealValue::pct(50.0)), 0, RealValue::pct(50.0))
+            .with_stops(vec![(0.0, Color::White), (0.5, Color::Red), (1.0, Color::Blue)])
+            .spread(SpreadMode::Reflect);
+        let circle = Circle {
+            cx: RealValue::px(10.0),
+            cy: RealValue::px(10.0),
+            r: RealValue::px(4.0),
+            fill: Some(Fill::gradient(radial)),
+            stroke: Some(Stroke::gradient(
+                Gradient::conic((10, 10), 0.0).stop(0.0, Color::Red).stop(1.0, Color::Red),
+            )),
+            ..Default::default()
+        };
+        let node = prim(Circle::NAME, Shape::Circle(circle), vec![]);
+
+        let svg = export(&node, 20.0, 20.0).unwrap();
+        assert_eq!(
+            svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20" viewBox="0 0 20 20">
+<defs>
+<radialGradient id="gradient1" gradientUnits="userSpaceOnUse" cx="10" cy="10" r="4" spreadMethod="reflect">
+<stop offset="0" stop-color="#ffffff"/>
+<stop offset="0.5" stop-color="#ff0000"/>
+<stop offset="1" stop-color="#0000ff"/>
+</radialGradient>
+</defs>
+<circle cx="10" cy="10" r="4" fill="url(#gradient1)" stroke="#ff0000" stroke-width="1" stroke-miterlimit="10"/>
+</svg>
+"##
+        );
+    }
+
+    #[test]
+    fn export_pattern_as_average_color() {
+        let image = Image::from_fn(2, 1, |x, _| if x == 0 { Color::Red } else { Color::Blue });
+        let circle = Circle {
+            cx: RealValue::px(10.0),
+            cy: RealValue::px(10.0),
+            r: RealValue::px(4.0),
+            fill: Some(Fill::pattern(Pattern::new(image).opacity(0.5))),
+            ..Default::default()
+        };
+        let node = prim(Circle::NAME, Shape::Circle(circle), vec![]);
+
+        let svg = export(&node, 20.0, 20.0).unwrap();
+        assert!(svg.contains(r##"<circle cx="10" cy="10" r="4" fill="#800080" fill-opacity="0.5"/>"##), "{}", svg);
+    }
+
+    #[test]
+    fn export_text_along_path() {
+        let mut text = Text {
+            content: "ab".to_string(),
+            font_size: RealValue::px(10.0),
+            align: (AlignHor::Center, AlignVer::Baseline),
+            path: Some(
+                TextPath::new(vec![PathCommand::Move([0.0, 0.0]), PathCommand::Line([0.0, 10.0])])
+                    .start_offset(RealValue::pct(50.0)),
+            ),
+            ..Default::default()
+        };
+        text.path.as_mut().unwrap().resolve(&UnitContext::default());
+        let metrics = TextMetrics {
+            ascender: 8.0,
+            descender: -2.0,
+            line_height: 10.0,
+        };
+        text.break_lines(&[2.0, 2.0], &[metrics]);
+        let node = prim(Text::NAME, Shape::Text(text), vec![]);
+
+        let svg = export(&node, 20.0, 20.0).unwrap();
+        assert_eq!(
+            svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20" viewBox="0 0 20 20">
+<text x="0" y="0" font-size="10" fill="#000000"><tspan x="0" y="3" rotate="90">a</tspan><tspan x="0" y="5" rotate="90">b</tspan></text>
+</svg>
+"##
+        );
+    }
+}
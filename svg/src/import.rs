@@ -0,0 +1,1057 @@
+use std::{borrow::Cow, collections::HashMap, error::Error, fmt};
+
+use exgui_core::{
+    AlignHor, AlignVer, Circle, Color, ColorStop, DashArray, Ellipse, Fill, FillRule, Gradient, Group, Line, LineCap,
+    LineJoin, Model, Node, Paint, Path, PathCommand, Polygon, Polyline, Prim, Real, RealValue, Rect, Rounding, Shape,
+    SpreadMode, Stroke, Text, Transform, TransformMatrix, ValueType,
+};
+use roxmltree::{Document, Node as XmlNode};
+
+const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
+
+/// The result of the SVG import: the node tree and the list of the SVG features that were skipped.
+pub struct Import<M: Model> {
+    pub node: Node<M>,
+    pub warnings: Vec<ImportWarning>,
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    Xml(roxmltree::Error),
+    NotSvg(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Xml(err) => write!(f, "Invalid SVG document: {}", err),
+            ImportError::NotSvg(name) => write!(f, "Expected <svg> root element, found <{}>", name),
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+impl From<roxmltree::Error> for ImportError {
+    fn from(err: roxmltree::Error) -> Self {
+        ImportError::Xml(err)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportWarning {
+    UnsupportedElement {
+        element: String,
+    },
+    UnsupportedAttribute {
+        element: String,
+        attribute: String,
+    },
+    InvalidAttribute {
+        element: String,
+        attribute: String,
+        value: String,
+    },
+}
+
+impl fmt::Display for ImportWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportWarning::UnsupportedElement { element } => write!(f, "Unsupported element <{}>", element),
+            ImportWarning::UnsupportedAttribute { element, attribute } => {
+                write!(f, "Unsupported attribute '{}' of <{}>", attribute, element)
+            }
+            ImportWarning::InvalidAttribute {
+                element,
+                attribute,
+                value,
+            } => write!(f, "Invalid value '{}' of attribute '{}' of <{}>", value, attribute, element),
+        }
+    }
+}
+
+/// Import the SVG document into the node tree made of `Group`, `Rect`, `Circle`, `Ellipse`, `Line`,
+/// `Polyline`, `Polygon`, `Path` and `Text`.
+pub fn import<M: Model>(source: &str) -> Result<Import<M>, ImportError> {
+    let document = Document::parse(source)?;
+    let root = document.root_element();
+    if root.tag_name().name() != "svg" {
+        return Err(ImportError::NotSvg(root.tag_name().name().to_string()));
+    }
+
+    let mut importer = Importer {
+        gradients: HashMap::new(),
+        warnings: Vec::new(),
+    };
+    for node in document.descendants() {
+        let name = node.tag_name().name();
+        if let (true, Some(id)) = (name == "linearGradient" || name == "radialGradient", node.attribute("id")) {
+            importer.gradients.insert(id.to_string(), node);
+        }
+    }
+
+    let style = Style::default();
+    let node = importer.import_svg(root, &style);
+    Ok(Import {
+        node,
+        warnings: importer.warnings,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SvgPaint {
+    Color(Color),
+    Url(String),
+}
+
+#[derive(Debug, Clone)]
+struct Style {
+    fill: Option<SvgPaint>,
+    fill_opacity: Real,
+    fill_rule: FillRule,
+    stroke: Option<SvgPaint>,
+    stroke_opacity: Real,
+    stroke_width: Real,
+    line_cap: LineCap,
+    line_join: LineJoin,
+    miter_limit: Real,
+    dash: DashArray,
+    dash_offset: Real,
+    font_family: String,
+    font_size: Real,
+    text_anchor: AlignHor,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            fill: Some(SvgPaint::Color(Color::Black)),
+            fill_opacity: 1.0,
+            fill_rule: FillRule::NonZero,
+            stroke: None,
+            stroke_opacity: 1.0,
+            stroke_width: 1.0,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            miter_limit: 4.0,
+            dash: DashArray::default(),
+            dash_offset: 0.0,
+            font_family: String::new(),
+            font_size: 16.0,
+            text_anchor: AlignHor::Left,
+        }
+    }
+}
+
+struct Importer<'a, 'input> {
+    gradients: HashMap<String, XmlNode<'a, 'input>>,
+    warnings: Vec<ImportWarning>,
+}
+
+impl<'a, 'input> Importer<'a, 'input> {
+    fn import_svg<M: Model>(&mut self, svg: XmlNode, parent_style: &Style) -> Node<M> {
+        let style = self.element_style(svg, parent_style);
+        let mut group = Group {
+            id: svg.attribute("id").map(ToString::to_string),
+            transparency: self.opacity(svg).map(|opacity| 1.0 - opacity),
+            ..Default::default()
+        };
+
+        let view_box = svg.attribute("viewBox").and_then(|value| {
+            let numbers = parse_numbers(value);
+            if numbers.len() == 4 {
+                Some([numbers[0], numbers[1], numbers[2], numbers[3]])
+            } else {
+                self.invalid_attribute(svg, "viewBox", value);
+                None
+            }
+        });
+        let width = svg.attribute("width").and_then(|value| parse_length(value, style.font_size));
+        let height = svg.attribute("height").and_then(|value| parse_length(value, style.font_size));
+        let mut matrix = TransformMatrix::identity();
+        if let Some(Transform::Local(local)) = self.transform(svg) {
+            matrix = local;
+        }
+        if let Some([x, y, view_width, view_height]) = view_box {
+            let width = width.filter(|width| width.1 == ValueType::Px && view_width > 0.0);
+            let height = height.filter(|height| height.1 == ValueType::Px && view_height > 0.0);
+            let scale_x = width.map(|width| width.val() / view_width);
+            let scale_y = height.map(|height| height.val() / view_height);
+
+            let aspect_ratio = svg.attribute("preserveAspectRatio").unwrap_or_default();
+            let mut aspect_ratio = aspect_ratio.split_whitespace();
+            let align = aspect_ratio.next().unwrap_or("xMidYMid");
+            let slice = aspect_ratio.next() == Some("slice");
+            let (scale_x, scale_y, align_x, align_y) = if align == "none" {
+                (scale_x.unwrap_or(1.0), scale_y.unwrap_or(1.0), 0.0, 0.0)
+            } else {
+                let scale = match (scale_x, scale_y) {
+                    (Some(scale_x), Some(scale_y)) if slice => scale_x.max(scale_y),
+                    (Some(scale_x), Some(scale_y)) => scale_x.min(scale_y),
+                    (scale_x, scale_y) => scale_x.or(scale_y).unwrap_or(1.0),
+                };
+                let align_fraction = |min: &str, max: &str| {
+                    if align.contains(min) {
+                        0.0
+                    } else if align.contains(max) {
+                        1.0
+                    } else {
+                        0.5
+                    }
+                };
+                (scale, scale, align_fraction("xMin", "xMax"), align_fraction("YMin", "YMax"))
+            };
+
+            // The scaled view box is aligned inside the viewport by the free space left on each axis
+            let offset_x = width.map_or(0.0, |width| (width.val() - view_width * scale_x) * align_x);
+            let offset_y = height.map_or(0.0, |height| (height.val() - view_height * scale_y) * align_y);
+            matrix = matrix
+                * matrix_from([
+                    scale_x,
+                    0.0,
+                    0.0,
+                    scale_y,
+                    offset_x - x * scale_x,
+                    offset_y - y * scale_y,
+                ]);
+        }
+        if !matrix.is_identity() {
+            group.transform = Transform::Local(matrix);
+        }
+
+        let children = self.import_children(svg, &style);
+        prim(Group::NAME, Shape::Group(group), children)
+    }
+
+    fn import_children<M: Model>(&mut self, parent: XmlNode, style: &Style) -> Vec<Node<M>> {
+        parent
+            .children()
+            .filter(|node| node.is_element())
+            .filter_map(|node| self.import_element(node, style))
+            .collect()
+    }
+
+    fn import_element<M: Model>(&mut self, element: XmlNode, parent_style: &Style) -> Option<Node<M>> {
+        let name = element.tag_name().name();
+        match name {
+            "g" | "a" => {
+                let style = self.element_style(element, parent_style);
+                let group = Group {
+                    id: element.attribute("id").map(ToString::to_string),
+                    transparency: self.opacity(element).map(|opacity| 1.0 - opacity),
+                    transform: self.transform(element).unwrap_or_default(),
+                    ..Default::default()
+                };
+                let children = self.import_children(element, &style);
+                Some(prim(Group::NAME, Shape::Group(group), children))
+            }
+            "svg" => Some(self.import_svg(element, parent_style)),
+            "rect" => {
+                let style = self.element_style(element, parent_style);
+                let x = self.length(element, "x", &style).unwrap_or_default();
+                let y = self.length(element, "y", &style).unwrap_or_default();
+                let width = self.length(element, "width", &style).unwrap_or_default();
+                let height = self.length(element, "height", &style).unwrap_or_default();
+                let rx = self.length(element, "rx", &style);
+                let ry = self.length(element, "ry", &style);
+                let rect = Rect {
+                    id: element.attribute("id").map(ToString::to_string),
+                    x,
+                    y,
+                    width,
+                    height,
+                    rounding: rx.or(ry).map(Rounding::from),
+                    transparency: self.transparency(element),
+                    fill: self.fill(element, &style),
+                    stroke: self.stroke(element, &style),
+                    transform: self.transform(element).unwrap_or_default(),
+                    ..Default::default()
+                };
+                Some(prim(Rect::NAME, Shape::Rect(rect), Vec::new()))
+            }
+            "circle" => {
+                let style = self.element_style(element, parent_style);
+                let cx = self.length(element, "cx", &style).unwrap_or_default();
+                let cy = self.length(element, "cy", &style).unwrap_or_default();
+                let r = self.length(element, "r", &style).unwrap_or_default();
+                let circle = Circle {
+                    id: element.attribute("id").map(ToString::to_string),
+                    cx,
+                    cy,
+                    r,
+                    transparency: self.transparency(element),
+                    fill: self.fill(element, &style),
+                    stroke: self.stroke(element, &style),
+                    transform: self.transform(element).unwrap_or_default(),
+                    ..Default::default()
+                };
+                Some(prim(Circle::NAME, Shape::Circle(circle), Vec::new()))
+            }
+            "ellipse" => {
+                let style = self.element_style(element, parent_style);
+                let ellipse = Ellipse {
+                    id: element.attribute("id").map(ToString::to_string),
+                    cx: self.length(element, "cx", &style).unwrap_or_default(),
+                    cy: self.length(element, "cy", &style).unwrap_or_default(),
+                    rx: self.length(element, "rx", &style).unwrap_or_default(),
+                    ry: self.length(element, "ry", &style).unwrap_or_default(),
+                    transparency: self.transparency(element),
+                    fill: self.fill(element, &style),
+                    stroke: self.stroke(element, &style),
+                    transform: self.transform(element).unwrap_or_default(),
+                    ..Default::default()
+                };
+                Some(prim(Ellipse::NAME, Shape::Ellipse(ellipse), Vec::new()))
+            }
+            "line" => {
+                let style = self.element_style(element, parent_style);
+                let line = Line {
+                    id: element.attribute("id").map(ToString::to_string),
+                    x1: self.length(element, "x1", &style).unwrap_or_default(),
+                    y1: self.length(element, "y1", &style).unwrap_or_default(),
+                    x2: self.length(element, "x2", &style).unwrap_or_default(),
+                    y2: self.length(element, "y2", &style).unwrap_or_default(),
+                    transparency: self.transparency(element),
+                    stroke: self.stroke(element, &style),
+                    transform: self.transform(element).unwrap_or_default(),
+                    ..Default::default()
+                };
+                Some(prim(Line::NAME, Shape::Line(line), Vec::new()))
+            }
+            "polyline" | "polygon" => {
+                let style = self.element_style(element, parent_style);
+                let points = element.attribute("points").unwrap_or_default();
+                let points = match parse_points(points) {
+                    Some(points) => points,
+                    None => {
+                        self.invalid_attribute(element, "points", points);
+                        return None;
+                    }
+                };
+                let id = element.attribute("id").map(ToString::to_string);
+                let (transparency, fill, stroke) =
+                    (self.transparency(element), self.fill(element, &style), self.stroke(element, &style));
+                let transform = self.transform(element).unwrap_or_default();
+                Some(if name == "polygon" {
+                    let polygon = Polygon {
+                        id,
+                        points,
+                        transparency,
+                        fill,
+                        stroke,
+                        transform,
+                        ..Default::default()
+                    };
+                    prim(Polygon::NAME, Shape::Polygon(polygon), Vec::new())
+                } else {
+                    let polyline = Polyline {
+                        id,
+                        points,
+                        transparency,
+                        fill,
+                        stroke,
+                        transform,
+                        ..Default::default()
+                    };
+                    prim(Polyline::NAME, Shape::Polyline(polyline), Vec::new())
+                })
+            }
+            "path" => {
+                let style = self.element_style(element, parent_style);
+                let data = element.attribute("d").unwrap_or_default();
+                match PathCommand::parse(data) {
+                    Ok(cmd) => Some(self.path(element, &style, cmd)),
+                    Err(_) => {
+                        self.invalid_attribute(element, "d", data);
+                        None
+                    }
+                }
+            }
+            "text" => {
+                let style = self.element_style(element, parent_style);
+                let content = element
+                    .descendants()
+                    .filter(|node| node.is_text())
+                    .filter_map(|node| node.text())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let content = content.split_whitespace().collect::<Vec<_>>().join(" ");
+                for child in element.descendants().skip(1).filter(|node| node.is_element()) {
+                    self.unsupported_element(child);
+                }
+
+                let text = Text {
+                    id: element.attribute("id").map(ToString::to_string),
+                    content,
+                    x: self.length(element, "x", &style).unwrap_or_default(),
+                    y: self.length(element, "y", &style).unwrap_or_default(),
+                    font_name: style.font_family.clone(),
+                    font_size: RealValue::px(style.font_size),
+                    align: (style.text_anchor, AlignVer::Baseline),
+                    transparency: self.transparency(element),
+                    fill: self.fill(element, &style),
+                    stroke: self.stroke(element, &style),
+                    transform: self.transform(element).unwrap_or_default(),
+                    ..Default::default()
+                };
+                Some(prim(Text::NAME, Shape::Text(text), Vec::new()))
+            }
+            "defs" | "title" | "desc" | "metadata" | "linearGradient" | "radialGradient" => None,
+            _ => {
+                self.unsupported_element(element);
+                None
+            }
+        }
+    }
+
+    fn path<M: Model>(&mut self, element: XmlNode, style: &Style, cmd: Vec<PathCommand>) -> Node<M> {
+        let path = Path {
+            id: element.attribute("id").map(ToString::to_string),
+            cmd,
+            transparency: self.transparency(element),
+            fill: self.fill(element, style),
+            stroke: self.stroke(element, style),
+            transform: self.transform(element).unwrap_or_default(),
+            ..Default::default()
+        };
+        prim(Path::NAME, Shape::Path(path), Vec::new())
+    }
+
+    fn element_style(&mut self, element: XmlNode, parent_style: &Style) -> Style {
+        let mut style = parent_style.clone();
+        let mut properties: Vec<(&str, &str)> = element
+            .attributes()
+            .iter()
+            .filter(|attribute| attribute.namespace().is_none())
+            .map(|attribute| (attribute.name(), attribute.value()))
+            .collect();
+        if let Some(inline) = element.attribute("style") {
+            properties.extend(inline.split(';').filter_map(|declaration| {
+                let mut parts = declaration.splitn(2, ':');
+                match (parts.next(), parts.next()) {
+                    (Some(name), Some(value)) => Some((name.trim(), value.trim())),
+                    _ => None,
+                }
+            }));
+        }
+
+        for (name, value) in properties {
+            match name {
+                "fill" => style.fill = self.svg_paint(element, name, value, &parent_style.fill),
+                "stroke" => style.stroke = self.svg_paint(element, name, value, &parent_style.stroke),
+                "fill-opacity" => style.fill_opacity = self.number(element, name, value).unwrap_or(1.0),
+                "fill-rule" => match value {
+                    "nonzero" => style.fill_rule = FillRule::NonZero,
+                    "evenodd" => style.fill_rule = FillRule::EvenOdd,
+                    _ => self.invalid_attribute(element, name, value),
+                },
+                "stroke-opacity" => style.stroke_opacity = self.number(element, name, value).unwrap_or(1.0),
+                "stroke-width" => {
+                    if let Some(width) = self.number(element, name, value) {
+                        style.stroke_width = width;
+                    }
+                }
+                "stroke-linecap" => match value {
+                    "butt" => style.line_cap = LineCap::Butt,
+                    "round" => style.line_cap = LineCap::Round,
+                    "square" => style.line_cap = LineCap::Square,
+                    _ => self.invalid_attribute(element, name, value),
+                },
+                "stroke-linejoin" => match value {
+                    "miter" => style.line_join = LineJoin::Miter,
+                    "round" => style.line_join = LineJoin::Round,
+                    "bevel" => style.line_join = LineJoin::Bevel,
+                    _ => self.invalid_attribute(element, name, value),
+                },
+                "stroke-miterlimit" => {
+                    if let Some(limit) = self.number(element, name, value) {
+                        style.miter_limit = limit;
+                    }
+                }
+                "stroke-dasharray" => {
+                    let lengths = parse_numbers(value);
+                    let items = value.split(|ch: char| ch.is_ascii_whitespace() || ch == ',');
+                    if value == "none" {
+                        style.dash = DashArray::default();
+                    } else if lengths.len() == items.filter(|item| !item.is_empty()).count() {
+                        style.dash = DashArray::from(lengths);
+                    } else {
+                        self.invalid_attribute(element, name, value);
+                    }
+                }
+                "stroke-dashoffset" => {
+                    if let Some(offset) = self.number(element, name, value) {
+                        style.dash_offset = offset;
+                    }
+                }
+                "font-family" => {
+                    style.font_family = value
+                        .split(',')
+                        .next()
+                        .unwrap_or_default()
+                        .trim()
+                        .trim_matches(|ch| ch == '\'' || ch == '"')
+                        .to_string();
+                }
+                "font-size" => match parse_length(value, parent_style.font_size) {
+                    Some(size) if size.1 == ValueType::Px => style.font_size = size.val(),
+                    _ => self.invalid_attribute(element, name, value),
+                },
+                "text-anchor" => match value {
+                    "start" => style.text_anchor = AlignHor::Left,
+                    "middle" => style.text_anchor = AlignHor::Center,
+                    "end" => style.text_anchor = AlignHor::Right,
+                    _ => self.invalid_attribute(element, name, value),
+                },
+                "clip-path" | "mask" | "filter" | "clip-rule" | "class" => {
+                    self.warnings.push(ImportWarning::UnsupportedAttribute {
+                        element: element.tag_name().name().to_string(),
+                        attribute: name.to_string(),
+                    })
+                }
+                _ => (),
+            }
+        }
+        style
+    }
+
+    fn svg_paint(
+        &mut self, element: XmlNode, attribute: &str, value: &str, inherited: &Option<SvgPaint>,
+    ) -> Option<SvgPaint> {
+        match value {
+            "none" => None,
+            "inherit" | "currentColor" => inherited.clone(),
+            _ if value.starts_with("url(") => {
+                let id = value
+                    .trim_start_matches("url(")
+                    .split(')')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .trim_start_matches('#');
+                Some(SvgPaint::Url(id.to_string()))
+            }
+            _ => match Color::parse(value).ok() {
+                Some(color) => Some(SvgPaint::Color(color)),
+                None => {
+                    self.invalid_attribute(element, attribute, value);
+                    inherited.clone()
+                }
+            },
+        }
+    }
+
+    fn fill(&mut self, element: XmlNode, style: &Style) -> Option<Fill> {
+        let paint = self.paint(element, style.fill.as_ref()?, style.fill_opacity)?;
+        Some(Fill {
+            paint,
+            fill_rule: style.fill_rule,
+        })
+    }
+
+    fn stroke(&mut self, element: XmlNode, style: &Style) -> Option<Stroke> {
+        let paint = self.paint(element, style.stroke.as_ref()?, style.stroke_opacity)?;
+        Some(Stroke {
+            paint,
+            width: style.stroke_width,
+            line_cap: style.line_cap,
+            line_join: style.line_join,
+            miter_limit: style.miter_limit,
+            dash: style.dash,
+            dash_offset: style.dash_offset,
+            ..Default::default()
+        })
+    }
+
+    fn paint(&mut self, element: XmlNode, paint: &SvgPaint, opacity: Real) -> Option<Paint> {
+        match paint {
+            SvgPaint::Color(color) => Some(Paint::Color(with_opacity(*color, opacity))),
+            SvgPaint::Url(id) => match self.gradients.get(id).copied() {
+                Some(gradient) => self.gradient(gradient, opacity).map(Paint::Gradient),
+                None => {
+                    self.invalid_attribute(element, "fill", &format!("url(#{})", id));
+                    None
+                }
+            },
+        }
+    }
+
+    /// Gradient with the percentage coordinates in the bounding box units, it has no stops if the stops
+    /// are not found.
+    fn gradient(&mut self, gradient: XmlNode, opacity: Real) -> Option<Gradient> {
+        // The gradients referred to in the chain are tracked to stop at the first repeated one of a cycle
+        let mut stops_source = gradient;
+        let mut visited = vec![gradient];
+        while stops_source.children().all(|node| node.tag_name().name() != "stop") {
+            match stops_source
+                .attribute((XLINK_NS, "href"))
+                .or_else(|| stops_source.attribute("href"))
+                .and_then(|href| self.gradients.get(href.trim_start_matches('#')).copied())
+            {
+                Some(source) if !visited.contains(&source) => {
+                    visited.push(source);
+                    stops_source = source;
+                },
+                _ => break,
+            }
+        }
+        let stops: Vec<ColorStop> = stops_source
+            .children()
+            .filter(|node| node.tag_name().name() == "stop")
+            .map(|stop| {
+                let offset = stop
+                    .attribute("offset")
+                    .and_then(|value| match value.trim().strip_suffix('%') {
+                        Some(pct) => pct.trim().parse::<Real>().ok().map(|pct| pct / 100.0),
+                        None => value.trim().parse().ok(),
+                    })
+                    .unwrap_or(0.0);
+                let color = stop
+                    .attribute("stop-color")
+                    .or_else(|| style_property(stop, "stop-color"))
+                    .and_then(|value| Color::parse(value).ok())
+                    .unwrap_or(Color::Black);
+                let stop_opacity = stop
+                    .attribute("stop-opacity")
+                    .or_else(|| style_property(stop, "stop-opacity"))
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(1.0);
+                ColorStop::new(offset, with_opacity(color, stop_opacity * opacity))
+            })
+            .collect();
+        if stops.is_empty() {
+            return None;
+        }
+        if gradient.attribute("gradientTransform").is_some() {
+            self.warnings.push(ImportWarning::UnsupportedAttribute {
+                element: gradient.tag_name().name().to_string(),
+                attribute: "gradientTransform".to_string(),
+            });
+        }
+        let spread = match gradient.attribute("spreadMethod") {
+            Some("reflect") => SpreadMode::Reflect,
+            Some("repeat") => SpreadMode::Repeat,
+            _ => SpreadMode::Pad,
+        };
+
+        let user_space = gradient.attribute("gradientUnits") == Some("userSpaceOnUse");
+        let coord = |attribute: &str, default: Real| {
+            let value = gradient.attribute(attribute).unwrap_or_default().trim();
+            let value = match value.strip_suffix('%') {
+                Some(pct) => pct.trim().parse::<Real>().ok().map(|pct| (pct / 100.0, true)),
+                None => value.parse::<Real>().ok().map(|number| (number, false)),
+            };
+            match value {
+                Some((number, false)) if user_space => RealValue::px(number),
+                Some((fraction, _)) => RealValue::pct(fraction * 100.0),
+                None => RealValue::pct(default * 100.0),
+            }
+        };
+
+        let gradient = match gradient.tag_name().name() {
+            "linearGradient" => {
+                Gradient::linear((coord("x1", 0.0), coord("y1", 0.0)), (coord("x2", 1.0), coord("y2", 0.0)))
+            }
+            _ => Gradient::radial((coord("cx", 0.5), coord("cy", 0.5)), 0, coord("r", 0.5)),
+        };
+        Some(gradient.with_stops(stops).spread(spread))
+    }
+
+    fn transform(&mut self, element: XmlNode) -> Option<Transform> {
+        let value = element.attribute("transform")?;
+        match parse_transform(value) {
+            Some(matrix) => Some(Transform::Local(matrix)),
+            None => {
+                self.invalid_attribute(element, "transform", value);
+                None
+            }
+        }
+    }
+
+    fn opacity(&mut self, element: XmlNode) -> Option<Real> {
+        let value = element
+            .attribute("opacity")
+            .or_else(|| style_property(element, "opacity"))?;
+        self.number(element, "opacity", value)
+    }
+
+    fn transparency(&mut self, element: XmlNode) -> Real {
+        self.opacity(element).map(|opacity| 1.0 - opacity).unwrap_or_default()
+    }
+
+    fn length(&mut self, element: XmlNode, attribute: &str, style: &Style) -> Option<RealValue> {
+        let value = element.attribute(attribute)?;
+        let length = parse_length(value, style.font_size);
+        if length.is_none() {
+            self.invalid_attribute(element, attribute, value);
+        }
+        length
+    }
+
+    fn number(&mut self, element: XmlNode, attribute: &str, value: &str) -> Option<Real> {
+        match parse_length(value, 0.0) {
+            Some(number) if number.1 == ValueType::Px => Some(number.val()),
+            _ => {
+                self.invalid_attribute(element, attribute, value);
+                None
+            }
+        }
+    }
+
+    fn invalid_attribute(&mut self, element: XmlNode, attribute: &str, value: &str) {
+        self.warnings.push(ImportWarning::InvalidAttribute {
+            element: element.tag_name().name().to_string(),
+            attribute: attribute.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    fn unsupported_element(&mut self, element: XmlNode) {
+        self.warnings.push(ImportWarning::UnsupportedElement {
+            element: element.tag_name().name().to_string(),
+        });
+    }
+}
+
+fn prim<M: Model>(name: &'static str, shape: Shape, children: Vec<Node<M>>) -> Node<M> {
+    Node::Prim(Prim::new(Cow::Borrowed(name), shape, children, HashMap::new()))
+}
+
+fn matrix_from(matrix: [Real; 6]) -> TransformMatrix {
+    TransformMatrix { matrix }
+}
+
+fn with_opacity(color: Color, opacity: Real) -> Color {
+    if opacity < 1.0 {
+        let [_, _, _, alpha] = color.as_arr();
+        color.with_alpha(alpha * opacity)
+    } else {
+        color
+    }
+}
+
+fn style_property<'a>(element: XmlNode<'a, '_>, property: &str) -> Option<&'a str> {
+    element.attribute("style").and_then(|style| {
+        style.split(';').find_map(|declaration| {
+            let mut parts = declaration.splitn(2, ':');
+            match (parts.next(), parts.next()) {
+                (Some(name), Some(value)) if name.trim() == property => Some(value.trim()),
+                _ => None,
+            }
+        })
+    })
+}
+
+fn parse_numbers(value: &str) -> Vec<Real> {
+    value
+        .split(|ch: char| ch.is_ascii_whitespace() || ch == ',')
+        .filter(|part| !part.is_empty())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+/// Parse the SVG length, the relative `em` units are resolved with the `font_size`.
+fn parse_length(value: &str, font_size: Real) -> Option<RealValue> {
+    let value = value.trim();
+    let units = [
+        ("px", 1.0),
+        ("pt", 4.0 / 3.0),
+        ("pc", 16.0),
+        ("mm", 96.0 / 25.4),
+        ("cm", 96.0 / 2.54),
+        ("in", 96.0),
+        ("em", font_size),
+    ];
+    if let Some(pct) = value.strip_suffix('%') {
+        return pct.trim().parse().ok().map(RealValue::pct);
+    }
+    for (suffix, factor) in units.iter() {
+        if let Some(number) = value.strip_suffix(suffix) {
+            return number
+                .trim()
+                .parse::<Real>()
+                .ok()
+                .map(|number| RealValue::px(number * factor));
+        }
+    }
+    value.parse().ok().map(RealValue::px)
+}
+
+/// Parse the points of the `polyline` or `polygon`, `None` if the list is invalid.
+fn parse_points(value: &str) -> Option<Vec<(RealValue, RealValue)>> {
+    if value.trim().is_empty() {
+        return Some(Vec::new());
+    }
+    // Points list has the same syntax as the move command with implicit lines
+    let cmd = PathCommand::parse(&format!("M{}", value)).ok()?;
+    cmd.iter()
+        .map(|cmd| match *cmd {
+            PathCommand::Move([x, y]) | PathCommand::Line([x, y]) => Some((RealValue::px(x), RealValue::px(y))),
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_transform(value: &str) -> Option<TransformMatrix> {
+    let mut matrix = TransformMatrix::identity();
+    let mut rest = value.trim();
+    while !rest.is_empty() {
+        let open = rest.find('(')?;
+        let close = rest.find(')')?;
+        let name = rest[..open].trim().trim_start_matches(',').trim();
+        let args = parse_numbers(&rest[open + 1..close]);
+        rest = rest[close + 1..].trim_start_matches(|ch: char| ch.is_ascii_whitespace() || ch == ',');
+
+        let next = match (name, args.as_slice()) {
+            ("matrix", [a, b, c, d, e, f]) => matrix_from([*a, *b, *c, *d, *e, *f]),
+            ("translate", [x]) => matrix_from([1.0, 0.0, 0.0, 1.0, *x, 0.0]),
+            ("translate", [x, y]) => matrix_from([1.0, 0.0, 0.0, 1.0, *x, *y]),
+            ("scale", [x]) => matrix_from([*x, 0.0, 0.0, *x, 0.0, 0.0]),
+            ("scale", [x, y]) => matrix_from([*x, 0.0, 0.0, *y, 0.0, 0.0]),
+            ("rotate", [angle]) => rotation(angle.to_radians()),
+            ("rotate", [angle, cx, cy]) => {
+                matrix_from([1.0, 0.0, 0.0, 1.0, *cx, *cy])
+                    * rotation(angle.to_radians())
+                    * matrix_from([1.0, 0.0, 0.0, 1.0, -cx, -cy])
+            }
+            ("skewX", [angle]) => matrix_from([1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0]),
+            ("skewY", [angle]) => matrix_from([1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0]),
+            _ => return None,
+        };
+        matrix = matrix * next;
+    }
+    Some(matrix)
+}
+
+fn rotation(theta: Real) -> TransformMatrix {
+    let (sin, cos) = theta.sin_cos();
+    matrix_from([cos, sin, -sin, cos, 0.0, 0.0])
+}
+
+#[cfg(test)]
+mod tests {
+    use exgui_core::{ChangeView, Shaped};
+
+    use super::*;
+
+    struct Icon;
+
+    impl Model for Icon {
+        type Message = ();
+        type Properties = ();
+
+        fn create(_props: Self::Properties) -> Self {
+            Icon
+        }
+
+        fn update(&mut self, _msg: Self::Message) -> ChangeView {
+            ChangeView::None
+        }
+
+        fn build_view(&self) -> Node<Self> {
+            prim(Group::NAME, Shape::Group(Group::default()), Vec::new())
+        }
+    }
+
+    #[test]
+    fn import_shapes() {
+        let import = import::<Icon>(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="0 0 100 50">
+                <defs>
+                    <linearGradient id="grad">
+                        <stop offset="0" stop-color="#ff0000"/>
+                        <stop offset="1" style="stop-color: blue"/>
+                    </linearGradient>
+                </defs>
+                <g fill="white" stroke="#000" stroke-width="2" transform="translate(10, 5)">
+                    <rect id="rect" x="0" y="0" width="20" height="10" rx="2" fill="url(#grad)"/>
+                    <circle cx="5" cy="5" r="50%" opacity="0.5"/>
+                    <path d="M0 0 L10 10" fill="none" stroke-dasharray="4, 2" stroke-dashoffset="1"/>
+                    <text x="1" y="2" font-family="Roboto, sans-serif" font-size="12" text-anchor="middle">Hi</text>
+                    <image href="icon.png"/>
+                </g>
+            </svg>"##,
+        )
+        .unwrap();
+
+        assert_eq!(import.warnings, vec![ImportWarning::UnsupportedElement {
+            element: "image".to_string(),
+        }]);
+
+        let root = import.node.as_prim().unwrap();
+        let root_group = root.shape.group().unwrap();
+        assert_eq!(
+            root_group.transform,
+            Transform::Local(matrix_from([2.0, 0.0, 0.0, 2.0, 0.0, 0.0]))
+        );
+
+        let group = root.children[0].as_prim().unwrap();
+        assert_eq!(
+            group.shape.group().unwrap().transform,
+            Transform::Local(matrix_from([1.0, 0.0, 0.0, 1.0, 10.0, 5.0]))
+        );
+        assert_eq!(group.children.len(), 4);
+
+        let rect = group.children[0].as_prim().unwrap().shape.rect().unwrap();
+        assert_eq!(rect.id(), Some("rect"));
+        assert_eq!(rect.rounding, Some(Rounding::from(2.0)));
+        assert_eq!(
+            rect.fill,
+            Some(Fill::gradient(
+                Gradient::linear(
+                    (RealValue::pct(0.0), RealValue::pct(0.0)),
+                    (RealValue::pct(100.0), RealValue::pct(0.0))
+                )
+                .stop(0.0, Color::RGB(1.0, 0.0, 0.0))
+                .stop(1.0, Color::Blue)
+            ))
+        );
+        assert_eq!(rect.stroke.as_ref().map(|stroke| stroke.width), Some(2.0));
+
+        let circle = group.children[1].as_prim().unwrap().shape.circle().unwrap();
+        assert_eq!(circle.r, RealValue::pct(50.0));
+        assert_eq!(circle.transparency, 0.5);
+        assert_eq!(circle.fill, Some(Fill::color(Color::White)));
+
+        let path = group.children[2].as_prim().unwrap().shape.path().unwrap();
+        assert_eq!(path.fill, None);
+        assert_eq!(path.cmd, vec![PathCommand::Move([0.0, 0.0]), PathCommand::Line([10.0, 10.0])]);
+        let stroke = path.stroke.as_ref().unwrap();
+        assert_eq!(stroke.dash.lengths(), &[4.0, 2.0]);
+        assert_eq!(stroke.dash_offset, 1.0);
+
+        let text = group.children[3].as_prim().unwrap().shape.text().unwrap();
+        assert_eq!(text.content, "Hi");
+        assert_eq!(text.font_name, "Roboto");
+        assert_eq!(text.font_size, RealValue::px(12.0));
+        assert_eq!(text.align, (AlignHor::Center, AlignVer::Baseline));
+    }
+
+    #[test]
+    fn import_gradient_stops() {
+        let import = import::<Icon>(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+                <radialGradient id="glow" gradientUnits="userSpaceOnUse" cx="5" cy="5" r="50%" spreadMethod="reflect">
+                    <stop offset="0" stop-color="white"/>
+                    <stop offset="40%" stop-color="#ff0000" stop-opacity="0.5"/>
+                    <stop offset="1" stop-color="blue"/>
+                </radialGradient>
+                <circle cx="5" cy="5" r="4" fill="url(#glow)"/>
+            </svg>"##,
+        )
+        .unwrap();
+        assert!(import.warnings.is_empty());
+
+        let root = import.node.as_prim().unwrap();
+        let circle = root.children[0].as_prim().unwrap().shape.circle().unwrap();
+        assert_eq!(
+            circle.fill,
+            Some(Fill::gradient(
+                Gradient::radial((5, 5), 0, RealValue::pct(50.0))
+                    .stop(0.0, Color::White)
+                    .stop(0.4, Color::RGBA(1.0, 0.0, 0.0, 0.5))
+                    .stop(1.0, Color::Blue)
+                    .spread(SpreadMode::Reflect)
+            ))
+        );
+    }
+
+    #[test]
+    fn import_gradient_reference_cycle() {
+        let import = import::<Icon>(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+                <linearGradient id="a" xlink:href="#b"/>
+                <linearGradient id="b" href="#c"/>
+                <linearGradient id="c" xlink:href="#b"/>
+                <rect width="10" height="10" fill="url(#a)"/>
+                <rect width="10" height="10" fill="url(#c)"/>
+            </svg>"##,
+        )
+        .unwrap();
+
+        let root = import.node.as_prim().unwrap();
+        assert_eq!(root.children.len(), 2);
+        for child in &root.children {
+            assert_eq!(child.as_prim().unwrap().shape.rect().unwrap().fill, None);
+        }
+    }
+
+    #[test]
+    fn import_view_box_aspect_ratio() {
+        let transform = |aspect_ratio: &str| {
+            let svg = format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="10 0 100 100" {}/>"#,
+                aspect_ratio
+            );
+            let import = import::<Icon>(&svg).unwrap();
+            import.node.as_prim().unwrap().shape.group().unwrap().transform
+        };
+
+        assert_eq!(transform(""), Transform::Local(matrix_from([1.0, 0.0, 0.0, 1.0, 40.0, 0.0])));
+        assert_eq!(
+            transform(r#"preserveAspectRatio="xMaxYMin meet""#),
+            Transform::Local(matrix_from([1.0, 0.0, 0.0, 1.0, 90.0, 0.0]))
+        );
+        assert_eq!(
+            transform(r#"preserveAspectRatio="xMinYMid slice""#),
+            Transform::Local(matrix_from([2.0, 0.0, 0.0, 2.0, -20.0, -50.0]))
+        );
+        assert_eq!(
+            transform(r#"preserveAspectRatio="none""#),
+            Transform::Local(matrix_from([2.0, 0.0, 0.0, 1.0, -20.0, 0.0]))
+        );
+    }
+
+    #[test]
+    fn import_fill_rule() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+                <g fill-rule="evenodd">
+                    <path d="M0 0 H10 V10 H0 Z M2 2 H8 V8 H2 Z"/>
+                </g>
+            </svg>"##;
+        let import = import::<Icon>(svg).unwrap();
+        assert!(import.warnings.is_empty());
+
+        let root = import.node.as_prim().unwrap();
+        let group = root.children[0].as_prim().unwrap();
+        let path = group.children[0].as_prim().unwrap().shape.path().unwrap();
+        assert_eq!(path.fill.as_ref().map(|fill| fill.fill_rule), Some(FillRule::EvenOdd));
+        assert!(!path.intersect(5.0, 5.0) && path.intersect(1.0, 1.0));
+
+        let exported = crate::export(&import.node, 10.0, 10.0).unwrap();
+        assert!(exported.contains(r#"fill-rule="evenodd""#), "{}", exported);
+    }
+
+    #[test]
+    fn import_primitives() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+                <line x1="0" y1="0" x2="50" y2="10" stroke="red"/>
+                <polyline points="0,0 10,0 10-10" fill="none" stroke="red"/>
+                <polygon points="0 0 10 0 5 10" fill="blue"/>
+                <ellipse cx="50" cy="50" rx="20" ry="10" fill="blue"/>
+                <polygon points="0 0 10"/>
+            </svg>"##;
+        let import = import::<Icon>(svg).unwrap();
+        assert_eq!(import.warnings.len(), 1);
+
+        let root = import.node.as_prim().unwrap();
+        assert_eq!(root.children.len(), 4);
+        let shape = |idx: usize| &root.children[idx].as_prim().unwrap().shape;
+        let line = shape(0).line().unwrap();
+        assert_eq!(line.bounds(), [0.0, 0.0, 50.0, 10.0]);
+        let polyline = shape(1).polyline().unwrap();
+        assert_eq!(polyline.points[2], (RealValue::px(10.0), RealValue::px(-10.0)));
+        assert_eq!(shape(2).polygon().unwrap().points.len(), 3);
+        assert_eq!(shape(3).ellipse().unwrap().bounds(), [30.0, 40.0, 40.0, 20.0]);
+
+        let exported = crate::export(&import.node, 100.0, 100.0).unwrap();
+        for element in [
+            r##"<line x1="0" y1="0" x2="50" y2="10" fill="none" stroke="#ff0000""##,
+            r##"<polyline points="0,0 10,0 10,-10" fill="none""##,
+            r##"<polygon points="0,0 10,0 5,10" fill="#0000ff""##,
+            r##"<ellipse cx="50" cy="50" rx="20" ry="10" fill="#0000ff""##,
+        ] {
+            assert!(exported.contains(element), "{}", exported);
+        }
+    }
+}
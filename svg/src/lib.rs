@@ -0,0 +1,4 @@
+pub use self::{export::*, import::*};
+
+pub mod export;
+pub mod import;